@@ -346,3 +346,216 @@ fn test_subcommand_out_dir() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test] // allow_core_test
+fn test_subcommand_workspace() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let cwd = std::env::current_dir()?;
+    let project_dir = cwd.join("tests/test_workspace");
+
+    let mut cmd = setup_command(&tmp_dir, &project_dir);
+    cmd.arg("cpp_api_from_rust");
+    cmd.arg("--workspace");
+
+    let output = cmd.output().expect("Failed to execute");
+
+    if !output.status.success() {
+        println!("{}", String::from_utf8_lossy(&output.stdout));
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        panic!("cargo-cpp_api_from_rust failed");
+    }
+
+    let debug_dir = tmp_dir.path().join("debug");
+    let headers_dir = debug_dir.join("include").join("crubit");
+
+    // We expect one header per library package, but none for the binary-only package.
+    assert!(headers_dir.join("base_lib.h").exists());
+    assert!(headers_dir.join("derived_lib.h").exists());
+    assert!(!headers_dir.join("tool.h").exists());
+
+    // The header of a crate includes the headers of its dependencies.
+    let derived_lib_h = std::fs::read_to_string(headers_dir.join("derived_lib.h"))?;
+    assert!(derived_lib_h.contains("crubit/base_lib.h"));
+
+    // We expect a single staticlib named after the workspace.
+    assert!(debug_dir.join("libtest_workspace.a").exists());
+    assert!(!debug_dir.join("libbase_lib.a").exists());
+    assert!(!debug_dir.join("libderived_lib.a").exists());
+
+    Ok(())
+}
+
+#[test] // allow_core_test
+fn test_subcommand_package() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let cwd = std::env::current_dir()?;
+    let project_dir = cwd.join("tests/test_workspace");
+
+    let mut cmd = setup_command(&tmp_dir, &project_dir);
+    cmd.arg("cpp_api_from_rust");
+    cmd.arg("-p");
+    cmd.arg("base_lib");
+
+    let output = cmd.output().expect("Failed to execute");
+
+    if !output.status.success() {
+        println!("{}", String::from_utf8_lossy(&output.stdout));
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        panic!("cargo-cpp_api_from_rust failed");
+    }
+
+    let debug_dir = tmp_dir.path().join("debug");
+    let headers_dir = debug_dir.join("include").join("crubit");
+
+    assert!(headers_dir.join("base_lib.h").exists());
+    assert!(!headers_dir.join("derived_lib.h").exists());
+    assert!(debug_dir.join("libbase_lib.a").exists());
+
+    Ok(())
+}
+
+#[test] // allow_core_test
+fn test_subcommand_lib_per_package() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let cwd = std::env::current_dir()?;
+    let project_dir = cwd.join("tests/test_workspace");
+
+    let mut cmd = setup_command(&tmp_dir, &project_dir);
+    cmd.arg("cpp_api_from_rust");
+    cmd.arg("--package=base_lib");
+    cmd.arg("--package=derived_lib");
+    cmd.arg("--lib-per-package");
+
+    let output = cmd.output().expect("Failed to execute");
+
+    if !output.status.success() {
+        println!("{}", String::from_utf8_lossy(&output.stdout));
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        panic!("cargo-cpp_api_from_rust failed");
+    }
+
+    let debug_dir = tmp_dir.path().join("debug");
+    let headers_dir = debug_dir.join("include").join("crubit");
+
+    assert!(headers_dir.join("base_lib.h").exists());
+    assert!(headers_dir.join("derived_lib.h").exists());
+    assert!(debug_dir.join("libbase_lib.a").exists());
+    assert!(debug_dir.join("libderived_lib.a").exists());
+    assert!(!debug_dir.join("libtest_workspace.a").exists());
+
+    Ok(())
+}
+
+#[test] // allow_core_test
+fn test_subcommand_package_without_lib() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let cwd = std::env::current_dir()?;
+    let project_dir = cwd.join("tests/test_workspace");
+
+    let mut cmd = setup_command(&tmp_dir, &project_dir);
+    cmd.arg("cpp_api_from_rust");
+    cmd.arg("-p");
+    cmd.arg("tool");
+
+    let output = cmd.output().expect("Failed to execute");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("has no library target"));
+
+    Ok(())
+}
+
+#[test] // allow_core_test
+fn test_subcommand_package_configs() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let cwd = std::env::current_dir()?;
+    let project_dir = cwd.join("tests/test_project");
+    let explicit_out_dir = tmp_dir.path().join("explicit_out_dir");
+
+    let mut cmd = setup_command(&tmp_dir, &project_dir);
+    cmd.arg("cpp_api_from_rust");
+    cmd.arg("--out-dir");
+    cmd.arg(&explicit_out_dir);
+
+    let output = cmd.output().expect("Failed to execute");
+
+    if !output.status.success() {
+        println!("{}", String::from_utf8_lossy(&output.stdout));
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        panic!("cargo-cpp_api_from_rust failed");
+    }
+
+    let static_lib = tmp_dir.path().join("debug").join("libtest_project.a");
+    let headers_dir = explicit_out_dir.join("include");
+
+    let cmake_config = std::fs::read_to_string(
+        explicit_out_dir.join("lib/cmake/test_project/test_projectConfig.cmake"),
+    )?;
+    assert!(cmake_config.contains("add_library(test_project::test_project STATIC IMPORTED)"));
+    assert!(cmake_config.contains(&format!("IMPORTED_LOCATION \"{}\"", static_lib.display())));
+    assert!(cmake_config
+        .contains(&format!("INTERFACE_INCLUDE_DIRECTORIES \"{};", headers_dir.display())));
+    // Rust's standard library needs at least libc.
+    assert!(cmake_config.contains("INTERFACE_LINK_LIBRARIES \""));
+    assert!(!cmake_config.contains("INTERFACE_LINK_LIBRARIES \"\""));
+    assert!(cmake_config.contains(
+        "INTERFACE_COMPILE_DEFINITIONS \"CRUBIT_HAS_TEST_PROJECT;\
+         TEST_PROJECT_FEATURE_DEFAULT;TEST_PROJECT_FEATURE_EXTRA_ITEMS\""
+    ));
+
+    let cmake_version = std::fs::read_to_string(
+        explicit_out_dir.join("lib/cmake/test_project/test_projectConfigVersion.cmake"),
+    )?;
+    assert!(cmake_version.contains("set(PACKAGE_VERSION \"0.1.0\")"));
+    assert!(cmake_version.contains("set(PACKAGE_VERSION_COMPATIBLE TRUE)"));
+    // `0.1.0` is not compatible with `0.2`.
+    assert!(cmake_version.contains("elseif(0 EQUAL 0 AND PACKAGE_FIND_VERSION_COUNT GREATER 1"));
+    assert!(cmake_version.contains("AND NOT PACKAGE_FIND_VERSION_MINOR EQUAL 1)"));
+
+    let pkg_config =
+        std::fs::read_to_string(explicit_out_dir.join("lib/pkgconfig/test_project.pc"))?;
+    assert!(pkg_config.contains("Name: test_project\n"));
+    assert!(pkg_config.contains("Version: 0.1.0\n"));
+    assert!(pkg_config.contains(&format!("includedir={}\n", headers_dir.display())));
+    assert!(pkg_config.contains(&format!("Libs: {} -l", static_lib.display())));
+    assert!(pkg_config.contains(
+        " -DCRUBIT_HAS_TEST_PROJECT -DTEST_PROJECT_FEATURE_DEFAULT \
+         -DTEST_PROJECT_FEATURE_EXTRA_ITEMS\n"
+    ));
+    assert!(cmake_config.contains("INTERFACE_COMPILE_FEATURES \"cxx_std_20\""));
+    assert!(pkg_config.contains("Cflags: -std=c++20 "));
+
+    Ok(())
+}
+
+#[test] // allow_core_test
+fn test_subcommand_package_configs_cpp_std() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = tempfile::tempdir()?;
+    let cwd = std::env::current_dir()?;
+    let project_dir = cwd.join("tests/test_project");
+    let explicit_out_dir = tmp_dir.path().join("explicit_out_dir");
+
+    let mut cmd = setup_command(&tmp_dir, &project_dir);
+    cmd.arg("cpp_api_from_rust");
+    cmd.arg("--out-dir");
+    cmd.arg(&explicit_out_dir);
+    cmd.arg("--cpp-std=gnu++23");
+
+    let output = cmd.output().expect("Failed to execute");
+
+    if !output.status.success() {
+        println!("{}", String::from_utf8_lossy(&output.stdout));
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        panic!("cargo-cpp_api_from_rust failed");
+    }
+
+    let cmake_config = std::fs::read_to_string(
+        explicit_out_dir.join("lib/cmake/test_project/test_projectConfig.cmake"),
+    )?;
+    assert!(cmake_config.contains("INTERFACE_COMPILE_FEATURES \"cxx_std_23\""));
+    let pkg_config =
+        std::fs::read_to_string(explicit_out_dir.join("lib/pkgconfig/test_project.pc"))?;
+    assert!(pkg_config.contains("Cflags: -std=gnu++23 "));
+
+    Ok(())
+}
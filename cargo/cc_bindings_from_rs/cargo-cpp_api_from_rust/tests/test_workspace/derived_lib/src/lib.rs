@@ -0,0 +1,11 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+pub fn derived_add_one(value: u64) -> u64 {
+    base_lib::base_add(value, 1)
+}
+
+pub fn origin() -> base_lib::Point {
+    base_lib::Point::default()
+}
@@ -0,0 +1,395 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Generates Rust bindings for C++ headers from a Cargo `build.rs`.
+//!
+//! ```no_run
+//! // build.rs, in `fn main()`:
+//! rs_bindings_from_cc_build::Builder::new()
+//!     .header("my_lib/my_lib.h")
+//!     .include_path("include")
+//!     .generate()
+//!     .expect("failed to generate bindings for my_lib.h");
+//! ```
+//!
+//! ```ignore
+//! // lib.rs
+//! #![feature(negative_impls)]
+//! include!(concat!(env!("OUT_DIR"), "/my_lib_rust_api.rs"));
+//! ```
+//!
+//! See crubit.rs/overview/cargo_build for details.
+
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub use crubit_feature::CrubitFeature;
+use flagset::FlagSet;
+
+/// The environment variable that overrides the path to the `rs_bindings_from_cc` binary.
+pub const RS_BINDINGS_FROM_CC_ENV_VAR: &str = "RS_BINDINGS_FROM_CC";
+
+/// The C++ standard that headers are parsed and compiled with, unless [`Builder::cpp_std`] is set.
+pub const DEFAULT_CPP_STD: &str = "c++20";
+
+/// Configures and runs `rs_bindings_from_cc` for a set of C++ headers.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    headers: Vec<String>,
+    include_paths: Vec<PathBuf>,
+    features: FlagSet<CrubitFeature>,
+    target: Option<String>,
+    clang_args: Vec<String>,
+    cpp_std: String,
+    rs_bindings_from_cc: Option<PathBuf>,
+    crubit_root: PathBuf,
+    out_dir: Option<PathBuf>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// Creates a builder with no headers, and the `supported` feature set.
+    pub fn new() -> Self {
+        Builder {
+            headers: vec![],
+            include_paths: vec![],
+            features: CrubitFeature::Supported | CrubitFeature::Types,
+            target: None,
+            clang_args: vec![],
+            cpp_std: DEFAULT_CPP_STD.to_string(),
+            rs_bindings_from_cc: None,
+            // This crate lives in `cargo/rs_bindings_from_cc/rs_bindings_from_cc_build` of a
+            // Crubit checkout, which also contains the support headers that the generated C++
+            // includes.
+            crubit_root: Path::new(env!("CARGO_MANIFEST_DIR")).join("../../.."),
+            out_dir: None,
+        }
+    }
+
+    /// Adds a public header to generate bindings for.
+    ///
+    /// The header is spelled the way it would be in a quote include (`#include "..."`), relative
+    /// to one of the include paths.
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.headers.push(header.into());
+        self
+    }
+
+    /// Adds several public headers. See [`Builder::header`].
+    pub fn headers<I>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.headers.extend(headers.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a directory to the include path, both when parsing the headers and when compiling
+    /// the generated C++ thunks.
+    pub fn include_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.include_paths.push(path.into());
+        self
+    }
+
+    /// Sets the Crubit features enabled for the headers (crubit.rs/features).
+    pub fn features(mut self, features: impl Into<FlagSet<CrubitFeature>>) -> Self {
+        self.features = features.into();
+        self
+    }
+
+    /// Sets the name of the target that owns the headers, e.g. `//my_lib`.
+    ///
+    /// This is the name that appears in the generated code and in error messages. Defaults to
+    /// `//` followed by the name of the package being built.
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Passes an extra argument to Clang when parsing the headers, e.g. `-DNDEBUG`.
+    pub fn clang_arg(mut self, arg: impl Into<String>) -> Self {
+        self.clang_args.push(arg.into());
+        self
+    }
+
+    /// Sets the C++ standard (e.g. `c++17`) used both to parse the headers and to compile the
+    /// generated C++ thunks. Defaults to [`DEFAULT_CPP_STD`].
+    ///
+    /// The headers are parsed as if by `-std=<cpp_std>`, so this should match the standard the
+    /// C++ library itself is compiled with.
+    pub fn cpp_std(mut self, cpp_std: impl Into<String>) -> Self {
+        self.cpp_std = cpp_std.into();
+        self
+    }
+
+    /// Sets the path to the `rs_bindings_from_cc` binary.
+    ///
+    /// Defaults to the value of the `RS_BINDINGS_FROM_CC` environment variable, or to
+    /// `rs_bindings_from_cc` on the `PATH`.
+    pub fn rs_bindings_from_cc(mut self, path: impl Into<PathBuf>) -> Self {
+        self.rs_bindings_from_cc = Some(path.into());
+        self
+    }
+
+    /// Sets the root of the Crubit checkout, which contains the `support/` headers included by
+    /// the generated C++ thunks. Defaults to the checkout this crate was built from.
+    pub fn crubit_root(mut self, path: impl Into<PathBuf>) -> Self {
+        self.crubit_root = path.into();
+        self
+    }
+
+    /// Sets the directory the bindings are written to. Defaults to `OUT_DIR`.
+    pub fn out_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(path.into());
+        self
+    }
+
+    /// Generates the bindings, compiles the C++ thunks into a static library, and tells Cargo to
+    /// link it.
+    ///
+    /// The Rust bindings are written to `$OUT_DIR/<crate>_rust_api.rs`, and are meant to be
+    /// `include!`d at the root of the crate that links the C++ library.
+    pub fn generate(self) -> io::Result<Bindings> {
+        let bindings = self.generate_sources()?;
+        self.cc_build(&bindings.cc_file)
+            .try_compile(&bindings.cc_lib_name)
+            .map_err(io::Error::other)?;
+        Ok(bindings)
+    }
+
+    /// Returns the `cc` configuration that compiles the generated C++ thunks in `cc_file`.
+    fn cc_build(&self, cc_file: &Path) -> cc::Build {
+        let mut build = cc::Build::new();
+        build.cpp(true).std(&self.cpp_std).file(cc_file).include(&self.crubit_root);
+        for path in &self.include_paths {
+            build.include(path);
+        }
+        build
+    }
+
+    /// Returns the Clang arguments used to parse the headers.
+    ///
+    /// The arguments passed with [`Builder::clang_arg`] come last, so they can override the
+    /// others.
+    fn all_clang_args(&self) -> Vec<String> {
+        let mut args = vec![format!("-I{}", self.crubit_root.display())];
+        args.extend(self.include_paths.iter().map(|path| format!("-I{}", path.display())));
+        args.push(format!("-std={}", self.cpp_std));
+        args.extend(self.clang_args.iter().cloned());
+        args
+    }
+
+    /// Like [`Builder::generate`], but doesn't compile the generated C++ file, e.g. for build
+    /// systems that compile it separately.
+    pub fn generate_sources(&self) -> io::Result<Bindings> {
+        if self.headers.is_empty() {
+            return Err(io::Error::other("no headers to generate bindings for"));
+        }
+        let package_name = env_var("CARGO_PKG_NAME")?;
+        let target = self.target.clone().unwrap_or_else(|| format!("//{package_name}"));
+        let crate_name = package_name.replace('-', "_");
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => PathBuf::from(env_var("OUT_DIR")?),
+        };
+
+        let rs_file = out_dir.join(format!("{crate_name}_rust_api.rs"));
+        let cc_file = out_dir.join(format!("{crate_name}_rust_api_impl.cc"));
+        let raw_rs_file = out_dir.join(format!("{crate_name}_rust_api.raw.rs"));
+
+        let target_args = serde_json::json!([{
+            "t": target,
+            "h": self.headers,
+            "f": self.features.into_iter().map(|f| f.short_name()).collect::<Vec<_>>(),
+        }]);
+
+        let rs_bindings_from_cc = match &self.rs_bindings_from_cc {
+            Some(path) => path.clone().into_os_string(),
+            None => {
+                println!("cargo::rerun-if-env-changed={RS_BINDINGS_FROM_CC_ENV_VAR}");
+                std::env::var_os(RS_BINDINGS_FROM_CC_ENV_VAR)
+                    .unwrap_or_else(|| OsString::from("rs_bindings_from_cc"))
+            }
+        };
+        let mut command = Command::new(&rs_bindings_from_cc);
+        command
+            .arg(format!("--target={target}"))
+            .arg(format!("--target_args={target_args}"))
+            .arg(format!("--public_headers={}", self.headers.join(",")))
+            .arg("--rs_out")
+            .arg(&raw_rs_file)
+            .arg("--cc_out")
+            .arg(&cc_file)
+            .arg("--crubit_support_path_format")
+            .arg("\"support/{header}\"")
+            .arg("--")
+            .args(self.all_clang_args());
+
+        let status = command.status().map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("failed to run {}: {e}", Path::new(&rs_bindings_from_cc).display()),
+            )
+        })?;
+        if !status.success() {
+            return Err(io::Error::other(format!(
+                "{} failed with {status}",
+                Path::new(&rs_bindings_from_cc).display()
+            )));
+        }
+
+        for header in &self.headers {
+            if let Some(path) =
+                self.include_paths.iter().map(|dir| dir.join(header)).find(|path| path.exists())
+            {
+                println!("cargo::rerun-if-changed={}", path.display());
+            }
+        }
+
+        let raw_rs = std::fs::read_to_string(&raw_rs_file)?;
+        let (inner_attributes, body) = split_inner_attributes(&raw_rs);
+        std::fs::write(&rs_file, body)?;
+
+        Ok(Bindings {
+            rs_file,
+            cc_file,
+            cc_lib_name: format!("{crate_name}_rust_api_impl"),
+            inner_attributes: inner_attributes.into_iter().map(str::to_string).collect(),
+        })
+    }
+}
+
+/// The bindings generated by [`Builder::generate`].
+#[derive(Clone, Debug)]
+pub struct Bindings {
+    rs_file: PathBuf,
+    cc_file: PathBuf,
+    cc_lib_name: String,
+    inner_attributes: Vec<String>,
+}
+
+impl Bindings {
+    /// The generated Rust bindings, which can be `include!`d at the root of a crate.
+    pub fn rs_file(&self) -> &Path {
+        &self.rs_file
+    }
+
+    /// The generated C++ thunks, which must be compiled and linked into the crate.
+    pub fn cc_file(&self) -> &Path {
+        &self.cc_file
+    }
+
+    /// The name of the static library that [`Builder::generate`] compiles `cc_file` into.
+    pub fn cc_lib_name(&self) -> &str {
+        &self.cc_lib_name
+    }
+
+    /// The crate-level attributes (e.g. `#![feature(negative_impls)]`) of the generated bindings.
+    ///
+    /// `include!` doesn't allow inner attributes, so these are removed from `rs_file`. The crate
+    /// that includes the bindings must declare the `#![feature(...)]` attributes itself.
+    pub fn inner_attributes(&self) -> &[String] {
+        &self.inner_attributes
+    }
+}
+
+fn env_var(name: &str) -> io::Result<String> {
+    std::env::var(name).map_err(|e| {
+        io::Error::new(io::ErrorKind::NotFound, format!("environment variable {name}: {e}"))
+    })
+}
+
+/// Splits the leading inner attributes (`#![...]`) off the generated Rust source.
+///
+/// Comments between the attributes are dropped along with them.
+fn split_inner_attributes(source: &str) -> (Vec<&str>, &str) {
+    let mut attributes = vec![];
+    let mut rest = source;
+    loop {
+        let trimmed = rest.trim_start();
+        if let Some(comment) = trimmed.strip_prefix("//")
+            && !comment.starts_with('/')
+            && !comment.starts_with('!')
+        {
+            rest = comment.split_once('\n').map_or("", |(_, after)| after);
+            continue;
+        }
+        if !trimmed.starts_with("#![") {
+            return (attributes, rest);
+        }
+        // Find the matching `]`. Inner attributes of generated code don't contain brackets in
+        // string literals, so counting is enough.
+        let mut depth = 0;
+        let Some(end) = trimmed.char_indices().skip(2).find_map(|(i, c)| {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(i + 1)
+        }) else {
+            return (attributes, rest);
+        };
+        attributes.push(&trimmed[..end]);
+        rest = &trimmed[end..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_inner_attributes() {
+        let source = "// Automatically @generated\n\n\
+            #![rustfmt::skip]\n#![feature(allocator_api, negative_impls)]\n\
+            #![allow(non_snake_case)] #![deny(warnings)]\n\n\
+            /// Doc.\npub struct S;\n";
+        let (attributes, body) = split_inner_attributes(source);
+        assert_eq!(
+            attributes,
+            [
+                "#![rustfmt::skip]",
+                "#![feature(allocator_api, negative_impls)]",
+                "#![allow(non_snake_case)]",
+                "#![deny(warnings)]",
+            ]
+        );
+        assert_eq!(body, "\n\n/// Doc.\npub struct S;\n");
+    }
+
+    #[test]
+    fn test_default_cpp_std() {
+        let builder = Builder::new().crubit_root("/crubit").include_path("include");
+        assert_eq!(builder.all_clang_args(), ["-I/crubit", "-Iinclude", "-std=c++20"]);
+    }
+
+    #[test]
+    fn test_cpp_std_is_passed_to_clang() {
+        let builder = Builder::new().crubit_root("/crubit").cpp_std("c++17").clang_arg("-DNDEBUG");
+        assert_eq!(builder.all_clang_args(), ["-I/crubit", "-std=c++17", "-DNDEBUG"]);
+    }
+
+    #[test]
+    fn test_cpp_std_is_passed_to_cc() {
+        let mut build = Builder::new().cpp_std("c++17").cc_build(Path::new("lib_rust_api_impl.cc"));
+        build
+            .target("x86_64-unknown-linux-gnu")
+            .host("x86_64-unknown-linux-gnu")
+            .opt_level(0)
+            .cargo_metadata(false);
+        let compiler = build.get_compiler();
+        let args = compiler.args();
+        assert!(args.iter().any(|arg| arg == "-std=c++17"), "{args:?}");
+        assert!(!args.iter().any(|arg| arg == "-std=c++20"), "{args:?}");
+    }
+}
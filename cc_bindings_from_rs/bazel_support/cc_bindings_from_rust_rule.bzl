@@ -562,12 +562,17 @@ private_common_attrs = {
             "//support/rs_std:slice_ref",
             "//support/rs_std:str_ref",
             "//support:bridge_cpp",
+            "//support:bridge_collections_cpp",
             "//support/rs_std:traits",
             "//support/rs_std:option",
             "//support/rs_std:result",
             "//support/rs_std:tuple",
             "//support/rs_std:unit",
             "//support/rs_std:vec",
+            "//support/rs_std:arc",
+            "//support/rs_std:rc",
+            "//support/rs_std:box",
+            "//support/rs_std:hash_map",
             "//support/rs_std:iterator_adapter",
             "//support/rs_std:dyn_erased_future_cc",
             "//common:include_allocator_symbols"
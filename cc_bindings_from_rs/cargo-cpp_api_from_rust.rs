@@ -14,20 +14,40 @@
 //! These outputs can be consumed by a C++ build system (e.g. CMake) to compile Rust into a C++
 //! codebase.
 //!
-//! The cargo project being built must specify a single library target in the root manifest.
-//! Generating bindings for multiple packages, such as in a workspace, is not supported. The
-//! subcommand will build your target Rust crate, failing with the compiler error if it fails to
-//! build.
+//! By default, bindings are generated for the library target of the root package. In a workspace,
+//! `-p/--package` (repeatable), `--workspace` and `--exclude` select the packages to generate
+//! bindings for, the same way they do for `cargo build`. One header is generated per crate, in
+//! dependency order, so that the header of a crate includes the headers of its dependencies.
+//!
+//! All the selected packages are linked into a single static library, named after the package if
+//! there is only one, or after the workspace directory otherwise (see `--lib-name`). With
+//! `--lib-per-package`, a separate static library is built for each selected package instead.
+//! Note that each Rust static library contains its own copy of the standard library, so linking
+//! several of them into the same C++ binary may fail with duplicate symbols.
+//!
+//! Next to the headers, the subcommand writes a CMake package config
+//! (`lib/cmake/<lib>/<lib>Config.cmake`) that defines the imported target `<lib>::<lib>`, its
+//! version file (`<lib>ConfigVersion.cmake`), and a pkg-config file (`lib/pkgconfig/<lib>.pc`).
+//! They carry the include directories, the static library, the system libraries it needs, and the
+//! compile definitions `CRUBIT_HAS_<LIB>` and `<LIB>_FEATURE_<FEATURE>` for each Cargo feature
+//! enabled in the main package. With `--out-dir=<dir>`, CMake's `find_package(<lib> <version>)`
+//! works against `CMAKE_PREFIX_PATH=<dir>`, and accepts the versions that Cargo would consider
+//! compatible.
+//!
+//! The subcommand will build your target Rust crates, failing with the compiler error if they fail
+//! to build.
 
 #![feature(rustc_private)]
 
 use arc_anyhow::{anyhow, bail, Result};
 use cargo_metadata::camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::diagnostic::Diagnostic;
 use cargo_metadata::{Artifact, Message, Metadata, Package, PackageId, Resolve};
 use clap::Parser;
 use cmdline::Cmdline;
+use output_cache::OutputCache;
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::env;
 use std::ffi;
 use std::fs;
@@ -43,12 +63,36 @@ struct Cli {
     manifest: clap_cargo::Manifest,
     #[command(flatten)]
     features: clap_cargo::Features,
+    #[command(flatten)]
+    workspace: clap_cargo::Workspace,
     #[arg(long, value_name = "DIRECTORY")]
     target_dir: Option<Utf8PathBuf>,
     #[arg(last = true)]
     build_args: Vec<String>,
     #[arg(long, value_name = "DIRECTORY")]
     out_dir: Option<Utf8PathBuf>,
+    /// Name of the static library containing the bindings of all the selected packages.
+    #[arg(long, value_name = "NAME", conflicts_with = "lib_per_package")]
+    lib_name: Option<String>,
+    /// Build one static library per selected package, instead of a single one for all of them.
+    #[arg(long)]
+    lib_per_package: bool,
+    /// Root of the Crubit checkout, which contains the `support/` headers included by the
+    /// generated headers. Defaults to the checkout this subcommand was built from, if it still
+    /// exists.
+    #[arg(long, value_name = "DIRECTORY")]
+    crubit_root: Option<Utf8PathBuf>,
+    /// C++ standard (e.g. `c++17`) that the generated CMake package config and pkg-config file
+    /// require from their users.
+    #[arg(long, value_name = "STANDARD", default_value = rs_bindings_from_cc_build::DEFAULT_CPP_STD)]
+    cpp_std: String,
+    /// Directory of a cache of generated bindings, which is reused across target directories
+    /// and `cargo clean`. See `cc_bindings_from_rs --cache-dir`.
+    #[arg(long, value_name = "DIRECTORY")]
+    cache_dir: Option<Utf8PathBuf>,
+    /// Print the hit rate and size of the `--cache-dir` cache after generating the bindings.
+    #[arg(long, requires = "cache_dir")]
+    cache_stats: bool,
 }
 
 struct ArtifactInfo {
@@ -71,7 +115,7 @@ fn main() -> Result<()> {
     cli.features.forward_metadata(&mut metadata);
     let metadata = metadata.exec()?;
 
-    let root = metadata.root_package().ok_or_else(|| anyhow!("Failed to find root package"))?;
+    let packages = select_packages(&cli.workspace, &metadata)?;
 
     let mut build_args = vec![];
     let mut target_dir = &metadata.target_directory;
@@ -81,8 +125,10 @@ fn main() -> Result<()> {
     }
     if let Some(manifest_path) = &cli.manifest.manifest_path {
         build_args.push(format!("--manifest-path={}", manifest_path.display()));
-    } else {
+    } else if let Some(root) = metadata.root_package() {
         build_args.push(format!("--manifest-path={}", root.manifest_path));
+    } else {
+        build_args.push(format!("--manifest-path={}", metadata.workspace_root.join("Cargo.toml")));
     }
     if cli.features.all_features {
         build_args.push("--all-features".to_string());
@@ -95,29 +141,125 @@ fn main() -> Result<()> {
     }
     build_args.extend(cli.build_args.clone());
 
-    let build_artifacts = build_crate_and_stream_artifacts(&build_args)?;
-    let target_libdir = get_target_libdir(&build_args)?;
-
-    let ctx = BindingGenerationContext::new(
+    let mut build_artifacts = BuildArtifacts::default();
+    for package in &packages {
+        build_artifacts
+            .extend(build_crate_and_stream_artifacts(&package_build_args(package, &build_args))?);
+    }
+    let target_libdir = get_target_libdir(&package_build_args(&packages[0], &build_args))?;
+
+    let crubit_root = match &cli.crubit_root {
+        Some(crubit_root) => Some(crubit_root.canonicalize_utf8().map_err(|err| {
+            anyhow!("Failed to find the Crubit root directory '{crubit_root}': {err}")
+        })?),
+        None => {
+            // An installed binary may outlive the checkout it was built from.
+            let crubit_root = Utf8Path::new(env!("CARGO_MANIFEST_DIR")).join("../../..");
+            match crubit_root.canonicalize_utf8() {
+                Ok(crubit_root) => Some(crubit_root),
+                Err(err) => {
+                    eprintln!(
+                        "warning: Failed to find the Crubit root directory '{crubit_root}': {err}. \
+                         The package configs won't include Crubit's support headers; pass \
+                         --crubit-root to add them."
+                    );
+                    None
+                }
+            }
+        }
+    };
+    let mut ctx = BindingGenerationContext::new(
         build_artifacts,
-        root.clone(),
+        &packages,
         &metadata,
         target_dir,
         cli.out_dir.as_deref(),
         target_libdir,
+        crubit_root,
+        cli.cpp_std.clone(),
     )?;
-    let lib_rs_content = ctx.generate_bindings()?;
+    ctx.cache_dir = cli.cache_dir.clone();
+    let bindings = ctx.generate_bindings()?;
+    if let Some(cache_dir) = cli.cache_dir.as_ref().filter(|_| cli.cache_stats) {
+        eprintln!("{}", OutputCache::new(cache_dir.as_std_path()).stats()?);
+    }
 
-    ctx.compile_staticlib(lib_rs_content, &metadata)?;
+    let static_libs: Vec<StaticLib> = if cli.lib_per_package {
+        packages
+            .iter()
+            .map(|package| ctx.static_lib(package.name.to_string(), &[package]))
+            .collect()
+    } else {
+        let name = match &cli.lib_name {
+            Some(name) => name.clone(),
+            None if packages.len() == 1 => packages[0].name.to_string(),
+            None => metadata
+                .workspace_root
+                .file_name()
+                .ok_or_else(|| anyhow!("Failed to determine the name of the workspace"))?
+                .to_string(),
+        };
+        vec![ctx.static_lib(name, &packages.iter().collect::<Vec<_>>())]
+    };
+    for static_lib in &static_libs {
+        ctx.compile_staticlib(static_lib, &bindings, &metadata)?;
+    }
 
     Ok(())
 }
 
+/// Returns the library packages selected by `-p/--package`, `--workspace` and `--exclude`.
+///
+/// Without any of these flags, this is the root package, or the default members of a virtual
+/// workspace.
+fn select_packages(workspace: &clap_cargo::Workspace, metadata: &Metadata) -> Result<Vec<Package>> {
+    let is_explicit = workspace.workspace || workspace.all || !workspace.package.is_empty();
+    let selected = match metadata.root_package() {
+        Some(root) if !is_explicit => vec![root],
+        _ => workspace.partition_packages(metadata).0,
+    };
+    for name in &workspace.package {
+        if !selected.iter().any(|package| package.name == name) {
+            bail!("Package '{name}' is not a member of the workspace");
+        }
+    }
+
+    let mut packages = vec![];
+    for package in selected {
+        if package.targets.iter().any(|target| target.is_lib() || target.is_rlib()) {
+            packages.push(package.clone());
+            continue;
+        }
+        // Packages without a library target (e.g. binaries) are skipped, unless they were
+        // explicitly requested.
+        let is_requested = workspace.package.iter().any(|name| package.name == name)
+            || (!is_explicit && metadata.root_package().is_some());
+        if is_requested {
+            bail!("Package '{}' has no library target", package.name);
+        }
+    }
+    if packages.is_empty() {
+        bail!("No library packages selected");
+    }
+    Ok(packages)
+}
+
+/// Returns the `cargo rustc` arguments to build the library target of `package`.
+fn package_build_args(package: &Package, build_args: &[String]) -> Vec<String> {
+    let mut args = vec![format!("--package={}", package.name), "--lib".to_string()];
+    args.extend(build_args.iter().cloned());
+    args
+}
+
 use std::process::Child;
 
-fn stream_cargo_build(
+/// Runs cargo with `args`, returning the compiler artifacts it produces.
+///
+/// Compiler diagnostics are printed, and passed to `on_diagnostic`.
+fn stream_cargo_build<F: FnMut(&Diagnostic)>(
     args: &[String],
-) -> Result<(Child, impl Iterator<Item = Result<Artifact, std::io::Error>>)> {
+    mut on_diagnostic: F,
+) -> Result<(Child, impl Iterator<Item = Result<Artifact, std::io::Error>> + use<F>)> {
     let mut build_command = Command::new(cargo_bin());
     build_command.args(args);
 
@@ -132,10 +274,11 @@ fn stream_cargo_build(
     // Print out any compiler diagnostics when we walk the iterator leaving only the compiler artifacts.
     Ok((
         command,
-        cargo_metadata::Message::parse_stream(reader).filter_map(|message| match message {
+        cargo_metadata::Message::parse_stream(reader).filter_map(move |message| match message {
             Ok(message) => match message {
                 Message::CompilerMessage(msg) => {
                     eprint!("{}", msg);
+                    on_diagnostic(&msg.message);
                     None
                 }
                 Message::TextLine(msg) => {
@@ -150,11 +293,32 @@ fn stream_cargo_build(
     ))
 }
 
+#[derive(Default)]
 struct BuildArtifacts {
     pkg_to_artifact: HashMap<String, ArtifactInfo>,
     dep_dirs: Vec<Utf8PathBuf>,
 }
 
+impl BuildArtifacts {
+    /// Merges the artifacts of another `cargo rustc` invocation into `self`.
+    fn extend(&mut self, other: BuildArtifacts) {
+        for (pkg_id_repr, info) in other.pkg_to_artifact {
+            // A dependency shared by several packages is only rebuilt by the first invocation, and
+            // reported as fresh by the following ones.
+            let fresh = self
+                .pkg_to_artifact
+                .get(&pkg_id_repr)
+                .map_or(info.fresh, |old| old.fresh && info.fresh);
+            self.pkg_to_artifact.insert(pkg_id_repr, ArtifactInfo { fresh, ..info });
+        }
+        for dir in other.dep_dirs {
+            if !self.dep_dirs.contains(&dir) {
+                self.dep_dirs.push(dir);
+            }
+        }
+    }
+}
+
 fn build_crate_and_stream_artifacts(build_args: &[String]) -> Result<BuildArtifacts> {
     let mut args = vec!["rustc".to_string(), "--message-format=json".to_string()];
     args.extend(build_args.iter().cloned());
@@ -171,7 +335,7 @@ fn build_crate_and_stream_artifacts(build_args: &[String]) -> Result<BuildArtifa
 
     let mut pkg_to_artifact = HashMap::new();
     let mut dep_dirs = BTreeSet::new();
-    let (command, stream) = stream_cargo_build(&args)?;
+    let (command, stream) = stream_cargo_build(&args, |_| {})?;
     for artifact in stream {
         let artifact = artifact.map_err(|err| anyhow!("Failed to parse cargo message: {}", err))?;
         for filename in &artifact.filenames {
@@ -218,6 +382,9 @@ struct Directories {
     /// Contains target-arch specific dependencies, e.g.
     /// "/path/to/target/x86_64-unknown-linux-gnu/release/deps".
     deps_dir: Utf8PathBuf,
+    /// Contains the generated headers and package configs, e.g.
+    /// "/path/to/target/x86_64-unknown-linux-gnu/release", or the `--out-dir`.
+    out_dir: Utf8PathBuf,
     /// Contains target-arch specific headers, e.g.
     /// "/path/to/target/x86_64-unknown-linux-gnu/release/include".
     headers_dir: Utf8PathBuf,
@@ -238,9 +405,10 @@ impl Directories {
             .to_string();
         let profile_dir = target_dir.join(profile_dir);
         let deps_dir = profile_dir.join("deps");
-        let headers_dir = out_dir.unwrap_or(profile_dir.as_path()).join("include");
+        let out_dir = out_dir.unwrap_or(profile_dir.as_path()).to_owned();
+        let headers_dir = out_dir.join("include");
         let host_deps_dir = target_dir.join(&profile_name).join("deps");
-        Ok(Directories { profile_dir, profile_name, deps_dir, headers_dir, host_deps_dir })
+        Ok(Directories { profile_dir, profile_name, deps_dir, out_dir, headers_dir, host_deps_dir })
     }
 }
 
@@ -255,25 +423,45 @@ struct CrateBindingInfo<'a> {
     stdlib_externs: &'a [(String, Utf8PathBuf)],
 }
 
+/// The bindings generated for the standard library and the selected packages.
+struct GeneratedBindings {
+    /// The `mod` declarations of the standard library bindings.
+    stdlib_modules: String,
+    /// The `mod` declaration of the bindings of each package, keyed by package ID.
+    package_modules: HashMap<String, String>,
+}
+
+/// A static library to build out of the generated bindings.
+struct StaticLib<'a> {
+    /// The name of the library, which is built as `lib{name}.a`.
+    name: String,
+    /// The package whose build configuration (e.g. the metadata hash) is used for the library.
+    main_package: &'a Package,
+    /// The packages whose bindings are linked into the library, including dependencies.
+    packages: HashSet<PackageId>,
+}
+
 struct BindingGenerationContext {
     pkg_to_artifact: HashMap<String, ArtifactInfo>,
     dep_dirs: Vec<Utf8PathBuf>,
-    root: Package,
     ordered: Vec<PackageId>,
     dirs: Directories,
     resolve: Resolve,
     target_libdir: Utf8PathBuf,
+    crubit_root: Option<Utf8PathBuf>,
+    cpp_std: String,
+    cache_dir: Option<Utf8PathBuf>,
 }
 
 fn determine_profile_dir(
     pkg_to_artifact: &HashMap<String, ArtifactInfo>,
-    root: &Package,
+    package: &Package,
     target_dir: &Utf8Path,
 ) -> Result<Utf8PathBuf> {
     let artifact_path = pkg_to_artifact
-        .get(&root.id.repr)
+        .get(&package.id.repr)
         .map(|info| info.path.as_path())
-        .ok_or_else(|| anyhow!("Failed to find root package artifact"))?;
+        .ok_or_else(|| anyhow!("Failed to find artifact of package '{}'", package.name))?;
     let rel_path = artifact_path.strip_prefix(target_dir).map_err(|_| {
         anyhow!("Artifact path '{artifact_path}' is not under target dir '{target_dir}'")
     })?;
@@ -304,16 +492,18 @@ fn determine_profile_dir(
 impl BindingGenerationContext {
     fn new(
         build_artifacts: BuildArtifacts,
-        root: Package,
+        packages: &[Package],
         metadata: &Metadata,
         target_dir: &Utf8PathBuf,
         out_dir: Option<&Utf8Path>,
         target_libdir: Utf8PathBuf,
+        crubit_root: Option<Utf8PathBuf>,
+        cpp_std: String,
     ) -> Result<Self> {
-        // It's important we check the path of root (and not one of our dependencies) or else we'll get
-        // the wrong path.
+        // It's important we check the path of a selected package (and not one of our dependencies)
+        // or else we'll get the wrong path.
         let profile_dir =
-            determine_profile_dir(&build_artifacts.pkg_to_artifact, &root, target_dir)?;
+            determine_profile_dir(&build_artifacts.pkg_to_artifact, &packages[0], target_dir)?;
         let dirs = Directories::new(target_dir.to_owned(), profile_dir, out_dir)?;
 
         let resolve = metadata
@@ -338,11 +528,13 @@ impl BindingGenerationContext {
         Ok(Self {
             pkg_to_artifact: build_artifacts.pkg_to_artifact,
             dep_dirs: build_artifacts.dep_dirs,
-            root,
             ordered,
             dirs,
             resolve: resolve.clone(),
             target_libdir,
+            crubit_root,
+            cpp_std,
+            cache_dir: None,
         })
     }
 
@@ -410,6 +602,9 @@ impl BindingGenerationContext {
             format!("--extern={}={}", info.crate_name, info.artifact_path),
         ];
 
+        if let Some(cache_dir) = &self.cache_dir {
+            current_args.push(format!("--cache-dir={cache_dir}"));
+        }
         if !self.dep_dirs.contains(deps_dir) && deps_dir.exists() {
             current_args.push(format!("-Ldependency={}", deps_dir));
         }
@@ -475,15 +670,10 @@ impl BindingGenerationContext {
         Ok(())
     }
 
-    fn generate_bindings(&self) -> Result<String> {
+    fn generate_bindings(&self) -> Result<GeneratedBindings> {
         let mut pkg_to_header = HashMap::new();
-        let mut lib_rs_content = r#"
-extern crate alloc;
-extern crate core;
-extern crate proc_macro;
-
-"#
-        .to_string();
+        let mut stdlib_modules = String::new();
+        let mut package_modules = HashMap::new();
         let headers_dir = &self.dirs.headers_dir;
         let profile_dir = &self.dirs.profile_dir;
 
@@ -508,7 +698,7 @@ extern crate proc_macro;
                     stdlib_externs: &stdlib_externs,
                 },
                 &mut pkg_to_header,
-                &mut lib_rs_content,
+                &mut stdlib_modules,
             )?;
             stdlib_externs.push((crate_name.clone(), rmeta_path.clone()));
         }
@@ -526,6 +716,7 @@ extern crate proc_macro;
             let rs_crate_name = crate_name.replace('-', "_");
             let hash = &artifact_info.hash;
 
+            let mut module = String::new();
             self.generate_crate_bindings(
                 CrateBindingInfo {
                     pkg_id_repr: &pkg_id.repr,
@@ -538,23 +729,45 @@ extern crate proc_macro;
                     stdlib_externs: &stdlib_externs,
                 },
                 &mut pkg_to_header,
-                &mut lib_rs_content,
+                &mut module,
             )?;
+            package_modules.insert(pkg_id.repr.clone(), module);
         }
 
-        Ok(lib_rs_content)
+        Ok(GeneratedBindings { stdlib_modules, package_modules })
+    }
+
+    /// Returns a static library named `name` that contains the bindings of `packages` and of their
+    /// dependencies.
+    fn static_lib<'a>(&self, name: String, packages: &[&'a Package]) -> StaticLib<'a> {
+        let mut closure = HashSet::new();
+        let mut stack = packages.iter().map(|package| package.id.clone()).collect::<Vec<_>>();
+        while let Some(pkg_id) = stack.pop() {
+            if !closure.insert(pkg_id.clone()) {
+                continue;
+            }
+            if let Some(node) = self.resolve.nodes.iter().find(|n| n.id == pkg_id) {
+                stack.extend(node.dependencies.iter().cloned());
+            }
+        }
+        StaticLib { name, main_package: packages[0], packages: closure }
     }
 
-    fn compile_staticlib(&self, lib_rs_content: String, metadata: &Metadata) -> Result<()> {
-        let root_name = &self.root.name;
+    fn compile_staticlib(
+        &self,
+        static_lib: &StaticLib<'_>,
+        bindings: &GeneratedBindings,
+        metadata: &Metadata,
+    ) -> Result<()> {
+        let root_name = &static_lib.name;
         let deps_dir = &self.dirs.deps_dir;
         let profile_dir = &self.dirs.profile_dir;
         let profile_name = &self.dirs.profile_name;
 
-        let root_artifact = self
-            .pkg_to_artifact
-            .get(&self.root.id.repr)
-            .ok_or_else(|| anyhow!("Failed to find root package artifact"))?;
+        let root_artifact =
+            self.pkg_to_artifact.get(&static_lib.main_package.id.repr).ok_or_else(|| {
+                anyhow!("Failed to find artifact of package '{}'", static_lib.main_package.name)
+            })?;
         let hash_suffix = if root_artifact.hash.is_empty() {
             "".to_string()
         } else {
@@ -563,6 +776,19 @@ extern crate proc_macro;
         let project_dir = deps_dir.join(format!("{}{}", root_name, hash_suffix));
         fs::create_dir_all(&project_dir)?;
 
+        let mut lib_rs_content = r#"
+extern crate alloc;
+extern crate core;
+extern crate proc_macro;
+
+"#
+        .to_string();
+        lib_rs_content.push_str(&bindings.stdlib_modules);
+        for pkg_id in self.ordered.iter().filter(|pkg_id| static_lib.packages.contains(*pkg_id)) {
+            if let Some(module) = bindings.package_modules.get(&pkg_id.repr) {
+                lib_rs_content.push_str(module);
+            }
+        }
         let lib_rs_path = project_dir.join(format!("{}_cc_api.rs", root_name));
         fs::write(&lib_rs_path, lib_rs_content)?;
 
@@ -583,7 +809,7 @@ crate-type = ["staticlib"]
 bridge_rust = {{ package = "crubit_bridge_rust", version = "0.0.1" }}
     "#,
             root_name = root_name,
-            edition = self.root.edition,
+            edition = static_lib.main_package.edition,
             lib_rs_filename = lib_rs_path.file_name().unwrap(),
         );
 
@@ -592,6 +818,7 @@ bridge_rust = {{ package = "crubit_bridge_rust", version = "0.0.1" }}
         for pkg in self
             .ordered
             .iter()
+            .filter(|pkg_id| static_lib.packages.contains(*pkg_id))
             .filter(|pkg_id| {
                 self.pkg_to_artifact
                     .get(&pkg_id.repr)
@@ -619,7 +846,7 @@ bridge_rust = {{ package = "crubit_bridge_rust", version = "0.0.1" }}
         fs::write(&cargo_toml_path, cargo_toml_content)?;
 
         let mut cargo_build = vec![
-            "build".to_string(),
+            "rustc".to_string(),
             "--manifest-path".to_string(),
             cargo_toml_path.to_string(),
             "--message-format=json".to_string(),
@@ -628,7 +855,14 @@ bridge_rust = {{ package = "crubit_bridge_rust", version = "0.0.1" }}
         if profile_name.as_str() == "release" {
             cargo_build.push("--release".to_string());
         }
-        let (child, stream) = stream_cargo_build(&cargo_build)?;
+        // The system libraries that the staticlib needs are reported by rustc as a note.
+        cargo_build.extend(["--".to_string(), "--print=native-static-libs".to_string()]);
+        let mut native_static_libs = vec![];
+        let (child, stream) = stream_cargo_build(&cargo_build, |diagnostic| {
+            if let Some(libs) = diagnostic.message.strip_prefix("native-static-libs:") {
+                native_static_libs = libs.split_whitespace().map(str::to_string).collect();
+            }
+        })?;
         let mut cargo_static_lib_path = None;
         for artifact in stream {
             let artifact =
@@ -657,6 +891,159 @@ bridge_rust = {{ package = "crubit_bridge_rust", version = "0.0.1" }}
             )
         })?;
 
+        self.write_package_configs(static_lib, &static_lib_path, &native_static_libs)?;
+
+        Ok(())
+    }
+
+    /// Writes the CMake package config and pkg-config file of `static_lib` to the output
+    /// directory.
+    fn write_package_configs(
+        &self,
+        static_lib: &StaticLib<'_>,
+        static_lib_path: &Utf8Path,
+        native_static_libs: &[String],
+    ) -> Result<()> {
+        let name = &static_lib.name;
+        let version = &static_lib.main_package.version;
+        let headers_dir = &self.dirs.headers_dir;
+        let cpp_std = &self.cpp_std;
+        // CMake only knows the standards by number, e.g. `cxx_std_17` for `c++17` or `gnu++17`.
+        let cxx_standard = match cpp_std
+            .strip_prefix("c++")
+            .or_else(|| cpp_std.strip_prefix("gnu++"))
+            .filter(|version| version.chars().all(|c| c.is_ascii_digit()))
+        {
+            Some(version) => format!("INTERFACE_COMPILE_FEATURES \"cxx_std_{version}\""),
+            None => format!("INTERFACE_COMPILE_OPTIONS \"-std={cpp_std}\""),
+        };
+        let mut include_dirs = vec![headers_dir.clone()];
+        include_dirs.extend(self.crubit_root.clone());
+
+        // CMake expects bare library names (`-lm` becomes `m`), while flags such as
+        // `-framework Security` are passed to the linker as-is.
+        let mut cmake_link_libraries = vec![];
+        let mut libs = native_static_libs.iter();
+        while let Some(lib) = libs.next() {
+            match lib.strip_prefix("-l") {
+                Some(lib) => cmake_link_libraries.push(lib.to_string()),
+                None if lib == "-framework" => {
+                    let framework = libs.next().map(String::as_str).unwrap_or_default();
+                    cmake_link_libraries.push(format!("-framework {framework}"));
+                }
+                None => cmake_link_libraries.push(lib.clone()),
+            }
+        }
+
+        // Items of the main package may depend on its features, e.g. `#[cfg(feature = "foo")]`.
+        let macro_name = |name: &str| {
+            name.to_ascii_uppercase().replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        };
+        let mut compile_definitions = vec![format!("CRUBIT_HAS_{}", macro_name(name))];
+        let main_node =
+            self.resolve.nodes.iter().find(|node| node.id == static_lib.main_package.id);
+        let mut features: Vec<&str> = main_node
+            .iter()
+            .flat_map(|node| node.features.iter())
+            .map(|feature| feature.as_str())
+            .collect();
+        features.sort();
+        for feature in features {
+            compile_definitions.push(format!(
+                "{}_FEATURE_{}",
+                macro_name(name),
+                macro_name(feature)
+            ));
+        }
+
+        let cmake_dir = self.dirs.out_dir.join("lib").join("cmake").join(name);
+        fs::create_dir_all(&cmake_dir)?;
+        fs::write(
+            cmake_dir.join(format!("{name}Config.cmake")),
+            format!(
+                r#"# Automatically @generated by `cargo cpp_api_from_rust`.
+
+if(TARGET {name}::{name})
+  return()
+endif()
+
+add_library({name}::{name} STATIC IMPORTED)
+set_target_properties({name}::{name} PROPERTIES
+  IMPORTED_LOCATION "{static_lib_path}"
+  INTERFACE_INCLUDE_DIRECTORIES "{include_dirs}"
+  INTERFACE_LINK_LIBRARIES "{link_libraries}"
+  INTERFACE_COMPILE_DEFINITIONS "{compile_definitions}"
+  {cxx_standard}
+)
+
+set({name}_VERSION "{version}")
+set({name}_FOUND TRUE)
+"#,
+                include_dirs =
+                    include_dirs.iter().map(Utf8PathBuf::as_str).collect::<Vec<_>>().join(";"),
+                link_libraries = cmake_link_libraries.join(";"),
+                compile_definitions = compile_definitions.join(";"),
+            ),
+        )?;
+        // Follows Cargo's semver rules: `1.2.3` is compatible with `1.0` but not `2.0`, and
+        // `0.1.2` is compatible with `0.1` but not `0.2`.
+        fs::write(
+            cmake_dir.join(format!("{name}ConfigVersion.cmake")),
+            format!(
+                r#"# Automatically @generated by `cargo cpp_api_from_rust`.
+
+set(PACKAGE_VERSION "{major}.{minor}.{patch}")
+
+if(NOT PACKAGE_FIND_VERSION)
+  set(PACKAGE_VERSION_COMPATIBLE TRUE)
+elseif(PACKAGE_VERSION VERSION_LESS PACKAGE_FIND_VERSION)
+  set(PACKAGE_VERSION_COMPATIBLE FALSE)
+elseif(NOT PACKAGE_FIND_VERSION_MAJOR EQUAL {major})
+  set(PACKAGE_VERSION_COMPATIBLE FALSE)
+elseif({major} EQUAL 0 AND PACKAGE_FIND_VERSION_COUNT GREATER 1
+       AND NOT PACKAGE_FIND_VERSION_MINOR EQUAL {minor})
+  set(PACKAGE_VERSION_COMPATIBLE FALSE)
+else()
+  set(PACKAGE_VERSION_COMPATIBLE TRUE)
+  if(PACKAGE_FIND_VERSION STREQUAL PACKAGE_VERSION)
+    set(PACKAGE_VERSION_EXACT TRUE)
+  endif()
+endif()
+"#,
+                major = version.major,
+                minor = version.minor,
+                patch = version.patch,
+            ),
+        )?;
+
+        let pkgconfig_dir = self.dirs.out_dir.join("lib").join("pkgconfig");
+        fs::create_dir_all(&pkgconfig_dir)?;
+        fs::write(
+            pkgconfig_dir.join(format!("{name}.pc")),
+            format!(
+                r#"# Automatically @generated by `cargo cpp_api_from_rust`.
+
+includedir={headers_dir}
+
+Name: {name}
+Description: C++ bindings for Rust, generated by Crubit
+Version: {version}
+Cflags: -std={cpp_std} {include_flags} {defines}
+Libs: {static_lib_path} {native_static_libs}
+"#,
+                native_static_libs = native_static_libs.join(" "),
+                include_flags = std::iter::once("-I${includedir}".to_string())
+                    .chain(self.crubit_root.iter().map(|crubit_root| format!("-I{crubit_root}")))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                defines = compile_definitions
+                    .iter()
+                    .map(|definition| format!("-D{definition}"))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+        )?;
+
         Ok(())
     }
 }
@@ -16,7 +16,7 @@ use std::path::PathBuf;
 
 use dyn_format::Format;
 
-#[derive(Debug, Parser)]
+#[derive(Clone, Debug, Parser)]
 #[clap(name = "cc_bindings_from_rs")]
 #[clap(about = "Generates C++ bindings for a Rust crate", long_about = None)]
 pub struct Cmdline {
@@ -205,6 +205,18 @@ pub struct Cmdline {
     /// List of source files whose symbols should be excluded from the generated bindings.
     #[clap(long = "ignore-symbols-from-files", value_parser, value_name = "FILE")]
     pub ignore_symbols_from_files: Vec<PathBuf>,
+
+    /// Directory of a cache of generated bindings, shared between invocations.
+    ///
+    /// If the command line, the version of Crubit, and the contents of all the files read to
+    /// generate the bindings match an earlier invocation, its outputs are copied from the cache
+    /// instead of being generated again.
+    #[clap(long, value_parser, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Print the hit rate and size of the `--cache-dir` cache to stderr.
+    #[clap(long, value_parser, value_name = "BOOL", default_value_t = false)]
+    pub cache_stats: bool,
 }
 
 impl Cmdline {
@@ -646,4 +658,27 @@ mod tests {
         .unwrap();
         assert_eq!(false, cmdline.enable_rmeta_interface);
     }
+
+    #[test]
+    fn test_cache_dir() {
+        let cmdline = new_cmdline([
+            "--h-out=foo.h",
+            "--rs-out=foo_impl.rs",
+            "--crubit-support-path-format=<crubit/support/{header}>",
+        ])
+        .unwrap();
+        assert_eq!(None, cmdline.cache_dir);
+        assert_eq!(false, cmdline.cache_stats);
+
+        let cmdline = new_cmdline([
+            "--h-out=foo.h",
+            "--rs-out=foo_impl.rs",
+            "--crubit-support-path-format=<crubit/support/{header}>",
+            "--cache-dir=/tmp/crubit_cache",
+            "--cache-stats",
+        ])
+        .unwrap();
+        assert_eq!(Some(PathBuf::from("/tmp/crubit_cache")), cmdline.cache_dir);
+        assert_eq!(true, cmdline.cache_stats);
+    }
 }
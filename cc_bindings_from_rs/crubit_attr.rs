@@ -16,6 +16,8 @@ extern crate rustc_middle;
 extern crate rustc_span;
 
 use anyhow::{bail, ensure, Result};
+use crubit_feature::CrubitFeature;
+use flagset::FlagSet;
 use rustc_hir::def::DefKind;
 use rustc_middle::ty::TyCtxt;
 use rustc_span::def_id::DefId;
@@ -125,6 +127,18 @@ pub struct CrubitAttrs {
 
     /// Whether the annotated item should be skipped during C++ bindings generation.
     pub do_not_bind: bool,
+
+    /// Crubit features enabled for the annotated item (and the items nested in it), in addition
+    /// to the features of its crate.
+    ///
+    /// This lets a crate try out an experimental feature on a few items, rather than enabling it
+    /// for the whole crate. For example:
+    ///
+    /// ```
+    /// #[crubit_annotate::feature("experimental")]
+    /// pub fn new() -> i32 {...}
+    /// ```
+    pub features: FlagSet<CrubitFeature>,
 }
 
 impl CrubitAttrs {
@@ -141,6 +155,7 @@ impl CrubitAttrs {
     pub const SAME_ABI: &'static str = "same_abi";
     pub const CPP_ORIGINATED_THREAD_SAFE: &'static str = "cpp_thread_safe";
     pub const DO_NOT_BIND: &'static str = "do_not_bind";
+    pub const FEATURE: &'static str = "feature";
 
     fn add_attr(&mut self, name: &str, symbol: Symbol) -> Result<()> {
         let set_opt_once = |slot: &mut Option<Symbol>, symbol: Symbol| -> Result<()> {
@@ -173,6 +188,13 @@ impl CrubitAttrs {
             CrubitAttrs::SAME_ABI => set_bool_once(&mut self.same_abi)?,
             CrubitAttrs::CPP_ORIGINATED_THREAD_SAFE => set_bool_once(&mut self.cpp_thread_safe)?,
             CrubitAttrs::DO_NOT_BIND => set_bool_once(&mut self.do_not_bind)?,
+            CrubitAttrs::FEATURE => {
+                let Some(features) = crubit_feature::named_features(symbol.as_str().as_bytes())
+                else {
+                    bail!("Invalid Crubit feature: \"{symbol}\"");
+                };
+                self.features |= features;
+            }
             _ => bail!("Invalid CRUBIT_ANNOTATE key: \"{name}\""),
         }
         Ok(())
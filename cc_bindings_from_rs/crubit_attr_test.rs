@@ -7,6 +7,7 @@
 
 use anyhow::Result;
 use crubit_attr::{get_attrs, CrubitAttrs};
+use crubit_feature::CrubitFeature;
 use run_compiler_test_support::{find_def_id_by_name, run_compiler_for_testing};
 use rustc_middle::ty::TyCtxt;
 use rustc_span::symbol::Symbol;
@@ -162,3 +163,31 @@ fn test_do_not_bind_invalid_on_struct() {
         );
     });
 }
+
+#[test]
+fn test_feature() {
+    let test_src = r#"
+            #[doc="CRUBIT_ANNOTATE: feature=experimental"]
+            #[doc="CRUBIT_ANNOTATE: feature=template_instantiation"]
+            pub fn foo() {}
+    "#;
+    run_compiler_for_testing(test_src, |tcx| {
+        let attrs = attrs_for_named_def(tcx, "foo").unwrap();
+        let mut expected_attrs = CrubitAttrs::default();
+        expected_attrs.features =
+            CrubitFeature::Experimental | CrubitFeature::TemplateInstantiation;
+        assert_eq!(attrs, expected_attrs);
+    });
+}
+
+#[test]
+fn test_feature_invalid() {
+    let test_src = r#"
+            #[doc="CRUBIT_ANNOTATE: feature=no_such_feature"]
+            pub fn foo() {}
+    "#;
+    run_compiler_for_testing(test_src, |tcx| {
+        let err = attrs_for_named_def(tcx, "foo").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid Crubit feature: \"no_such_feature\"");
+    });
+}
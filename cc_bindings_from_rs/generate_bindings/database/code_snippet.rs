@@ -155,6 +155,15 @@ impl<'tcx> RsStdTemplateSpecialization<'tcx> {
     pub fn is_vec(&self) -> bool {
         matches!(self.args, RsStdSpecializationArgs::Vec(_))
     }
+    pub fn is_arc_or_rc(&self) -> bool {
+        matches!(self.args, RsStdSpecializationArgs::Arc(_) | RsStdSpecializationArgs::Rc(_))
+    }
+    pub fn is_box(&self) -> bool {
+        matches!(self.args, RsStdSpecializationArgs::Box(_))
+    }
+    pub fn is_hash_map(&self) -> bool {
+        matches!(self.args, RsStdSpecializationArgs::HashMap { .. })
+    }
 
     pub fn support_header(&self, db: &BindingsGenerator<'tcx>) -> CcInclude {
         match &self.args {
@@ -164,6 +173,10 @@ impl<'tcx> RsStdTemplateSpecialization<'tcx> {
             },
             RsStdSpecializationArgs::Tuple(_) => db.support_header("rs_std/tuple.h"),
             RsStdSpecializationArgs::Vec(_) => db.support_header("rs_std/vec.h"),
+            RsStdSpecializationArgs::Arc(_) => db.support_header("rs_std/arc.h"),
+            RsStdSpecializationArgs::Rc(_) => db.support_header("rs_std/rc.h"),
+            RsStdSpecializationArgs::Box(_) => db.support_header("rs_std/box.h"),
+            RsStdSpecializationArgs::HashMap { .. } => db.support_header("rs_std/hash_map.h"),
         }
     }
 }
@@ -188,6 +201,15 @@ pub enum RsStdSpecializationArgs<'tcx> {
     Enum(RsStdEnumSpecialization<'tcx>),
     Tuple(Vec<FormattedTy<'tcx>>),
     Vec(FormattedTy<'tcx>),
+    Arc(FormattedTy<'tcx>),
+    Rc(FormattedTy<'tcx>),
+    /// The `rs_std::BoxDeleter<T>` of a `Box<T>`, which is bridged to `rs_std::Box<T>`.
+    Box(FormattedTy<'tcx>),
+    /// An opaque `rs_std::HashMap<K, V>`, which is used when the `HashMap` isn't bridged.
+    HashMap {
+        key_ty: FormattedTy<'tcx>,
+        value_ty: FormattedTy<'tcx>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -249,8 +271,9 @@ impl<'tcx> FormattedTy<'tcx> {
         ty: Ty<'tcx>,
         location: crate::TypeLocation,
         db: &BindingsGenerator<'tcx>,
+        features: flagset::FlagSet<crubit_feature::CrubitFeature>,
     ) -> Result<Self> {
-        let for_cc = db.format_ty_for_cc(ty, location)?;
+        let for_cc = db.format_ty_for_cc(ty, location, features)?;
         let for_rs = db.format_ty_for_rs(ty)?;
         Ok(Self { ty, for_cc, for_rs })
     }
@@ -208,7 +208,7 @@ memoized::query_group! {
       /// Formats a C++ identifier, if possible.
       ///
       /// Implementation: cc_bindings_from_rs/generate_bindings/format_type.rs?q=function:format_cc_ident_symbol
-      fn format_cc_ident(&self, ident: Symbol) -> Result<Ident>;
+      fn format_cc_ident(&self, ident: Symbol, features: flagset::FlagSet<crubit_feature::CrubitFeature>) -> Result<Ident>;
 
       /// Formats the top-level namespace for the given crate, e.g. as `self::foo`, or
       /// `somecrate::foo`.
@@ -219,11 +219,14 @@ memoized::query_group! {
       /// Formats `ty` into a `CcSnippet` that represents how the type should be
       /// spelled in a C++ declaration of a function parameter or field.
       ///
+      /// `features` are the Crubit features of the item that uses `ty` (see `item_features`).
+      ///
       /// Implementation: cc_bindings_from_rs/generate_bindings/format_type.rs?q=function:format_ty_for_cc
       fn format_ty_for_cc(
           &self,
           ty: Ty<'tcx>,
           location: TypeLocation,
+          features: flagset::FlagSet<crubit_feature::CrubitFeature>,
       ) -> Result<CcSnippet<'tcx>>;
 
       /// Formats `ty` into a `CcSnippet` that represents how the type should be
@@ -334,7 +337,11 @@ memoized::query_group! {
       /// `format_ty`).  The 2nd case is needed for ADTs defined in any crate.
       fn generate_adt_core(&self, def_id: DefId) -> Result<Rc<AdtCoreBindings<'tcx>>>;
 
-      fn crubit_abi_type_from_ty(&self, ty: Ty<'tcx>) -> Result<CrubitAbiTypeWithCcPrereqs<'tcx>>;
+      fn crubit_abi_type_from_ty(
+          &self,
+          ty: Ty<'tcx>,
+          features: flagset::FlagSet<crubit_feature::CrubitFeature>,
+      ) -> Result<CrubitAbiTypeWithCcPrereqs<'tcx>>;
 
       /// Gathers all  `From` trait impls for the current crate and provides a mapping from the
       /// argument type to the impl. This is useful for determining `From` impls of ADTs where the
@@ -370,10 +377,15 @@ memoized::query_group! {
       /// and contains valid types. It does not check if the template specialization should be used
       /// (vs using composable bridging).
       ///
+      /// `features` are the Crubit features of the item that uses the specialization (see
+      /// `item_features`). They only decide whether the type arguments are supported: the C++
+      /// specialization of a type is the same for every item that uses it.
+      ///
       /// Implementation: cc_bindings_from_rs/generate_bindings/generate_template_specialization.rs?q=function:parse_rs_std_template_specialization
       fn parse_rs_std_template_specialization(
           &self,
           self_ty: Ty<'tcx>,
+          features: flagset::FlagSet<crubit_feature::CrubitFeature>,
       ) -> Option<Result<RsStdTemplateSpecialization<'tcx>>>;
   }
 }
@@ -391,4 +403,33 @@ impl<'tcx> BindingsGenerator<'tcx> {
         };
         features.copied().unwrap_or_else(|| self.default_features())
     }
+
+    /// Returns the features enabled for `def_id`: the features of its crate, and those enabled by
+    /// `#[crubit_annotate::feature(...)]` on the item or on any item that encloses it.
+    pub fn item_features(&self, def_id: DefId) -> flagset::FlagSet<crubit_feature::CrubitFeature> {
+        let tcx = self.tcx();
+        let mut features = self.crate_features(def_id.krate);
+        let mut current = Some(def_id);
+        while let Some(def_id) = current {
+            // Malformed attributes are reported when generating bindings for the item itself.
+            if let Ok(attrs) = crubit_attr::get_attrs(tcx, def_id) {
+                features |= attrs.features;
+            }
+            current = tcx.opt_parent(def_id);
+        }
+        features
+    }
+
+    /// Returns the features to generate bindings with on behalf of `def_id`: its `item_features`
+    /// if it is defined in the source crate, and the source crate's features otherwise (e.g. for
+    /// tuples or for specializations of types from other crates).
+    pub fn bindings_features(
+        &self,
+        def_id: Option<DefId>,
+    ) -> flagset::FlagSet<crubit_feature::CrubitFeature> {
+        match def_id {
+            Some(def_id) if def_id.is_local() => self.item_features(def_id),
+            _ => self.crate_features(self.source_crate_num()),
+        }
+    }
 }
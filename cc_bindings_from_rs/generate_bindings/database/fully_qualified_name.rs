@@ -70,9 +70,12 @@ pub struct FullyQualifiedName {
 fn format_ns_path_for_cc(
     db: &BindingsGenerator<'_>,
     ns: &NamespaceQualifier,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<TokenStream> {
-    let idents =
-        ns.parts().map(|s| db.format_cc_ident(Symbol::intern(s))).collect::<Result<Vec<_>>>()?;
+    let idents = ns
+        .parts()
+        .map(|s| db.format_cc_ident(Symbol::intern(s), features))
+        .collect::<Result<Vec<_>>>()?;
     Ok(quote! { #(#idents::)* })
 }
 
@@ -125,9 +128,9 @@ impl FullyQualifiedName {
         let cpp_top_level_ns = self
             .cpp_top_level_ns
             .iter()
-            .map(|ns| db.format_cc_ident(*ns))
+            .map(|ns| db.format_cc_ident(*ns, features))
             .collect::<Result<Vec<_>>>()?;
-        let ns_path = format_ns_path_for_cc(db, &self.cpp_ns_path)?;
+        let ns_path = format_ns_path_for_cc(db, &self.cpp_ns_path, features)?;
         let name = format_cc_type_name(name.as_str(), features)?;
         Ok(quote! { :: #(#cpp_top_level_ns::)* #ns_path #name })
     }
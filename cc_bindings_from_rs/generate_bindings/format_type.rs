@@ -17,12 +17,13 @@ use crate::{
     matches_qualified_name, CcType,
 };
 use arc_anyhow::{Context, Result};
-use code_gen_utils::CcInclude;
+use code_gen_utils::{CcConstQualifier, CcInclude};
 use crubit_abi_type::{CrubitAbiType, FullyQualifiedPath};
 use crubit_attr::BridgingAttrs;
 use crubit_feature::CrubitFeature;
 use database::code_snippet::{
-    CcPrerequisites, CcSnippet, CrubitAbiTypeWithCcPrereqs, TemplateSpecialization,
+    CcPrerequisites, CcSnippet, CrubitAbiTypeWithCcPrereqs, RsStdSpecializationArgs,
+    TemplateSpecialization,
 };
 use database::BindingsGenerator;
 use database::{
@@ -110,17 +111,27 @@ pub fn format_top_level_ns_for_crate(db: &BindingsGenerator<'_>, krate: CrateNum
     }
 }
 
-pub fn format_cc_ident_symbol(db: &BindingsGenerator, ident: Symbol) -> Result<Ident> {
-    format_cc_ident(db, ident.as_str())
+pub fn format_cc_ident_symbol(
+    db: &BindingsGenerator,
+    ident: Symbol,
+    features: flagset::FlagSet<CrubitFeature>,
+) -> Result<Ident> {
+    format_cc_ident(db, ident.as_str(), features)
 }
 
 /// Implementation of `BindingsGenerator::format_cc_ident`.
-pub fn format_cc_ident(db: &BindingsGenerator, ident: &str) -> Result<Ident> {
+///
+/// `features` should be the features of the item that declares `ident`, or
+/// those of its crate for identifiers shared between items (e.g. namespaces).
+pub fn format_cc_ident(
+    db: &BindingsGenerator,
+    ident: &str,
+    features: flagset::FlagSet<CrubitFeature>,
+) -> Result<Ident> {
     // TODO(b/254104998): Check whether the crate where the identifier is defined is
     // enabled for the feature. Right now if the dep enables the feature but the
     // current crate doesn't, we will escape the identifier in the dep but
     // consider it failed in the current crate.
-    let features = db.crate_features(db.source_crate_num());
     if check_feature_enabled_on_self_and_all_deps(db, FineGrainedFeature::EscapeCppReservedKeyword)
     {
         code_gen_utils::format_cc_ident(
@@ -137,6 +148,7 @@ fn format_pointer_or_reference_ty_for_cc<'tcx>(
     pointee: Ty<'tcx>,
     mutability: Mutability,
     pointer_sigil: TokenStream,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<CcSnippet<'tcx>> {
     let tcx = db.tcx();
     let const_qualifier = match mutability {
@@ -146,7 +158,8 @@ fn format_pointer_or_reference_ty_for_cc<'tcx>(
     if pointee.is_c_void(tcx) {
         return Ok(CcSnippet { tokens: quote! { #const_qualifier void* }, ..Default::default() });
     }
-    let CcSnippet { tokens, mut prereqs } = db.format_ty_for_cc(pointee, TypeLocation::Other)?;
+    let CcSnippet { tokens, mut prereqs } =
+        db.format_ty_for_cc(pointee, TypeLocation::Other, features)?;
     prereqs.move_defs_to_fwd_decls();
     Ok(CcSnippet { prereqs, tokens: quote! { #tokens #const_qualifier #pointer_sigil } })
 }
@@ -155,9 +168,10 @@ fn format_slice_ref_for_cc<'tcx>(
     db: &BindingsGenerator<'tcx>,
     element_ty: Ty<'tcx>,
     mutability: rustc_middle::mir::Mutability,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<CcSnippet<'tcx>> {
     let CcSnippet { mut tokens, mut prereqs } =
-        db.format_ty_for_cc(element_ty, TypeLocation::Other).with_context(|| {
+        db.format_ty_for_cc(element_ty, TypeLocation::Other, features).with_context(|| {
             format!("Failed to format the element type of the slice type `{element_ty}`")
         })?;
     prereqs.includes.insert(db.support_header("rs_std/slice_ref.h"));
@@ -204,6 +218,7 @@ fn format_transparent_pointee_or_reference_for_cc<'tcx>(
     referent_ty: Ty<'tcx>,
     mutability: rustc_middle::mir::Mutability,
     pointer_sigil: TokenStream,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Option<CcSnippet<'tcx>> {
     let ty::TyKind::Adt(adt, substs) = referent_ty.kind() else {
         return None;
@@ -216,7 +231,7 @@ fn format_transparent_pointee_or_reference_for_cc<'tcx>(
     }
 
     let referent = substs[0].expect_ty();
-    format_pointer_or_reference_ty_for_cc(db, referent, mutability, pointer_sigil).ok()
+    format_pointer_or_reference_ty_for_cc(db, referent, mutability, pointer_sigil, features).ok()
 }
 
 fn format_legacy_bridged_type_with_placeholders<'tcx>(
@@ -225,6 +240,7 @@ fn format_legacy_bridged_type_with_placeholders<'tcx>(
     adt: ty::AdtDef<'tcx>,
     substs: &'tcx ty::List<ty::GenericArg<'tcx>>,
     prereqs: &mut CcPrerequisites<'tcx>,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<TokenStream> {
     let tcx = db.tcx();
     let generics = tcx.generics_of(adt.did());
@@ -256,7 +272,7 @@ fn format_legacy_bridged_type_with_placeholders<'tcx>(
         if !result_str.contains(&placeholder) {
             continue;
         }
-        let snippet = db.format_ty_for_cc(ty, TypeLocation::Other).map_err(|err| {
+        let snippet = db.format_ty_for_cc(ty, TypeLocation::Other, features).map_err(|err| {
             let err = err.to_string().replace('\n', "\n  ");
             anyhow!(
                 "`{ty}` has no layout-compatible C++ type, but is used as a generic parameter\n  {err}"
@@ -348,6 +364,7 @@ pub fn format_ty_for_cc<'tcx>(
     db: &BindingsGenerator<'tcx>,
     ty: Ty<'tcx>,
     location: TypeLocation,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<CcSnippet<'tcx>> {
     let tcx = db.tcx();
 
@@ -393,12 +410,9 @@ pub fn format_ty_for_cc<'tcx>(
             if types.is_empty() && matches!(location, TypeLocation::FnReturn { .. }) {
                 keyword(quote! { void })
             } else if !location.is_bridgeable()
-                || (db
-                    .crate_features(db.source_crate_num())
-                    .contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
-                    && !types.is_empty())
+                || (features.contains(CrubitFeature::LayoutCompatTuple) && !types.is_empty())
             {
-                let Some(rs_std) = db.parse_rs_std_template_specialization(ty) else {
+                let Some(rs_std) = db.parse_rs_std_template_specialization(ty, features) else {
                     bail!("Tuple type `{ty}` is not supported in this context");
                 };
                 let rs_std = rs_std?;
@@ -425,7 +439,7 @@ pub fn format_ty_for_cc<'tcx>(
 
                 let mut cc_types = Vec::with_capacity(types.len());
                 for element_type in types {
-                    if is_bridged_type(db, element_type)?
+                    if is_bridged_type(db, element_type, features)?
                         .is_some_and(|b| matches!(b, BridgedType::Composable(_)))
                     {
                         bail!(
@@ -435,7 +449,7 @@ pub fn format_ty_for_cc<'tcx>(
                         );
                     }
                     cc_types.push(
-                        db.format_ty_for_cc(element_type, TypeLocation::NestedBridgeable)?
+                        db.format_ty_for_cc(element_type, TypeLocation::NestedBridgeable, features)?
                             .into_tokens(&mut prereqs),
                     );
                 }
@@ -452,8 +466,9 @@ pub fn format_ty_for_cc<'tcx>(
             // We need to be able to handle expressions at the type level that are not simple
             // numeric literals.
             let target_size = evaluate_const_as_u64(db.tcx(), length)?;
-            let cc_element_ty =
-                db.format_ty_for_cc(element_type, TypeLocation::Other)?.into_tokens(&mut prereqs);
+            let cc_element_ty = db
+                .format_ty_for_cc(element_type, TypeLocation::Other, features)?
+                .into_tokens(&mut prereqs);
             let c_int = Literal::u64_unsuffixed(target_size);
             CcSnippet { prereqs, tokens: quote! { ::std::array<#cc_element_ty, #c_int> } }
         }
@@ -528,8 +543,13 @@ pub fn format_ty_for_cc<'tcx>(
                     format_non_owning_pointer_prefix(db, region, referent, &mut prereqs)
                 };
 
-            let mut snippet =
-                format_pointer_or_reference_ty_for_cc(db, referent, Mutability::Mut, sigil)?;
+            let mut snippet = format_pointer_or_reference_ty_for_cc(
+                db,
+                referent,
+                Mutability::Mut,
+                sigil,
+                features,
+            )?;
             snippet.prereqs += prereqs;
             return Ok(snippet);
         }
@@ -549,7 +569,7 @@ pub fn format_ty_for_cc<'tcx>(
                 );
             }
 
-            let specialization = db.parse_rs_std_template_specialization(ty);
+            let specialization = db.parse_rs_std_template_specialization(ty, features);
             if specialization.as_ref().is_some_and(|specialization| {
                 // We only want to consider errors when bridging could not occur.
                 // Otherwise, fallthrough to the normal bridging logic.
@@ -558,9 +578,11 @@ pub fn format_ty_for_cc<'tcx>(
                     (!location.is_bridgeable() && rs_std_enum.is_option())
                         || rs_std_enum.is_result()
                         || rs_std_enum.is_vec()
-                        || db
-                            .crate_features(db.source_crate_num())
-                            .contains(CrubitFeature::AlwaysSpecializeGenericsInCppApiFromRust)
+                        || rs_std_enum.is_arc_or_rc()
+                        || rs_std_enum.is_hash_map()
+                        || (!rs_std_enum.is_box()
+                            && features
+                                .contains(CrubitFeature::AlwaysSpecializeGenericsInCppApiFromRust))
                 });
                 error_occurred || is_option_or_result
             }) {
@@ -581,7 +603,7 @@ pub fn format_ty_for_cc<'tcx>(
                         .insert(TemplateSpecialization::RsStd(rs_std));
                 }
                 return Ok(CcSnippet { tokens, prereqs });
-            } else if let Some(bridged_type) = is_bridged_type(db, ty)? {
+            } else if let Some(bridged_type) = is_bridged_type(db, ty, features)? {
                 if !bridged_type.is_layout_compatible() {
                     location.check_bridgeable()?;
                 }
@@ -603,6 +625,7 @@ pub fn format_ty_for_cc<'tcx>(
                                 adt,
                                 substs,
                                 &mut prereqs,
+                                features,
                             )?
                         } else {
                             match cpp_type_str.parse::<TokenStream>() {
@@ -623,6 +646,13 @@ pub fn format_ty_for_cc<'tcx>(
                         // The existence of crubit_abi_type implies that the type can fully
                         // composably bridge.
                         let mut tokens = composable.cpp_type.to_token_stream();
+                        // The hasher and allocator of a collection or `Box` are not part of the C++
+                        // type.
+                        let substs = match BridgedCollection::new(db, adt) {
+                            Some(collection) => collection.type_args(db, adt, substs)?,
+                            None if adt.is_box() => &substs[..1],
+                            None => &substs[..],
+                        };
                         if !substs.is_empty() {
                             let mut generic_types_tokens = Vec::with_capacity(substs.len());
                             for subst in substs {
@@ -630,6 +660,7 @@ pub fn format_ty_for_cc<'tcx>(
                                     db,
                                     subst.expect_ty(),
                                     TypeLocation::NestedBridgeable,
+                                    features,
                                 )?;
                                 generic_types_tokens
                                     .push(snippet.into_tokens(&mut composable.prereqs));
@@ -669,16 +700,20 @@ pub fn format_ty_for_cc<'tcx>(
         ty::TyKind::RawPtr(pointee_ty, mutbl) => {
             if let ty::TyKind::Slice(slice_ty) = pointee_ty.kind() {
                 check_slice_layout(db.tcx(), ty);
-                return format_slice_ref_for_cc(db, *slice_ty, mutbl);
+                return format_slice_ref_for_cc(db, *slice_ty, mutbl, features);
             }
             // Early return in case we handle a transparent pointer type.
-            if let Some(snippet) =
-                format_transparent_pointee_or_reference_for_cc(db, pointee_ty, mutbl, quote! { * })
-            {
+            if let Some(snippet) = format_transparent_pointee_or_reference_for_cc(
+                db,
+                pointee_ty,
+                mutbl,
+                quote! { * },
+                features,
+            ) {
                 return Ok(snippet);
             }
 
-            format_pointer_or_reference_ty_for_cc(db, pointee_ty, mutbl, quote! { * })
+            format_pointer_or_reference_ty_for_cc(db, pointee_ty, mutbl, quote! { * }, features)
                 .with_context(|| {
                     format!("Failed to format the pointee of the pointer type `{ty}`")
                 })?
@@ -687,7 +722,7 @@ pub fn format_ty_for_cc<'tcx>(
         ty::TyKind::Ref(region, referent, mutability) => {
             if let ty::TyKind::Slice(element_ty) = *referent.kind() {
                 check_slice_layout(db.tcx(), ty);
-                return format_slice_ref_for_cc(db, element_ty, mutability);
+                return format_slice_ref_for_cc(db, element_ty, mutability, features);
             }
 
             if matches!(referent.kind(), ty::TyKind::Str) {
@@ -727,17 +762,21 @@ pub fn format_ty_for_cc<'tcx>(
                 referent,
                 mutability,
                 ptr_or_ref_prefix.clone(),
+                features,
             ) {
                 snippet.prereqs += prereqs;
                 return Ok(snippet);
             }
 
-            let tokens =
-                format_pointer_or_reference_ty_for_cc(db, referent, mutability, ptr_or_ref_prefix)
-                    .with_context(|| {
-                        format!("Failed to format the referent of the reference type `{ty}`")
-                    })?
-                    .into_tokens(&mut prereqs);
+            let tokens = format_pointer_or_reference_ty_for_cc(
+                db,
+                referent,
+                mutability,
+                ptr_or_ref_prefix,
+                features,
+            )
+            .with_context(|| format!("Failed to format the referent of the reference type `{ty}`"))?
+            .into_tokens(&mut prereqs);
             CcSnippet { tokens, prereqs }
         }
         ty::TyKind::FnPtr(sig_tys, fn_header) => {
@@ -804,9 +843,9 @@ pub fn format_ty_for_cc<'tcx>(
             let mut prereqs = CcPrerequisites::default();
             prereqs.includes.insert(db.support_header("internal/cxx20_backports.h"));
 
-            let ret_type = format_ret_ty_for_cc(db, &sig)?.into_tokens(&mut prereqs);
+            let ret_type = format_ret_ty_for_cc(db, &sig, features)?.into_tokens(&mut prereqs);
             let param_types =
-                format_param_types_for_cc_api(db, &sig, /*has_self_param=*/ false)?
+                format_param_types_for_cc_api(db, &sig, /*has_self_param=*/ false, features)?
                     .into_iter()
                     .map(|cc_param| cc_param.snippet.into_tokens(&mut prereqs));
             let tokens = quote! {
@@ -904,9 +943,10 @@ fn treat_ref_as_ptr<'tcx>(
 pub fn format_ret_ty_for_cc<'tcx>(
     db: &BindingsGenerator<'tcx>,
     sig_mid: &ty::FnSig<'tcx>,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<CcSnippet<'tcx>> {
     let output_ty = sig_mid.output();
-    db.format_ty_for_cc(output_ty, TypeLocation::FnReturn { is_constructor: false })
+    db.format_ty_for_cc(output_ty, TypeLocation::FnReturn { is_constructor: false }, features)
         .with_context(|| format!("Error formatting function return type `{output_ty}`"))
 }
 
@@ -953,13 +993,12 @@ fn format_param_types_for_cc_impl<'tcx>(
     sig_mid: &ty::FnSig<'tcx>,
     has_self_param: bool,
     is_thunk: bool,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<Vec<CcParamTy<'tcx>>> {
     let elided_is_output = has_elided_region(db.tcx(), sig_mid.output());
     let param_types = sig_mid.inputs();
     let mut snippets = Vec::with_capacity(param_types.len());
-    let ctor_plain_values = db
-        .crate_features(db.source_crate_num())
-        .contains(crubit_feature::CrubitFeature::CtorPlainValues);
+    let ctor_plain_values = features.contains(CrubitFeature::CtorPlainValues);
 
     for (i, mut param_type) in param_types.iter().copied().enumerate() {
         let is_self_param = i == 0 && has_self_param;
@@ -974,7 +1013,7 @@ fn format_param_types_for_cc_impl<'tcx>(
         }
 
         let cc_type = db
-            .format_ty_for_cc(param_type, location)
+            .format_ty_for_cc(param_type, location, features)
             .with_context(|| format!("Error handling parameter #{i} of type `{param_type}`"))?;
         snippets.push(CcParamTy {
             snippet: cc_type,
@@ -992,8 +1031,9 @@ pub fn format_param_types_for_cc_api<'tcx>(
     db: &BindingsGenerator<'tcx>,
     sig_mid: &ty::FnSig<'tcx>,
     has_self_param: bool,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<Vec<CcParamTy<'tcx>>> {
-    format_param_types_for_cc_impl(db, sig_mid, has_self_param, /*is_thunk=*/ false)
+    format_param_types_for_cc_impl(db, sig_mid, has_self_param, /*is_thunk=*/ false, features)
 }
 
 /// Returns the C++ parameter types for the thunks.
@@ -1001,8 +1041,9 @@ pub fn format_param_types_for_cc_thunk<'tcx>(
     db: &BindingsGenerator<'tcx>,
     sig_mid: &ty::FnSig<'tcx>,
     has_self_param: bool,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<Vec<CcParamTy<'tcx>>> {
-    format_param_types_for_cc_impl(db, sig_mid, has_self_param, /*is_thunk=*/ true)
+    format_param_types_for_cc_impl(db, sig_mid, has_self_param, /*is_thunk=*/ true, features)
 }
 
 fn try_ty_as_maybe_uninit<'tcx>(
@@ -1120,16 +1161,35 @@ pub fn format_ty_for_rs<'tcx>(db: &BindingsGenerator<'tcx>, ty: Ty<'tcx>) -> Res
             quote! { [ #rs_element_type; #unsuffixed_length ] }
         }
         ty::TyKind::Adt(adt, substs) => {
-            if let Some(BridgedBuiltin::Vec) = BridgedBuiltin::new(db, adt) {
+            let alloc_path = match BridgedBuiltin::new(db, adt) {
+                Some(BridgedBuiltin::Vec) => Some(quote! { ::alloc::vec::Vec }),
+                Some(BridgedBuiltin::Arc) => Some(quote! { ::alloc::sync::Arc }),
+                Some(BridgedBuiltin::Rc) => Some(quote! { ::alloc::rc::Rc }),
+                Some(BridgedBuiltin::Box) => Some(quote! { ::alloc::boxed::Box }),
+                _ => None,
+            };
+            if let Some(alloc_path) = alloc_path {
                 let t_param = match substs[0].kind() {
                     ty::GenericArgKind::Type(ty) => db.format_ty_for_rs(ty)?,
-                    _ => panic!("First generic argument of Vec must be a type"),
+                    _ => panic!("First generic argument of {alloc_path} must be a type"),
                 };
-                return Ok(quote! { ::alloc::vec::Vec<#t_param> });
+                return Ok(quote! { #alloc_path<#t_param> });
+            }
+            if let Some(collection) = BridgedCollection::new(db, adt) {
+                let type_args = collection
+                    .type_args(db, adt, substs)?
+                    .iter()
+                    .map(|arg| db.format_ty_for_rs(arg.expect_ty()))
+                    .collect::<Result<Vec<TokenStream>>>()?;
+                let name = collection.rs_name();
+                return Ok(quote! { #name<#(#type_args),*> });
             }
-            let has_cpp_type = crubit_attr::get_attrs(db.tcx(), adt.did())?.cpp_type.is_some();
+            let attrs = crubit_attr::get_attrs(db.tcx(), adt.did())?;
+            let has_cpp_type = attrs.cpp_type.is_some();
+            // The Rust spelling doesn't depend on the enabled features, so this only checks the
+            // annotation. Whether the type can be bridged is checked by `format_ty_for_cc`.
             let has_composable_bridging =
-                matches!(is_bridged_type(db, ty)?, Some(BridgedType::Composable(_)));
+                matches!(attrs.get_bridging_attrs()?, Some(BridgingAttrs::Composable { .. }));
             // We support generics if they're for `std::option::Option` or `std::result::Result`.
             let is_supported_generic_type = BridgedBuiltin::new(db, adt).is_some()
                 || !has_non_lifetime_substs(substs)
@@ -1334,6 +1394,7 @@ pub fn ensure_ty_is_pointer_like<'tcx>(db: &BindingsGenerator<'tcx>, ty: Ty<'tcx
 pub fn crubit_abi_type_from_ty<'tcx>(
     db: &BindingsGenerator<'tcx>,
     ty: Ty<'tcx>,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<CrubitAbiTypeWithCcPrereqs<'tcx>> {
     Ok(CrubitAbiTypeWithCcPrereqs::from(match ty.kind() {
         ty::TyKind::Bool => CrubitAbiType::transmute("bool", "bool"),
@@ -1367,7 +1428,9 @@ pub fn crubit_abi_type_from_ty<'tcx>(
             if let Some(bridging_attrs) = attrs.get_bridging_attrs()? {
                 match bridging_attrs {
                     BridgingAttrs::Composable { abi_rust, abi_cpp, .. } => {
-                        return crubit_abi_type_from_bridged_adt(db, abi_rust, abi_cpp, substs);
+                        return crubit_abi_type_from_bridged_adt(
+                            db, abi_rust, abi_cpp, substs, features,
+                        );
                     }
                     BridgingAttrs::JustCppType { include_paths, cpp_type } => {
                         let fully_qualified_name = db.symbol_canonical_name(adt.did()).ok_or_else(|| {
@@ -1388,17 +1451,61 @@ pub fn crubit_abi_type_from_ty<'tcx>(
                             prereqs,
                         });
                     }
-                    _ => bail!("Failed to construct the CrubitAbiType for {ty} because it has bridging attrs for a different kind of bridging: {bridging_attrs:?}"),
+                    BridgingAttrs::ExternCFuncConverters { include_paths, cpp_type, .. } => {
+                        // Protobuf messages are passed by value using their converter functions,
+                        // but are composed with other bridged types by sharing the underlying C++
+                        // message.
+                        let Some(proto_message_rust_bridge) = proto_message_rust_bridge(db, *adt)
+                        else {
+                            bail!("Failed to construct the CrubitAbiType for {ty} because it is bridged with converter functions, and is not a protobuf message");
+                        };
+                        let fully_qualified_name =
+                            db.symbol_canonical_name(adt.did()).ok_or_else(|| {
+                                anyhow!("Failed to get canonical name for {:?}", adt.did())
+                            })?;
+                        let mut prereqs = CcPrerequisites::default();
+                        for path in &include_paths {
+                            prereqs.includes.insert(CcInclude::from_path(path.as_str()));
+                        }
+                        return Ok(CrubitAbiTypeWithCcPrereqs {
+                            crubit_abi_type: CrubitAbiType::ProtoMessage {
+                                proto_message_rust_bridge,
+                                rust_proto_path: FullyQualifiedPath {
+                                    start_with_colon2: true,
+                                    parts: fully_qualified_name.rs_name_parts().collect(),
+                                },
+                                cpp_proto_path: FullyQualifiedPath::new(cpp_type.as_str()),
+                            },
+                            prereqs,
+                        });
+                    }
                 }
             } else {
+                if BridgedProtoView::new(db, *adt).is_some() {
+                    // The view is a pointer to the C++ container, so it can be transmuted.
+                    let rust_type = db.format_ty_for_rs(ty)?;
+                    let CcSnippet { tokens: cpp_type, prereqs } =
+                        db.format_ty_for_cc(ty, TypeLocation::Other, features)?;
+                    return Ok(CrubitAbiTypeWithCcPrereqs {
+                        crubit_abi_type: CrubitAbiType::Transmute { rust_type, cpp_type },
+                        prereqs,
+                    });
+                }
+
                 // if it doesn't, try seeing if it's a builtin.
-                if let Some(bridged_builtin @ BridgedBuiltin::Option) =
+                if let Some(bridged_builtin @ (BridgedBuiltin::Option | BridgedBuiltin::Box)) =
                     BridgedBuiltin::new(db, *adt)
                 {
-                    return bridged_builtin.crubit_abi_type(db, substs);
+                    return bridged_builtin.crubit_abi_type(db, substs, features);
                 }
 
-                if let Some(spec) = db.parse_rs_std_template_specialization(ty) {
+                if let Some(collection) = BridgedCollection::new(db, *adt)
+                    && features.contains(CrubitFeature::BridgeCollections)
+                {
+                    return collection.crubit_abi_type(db, *adt, substs, features);
+                }
+
+                if let Some(spec) = db.parse_rs_std_template_specialization(ty, features) {
                     // Specifically when embedding a template specialization within an Option, we
                     // need it to be movable.
                     if !db.has_move_ctor_and_assignment_operator(Some(adt.did()), ty).is_some() {
@@ -1451,7 +1558,7 @@ pub fn crubit_abi_type_from_ty<'tcx>(
             // Do we need to confirm that pointee is layout compatible?
             let rust_type = db.format_ty_for_rs(pointee)?;
             let CcSnippet { tokens: cpp_type, prereqs } =
-                db.format_ty_for_cc(pointee, TypeLocation::Other)?;
+                db.format_ty_for_cc(pointee, TypeLocation::Other, features)?;
 
             return Ok(CrubitAbiTypeWithCcPrereqs {
                 crubit_abi_type: CrubitAbiType::Ptr {
@@ -1468,7 +1575,7 @@ pub fn crubit_abi_type_from_ty<'tcx>(
         ty::TyKind::Ref(_, _, _) => {
             let rust_type = db.format_ty_for_rs(ty)?;
             let CcSnippet { tokens: cpp_type, prereqs } =
-                db.format_ty_for_cc(ty, TypeLocation::Other)?;
+                db.format_ty_for_cc(ty, TypeLocation::Other, features)?;
             return Ok(CrubitAbiTypeWithCcPrereqs {
                 crubit_abi_type: CrubitAbiType::Transmute { rust_type, cpp_type },
                 prereqs,
@@ -1483,17 +1590,33 @@ pub enum BridgedBuiltin {
     Result,
     Option,
     Vec,
+    Arc,
+    Rc,
+    Box,
 }
 
 impl BridgedBuiltin {
-    /// Determines if an AdtDef is for a Result, Option, or Vec.
+    /// Determines if an AdtDef is for a Result, Option, Vec, Arc, Rc, or Box.
     pub fn new(db: &BindingsGenerator<'_>, adt: AdtDef<'_>) -> Option<Self> {
         let tcx = db.tcx();
+        if adt.is_box() {
+            return Some(BridgedBuiltin::Box);
+        }
         if tcx.is_diagnostic_item(rustc_span::symbol::sym::Vec, adt.did())
             || crate::matches_qualified_name(db, adt.did(), &["alloc", "vec", "Vec"])
         {
             return Some(BridgedBuiltin::Vec);
         }
+        if tcx.is_diagnostic_item(rustc_span::symbol::sym::Arc, adt.did())
+            || crate::matches_qualified_name(db, adt.did(), &["alloc", "sync", "Arc"])
+        {
+            return Some(BridgedBuiltin::Arc);
+        }
+        if tcx.is_diagnostic_item(rustc_span::symbol::sym::Rc, adt.did())
+            || crate::matches_qualified_name(db, adt.did(), &["alloc", "rc", "Rc"])
+        {
+            return Some(BridgedBuiltin::Rc);
+        }
 
         let variant = adt.variants().iter().next()?;
 
@@ -1511,31 +1634,83 @@ impl BridgedBuiltin {
         self,
         db: &BindingsGenerator<'tcx>,
         substs: &[GenericArg<'tcx>],
+        features: flagset::FlagSet<CrubitFeature>,
     ) -> Result<CrubitAbiTypeWithCcPrereqs<'tcx>> {
         match self {
-            BridgedBuiltin::Result | BridgedBuiltin::Vec => {
-                bail!("Result/Vec as a bridge type is not yet supported")
+            BridgedBuiltin::Result
+            | BridgedBuiltin::Vec
+            | BridgedBuiltin::Arc
+            | BridgedBuiltin::Rc => {
+                bail!("Result/Vec/Arc/Rc as a bridge type is not yet supported")
             }
             BridgedBuiltin::Option => {
-                let inner = db.crubit_abi_type_from_ty(substs[0].expect_ty())?;
+                let inner = db.crubit_abi_type_from_ty(substs[0].expect_ty(), features)?;
                 Ok(CrubitAbiTypeWithCcPrereqs {
                     crubit_abi_type: CrubitAbiType::option(inner.crubit_abi_type),
                     prereqs: inner.prereqs,
                 })
             }
+            BridgedBuiltin::Box => {
+                let tcx = db.tcx();
+                let pointee = substs[0].expect_ty();
+                let box_ty = Ty::new_box(tcx, pointee);
+                let ty::TyKind::Adt(_, box_substs) = box_ty.kind() else {
+                    unreachable!("Box<T> must be an ADT");
+                };
+                ensure!(
+                    box_substs[..] == *substs,
+                    "Bridging a Box with a custom allocator is not supported"
+                );
+                // C++ accesses the pointee in place, so it must not contain bridged types.
+                for ty in pointee.walk().filter_map(|arg| arg.as_type()) {
+                    if let Some(bridged) = is_bridged_type(db, ty, features)?
+                        && !bridged.is_layout_compatible()
+                    {
+                        bail!(
+                            "crubit.rs/errors/unsupported_type: Types containing generic parameter `{ty}` (which is a bridged type) are not supported."
+                        );
+                    }
+                }
+                // The `rs_std::BoxDeleter<T>` specialization drops the `Box` in Rust.
+                let spec = db
+                    .parse_rs_std_template_specialization(box_ty, features)
+                    .expect("Box should have an rs_std specialization")?;
+                let RsStdSpecializationArgs::Box(pointee_ty) = &spec.args else {
+                    unreachable!("Box should be specialized as a BoxDeleter");
+                };
+                let mut prereqs = CcPrerequisites::default();
+                let rust_type = pointee_ty.for_rs.clone();
+                let cpp_type = pointee_ty.for_cc.clone().into_tokens(&mut prereqs);
+                prereqs.includes.insert(spec.support_header(db));
+                prereqs.template_specializations.insert(TemplateSpecialization::RsStd(spec));
+                Ok(CrubitAbiTypeWithCcPrereqs {
+                    crubit_abi_type: CrubitAbiType::RustBox { rust_type, cpp_type },
+                    prereqs,
+                })
+            }
         }
     }
 
-    pub fn cpp_name(self) -> FullyQualifiedPath {
+    pub fn cpp_name(self) -> Result<FullyQualifiedPath> {
         match self {
-            BridgedBuiltin::Result | BridgedBuiltin::Vec => todo!(),
-            BridgedBuiltin::Option => FullyQualifiedPath::new("::std::optional"),
+            BridgedBuiltin::Result
+            | BridgedBuiltin::Vec
+            | BridgedBuiltin::Arc
+            | BridgedBuiltin::Rc => {
+                bail!("Result/Vec/Arc/Rc as a bridge type is not yet supported")
+            }
+            BridgedBuiltin::Option => Ok(FullyQualifiedPath::new("::std::optional")),
+            BridgedBuiltin::Box => Ok(FullyQualifiedPath::new("::rs_std::Box")),
         }
     }
 
     pub fn prereqs<'tcx>(self) -> CcPrerequisites<'tcx> {
         match self {
-            BridgedBuiltin::Result | BridgedBuiltin::Vec => CcPrerequisites::default(),
+            BridgedBuiltin::Result
+            | BridgedBuiltin::Vec
+            | BridgedBuiltin::Arc
+            | BridgedBuiltin::Rc
+            | BridgedBuiltin::Box => CcPrerequisites::default(),
             BridgedBuiltin::Option => {
                 let mut prereqs = CcPrerequisites::default();
                 prereqs.includes.insert(CcInclude::optional());
@@ -1545,6 +1720,286 @@ impl BridgedBuiltin {
     }
 }
 
+/// A standard library collection, which is bridged to a C++ container by converting each element
+/// when the item using it enables `CrubitFeature::BridgeCollections`. Otherwise, a `HashMap` is the
+/// opaque `rs_std::HashMap<K, V>` template specialization.
+#[derive(Copy, Clone)]
+pub enum BridgedCollection {
+    HashMap,
+    HashSet,
+    BTreeMap,
+    BTreeSet,
+}
+
+impl BridgedCollection {
+    /// Determines if an AdtDef is for a collection that can be bridged. Whether it is actually
+    /// bridged depends on `CrubitFeature::BridgeCollections`, which the caller checks.
+    pub fn new(db: &BindingsGenerator<'_>, adt: AdtDef<'_>) -> Option<Self> {
+        match db.tcx().get_diagnostic_name(adt.did())? {
+            rustc_span::symbol::sym::HashMap => Some(BridgedCollection::HashMap),
+            rustc_span::symbol::sym::HashSet => Some(BridgedCollection::HashSet),
+            rustc_span::symbol::sym::BTreeMap => Some(BridgedCollection::BTreeMap),
+            rustc_span::symbol::sym::BTreeSet => Some(BridgedCollection::BTreeSet),
+            _ => None,
+        }
+    }
+
+    /// Returns the generic args of the collection which are bridged: the key and value types of a
+    /// map, or the element type of a set.
+    ///
+    /// Returns an error if the collection has a custom hasher or allocator, which can't be
+    /// bridged.
+    pub fn type_args<'a, 'tcx>(
+        self,
+        db: &BindingsGenerator<'tcx>,
+        adt: AdtDef<'tcx>,
+        substs: &'a [GenericArg<'tcx>],
+    ) -> Result<&'a [GenericArg<'tcx>]> {
+        let tcx = db.tcx();
+        let type_args = tcx.generics_of(adt.did()).own_args_no_defaults(tcx, substs);
+        let expected_len = match self {
+            BridgedCollection::HashMap | BridgedCollection::BTreeMap => 2,
+            BridgedCollection::HashSet | BridgedCollection::BTreeSet => 1,
+        };
+        ensure!(
+            type_args.len() == expected_len,
+            "Bridging collections with a custom hasher or allocator is not supported"
+        );
+        Ok(type_args)
+    }
+
+    /// Returns the CrubitAbiType of the collection, which composes the CrubitAbiTypes of its
+    /// `type_args`.
+    pub fn crubit_abi_type<'tcx>(
+        self,
+        db: &BindingsGenerator<'tcx>,
+        adt: AdtDef<'tcx>,
+        substs: &[GenericArg<'tcx>],
+        features: flagset::FlagSet<CrubitFeature>,
+    ) -> Result<CrubitAbiTypeWithCcPrereqs<'tcx>> {
+        let mut prereqs = self.prereqs(db, features);
+        let type_args = self
+            .type_args(db, adt, substs)?
+            .iter()
+            .map(|arg| {
+                Ok(db
+                    .crubit_abi_type_from_ty(arg.expect_ty(), features)?
+                    .crubit_abi_type(&mut prereqs))
+            })
+            .collect::<Result<Rc<[CrubitAbiType]>>>()?;
+        let (rust_abi_path, cpp_abi_path) = match (self, self.uses_std_hash_containers(features)) {
+            (BridgedCollection::HashMap, false) => {
+                ("::bridge_rust::HashMapAbi", "::crubit::FlatHashMapAbi")
+            }
+            (BridgedCollection::HashMap, true) => {
+                ("::bridge_rust::HashMapAbi", "::crubit::StdUnorderedMapAbi")
+            }
+            (BridgedCollection::HashSet, false) => {
+                ("::bridge_rust::HashSetAbi", "::crubit::FlatHashSetAbi")
+            }
+            (BridgedCollection::HashSet, true) => {
+                ("::bridge_rust::HashSetAbi", "::crubit::StdUnorderedSetAbi")
+            }
+            (BridgedCollection::BTreeMap, _) => {
+                ("::bridge_rust::BTreeMapAbi", "::crubit::StdMapAbi")
+            }
+            (BridgedCollection::BTreeSet, _) => {
+                ("::bridge_rust::BTreeSetAbi", "::crubit::StdSetAbi")
+            }
+        };
+        Ok(CrubitAbiTypeWithCcPrereqs {
+            crubit_abi_type: CrubitAbiType::Type {
+                rust_abi_path: FullyQualifiedPath::new(rust_abi_path),
+                cpp_abi_path: FullyQualifiedPath::new(cpp_abi_path),
+                type_args,
+            },
+            prereqs,
+        })
+    }
+
+    /// Returns the Rust path of the collection, to be used in `..._cc_api_impl.rs`.
+    pub fn rs_name(self) -> TokenStream {
+        match self {
+            BridgedCollection::HashMap => quote! { ::std::collections::HashMap },
+            BridgedCollection::HashSet => quote! { ::std::collections::HashSet },
+            BridgedCollection::BTreeMap => quote! { ::std::collections::BTreeMap },
+            BridgedCollection::BTreeSet => quote! { ::std::collections::BTreeSet },
+        }
+    }
+
+    /// Returns the C++ container template that the collection is bridged to.
+    pub fn cpp_name(self, features: flagset::FlagSet<CrubitFeature>) -> FullyQualifiedPath {
+        FullyQualifiedPath::new(match (self, self.uses_std_hash_containers(features)) {
+            (BridgedCollection::HashMap, false) => "::absl::flat_hash_map",
+            (BridgedCollection::HashMap, true) => "::std::unordered_map",
+            (BridgedCollection::HashSet, false) => "::absl::flat_hash_set",
+            (BridgedCollection::HashSet, true) => "::std::unordered_set",
+            (BridgedCollection::BTreeMap, _) => "::std::map",
+            (BridgedCollection::BTreeSet, _) => "::std::set",
+        })
+    }
+
+    pub fn prereqs<'tcx>(
+        self,
+        db: &BindingsGenerator<'tcx>,
+        features: flagset::FlagSet<CrubitFeature>,
+    ) -> CcPrerequisites<'tcx> {
+        let mut prereqs = CcPrerequisites::default();
+        prereqs.includes.insert(db.support_header("bridge_collections.h"));
+        prereqs.includes.insert(match (self, self.uses_std_hash_containers(features)) {
+            (BridgedCollection::HashMap, false) => {
+                CcInclude::from_path("absl/container/flat_hash_map.h")
+            }
+            (BridgedCollection::HashMap, true) => CcInclude::from_path("<unordered_map>"),
+            (BridgedCollection::HashSet, false) => {
+                CcInclude::from_path("absl/container/flat_hash_set.h")
+            }
+            (BridgedCollection::HashSet, true) => CcInclude::from_path("<unordered_set>"),
+            (BridgedCollection::BTreeMap, _) => CcInclude::from_path("<map>"),
+            (BridgedCollection::BTreeSet, _) => CcInclude::from_path("<set>"),
+        });
+        prereqs
+    }
+
+    fn uses_std_hash_containers(self, features: flagset::FlagSet<CrubitFeature>) -> bool {
+        features.contains(CrubitFeature::StdHashCollections)
+    }
+}
+
+/// A view of a repeated or map field of a protobuf message. With the C++ protobuf kernel, the view
+/// wraps a pointer to the C++ container, so it is bridged to a `const` pointer to that container.
+#[derive(Copy, Clone)]
+pub enum BridgedProtoView {
+    RepeatedView,
+    MapView,
+}
+
+impl BridgedProtoView {
+    /// Determines if an AdtDef is for a `RepeatedView` or `MapView` of the C++ protobuf kernel.
+    pub fn new(db: &BindingsGenerator<'_>, adt: AdtDef<'_>) -> Option<Self> {
+        if matches_qualified_name(db, adt.did(), &["protobuf_cpp", "repeated", "RepeatedView"]) {
+            Some(BridgedProtoView::RepeatedView)
+        } else if matches_qualified_name(db, adt.did(), &["protobuf_cpp", "map", "MapView"]) {
+            Some(BridgedProtoView::MapView)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the BridgedType of the view, which is a `const` pointer to a
+    /// `google::protobuf::RepeatedField`, `google::protobuf::RepeatedPtrField`, or
+    /// `google::protobuf::Map`.
+    ///
+    /// Returns an error if an element, key, or value type has no C++ protobuf equivalent.
+    fn bridged_type<'tcx>(
+        self,
+        db: &BindingsGenerator<'tcx>,
+        ty: Ty<'tcx>,
+        substs: &'tcx ty::List<ty::GenericArg<'tcx>>,
+    ) -> Result<BridgedType<'tcx>> {
+        ensure_ty_is_pointer_like(db, ty)?;
+        let mut include_paths = vec![];
+        let mut types = substs.types();
+        let mut format_field_ty =
+            || format_proto_field_ty_for_cc(db, types.next().unwrap(), &mut include_paths);
+        let cpp_type = match self {
+            BridgedProtoView::RepeatedView => {
+                let (element, is_ptr_field) = format_field_ty()?;
+                if is_ptr_field {
+                    format!("const ::google::protobuf::RepeatedPtrField<{element}>*")
+                } else {
+                    format!("const ::google::protobuf::RepeatedField<{element}>*")
+                }
+            }
+            BridgedProtoView::MapView => {
+                let (key, _) = format_field_ty()?;
+                let (value, _) = format_field_ty()?;
+                format!("const ::google::protobuf::Map<{key}, {value}>*")
+            }
+        };
+        include_paths.push(Symbol::intern(match self {
+            BridgedProtoView::RepeatedView => "google/protobuf/repeated_field.h",
+            BridgedProtoView::MapView => "google/protobuf/map.h",
+        }));
+        Ok(BridgedType::Legacy {
+            cpp_type: CcType::Pointer {
+                cpp_type: Symbol::intern(&cpp_type),
+                cv: CcConstQualifier::Const,
+            },
+            include_paths,
+            conversion_info: BridgedTypeConversionInfo::PointerLikeTransmute { is_pointer: true },
+        })
+    }
+}
+
+/// Returns the C++ spelling of `ty` as the element, key, or value type of a protobuf field, and
+/// whether a repeated field of `ty` is a `RepeatedPtrField` rather than a `RepeatedField`.
+///
+/// The headers declaring the C++ type are added to `include_paths`.
+fn format_proto_field_ty_for_cc<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    ty: Ty<'tcx>,
+    include_paths: &mut Vec<Symbol>,
+) -> Result<(String, bool)> {
+    let scalar = match ty.kind() {
+        ty::TyKind::Bool => Some("bool"),
+        ty::TyKind::Int(ty::IntTy::I32) => Some("::std::int32_t"),
+        ty::TyKind::Int(ty::IntTy::I64) => Some("::std::int64_t"),
+        ty::TyKind::Uint(ty::UintTy::U32) => Some("::std::uint32_t"),
+        ty::TyKind::Uint(ty::UintTy::U64) => Some("::std::uint64_t"),
+        ty::TyKind::Float(ty::FloatTy::F32) => Some("float"),
+        ty::TyKind::Float(ty::FloatTy::F64) => Some("double"),
+        _ => None,
+    };
+    if let Some(scalar) = scalar {
+        if scalar.starts_with("::std::") {
+            include_paths.push(Symbol::intern("<cstdint>"));
+        }
+        return Ok((scalar.to_string(), false));
+    }
+    if let ty::TyKind::Adt(adt, _) = ty.kind() {
+        if matches_qualified_name(db, adt.did(), &["protobuf_cpp", "string", "ProtoString"])
+            || matches_qualified_name(db, adt.did(), &["protobuf_cpp", "string", "ProtoBytes"])
+        {
+            include_paths.push(Symbol::intern("<string>"));
+            return Ok(("::std::string".to_string(), true));
+        }
+        if proto_message_rust_bridge(db, *adt).is_some()
+            && let Some(BridgingAttrs::ExternCFuncConverters {
+                include_paths: message_include_paths,
+                cpp_type,
+                ..
+            }) = crubit_attr::get_attrs(db.tcx(), adt.did())?.get_bridging_attrs()?
+        {
+            include_paths.extend(message_include_paths);
+            return Ok((cpp_type.as_str().to_string(), true));
+        }
+    }
+    bail!("`{ty}` is not supported as the element, key, or value type of a bridged protobuf field")
+}
+
+/// Returns the `ProtoMessageRustBridge` of the Rust protobuf crate that defines `adt`, or `None` if
+/// `adt` is not defined by a Rust protobuf crate.
+fn proto_message_rust_bridge(
+    db: &BindingsGenerator<'_>,
+    adt: AdtDef<'_>,
+) -> Option<FullyQualifiedPath> {
+    let tcx = db.tcx();
+    let bridge_name = Symbol::intern("ProtoMessageRustBridge");
+    if !crate::module_children(tcx, adt.did().krate.as_def_id())
+        .iter()
+        .any(|child| child.ident.name == bridge_name)
+    {
+        return None;
+    }
+    // Use the crate name as spelled in the bindings, which may be renamed for protobuf crates.
+    let krate = db.symbol_canonical_name(adt.did())?.rs_name_parts().next()?;
+    Some(FullyQualifiedPath {
+        start_with_colon2: true,
+        parts: Rc::from([krate, Ident::new("ProtoMessageRustBridge", Span::call_site())]),
+    })
+}
+
 /// Returns a CrubitAbiType for a manually annotated composable bridged ADT.
 /// May return an error is `crubit_abi_type_from_ty` fails for any of the generic args.
 fn crubit_abi_type_from_bridged_adt<'tcx>(
@@ -1552,6 +2007,7 @@ fn crubit_abi_type_from_bridged_adt<'tcx>(
     abi_rust: Symbol,
     abi_cpp: Symbol,
     substs: &[GenericArg<'tcx>],
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<CrubitAbiTypeWithCcPrereqs<'tcx>> {
     let mut prereqs = CcPrerequisites::default();
     let crubit_abi_type = CrubitAbiType::Type {
@@ -1570,7 +2026,7 @@ fn crubit_abi_type_from_bridged_adt<'tcx>(
             .iter()
             .map(|subst| {
                 let crubit_abi_type_with_cc_prereqs =
-                    db.crubit_abi_type_from_ty(subst.expect_ty())?;
+                    db.crubit_abi_type_from_ty(subst.expect_ty(), features)?;
                 Ok(crubit_abi_type_with_cc_prereqs.crubit_abi_type(&mut prereqs))
             })
             .collect::<Result<Rc<[CrubitAbiType]>>>()?,
@@ -1584,6 +2040,7 @@ fn crubit_abi_type_from_bridged_adt<'tcx>(
 fn is_manually_annotated_bridged_adt<'tcx>(
     db: &BindingsGenerator<'tcx>,
     ty: Ty<'tcx>,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<Option<BridgedType<'tcx>>> {
     // We take a `Ty` instead of adt + substs directly so we can use `Ty` in error messages.
     let ty::TyKind::Adt(adt, substs) = ty.kind() else {
@@ -1654,7 +2111,7 @@ fn is_manually_annotated_bridged_adt<'tcx>(
         }
         BridgingAttrs::Composable { cpp_type, abi_rust, abi_cpp } => {
             let crubit_abi_type_with_cc_prereqs =
-                crubit_abi_type_from_bridged_adt(db, abi_rust, abi_cpp, substs)?;
+                crubit_abi_type_from_bridged_adt(db, abi_rust, abi_cpp, substs, features)?;
             Ok(Some(BridgedType::Composable(Box::new(BridgedTypeComposable {
                 cpp_type: FullyQualifiedPath::new(cpp_type.as_str()),
                 prereqs: crubit_abi_type_with_cc_prereqs.prereqs,
@@ -1670,6 +2127,7 @@ fn is_manually_annotated_bridged_adt<'tcx>(
 pub fn is_bridged_type<'tcx>(
     db: &BindingsGenerator<'tcx>,
     ty: Ty<'tcx>,
+    features: flagset::FlagSet<CrubitFeature>,
 ) -> Result<Option<BridgedType<'tcx>>> {
     // The ABI of bridged types is lifetime-independent, and the Crubit thunks replace all
     // lifetimes with static.
@@ -1677,7 +2135,7 @@ pub fn is_bridged_type<'tcx>(
 
     match *ty.kind() {
         ty::TyKind::Ref(_, referent, _) => {
-            if let Some(bridged) = is_bridged_type(db, referent)?
+            if let Some(bridged) = is_bridged_type(db, referent, features)?
                 && !bridged.is_layout_compatible()
             {
                 // Bridge types behind a reference are not allowed. But Option is an exception
@@ -1695,7 +2153,7 @@ pub fn is_bridged_type<'tcx>(
             Ok(None)
         }
         ty::TyKind::RawPtr(pointee, _) => {
-            if let Some(bridged) = is_bridged_type(db, pointee)?
+            if let Some(bridged) = is_bridged_type(db, pointee, features)?
                 && !bridged.is_layout_compatible()
             {
                 bail!(
@@ -1705,31 +2163,53 @@ pub fn is_bridged_type<'tcx>(
             Ok(None)
         }
         ty::TyKind::Adt(adt, substs) => {
-            if let Some(bridged_type) = is_manually_annotated_bridged_adt(db, ty)? {
+            if let Some(bridged_type) = is_manually_annotated_bridged_adt(db, ty, features)? {
                 return Ok(Some(bridged_type));
             }
 
-            let always_specialize_generics = db
-                .crate_features(db.source_crate_num())
-                .contains(CrubitFeature::AlwaysSpecializeGenericsInCppApiFromRust);
+            if let Some(proto_view) = BridgedProtoView::new(db, adt) {
+                return proto_view.bridged_type(db, ty, substs).map(Some);
+            }
+
+            let always_specialize_generics =
+                features.contains(CrubitFeature::AlwaysSpecializeGenericsInCppApiFromRust);
 
-            if !always_specialize_generics
-                && let Some(bridged_builtin) = BridgedBuiltin::new(db, adt)
+            // `Box` has no specialization, so it is always bridged.
+            if let Some(bridged_builtin) = BridgedBuiltin::new(db, adt)
+                && (!always_specialize_generics || matches!(bridged_builtin, BridgedBuiltin::Box))
             {
-                if let BridgedBuiltin::Result | BridgedBuiltin::Vec = bridged_builtin {
-                    // We can't ask for the CrubitAbiType of a Result/Vec, because it will return an Err,
-                    // so we check for it here and return Ok.
+                if let BridgedBuiltin::Result
+                | BridgedBuiltin::Vec
+                | BridgedBuiltin::Arc
+                | BridgedBuiltin::Rc = bridged_builtin
+                {
+                    // We can't ask for the CrubitAbiType of a Result/Vec/Arc/Rc, because it will
+                    // return an Err, so we check for it here and return Ok.
                     return Ok(None);
                 }
-                // The ADT is either a Result or an Option, which are composable bridged types.
+                // The ADT is an Option or a Box, which are composable bridged types.
                 let crubit_abi_type_with_cc_prereqs =
-                    bridged_builtin.crubit_abi_type(db, substs)?;
+                    bridged_builtin.crubit_abi_type(db, substs, features)?;
 
                 let mut prereqs = bridged_builtin.prereqs();
                 let crubit_abi_type = crubit_abi_type_with_cc_prereqs.crubit_abi_type(&mut prereqs);
 
                 return Ok(Some(BridgedType::Composable(Box::new(BridgedTypeComposable {
-                    cpp_type: bridged_builtin.cpp_name(),
+                    cpp_type: bridged_builtin.cpp_name()?,
+                    prereqs,
+                    crubit_abi_type,
+                }))));
+            }
+
+            if let Some(collection) = BridgedCollection::new(db, adt)
+                && features.contains(CrubitFeature::BridgeCollections)
+            {
+                let mut prereqs = CcPrerequisites::default();
+                let crubit_abi_type = collection
+                    .crubit_abi_type(db, adt, substs, features)?
+                    .crubit_abi_type(&mut prereqs);
+                return Ok(Some(BridgedType::Composable(Box::new(BridgedTypeComposable {
+                    cpp_type: collection.cpp_name(features),
                     prereqs,
                     crubit_abi_type,
                 }))));
@@ -1738,11 +2218,11 @@ pub fn is_bridged_type<'tcx>(
             // It's neither of the above, so check that it doesn't have any bridged substs.
 
             // The ADT does not need to be bridged, but check if it has generic types that
-            // need to be bridged e.g. Box<BridgedType> cannot be formatted at
+            // need to be bridged e.g. Cell<BridgedType> cannot be formatted at
             // the moment. If we encounter a type like this we return an error.
             for subst in substs {
                 if let Some(ty) = subst.as_type()
-                    && let Some(bridged) = is_bridged_type(db, ty)?
+                    && let Some(bridged) = is_bridged_type(db, ty, features)?
                     && !bridged.is_layout_compatible()
                 {
                     bail!(
@@ -92,7 +92,11 @@ fn test_format_ret_ty_for_cc_successes() {
             let actual = {
                 let db = bindings_db_for_tests(tcx);
                 let cc_snippet = db
-                    .format_ty_for_cc(ty, TypeLocation::FnReturn { is_constructor: false })
+                    .format_ty_for_cc(
+                        ty,
+                        TypeLocation::FnReturn { is_constructor: false },
+                        db.bindings_features(None),
+                    )
                     .unwrap();
                 cc_snippet.tokens.to_string()
             };
@@ -422,6 +426,7 @@ fn test_format_ty_for_cc_successes() {
                     .format_ty_for_cc(
                         ty,
                         TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+                        db.bindings_features(None),
                     )
                     .unwrap_or_else(|e| panic!("Failed to format type {}: {}", ty, e));
                 (s.tokens.to_string(), s.prereqs)
@@ -571,6 +576,7 @@ fn test_format_ty_for_cc_failures() {
                 .format_ty_for_cc(
                     ty,
                     TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+                    db.bindings_features(None),
                 )
                 .expect_err(&format!("Expecting error for: {desc}"));
             let actual_msg = format!("{anyhow_err:#}");
@@ -716,6 +722,7 @@ fn test_format_ty_for_cc_rvalue_reference_param() {
                 .format_ty_for_cc(
                     ty,
                     TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+                    db.bindings_features(None),
                 )
                 .unwrap();
             let parsed_expected = expected.parse::<TokenStream>().unwrap().to_string();
@@ -732,8 +739,13 @@ fn test_format_ty_for_cc_rvalue_reference_return() {
         rvalue_reference_preamble(),
         |desc, tcx, ty, expected| {
             let db = bindings_db_for_tests(tcx);
-            let cc_snippet =
-                db.format_ty_for_cc(ty, TypeLocation::FnReturn { is_constructor: false }).unwrap();
+            let cc_snippet = db
+                .format_ty_for_cc(
+                    ty,
+                    TypeLocation::FnReturn { is_constructor: false },
+                    db.bindings_features(None),
+                )
+                .unwrap();
             let parsed_expected = expected.parse::<TokenStream>().unwrap().to_string();
             assert_eq!(cc_snippet.tokens.to_string(), parsed_expected, "{desc}");
         },
@@ -748,7 +760,9 @@ fn test_format_ty_for_cc_template_arg_integer_types() {
         quote! {},
         |desc, tcx, ty, &expected_cc_ty| {
             let db = bindings_db_for_tests(tcx);
-            let cc_snippet = db.format_ty_for_cc(ty, TypeLocation::TemplateArg).unwrap();
+            let cc_snippet = db
+                .format_ty_for_cc(ty, TypeLocation::TemplateArg, db.bindings_features(None))
+                .unwrap();
             let parsed_expected = expected_cc_ty.parse::<TokenStream>().unwrap().to_string();
             assert_eq!(cc_snippet.tokens.to_string(), parsed_expected, "{desc}");
         },
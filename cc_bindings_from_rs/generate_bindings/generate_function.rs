@@ -159,6 +159,7 @@ fn ident_for_each(prefix: &str, n: usize) -> Vec<Ident> {
 ///   trivially destructible (no drop glue).
 /// * `includes` - an output parameter used to store the set of C++ includes required
 /// * `statements` - an output parameter used to store the C++ statements performing the conversion
+/// * `features` - the Crubit features of the item whose thunk is called
 ///
 /// Returns a `TokenStream` containing an expression that evaluates to the
 /// C-ABI-compatible version of the type.
@@ -169,8 +170,9 @@ pub(crate) fn cc_param_to_c_abi<'tcx>(
     post_analysis_typing_env: ty::TypingEnv<'tcx>,
     includes: &mut BTreeSet<CcInclude>,
     statements: &mut TokenStream,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<TokenStream> {
-    Ok(if let Some(bridged_type) = is_bridged_type(db, ty)? {
+    Ok(if let Some(bridged_type) = is_bridged_type(db, ty, features)? {
         match bridged_type {
             BridgedType::Legacy { cpp_type, .. } => {
                 if let CcType::Pointer { .. } = cpp_type {
@@ -214,9 +216,7 @@ pub(crate) fn cc_param_to_c_abi<'tcx>(
     } else if is_c_abi_compatible_by_value(db, ty) {
         quote! { #cc_ident }
     } else if let ty::TyKind::Tuple(tuple_tys) = ty.kind()
-        && !db
-            .crate_features(db.source_crate_num())
-            .contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
+        && !features.contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
     {
         let n = tuple_tys.len();
         let c_abi_names = ident_for_each(&format!("{cc_ident}_cabi"), n);
@@ -241,6 +241,7 @@ pub(crate) fn cc_param_to_c_abi<'tcx>(
                 post_analysis_typing_env,
                 includes,
                 statements,
+                features,
             )?;
             if matches!(tuple_tys[i].kind(), ty::TyKind::Tuple(_)) {
                 // Elements which are arrays must be referenced again in order
@@ -297,9 +298,10 @@ fn format_ty_for_cc_amending_prereqs<'tcx>(
     db: &BindingsGenerator<'tcx>,
     ty: Ty<'tcx>,
     prereqs: &mut CcPrerequisites<'tcx>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<TokenStream> {
     let CcSnippet { tokens: cc_type, prereqs: ty_prereqs } =
-        db.format_ty_for_cc(ty, TypeLocation::Other)?;
+        db.format_ty_for_cc(ty, TypeLocation::Other, features)?;
     *prereqs += ty_prereqs;
     Ok(cc_type)
 }
@@ -312,16 +314,21 @@ fn cc_return_value_from_c_abi<'tcx>(
     prereqs: &mut CcPrerequisites<'tcx>,
     storage_statements: &mut TokenStream,
     recursive: bool,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<ReturnConversion> {
     let storage_name = &expect_format_cc_ident(&format!("__{ident}_storage"));
     // TODO: b/459482188 - The order of this check must align with the order in `generate_thunk_decl`.
     // We should centralize this logic so that the order exists in a singular location used by both
     // places.
-    if let Some(bridged_type) = is_bridged_type(db, ty)? {
+    if let Some(bridged_type) = is_bridged_type(db, ty, features)? {
         match bridged_type {
             BridgedType::Legacy { .. } => {
                 let cpp_type = db
-                    .format_ty_for_cc(ty, TypeLocation::FnReturn { is_constructor: false })?
+                    .format_ty_for_cc(
+                        ty,
+                        TypeLocation::FnReturn { is_constructor: false },
+                        features,
+                    )?
                     .into_tokens(prereqs);
                 if ty.needs_drop(db.tcx(), post_analysis_typing_env) {
                     prereqs.includes.insert(db.support_header("internal/slot.h"));
@@ -380,7 +387,7 @@ fn cc_return_value_from_c_abi<'tcx>(
             }
         }
     } else if is_c_abi_compatible_by_value(db, ty) {
-        let cc_type = &format_ty_for_cc_amending_prereqs(db, ty, prereqs)?;
+        let cc_type = &format_ty_for_cc_amending_prereqs(db, ty, prereqs, features)?;
         let local_name = &expect_format_cc_ident(&format!("__{ident}_ret_val_holder"));
         storage_statements.extend(quote! {
             #cc_type #local_name;
@@ -391,9 +398,7 @@ fn cc_return_value_from_c_abi<'tcx>(
             unpack_expr: quote! { *#storage_name },
         })
     } else if let ty::TyKind::Tuple(tuple_tys) = ty.kind()
-        && !db
-            .crate_features(db.source_crate_num())
-            .contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
+        && !features.contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
     {
         let n = tuple_tys.len();
         let mut storage_names = Vec::with_capacity(n);
@@ -411,6 +416,7 @@ fn cc_return_value_from_c_abi<'tcx>(
                 prereqs,
                 storage_statements,
                 /*recursive=*/ true,
+                features,
             )?;
             storage_names.push(element_storage_name);
             unpack_exprs.push(element_unpack_expr);
@@ -424,11 +430,10 @@ fn cc_return_value_from_c_abi<'tcx>(
         })
     } else {
         if recursive && let Some(adt_def) = ty.ty_adt_def() {
-            let always_specialize_generics = db
-                .crate_features(db.source_crate_num())
+            let always_specialize_generics = features
                 .contains(crubit_feature::CrubitFeature::AlwaysSpecializeGenericsInCppApiFromRust);
             let def_id = if always_specialize_generics
-                && db.parse_rs_std_template_specialization(ty).is_some()
+                && db.parse_rs_std_template_specialization(ty, features).is_some()
             {
                 None
             } else {
@@ -439,7 +444,7 @@ fn cc_return_value_from_c_abi<'tcx>(
             }
         }
         let local_name = expect_format_cc_ident(&format!("__{ident}_ret_val_holder"));
-        let cc_type = format_ty_for_cc_amending_prereqs(db, ty, prereqs)?;
+        let cc_type = format_ty_for_cc_amending_prereqs(db, ty, prereqs, features)?;
         storage_statements.extend(quote! {
             crubit::Slot<#cc_type> #local_name;
             auto* #storage_name = #local_name.Get();
@@ -637,6 +642,7 @@ pub(crate) fn generate_thunk_call<'tcx>(
     self_param: ThunkSelfParameter,
     params: &[Param<'tcx>],
     is_async: bool,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<CcSnippet<'tcx>> {
     let tcx = db.tcx();
     let mut prereqs = CcPrerequisites::default();
@@ -667,6 +673,7 @@ pub(crate) fn generate_thunk_call<'tcx>(
                 post_analysis_typing_env(tcx, def_id),
                 &mut prereqs.includes,
                 &mut tokens,
+                features,
             )
         })
         .collect::<Result<Vec<TokenStream>>>()?;
@@ -699,9 +706,10 @@ pub(crate) fn generate_thunk_call<'tcx>(
     let qualifier = if self_param.is_trait_method {
         // Trait implementations don't live alongside their callsite so we have to be more specific
         // about namespaces when we invoke them.
+        let crate_features = db.crate_features(db.source_crate_num());
         let cpp_top_level_ns = format_top_level_ns_for_crate(db, db.source_crate_num())
             .iter()
-            .map(|ns| db.format_cc_ident(*ns))
+            .map(|ns| db.format_cc_ident(*ns, crate_features))
             .collect::<Result<Vec<_>>>()?;
         quote! { #(#cpp_top_level_ns)::* :: __crubit_internal }
     } else {
@@ -709,8 +717,11 @@ pub(crate) fn generate_thunk_call<'tcx>(
     };
 
     let return_body = if is_async {
-        let CcSnippet { tokens: cc_ret_ty, prereqs: ret_prereqs } =
-            db.format_ty_for_cc(rs_return_type, TypeLocation::FnReturn { is_constructor: false })?;
+        let CcSnippet { tokens: cc_ret_ty, prereqs: ret_prereqs } = db.format_ty_for_cc(
+            rs_return_type,
+            TypeLocation::FnReturn { is_constructor: false },
+            features,
+        )?;
         prereqs += ret_prereqs;
         let local_name = expect_format_cc_ident("__return_value_ret_val_holder");
         prereqs.includes.insert(CcInclude::utility()); // for `std::move`
@@ -722,7 +733,7 @@ pub(crate) fn generate_thunk_call<'tcx>(
             #qualifier::#thunk_name(#( #thunk_args ),*);
             return ::std::move(#local_name).AssumeInitAndTakeValue();
         }
-    } else if is_bridged_type(db, rs_return_type)?.is_none()
+    } else if is_bridged_type(db, rs_return_type, features)?.is_none()
         && is_c_abi_compatible_by_value(db, rs_return_type)
     {
         // C++ compilers can emit diagnostics if a function marked [[noreturn]] looks like it
@@ -744,6 +755,7 @@ pub(crate) fn generate_thunk_call<'tcx>(
             &mut prereqs,
             &mut tokens,
             /*recursive=*/ false,
+            features,
         )?;
         thunk_args.push(quote! { #storage_name });
         // We don't have to worry about the [[noreturn]] situation described above because all
@@ -781,16 +793,59 @@ fn get_function_cc_name(db: &BindingsGenerator, def_id: DefId) -> Result<Ident>
         panic!("`generate_function` called on unnamed function {}", tcx.def_path_str(def_id));
     };
     let cc_name = unqualified_fn_name.cpp_name.as_str();
-    match tcx.def_kind(def_id) {
-        DefKind::Ctor { .. } => format_cc_ident(db, &format_variant_ctor_cc_name(cc_name)),
-        _ => format_cc_ident(db, cc_name),
+    let features = db.item_features(def_id);
+    let formatted = match tcx.def_kind(def_id) {
+        DefKind::Ctor { .. } => {
+            format_cc_ident(db, &format_variant_ctor_cc_name(cc_name), features)
+        }
+        _ => {
+            let formatted = format_cc_ident(db, cc_name, features);
+            if formatted.is_err() && code_gen_utils::is_cpp_reserved_keyword(cc_name) {
+                suggest_cpp_name(db, def_id, &format!("{cc_name}_"));
+            }
+            formatted
+        }
+    };
+    formatted.context("Error formatting function name")
+}
+
+/// Suggests a `#[crubit_annotate::cpp_name]` attribute which renames the function `def_id` to
+/// `cpp_name` in C++.
+///
+/// Nothing is suggested if the function already has a `cpp_name`, since that was chosen on
+/// purpose.
+fn suggest_cpp_name(db: &BindingsGenerator, def_id: DefId, cpp_name: &str) {
+    let tcx = db.tcx();
+    let has_cpp_name =
+        crubit_attr::get_attrs(tcx, def_id).is_ok_and(|attributes| attributes.cpp_name.is_some());
+    let def_span = tcx.def_span(def_id);
+    if has_cpp_name || def_span.is_dummy() || !def_id.is_local() {
+        return;
     }
-    .context("Error formatting function name")
+    let source_map = tcx.sess.source_map();
+    let sf = source_map.lookup_source_file(def_span.lo());
+    let loc = source_map.lookup_char_pos(def_span.lo());
+    let indent: String = sf
+        .get_line(loc.line - 1)
+        .map(|line| line.chars().take_while(|c| c.is_whitespace()).collect())
+        .unwrap_or_default();
+    let file_name =
+        source_map.span_to_filename(def_span).prefer_local_unconditionally().to_string();
+    let file_name = file_name.strip_prefix("./").unwrap_or(file_name.as_str());
+    db.errors().suggest(error_report::Suggestion::insert(
+        format!("name this function `{cpp_name}` in C++"),
+        file_name,
+        sf.relative_position(def_span.lo()).0 as u64,
+        loc.line as u32,
+        loc.col.0 as u32 + 1,
+        format!("#[crubit_annotate::cpp_name(\"{cpp_name}\")]\n{indent}"),
+    ));
 }
 
 fn format_trait_ref_for_cc<'tcx>(
     db: &BindingsGenerator<'tcx>,
     trait_ref: &TraitRef<'tcx>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<CcSnippet<'tcx>> {
     let trait_name = db
         .symbol_canonical_name(trait_ref.def_id)
@@ -803,7 +858,9 @@ fn format_trait_ref_for_cc<'tcx>(
     } else {
         let arg_tokens = trait_args
             .map(|ty_arg| {
-                Ok(db.format_ty_for_cc(ty_arg, TypeLocation::Other)?.into_tokens(&mut prereqs))
+                Ok(db
+                    .format_ty_for_cc(ty_arg, TypeLocation::Other, features)?
+                    .into_tokens(&mut prereqs))
             })
             .collect::<Result<Vec<_>>>()?;
         quote! { #trait_name<#(#arg_tokens),*> }
@@ -869,6 +926,7 @@ pub fn generate_function<'tcx>(
         liberate_and_deanonymize_late_bound_regions(tcx, early_bound_fn_sig, def_id)
     };
     check_fn_sig(&sig_mid)?;
+    let features = db.item_features(def_id);
 
     let rs_return_type = sig_mid.output();
     let is_async = tcx.asyncness(def_id).is_async();
@@ -876,9 +934,7 @@ pub fn generate_function<'tcx>(
         let send_trait_id = tcx
             .get_diagnostic_item(sym::Send)
             .ok_or_else(|| anyhow!("crubit.rs-bug: Send trait not found"))?;
-        let is_send = if db
-            .crate_features(db.source_crate_num())
-            .contains(crubit_feature::CrubitFeature::AsyncFnSendModuloRegions)
+        let is_send = if features.contains(crubit_feature::CrubitFeature::AsyncFnSendModuloRegions)
         {
             does_type_implement_trait(tcx, rs_return_type, send_trait_id, [])
         } else {
@@ -894,7 +950,7 @@ pub fn generate_function<'tcx>(
             bail!("Crubit currently only supports async functions that return a Send future.");
         }
         let future_output_ty = get_async_future_output_ty(tcx, rs_return_type)?;
-        if let Some(bridged) = is_bridged_type(db, future_output_ty)?
+        if let Some(bridged) = is_bridged_type(db, future_output_ty, features)?
             && !bridged.is_layout_compatible()
         {
             bail!("Crubit currently does not support async functions returning bridged types that require conversion thunks, found `{future_output_ty}`.");
@@ -941,18 +997,19 @@ pub fn generate_function<'tcx>(
         let CcSnippet { tokens: cc_ret_ty, prereqs: ret_prereqs } = db.format_ty_for_cc(
             actual_rs_return_type,
             TypeLocation::FnReturn { is_constructor: false },
+            features,
         )?;
         main_api_prereqs += ret_prereqs;
         main_api_prereqs.includes.insert(db.support_header("rs_std/dyn_erased_future.h"));
         quote! { ::crubit::DynErasedFuture<#cc_ret_ty> }
     } else {
-        format_ret_ty_for_cc(db, &sig_mid)?.into_tokens(&mut main_api_prereqs)
+        format_ret_ty_for_cc(db, &sig_mid, features)?.into_tokens(&mut main_api_prereqs)
     };
 
     let params = {
         let names = fn_arg_idents(tcx, def_id);
         let cpp_types =
-            format_param_types_for_cc_api(db, &sig_mid, function_kind.has_self_param())?;
+            format_param_types_for_cc_api(db, &sig_mid, function_kind.has_self_param(), features)?;
         names
             .into_iter()
             .enumerate()
@@ -963,7 +1020,7 @@ pub fn generate_function<'tcx>(
                 let mut cc_name = None;
                 if let Some(ident) = name.as_ref()
                     && ident.name.as_str() != "_"
-                    && let Ok(name) = format_cc_ident(db, ident.name.as_str())
+                    && let Ok(name) = format_cc_ident(db, ident.name.as_str(), features)
                 {
                     cc_name = Some(name);
                 }
@@ -1029,16 +1086,17 @@ pub fn generate_function<'tcx>(
         substs.iter().any(|subst| subst.as_region().is_none())
     }
 
-    let struct_name = match self_ty {
+    let struct_def_id = match self_ty {
         Some(ty) => match ty.kind() {
             ty::TyKind::Adt(adt, substs) => {
                 assert!(!has_non_lifetime_substs(substs), "Callers should filter out generics");
-                db.symbol_canonical_name(adt.did())
+                Some(adt.did())
             }
             _ => panic!("Non-ADT `impl`s should be filtered by caller"),
         },
         None => None,
     };
+    let struct_name = struct_def_id.and_then(|def_id| db.symbol_canonical_name(def_id));
     let needs_definition = unqualified_rust_fn_name.as_str() != thunk_name;
     let main_api_params = params
         .iter()
@@ -1055,7 +1113,8 @@ pub fn generate_function<'tcx>(
             quote! { #cpp_type #cc_name #annotation }
         })
         .collect_vec();
-    let thunk_name_cc = format_cc_ident(db, &thunk_name).context("Error formatting thunk name")?;
+    let thunk_name_cc =
+        format_cc_ident(db, &thunk_name, features).context("Error formatting thunk name")?;
     let impl_body = generate_thunk_call(
         db,
         def_id,
@@ -1064,6 +1123,7 @@ pub fn generate_function<'tcx>(
         thunk_self,
         &params,
         is_async,
+        features,
     )?
     .into_tokens(&mut main_api_prereqs);
 
@@ -1142,12 +1202,14 @@ pub fn generate_function<'tcx>(
             /*is_constructor=*/ false,
             /*within_template=*/ false,
             is_async,
+            features,
         )?
         .into_tokens(&mut prereqs);
         if static_method_mode == StaticMethodMode::ForceStaticMethod {
+            let crate_features = db.crate_features(db.source_crate_num());
             let cpp_top_level_ns = format_top_level_ns_for_crate(db, db.source_crate_num())
                 .iter()
-                .map(|ns| db.format_cc_ident(*ns))
+                .map(|ns| db.format_cc_ident(*ns, crate_features))
                 .collect::<Result<Vec<_>>>()?;
             thunk_decl = quote! {
                 namespace #(#cpp_top_level_ns)::* {
@@ -1164,16 +1226,17 @@ pub fn generate_function<'tcx>(
                     .as_ref()
                     .and_then(|fully_qualified_name| fully_qualified_name.format_for_cc(db).ok())
                     .expect("Generated trait method for an ADT with an invalid rust name");
-                let trait_name_with_args = format_trait_ref_for_cc(db, trait_ref)
+                let trait_name_with_args = format_trait_ref_for_cc(db, trait_ref, features)
                     .expect("Implementation of trait containing invalid type requested. Caller should have verified type arguments were valid.")
                     .into_tokens(&mut prereqs);
                 quote! { rs_std :: impl <#struct_name, #trait_name_with_args> :: #bracketed_decl_name }
             }
-            StaticMethodMode::Infer => struct_name
-                .as_ref()
-                .map(|fully_qualified_name| {
+            StaticMethodMode::Infer => struct_def_id
+                .zip(struct_name.as_ref())
+                .map(|(struct_def_id, fully_qualified_name)| {
                     let name = fully_qualified_name.unqualified.cpp_name;
-                    let name = format_cc_ident(db, name.as_str()).expect(
+                    let struct_features = db.item_features(struct_def_id);
+                    let name = format_cc_ident(db, name.as_str(), struct_features).expect(
                         "Caller of generate_function should verify struct via generate_adt_core",
                     );
                     quote! { #name :: #bracketed_decl_name }
@@ -1227,6 +1290,7 @@ pub fn generate_function<'tcx>(
             fully_qualified_fn_name,
             /*is_constructor=*/ false,
             is_async,
+            features,
         )?
     };
 
@@ -1782,3 +1782,73 @@ fn test_return_composable_bridged_type_in_tuple() {
         );
     });
 }
+
+#[test]
+fn test_item_level_layout_compat_tuple() {
+    let test_src = r#"
+            #[doc="CRUBIT_ANNOTATE: feature=layout_compat_tuple"]
+            pub fn compat() -> (i32, i32) { (1, 2) }
+
+            pub fn not_compat() -> (i32, i32) { (1, 2) }
+        "#;
+    test_format_item(test_src, "compat", |result| {
+        let result = result.unwrap().unwrap();
+        assert_cc_matches!(
+            result.main_api.tokens,
+            quote! {
+                rs_std::Tuple<::std::int32_t, ::std::int32_t> compat();
+            }
+        );
+        assert_cc_matches!(
+            result.cc_details.tokens,
+            quote! {
+                extern "C" void ...(rs_std::Tuple<::std::int32_t, ::std::int32_t>* __ret_ptr);
+            }
+        );
+    });
+    // The feature of `compat` doesn't leak to the rest of the crate.
+    test_format_item(test_src, "not_compat", |result| {
+        let result = result.unwrap().unwrap();
+        assert_cc_matches!(
+            result.main_api.tokens,
+            quote! {
+                ::std::tuple<::std::int32_t, ::std::int32_t> not_compat();
+            }
+        );
+        assert_cc_matches!(
+            result.cc_details.tokens,
+            quote! {
+                extern "C" void ...(void** __ret_ptr);
+            }
+        );
+    });
+}
+
+#[test]
+fn test_item_level_reserve_standard_macros() {
+    let test_src = r#"
+            #[doc="CRUBIT_ANNOTATE: feature=reserve_standard_macros"]
+            pub fn stdin() {}
+
+            pub fn stdout() {}
+        "#;
+    test_format_item(test_src, "stdin", |result| {
+        let result = result.unwrap().unwrap();
+        assert_cc_matches!(
+            result.main_api.tokens,
+            quote! {
+                void stdin_();
+            }
+        );
+    });
+    // The feature of `stdin` doesn't leak to the rest of the crate.
+    test_format_item(test_src, "stdout", |result| {
+        let result = result.unwrap().unwrap();
+        assert_cc_matches!(
+            result.main_api.tokens,
+            quote! {
+                void stdout();
+            }
+        );
+    });
+}
@@ -35,12 +35,12 @@ use std::collections::{BTreeSet, HashMap};
 ///
 /// Tuples are passed via a pointer to an array of `void*` where
 /// each pointer points to the corresponding element of the tuple.
-fn tuple_c_abi_c_type(db: &BindingsGenerator<'_>, possibly_tuple_ty: Ty) -> Option<TokenStream> {
+fn tuple_c_abi_c_type(
+    possibly_tuple_ty: Ty,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
+) -> Option<TokenStream> {
     let ty::TyKind::Tuple(_) = possibly_tuple_ty.kind() else { return None };
-    if db
-        .crate_features(db.source_crate_num())
-        .contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
-    {
+    if features.contains(crubit_feature::CrubitFeature::LayoutCompatTuple) {
         return None;
     }
     // Sized array types are sadly not usable by-pointer in C++.
@@ -52,12 +52,12 @@ fn tuple_c_abi_c_type(db: &BindingsGenerator<'_>, possibly_tuple_ty: Ty) -> Opti
 ///
 /// Tuples are passed via a pointer to an array of `*const c_void` where
 /// each pointer points to the corresponding element of the tuple.
-fn tuple_c_abi_rs_type(db: &BindingsGenerator<'_>, possibly_tuple_ty: Ty) -> Option<TokenStream> {
+fn tuple_c_abi_rs_type(
+    possibly_tuple_ty: Ty,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
+) -> Option<TokenStream> {
     let ty::TyKind::Tuple(tuple_tys) = possibly_tuple_ty.kind() else { return None };
-    if db
-        .crate_features(db.source_crate_num())
-        .contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
-    {
+    if features.contains(crubit_feature::CrubitFeature::LayoutCompatTuple) {
         return None;
     }
     let num_elements = tuple_tys.len();
@@ -111,6 +111,7 @@ pub fn generate_thunk_decl<'tcx>(
     is_constructor: bool,
     within_template: bool,
     is_async: bool,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<CcSnippet<'tcx>> {
     let tcx = db.tcx();
     let mut prereqs = CcPrerequisites::default();
@@ -120,26 +121,29 @@ pub fn generate_thunk_decl<'tcx>(
         sig_mid.output()
     };
     let main_api_ret_type = if is_async {
-        let CcSnippet { tokens: cc_ret_ty, prereqs: ret_prereqs } =
-            db.format_ty_for_cc(actual_output_ty, TypeLocation::FnReturn { is_constructor })?;
+        let CcSnippet { tokens: cc_ret_ty, prereqs: ret_prereqs } = db.format_ty_for_cc(
+            actual_output_ty,
+            TypeLocation::FnReturn { is_constructor },
+            features,
+        )?;
         prereqs += ret_prereqs;
         prereqs.includes.insert(db.support_header("rs_std/dyn_erased_future.h"));
         quote! { ::crubit::DynErasedFuture<#cc_ret_ty> }
     } else {
-        db.format_ty_for_cc(actual_output_ty, TypeLocation::FnReturn { is_constructor })
+        db.format_ty_for_cc(actual_output_ty, TypeLocation::FnReturn { is_constructor }, features)
             .with_context(|| format!("Error formatting function return type `{actual_output_ty}`"))?
             .into_tokens(&mut prereqs)
     };
 
     let mut thunk_params = {
-        let cpp_types = format_param_types_for_cc_thunk(db, sig_mid, has_self_param)?;
+        let cpp_types = format_param_types_for_cc_thunk(db, sig_mid, has_self_param, features)?;
         sig_mid
             .inputs()
             .iter()
             .zip(cpp_types)
             .map(|(&ty, cpp_type)| -> Result<TokenStream> {
                 let cpp_type = cpp_type.snippet.into_tokens(&mut prereqs);
-                let bridged_type_opt = is_bridged_type(db, ty)?;
+                let bridged_type_opt = is_bridged_type(db, ty, features)?;
                 if let Some(bridged_type) = bridged_type_opt {
                     match bridged_type {
                         BridgedType::Legacy { .. } => {
@@ -154,7 +158,7 @@ pub fn generate_thunk_decl<'tcx>(
                     }
                 } else if is_c_abi_compatible_by_value(db, ty) {
                     Ok(quote! { #cpp_type })
-                } else if let Some(tuple_abi) = tuple_c_abi_c_type(db, ty) {
+                } else if let Some(tuple_abi) = tuple_c_abi_c_type(ty, features) {
                     Ok(tuple_abi)
                 } else if let ty::TyKind::Array(inner_ty, _) = ty.kind() {
                     array_c_abi_c_type(db.tcx(), *inner_ty)
@@ -164,9 +168,9 @@ pub fn generate_thunk_decl<'tcx>(
                     // the compiler to try to analyze the layout of `Option<T>`, which fails because `T`
                     // does not have a fixed layout. Passing `None` forces the query to fall back to
                     // a fully monomorphized typing environment to layout the concrete type (e.g., `Option<i32>`).
-                    let always_specialize_generics = db.crate_features(db.source_crate_num())
+                    let always_specialize_generics = features
                         .contains(crubit_feature::CrubitFeature::AlwaysSpecializeGenericsInCppApiFromRust);
-                    let def_id = if always_specialize_generics && db.parse_rs_std_template_specialization(ty).is_some() {
+                    let def_id = if always_specialize_generics && db.parse_rs_std_template_specialization(ty, features).is_some() {
                         None
                     } else {
                         Some(adt_def.did())
@@ -188,14 +192,18 @@ pub fn generate_thunk_decl<'tcx>(
     // places.
     let thunk_ret_type = if is_async {
         let cc_ret_ty = db
-            .format_ty_for_cc(actual_output_ty, TypeLocation::FnReturn { is_constructor })?
+            .format_ty_for_cc(
+                actual_output_ty,
+                TypeLocation::FnReturn { is_constructor },
+                features,
+            )?
             .tokens;
         thunk_params.push(quote! { ::crubit::DynErasedFuture<#cc_ret_ty>* __ret_ptr });
         quote! { void }
     } else if is_constructor && is_bridged_layout_compat_type(db, sig_mid.output()) {
         thunk_params.push(quote! { #main_api_ret_type* __ret_ptr });
         quote! { void }
-    } else if let Some(briging) = is_bridged_type(db, sig_mid.output())? {
+    } else if let Some(briging) = is_bridged_type(db, sig_mid.output(), features)? {
         match briging {
             BridgedType::Legacy { .. } => {
                 thunk_params.push(quote! { #main_api_ret_type* __ret_ptr });
@@ -208,7 +216,7 @@ pub fn generate_thunk_decl<'tcx>(
         }
     } else if is_c_abi_compatible_by_value(db, sig_mid.output()) {
         main_api_ret_type
-    } else if let Some(tuple_abi) = tuple_c_abi_c_type(db, sig_mid.output()) {
+    } else if let Some(tuple_abi) = tuple_c_abi_c_type(sig_mid.output(), features) {
         thunk_params.push(quote! { #tuple_abi __ret_ptr });
         quote! { void }
     } else if let ty::TyKind::Array(inner_ty, _) = sig_mid.output().kind() {
@@ -312,16 +320,18 @@ fn convert_tuple_from_c_abi_to_rust<'tcx>(
     tuple_tys: &[Ty<'tcx>],
     local_name: &Ident,
     extern_c_decls: &mut BTreeSet<ExternCDecl>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<TokenStream> {
     let mut read_elements = Vec::with_capacity(tuple_tys.len());
     for (i, element_type) in tuple_tys.iter().copied().enumerate() {
-        let element_c_abi_type = c_abi_for_param_type(db, element_type)?;
+        let element_c_abi_type = c_abi_for_param_type(db, element_type, features)?;
         let element_local_name = format_ident!("{local_name}_{i}");
         let from_c_abi_to_rust = convert_value_from_c_abi_to_rust(
             db,
             element_type,
             &element_local_name,
             extern_c_decls,
+            features,
         )?;
         read_elements.push(quote! { {
             let #element_local_name: #element_c_abi_type = ((*#local_name)[#i] as *const #element_c_abi_type).read();
@@ -341,8 +351,9 @@ fn convert_value_from_c_abi_to_rust<'tcx>(
     ty: Ty<'tcx>,
     local_name: &Ident,
     extern_c_decls: &mut BTreeSet<ExternCDecl>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<TokenStream> {
-    if let Some(bridged) = is_bridged_type(db, ty)? {
+    if let Some(bridged) = is_bridged_type(db, ty, features)? {
         return convert_bridged_type_from_c_abi_to_rust(
             db,
             ty,
@@ -355,19 +366,27 @@ fn convert_value_from_c_abi_to_rust<'tcx>(
         return Ok(quote! {});
     }
     if let ty::TyKind::Tuple(tuple_tys) = ty.kind()
-        && !db
-            .crate_features(db.source_crate_num())
-            .contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
+        && !features.contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
     {
-        return convert_tuple_from_c_abi_to_rust(db, tuple_tys, local_name, extern_c_decls);
+        return convert_tuple_from_c_abi_to_rust(
+            db,
+            tuple_tys,
+            local_name,
+            extern_c_decls,
+            features,
+        );
     }
     // Non-C-ABI-compatible-by-value types are passed by
     // `*mut T`, so we need to read out the value.
     Ok(quote! { let #local_name = #local_name.read(); })
 }
 
-fn c_abi_for_param_type<'tcx>(db: &BindingsGenerator<'tcx>, ty: Ty<'tcx>) -> Result<TokenStream> {
-    if let Some(bridged) = is_bridged_type(db, ty)? {
+fn c_abi_for_param_type<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    ty: Ty<'tcx>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
+) -> Result<TokenStream> {
+    if let Some(bridged) = is_bridged_type(db, ty, features)? {
         match bridged {
             BridgedType::Legacy { .. } => Ok(quote! { *const core::ffi::c_void }),
             BridgedType::Composable(_) => Ok(quote! { *const core::ffi::c_uchar }),
@@ -375,7 +394,7 @@ fn c_abi_for_param_type<'tcx>(db: &BindingsGenerator<'tcx>, ty: Ty<'tcx>) -> Res
     } else if is_c_abi_compatible_by_value(db, ty) {
         let rs_type = db.format_ty_for_rs(ty)?;
         Ok(quote! { #rs_type })
-    } else if let Some(tuple_abi) = tuple_c_abi_rs_type(db, ty) {
+    } else if let Some(tuple_abi) = tuple_c_abi_rs_type(ty, features) {
         Ok(quote! { #tuple_abi })
     } else {
         let rs_type = db.format_ty_for_rs(ty)?;
@@ -442,11 +461,12 @@ fn write_rs_value_to_c_abi_ptr<'tcx>(
     rs_type: Ty<'tcx>,
     extern_c_decls: &mut BTreeSet<ExternCDecl>,
     is_constructor: bool,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<TokenStream> {
     let write_directly = || -> Result<TokenStream> {
         Ok(quote! { ::core::ptr::write(#c_ptr as *mut _, #rs_value); })
     };
-    Ok(if let Some(bridged_type) = is_bridged_type(db, rs_type)? {
+    Ok(if let Some(bridged_type) = is_bridged_type(db, rs_type, features)? {
         match bridged_type {
             BridgedType::Legacy { conversion_info, .. } => match conversion_info {
                 BridgedTypeConversionInfo::PointerLikeTransmute { .. } => write_directly()?,
@@ -486,9 +506,7 @@ fn write_rs_value_to_c_abi_ptr<'tcx>(
         write_directly()?
     } else if let ty::TyKind::Tuple(tuple_tys) = rs_type.kind()
         && !is_constructor
-        && !db
-            .crate_features(db.source_crate_num())
-            .contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
+        && !features.contains(crubit_feature::CrubitFeature::LayoutCompatTuple)
     {
         let num_elements = tuple_tys.len();
         let rs_element_names =
@@ -508,6 +526,7 @@ fn write_rs_value_to_c_abi_ptr<'tcx>(
                     tuple_tys[i],
                     extern_c_decls,
                     /*is_constructor=*/ false,
+                    features,
                 )
             })
             .collect::<Result<TokenStream>>()?;
@@ -570,6 +589,7 @@ pub fn generate_thunk_impl<'tcx>(
     fully_qualified_fn_name: TokenStream,
     is_constructor: bool,
     is_async: bool,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Result<RsSnippet> {
     let tcx = db.tcx();
 
@@ -588,7 +608,7 @@ pub fn generate_thunk_impl<'tcx>(
     let mut thunk_params = param_names_and_types
         .iter()
         .map(|(param_name, ty)| {
-            let c_abi_type = c_abi_for_param_type(db, *ty)
+            let c_abi_type = c_abi_for_param_type(db, *ty, features)
                 .with_context(|| format!("Error handling parameter `{param_name}`"))?;
             Ok(quote! { #param_name: #c_abi_type })
         })
@@ -600,13 +620,13 @@ pub fn generate_thunk_impl<'tcx>(
     let fn_args_conversions = param_names_and_types
         .iter()
         .map(|(param_name, ty)| {
-            convert_value_from_c_abi_to_rust(db, *ty, param_name, &mut extern_c_decls)
+            convert_value_from_c_abi_to_rust(db, *ty, param_name, &mut extern_c_decls, features)
         })
         .collect::<Result<Vec<TokenStream>>>()?;
 
     let fn_args: Vec<Ident> =
         param_names_and_types.into_iter().map(|(rs_name, _ty)| rs_name).collect();
-    let output_is_bridged = is_bridged_type(db, sig.output())?;
+    let output_is_bridged = is_bridged_type(db, sig.output(), features)?;
     let thunk_return_type;
     let thunk_return_expression;
     if is_async {
@@ -652,6 +672,7 @@ pub fn generate_thunk_impl<'tcx>(
             sig.output(),
             &mut extern_c_decls,
             is_constructor,
+            features,
         )?;
         thunk_return_expression = quote! {
             let #rs_return_value_ident = #fully_qualified_fn_name( #( #fn_args ),* );
@@ -729,6 +750,8 @@ pub fn generate_trait_thunks<'tcx>(
 ) -> Result<TraitThunks<'tcx>> {
     let tcx = db.tcx();
     assert!(tcx.is_trait(trait_id));
+    // The thunks belong to the bindings of `self_ty`, so they use its features.
+    let features = db.bindings_features(def_id);
 
     let is_drop_trait = Some(trait_id) == tcx.lang_items().drop_trait();
     if is_drop_trait {
@@ -814,7 +837,7 @@ pub fn generate_trait_thunks<'tcx>(
         let sig_mid = ty::Unnormalized::new(sig_mid);
         let sig_mid = liberate_and_deanonymize_late_bound_regions(tcx, sig_mid, method.def_id);
 
-        let thunk_name_cc_ident = format_cc_ident(db, &thunk_name)?;
+        let thunk_name_cc_ident = format_cc_ident(db, &thunk_name, features)?;
         let is_async = tcx.asyncness(method.def_id).is_async();
         cc_thunk_decls += generate_thunk_decl(
             db,
@@ -824,6 +847,7 @@ pub fn generate_trait_thunks<'tcx>(
             is_constructor,
             within_template,
             is_async,
+            features,
         )?;
         method_name_to_cc_thunk_name.insert(method.name(), thunk_name_cc_ident);
 
@@ -881,6 +905,7 @@ pub fn generate_trait_thunks<'tcx>(
                 fully_qualified_fn_name,
                 is_constructor,
                 is_async,
+                features,
             )?
         };
     }
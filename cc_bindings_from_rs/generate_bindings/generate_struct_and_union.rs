@@ -105,7 +105,7 @@ pub(crate) fn cpp_enum_cpp_underlying_type<'tcx>(
     def_id: DefId,
 ) -> Result<CcSnippet<'tcx>> {
     let field_type = cpp_enum_rust_underlying_type(db.tcx(), def_id)?;
-    db.format_ty_for_cc(field_type, TypeLocation::Field)
+    db.format_ty_for_cc(field_type, TypeLocation::Field, db.bindings_features(Some(def_id)))
 }
 
 /// Returns a string representation of the value of a given numeric Scalar having a given TyKind.
@@ -243,7 +243,8 @@ fn generate_cpp_enum<'tcx>(
                 ));
                 return None;
             };
-            let enumerator_name = format_cc_ident(db, name.as_str()).unwrap();
+            let enumerator_name =
+                format_cc_ident(db, name.as_str(), db.item_features(assoc_item.def_id)).unwrap();
             let (opt_doc_comment, bracketed_enumerator_name) = if db.kythe_annotations() {
                 (
                     generate_doc_comment(db, assoc_item.def_id),
@@ -409,9 +410,8 @@ pub(crate) fn generate_associated_item<'tcx>(
                 })
         }
     };
-    let result = result.and_then(|snippet| {
-        snippet.resolve_feature_requirements(db.crate_features(db.source_crate_num()))
-    });
+    let result =
+        result.and_then(|snippet| snippet.resolve_feature_requirements(db.item_features(def_id)));
     match result {
         Err(err) => {
             if crubit_attr::get_attrs(tcx, def_id).unwrap().must_bind {
@@ -505,6 +505,8 @@ fn generate_into_impls<'tcx>(
 ) -> ApiSnippets<'tcx> {
     let tcx = db.tcx();
     let cc_struct_name = &core.common.cc_short_name;
+    let features = db.bindings_features(core.def_id);
+    let fn_return_location = TypeLocation::FnReturn { is_constructor: false };
 
     let into_trait = tcx.get_diagnostic_item(sym::Into).expect("Could not find Into trait");
     let Some(def_id) = core.def_id else {
@@ -528,9 +530,7 @@ fn generate_into_impls<'tcx>(
             }
             // We know that our type will always appear in FnReturn position for the `into` method.
             // If our type isn't C++-compatible, we can't generate an `into` impl.
-            let cc_ty = db
-                .format_ty_for_cc(from_middle_ty, TypeLocation::FnReturn { is_constructor: false })
-                .ok()?;
+            let cc_ty = db.format_ty_for_cc(from_middle_ty, fn_return_location, features).ok()?;
             Some((from_middle_ty, cc_ty, *from_impl_id))
         });
     let into_impls =
@@ -544,9 +544,7 @@ fn generate_into_impls<'tcx>(
             }
 
             // If our type isn't C++ compatible, we can't generate an `into` impl.
-            let cc_ty = db
-                .format_ty_for_cc(into_middle_ty, TypeLocation::FnReturn { is_constructor: false })
-                .ok()?;
+            let cc_ty = db.format_ty_for_cc(into_middle_ty, fn_return_location, features).ok()?;
 
             Some((into_middle_ty, cc_ty, into_impl_id))
         });
@@ -599,6 +597,7 @@ fn generate_into_impls<'tcx>(
                 .format_ty_for_cc(
                     core.common.self_ty,
                     TypeLocation::FnParam { is_self_param: true, elided_is_output: true },
+                    features,
                 )
                 .expect(
                     "ADT's self type should be C++-convertible after generate_adt_core succeeds",
@@ -622,6 +621,7 @@ fn generate_into_impls<'tcx>(
                     ty: core.common.self_ty,
                 }],
                 /*is_async=*/ false,
+                features,
             )
             .expect("Self type of `Into` impl should be bridgeable");
 
@@ -685,6 +685,7 @@ fn generate_constructor_impls<'tcx>(
 
     // We need there to be a `def_id` to generate a constructor from.
     let def_id = core.def_id.expect("ADT must have a def_id");
+    let features = db.bindings_features(core.def_id);
 
     // Find From impls from the selected ADT
     let from_trait = tcx.get_diagnostic_item(sym::From).expect("Could not find From trait");
@@ -714,6 +715,7 @@ fn generate_constructor_impls<'tcx>(
                 .format_ty_for_cc(
                     src_ty,
                     TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+                    features,
                 )
                 .ok()?;
 
@@ -742,6 +744,7 @@ fn generate_constructor_impls<'tcx>(
                     .format_ty_for_cc(
                         src_ty,
                         TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+                        features,
                     )
                     .ok()?;
 
@@ -828,7 +831,7 @@ fn generate_constructor_impls<'tcx>(
                     db,
                     ThunkKind::TraitMethod { method: into_trait_assoc_fn, substs: trait_args },
                 );
-                let thunk_name_cc_ident = format_cc_ident(db, &thunk_name).ok()?;
+                let thunk_name_cc_ident = format_cc_ident(db, &thunk_name, features).ok()?;
                 let cc_thunk_decls = generate_thunk_decl(
                     db,
                     &sig,
@@ -837,6 +840,7 @@ fn generate_constructor_impls<'tcx>(
                     /*is_constructor=*/ true,
                     /*within_template=*/ false,
                     /*is_async=*/ false,
+                    features,
                 )
                 .ok()?;
                 let rs_details = {
@@ -862,6 +866,7 @@ fn generate_constructor_impls<'tcx>(
                             fully_qualified_fn_name,
                             /*is_constructor=*/ true,
                             /*is_async=*/ false,
+                            features,
                         )
                         .ok()?
                     }
@@ -878,6 +883,7 @@ fn generate_constructor_impls<'tcx>(
                 ty::TypingEnv::fully_monomorphized(),
                 &mut prereqs.includes,
                 &mut statements,
+                features,
             )
             .ok()?;
 
@@ -1187,6 +1193,7 @@ fn generate_ord_impls<'tcx>(
     let ref_self_cc_ty = db.format_ty_for_cc(
         ref_self_ty,
         TypeLocation::FnParam { is_self_param: true, elided_is_output: true },
+        db.bindings_features(core.def_id),
     )?;
     let ref_self_cc_tokens = ref_self_cc_ty.into_tokens(&mut cc_details_prereqs);
 
@@ -1241,15 +1248,18 @@ fn generate_partial_ord_impls<'tcx>(
     }
 
     let ref_rhs_ty = Ty::new_imm_ref(tcx, tcx.lifetimes.re_erased, rhs_ty);
+    let features = db.bindings_features(core.def_id);
 
     let rhs_cc_ty_for_main = db.format_ty_for_cc(
         ref_rhs_ty,
         TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+        features,
     )?;
 
     let rhs_cc_ty_for_impl = db.format_ty_for_cc(
         ref_rhs_ty,
         TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+        features,
     )?;
 
     let rhs_rs_ty = db.format_ty_for_rs(rhs_ty)?;
@@ -1286,12 +1296,14 @@ fn generate_partial_ord_impls<'tcx>(
     let ref_self_cc_ty = db.format_ty_for_cc(
         ref_self_ty,
         TypeLocation::FnParam { is_self_param: true, elided_is_output: true },
+        features,
     )?;
     let ref_self_cc_tokens = ref_self_cc_ty.into_tokens(&mut cc_details_prereqs);
 
     let ref_rhs_cc_ty = db.format_ty_for_cc(
         ref_rhs_ty,
         TypeLocation::FnParam { is_self_param: false, elided_is_output: false },
+        features,
     )?;
     let ref_rhs_cc_tokens = ref_rhs_cc_ty.into_tokens(&mut cc_details_prereqs);
 
@@ -1739,8 +1751,12 @@ pub fn generate_adt_core<'tcx>(
         bail!("`generate_adt_core` called on non-reachable type {}", tcx.def_path_str(def_id));
     };
     let rs_fully_qualified_name = fully_qualified_name.format_for_rs();
-    let cpp_name = format_cc_ident(db, fully_qualified_name.unqualified.cpp_name.as_str())
-        .context("Error formatting item name")?;
+    let cpp_name = format_cc_ident(
+        db,
+        fully_qualified_name.unqualified.cpp_name.as_str(),
+        db.item_features(def_id),
+    )
+    .context("Error formatting item name")?;
 
     // The check below ensures that `generate_trait_thunks` will succeed for the
     // `Drop`, `Default`, and/or `Clone` trait. Ideally we would directly check
@@ -1930,11 +1946,12 @@ fn generate_variant_ctor<'tcx>(
     // in our API, so opt to avoid presenting a constructor for types that contain a blob of
     // bytes.
     let mut main_api_prereqs = CcPrerequisites::default();
+    let features = db.bindings_features(core.def_id);
     let main_api_params = field_tys
         .into_iter()
         .enumerate()
         .map(|(i, field_ty)| {
-            let cpp_type = db.format_ty_for_cc(field_ty, TypeLocation::Other)?;
+            let cpp_type = db.format_ty_for_cc(field_ty, TypeLocation::Other, features)?;
             let cc_name = anonymous_field_ident(i);
             let cpp_type = cpp_type.into_tokens(&mut main_api_prereqs);
             Ok(quote! { #cpp_type #cc_name })
@@ -2059,7 +2076,7 @@ fn generate_variant_ctor<'tcx>(
                 quote! { constexpr }
             };
             let doc_comment = generate_doc_comment(db, variant.def_id);
-            let method_name = format_cc_ident(db, &method_name)?;
+            let method_name = format_cc_ident(db, &method_name, features)?;
             mark_method_name_as_used();
             Ok(ApiSnippets {
                 main_api: CcSnippet {
@@ -2318,8 +2335,9 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
         #[rustversion::since(2026-05-13)]
         let ty = crate::normalize_ty(tcx, tcx.param_env(field_def.did), ty);
         let size = get_layout(tcx, ty).map(|layout| layout.size().bytes())?;
+        let features = self.db.item_features(field_def.did);
 
-        if is_bridged_type(self.db, ty).is_ok_and(|bridged_type| {
+        if is_bridged_type(self.db, ty, features).is_ok_and(|bridged_type| {
             bridged_type.is_some_and(|bridged_type| !bridged_type.is_layout_compatible())
         }) && !ty
             .ty_adt_def()
@@ -2334,8 +2352,8 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
 
         let cpp_type = self
             .db
-            .format_ty_for_cc(ty, TypeLocation::Field)?
-            .resolve_feature_requirements(self.db.crate_features(self.db.source_crate_num()))?;
+            .format_ty_for_cc(ty, TypeLocation::Field, features)?
+            .resolve_feature_requirements(features)?;
 
         Ok(FieldTypeInfo { size, cpp_type })
     }
@@ -2344,14 +2362,14 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
         let tcx = self.db.tcx();
         let type_info = self.prepare_field_type(field_def);
         let name = field_def.ident(tcx).to_string();
-        let features = self.db.crate_features(self.db.source_crate_num());
+        let features = self.db.item_features(field_def.did);
         let cc_name = code_gen_utils::unkeyword_cpp_ident(&name, features).to_string();
         let cc_name = if self.member_function_names.contains(&cc_name) {
             format!("{cc_name}_")
         } else {
             cc_name
         };
-        let cc_name = format_cc_ident(self.db, cc_name.as_str())
+        let cc_name = format_cc_ident(self.db, cc_name.as_str(), features)
             .unwrap_or_else(|_err| anonymous_field_ident(index));
         let rs_name = {
             let name_starts_with_digit = name
@@ -2670,8 +2688,12 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
                             if variant.size == 0 {
                                 quote! {}
                             } else {
-                                let cc_variant_struct_name = format_cc_ident(self.db, variant_def.ident(tcx).as_str())
-                                    .unwrap_or_else(|_err| format_ident!("err_field"));
+                                let cc_variant_struct_name = format_cc_ident(
+                                    self.db,
+                                    variant_def.ident(tcx).as_str(),
+                                    self.db.item_features(variant_def.def_id),
+                                )
+                                .unwrap_or_else(|_err| format_ident!("err_field"));
                                 let tag_unsuffixed = Literal::u64_unsuffixed(tag_size_with_padding);
                                 quote! { static_assert(#tag_unsuffixed == offsetof(#adt_cc_name, #cc_variant_struct_name)); }
                             }
@@ -2749,7 +2771,11 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
 
                     let tag_tokens = self
                         .db
-                        .format_ty_for_cc(tag_ty, TypeLocation::Other)
+                        .format_ty_for_cc(
+                            tag_ty,
+                            TypeLocation::Other,
+                            self.db.bindings_features(Some(self.adt_def.did())),
+                        )
                         .expect("discriminant should be a integer type.")
                         .into_tokens(&mut prereqs);
 
@@ -2758,9 +2784,12 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
                         .variants()
                         .iter_enumerated()
                         .map(|(variant_index, variant_def)| {
-                            let cc_variant_name =
-                                format_cc_ident(self.db, variant_def.name.as_str())
-                                    .unwrap_or_else(|_err| format_ident!("err_field"));
+                            let cc_variant_name = format_cc_ident(
+                                self.db,
+                                variant_def.name.as_str(),
+                                self.db.item_features(variant_def.def_id),
+                            )
+                            .unwrap_or_else(|_err| format_ident!("err_field"));
                             let (tag_size, _signed) = tag_ty.int_size_and_signed(tcx);
                             let (scalar_int, _) = ty::ScalarInt::truncate_from_uint(
                                 self.adt_def.discriminant_for_variant(tcx, variant_index).val,
@@ -2829,6 +2858,7 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
                     let cc_variant_struct_name = format_cc_ident(
                         self.db,
                         format!("__crubit_{}_struct", variant_def.ident(tcx).as_str()).as_ref(),
+                        self.db.item_features(variant_def.def_id),
                     )
                     .unwrap_or_else(|_err| format_ident!("err_struct"));
 
@@ -2838,8 +2868,12 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
                         Literal::u64_unsuffixed(variant_alignments[variant_index.index()]);
 
                     if variants[variant_index.index()].size == 0 {
-                        let cc_variant_name = format_cc_ident(self.db, variant_def.name.as_str())
-                            .unwrap_or_else(|_err| format_ident!("err_field"));
+                        let cc_variant_name = format_cc_ident(
+                            self.db,
+                            variant_def.name.as_str(),
+                            self.db.item_features(variant_def.def_id),
+                        )
+                        .unwrap_or_else(|_err| format_ident!("err_field"));
                         let msg = format!(
                             "Variant {} has no size, so no struct is generated.",
                             cc_variant_name
@@ -2862,11 +2896,16 @@ impl<'a, 'tcx> AdtFieldGenerator<'a, 'tcx> {
                 .variants()
                 .iter_enumerated()
                 .map(|(variant_index, variant_def)| {
-                    let cc_variant_name = format_cc_ident(self.db, variant_def.name.as_str())
-                        .unwrap_or_else(|_err| format_ident!("err_field"));
+                    let cc_variant_name = format_cc_ident(
+                        self.db,
+                        variant_def.name.as_str(),
+                        self.db.item_features(variant_def.def_id),
+                    )
+                    .unwrap_or_else(|_err| format_ident!("err_field"));
                     let cc_variant_struct_type = format_cc_ident(
                         self.db,
                         format!("__crubit_{}_struct", variant_def.ident(tcx).as_str()).as_ref(),
+                        self.db.item_features(variant_def.def_id),
                     )
                     .unwrap_or_else(|_err| format_ident!("err_struct"));
 
@@ -3128,13 +3167,14 @@ fn generate_begin_and_end_for_type<'tcx>(
     let into_iter_ty = get_into_iter_ty(tcx, check_ty, into_iterator_trait_id)?;
 
     let item_ty = get_into_iter_item_ty(tcx, check_ty, into_iterator_trait_id)?;
+    let features = db.bindings_features(core.def_id);
 
     let _ = db
-        .format_ty_for_cc(item_ty, TypeLocation::Other)
+        .format_ty_for_cc(item_ty, TypeLocation::Other, features)
         .context("Failed to format IntoIterator::Item")?;
 
     let into_iter_cc_ty = db
-        .format_ty_for_cc(into_iter_ty, TypeLocation::Other)
+        .format_ty_for_cc(into_iter_ty, TypeLocation::Other, features)
         .context("Failed to format IntoIterator::IntoIter")?;
 
     let static_check_ty = replace_all_regions_with_static(tcx, check_ty);
@@ -3189,6 +3229,7 @@ fn generate_begin_and_end_for_type<'tcx>(
         ),
         &[param],
         /*is_async=*/ false,
+        features,
     )?;
 
     let mut main_api_prereqs = CcPrerequisites::default();
@@ -90,7 +90,7 @@ fn test_format_bridged_type_in_generic_types() {
         assert_eq!(
             err,
             "Error handling parameter #0 of type `std::option::Option<std::boxed::Box<std::result::Result<RustType, ()>>>`: \
-            Failed to construct CrubitAbiType for std::boxed::Box<std::result::Result<RustType, ()>> because it does not have a move ctor or assignment operator."
+            crubit.rs/errors/unsupported_type: Types containing generic parameter `RustType` (which is a bridged type) are not supported."
         );
     });
 }
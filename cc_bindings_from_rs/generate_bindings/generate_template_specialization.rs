@@ -2,6 +2,7 @@
 // Exceptions. See /LICENSE for license information.
 // SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
 
+use crate::format_type::BridgedCollection;
 use crate::generate_function_thunk::{make_thunk_name, replace_all_regions_with_static, ThunkKind};
 use crate::generate_struct_and_union::{
     generate_associated_item, generate_relocating_ctor, has_type_or_const_vars,
@@ -12,14 +13,16 @@ use arc_anyhow::{bail, Result};
 use code_gen_utils::{escape_non_identifier_chars, CcInclude};
 use database::code_snippet::{
     ApiSnippets, CcPrerequisites, CcSnippet, EnumSpecializationKind, FormattedTy,
-    NegativeAutoTraitImplTemplateSpecialization, RsStdEnumSpecialization, RsStdSpecializationArgs,
-    RsStdTemplateSpecialization, TemplateSpecialization, TraitImplTemplateSpecialization,
+    NegativeAutoTraitImplTemplateSpecialization, RsSnippet, RsStdEnumSpecialization,
+    RsStdSpecializationArgs, RsStdTemplateSpecialization, TemplateSpecialization,
+    TraitImplTemplateSpecialization,
 };
 use database::{
     AdtCoreBindings, BindingsGenerator, CoreBindingsCommon, StaticMethodMode, TypeLocation,
 };
 use error_report::anyhow;
 use itertools::Itertools;
+use proc_macro2::Ident;
 use proc_macro2::Literal;
 use proc_macro2::TokenStream;
 use query_compiler::{self, get_layout, post_analysis_typing_env};
@@ -40,12 +43,14 @@ use rustc_middle::ty::Flags;
 use rustc_middle::ty::Unnormalized;
 use rustc_middle::ty::{self, AdtDef, Ty, TyCtxt, TypingEnv};
 use rustc_span::def_id::DefId;
+use rustc_span::symbol::sym;
 use std::collections::HashSet;
 use std::rc::Rc;
 
 pub(crate) fn parse_rs_std_template_specialization<'tcx>(
     db: &BindingsGenerator<'tcx>,
     self_ty: Ty<'tcx>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Option<Result<RsStdTemplateSpecialization<'tcx>>> {
     let tcx = db.tcx();
     #[rustversion::before(2026-04-22)]
@@ -68,10 +73,10 @@ pub(crate) fn parse_rs_std_template_specialization<'tcx>(
 
     match self_ty.kind() {
         ty::TyKind::Adt(adt, substs) => {
-            parse_adt_template_specialization(db, self_ty, *adt, substs)
+            parse_adt_template_specialization(db, self_ty, *adt, substs, features)
         }
         ty::TyKind::Tuple(types) if !types.is_empty() => {
-            parse_tuple_template_specialization(db, self_ty, types)
+            parse_tuple_template_specialization(db, self_ty, types, features)
         }
         _ => None,
     }
@@ -99,10 +104,18 @@ fn parse_adt_template_specialization<'tcx>(
     self_ty: Ty<'tcx>,
     adt: ty::AdtDef<'tcx>,
     substs: ty::GenericArgsRef<'tcx>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Option<Result<RsStdTemplateSpecialization<'tcx>>> {
     use crate::BridgedBuiltin;
     use database::code_snippet::EnumSpecializationKind;
     let tcx = db.tcx();
+    // A `HashMap` is converted to a C++ container by value when the feature is enabled, and is
+    // otherwise an opaque `rs_std::HashMap<K, V>`.
+    if let Some(BridgedCollection::HashMap) = BridgedCollection::new(db, adt)
+        && !features.contains(crubit_feature::CrubitFeature::BridgeCollections)
+    {
+        return Some(parse_hash_map_template_specialization(db, self_ty, adt, substs, features));
+    }
     BridgedBuiltin::new(db, adt).map(|bridged_builtin| {
         match bridged_builtin {
             BridgedBuiltin::Option => {
@@ -112,6 +125,7 @@ fn parse_adt_template_specialization<'tcx>(
                                 substs.type_at(0),
                                 TypeLocation::TemplateArg,
                                 db,
+                                features,
                             )
                         })?;
                 let layout = get_layout(tcx, self_ty)?;
@@ -131,7 +145,7 @@ fn parse_adt_template_specialization<'tcx>(
                     rustc_abi::Variants::Multiple { tag, .. } => tag,
                 };
                 let tag_type_rs = tag.primitive().to_int_ty(tcx);
-                let tag_type_cc = db.format_ty_for_cc(tag_type_rs, TypeLocation::Other)?;
+                let tag_type_cc = db.format_ty_for_cc(tag_type_rs, TypeLocation::Other, features)?;
                 let self_ty_cc = {
                     let mut prereqs = CcPrerequisites::default();
                     let some_ty_cc = some_ty.for_cc.clone().into_tokens(&mut prereqs);
@@ -155,12 +169,14 @@ fn parse_adt_template_specialization<'tcx>(
                         substs.type_at(0),
                         TypeLocation::TemplateArg,
                         db,
+                        features,
                     ))?;
                 let err_ty = parse_unit_in_specialization(db, substs.type_at(1))
                     .unwrap_or_else(|| FormattedTy::try_from_ty(
                         substs.type_at(1),
                         TypeLocation::TemplateArg,
                         db,
+                        features,
                     ))?;
 
                 let layout = get_layout(tcx, self_ty)?;
@@ -187,7 +203,7 @@ fn parse_adt_template_specialization<'tcx>(
                 };
 
                 let tag_type_rs = tag.primitive().to_int_ty(tcx);
-                let tag_type_cc = db.format_ty_for_cc(tag_type_rs, TypeLocation::Other)?;
+                let tag_type_cc = db.format_ty_for_cc(tag_type_rs, TypeLocation::Other, features)?;
                 let self_ty_cc = {
                     let mut prereqs = CcPrerequisites::default();
                     let ok_ty_cc = ok_ty.for_cc.clone().into_tokens(&mut prereqs);
@@ -215,6 +231,7 @@ fn parse_adt_template_specialization<'tcx>(
                     substs.type_at(0),
                     TypeLocation::TemplateArg,
                     db,
+                    features,
                 )?;
                 let layout = get_layout(tcx, self_ty)?;
                 let self_ty_cc = {
@@ -230,19 +247,102 @@ fn parse_adt_template_specialization<'tcx>(
                     args: RsStdSpecializationArgs::Vec(inner_ty),
                 })
             }
+            BridgedBuiltin::Arc | BridgedBuiltin::Rc => {
+                let pointee = substs.type_at(0);
+                if !pointee.is_sized(tcx, TypingEnv::fully_monomorphized()) {
+                    bail!("Arc and Rc of dynamically sized types are not supported yet");
+                }
+                let pointee_ty =
+                    FormattedTy::try_from_ty(pointee, TypeLocation::TemplateArg, db, features)?;
+                let layout = get_layout(tcx, self_ty)?;
+                let self_ty_cc = {
+                    let mut prereqs = CcPrerequisites::default();
+                    let pointee_ty_cc = pointee_ty.for_cc.clone().into_tokens(&mut prereqs);
+                    prereqs.forward_declare_type(pointee);
+                    let tokens = match bridged_builtin {
+                        BridgedBuiltin::Arc => quote! { rs_std::Arc<#pointee_ty_cc> },
+                        _ => quote! { rs_std::Rc<#pointee_ty_cc> },
+                    };
+                    CcSnippet { tokens, prereqs }
+                };
+                let args = match bridged_builtin {
+                    BridgedBuiltin::Arc => RsStdSpecializationArgs::Arc(pointee_ty),
+                    _ => RsStdSpecializationArgs::Rc(pointee_ty),
+                };
+                Ok(RsStdTemplateSpecialization { layout, self_ty_rs: self_ty, self_ty_cc, args })
+            }
+            BridgedBuiltin::Box => {
+                let pointee = substs.type_at(0);
+                if !pointee.is_sized(tcx, TypingEnv::fully_monomorphized()) {
+                    bail!("Box of dynamically sized types is not supported yet");
+                }
+                let pointee_ty =
+                    FormattedTy::try_from_ty(pointee, TypeLocation::TemplateArg, db, features)?;
+                let layout = get_layout(tcx, self_ty)?;
+                let self_ty_cc = {
+                    let mut prereqs = CcPrerequisites::default();
+                    let pointee_ty_cc = pointee_ty.for_cc.clone().into_tokens(&mut prereqs);
+                    prereqs.forward_declare_type(pointee);
+                    CcSnippet { tokens: quote! { rs_std::BoxDeleter<#pointee_ty_cc> }, prereqs }
+                };
+                Ok(RsStdTemplateSpecialization {
+                    layout,
+                    self_ty_rs: self_ty,
+                    self_ty_cc,
+                    args: RsStdSpecializationArgs::Box(pointee_ty),
+                })
+            }
         }
     })
 }
 
+fn parse_hash_map_template_specialization<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    self_ty: Ty<'tcx>,
+    adt: ty::AdtDef<'tcx>,
+    substs: ty::GenericArgsRef<'tcx>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
+) -> Result<RsStdTemplateSpecialization<'tcx>> {
+    let type_args = BridgedCollection::HashMap.type_args(db, adt, substs)?;
+    let (key, value) = (type_args[0].expect_ty(), type_args[1].expect_ty());
+    let tcx = db.tcx();
+    let is_hashable = [sym::Eq, sym::Hash].into_iter().all(|trait_name| {
+        let trait_id =
+            tcx.get_diagnostic_item(trait_name).expect("Could not find Eq or Hash trait");
+        query_compiler::does_type_implement_trait(tcx, key, trait_id, [])
+    });
+    if !is_hashable {
+        bail!("The key type `{key}` of a HashMap must implement `Eq` and `Hash`");
+    }
+    let key_ty = FormattedTy::try_from_ty(key, TypeLocation::TemplateArg, db, features)?;
+    let value_ty = FormattedTy::try_from_ty(value, TypeLocation::TemplateArg, db, features)?;
+    let layout = get_layout(tcx, self_ty)?;
+    let self_ty_cc = {
+        let mut prereqs = CcPrerequisites::default();
+        let key_ty_cc = key_ty.for_cc.clone().into_tokens(&mut prereqs);
+        let value_ty_cc = value_ty.for_cc.clone().into_tokens(&mut prereqs);
+        prereqs.forward_declare_type(key);
+        prereqs.forward_declare_type(value);
+        CcSnippet { tokens: quote! { rs_std::HashMap<#key_ty_cc, #value_ty_cc> }, prereqs }
+    };
+    Ok(RsStdTemplateSpecialization {
+        layout,
+        self_ty_rs: self_ty,
+        self_ty_cc,
+        args: RsStdSpecializationArgs::HashMap { key_ty, value_ty },
+    })
+}
+
 fn parse_tuple_template_specialization<'tcx>(
     db: &BindingsGenerator<'tcx>,
     self_ty: Ty<'tcx>,
     types: &'tcx ty::List<Ty<'tcx>>,
+    features: flagset::FlagSet<crubit_feature::CrubitFeature>,
 ) -> Option<Result<RsStdTemplateSpecialization<'tcx>>> {
     let tcx = db.tcx();
     let element_tys = types
         .iter()
-        .map(|ty| FormattedTy::try_from_ty(ty, TypeLocation::TemplateArg, db))
+        .map(|ty| FormattedTy::try_from_ty(ty, TypeLocation::TemplateArg, db, features))
         .collect::<Result<Vec<_>>>()
         .ok()?;
 
@@ -860,6 +960,23 @@ fn find_pointer_field_offset_impl<'tcx>(
     None
 }
 
+/// Returns the name of the thunk for the only method of the trait `trait_id`, implemented by
+/// `self_ty`.
+fn trait_method_thunk_ident<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    trait_id: DefId,
+    self_ty: Ty<'tcx>,
+) -> Ident {
+    let tcx = db.tcx();
+    let method = tcx
+        .associated_items(trait_id)
+        .in_definition_order()
+        .find(|item| matches!(item.kind, ty::AssocKind::Fn { .. }))
+        .expect("Trait should have a method");
+    let substs = tcx.mk_args_trait(self_ty, std::iter::empty());
+    format_ident!("{}", make_thunk_name(db, ThunkKind::TraitMethod { method, substs }))
+}
+
 /// Computes the offsets of the pointer and length fields of `Vec`.
 ///
 /// At the top level of `Vec<T>`, the layout consists of:
@@ -956,16 +1073,7 @@ fn specialize_vec<'tcx>(
     let qualified_name = cc_fully_qualified_name.to_string();
     let name = escape_non_identifier_chars(&qualified_name);
     let drop_trait = tcx.lang_items().drop_trait().expect("Could not find Drop trait");
-    let drop_assoc_fn = tcx
-        .associated_items(drop_trait)
-        .in_definition_order()
-        .find(|item| matches!(item.kind, ty::AssocKind::Fn { .. }))
-        .expect("Drop should have a method");
-    let substs = tcx.mk_args_trait(rs_std.self_ty_rs, std::iter::empty());
-    let drop_thunk_name = format_ident!(
-        "{}",
-        make_thunk_name(db, ThunkKind::TraitMethod { method: drop_assoc_fn, substs })
-    );
+    let drop_thunk_name = trait_method_thunk_ident(db, drop_trait, rs_std.self_ty_rs);
 
     let rs_drop = quote! {
         #[unsafe(no_mangle)]
@@ -1093,6 +1201,544 @@ fn specialize_vec<'tcx>(
     }
 }
 
+#[derive(Clone, Copy)]
+enum SharedPtrKind {
+    Arc,
+    Rc,
+}
+
+impl SharedPtrKind {
+    fn cc_short_name(self) -> Ident {
+        match self {
+            SharedPtrKind::Arc => format_ident!("Arc"),
+            SharedPtrKind::Rc => format_ident!("Rc"),
+        }
+    }
+
+    fn rs_path(self) -> TokenStream {
+        match self {
+            SharedPtrKind::Arc => quote! { ::alloc::sync::Arc },
+            SharedPtrKind::Rc => quote! { ::alloc::rc::Rc },
+        }
+    }
+}
+
+/// Generates the `rs_std::Arc<T>` or `rs_std::Rc<T>` specialization.
+///
+/// Each C++ object owns one strong reference: copying it increments the reference count through
+/// the `Clone` thunk, and destroying it decrements the count through the `Drop` thunk. Rust only
+/// hands out shared references to the pointee, so C++ can only access it through a const pointer.
+fn specialize_shared_ptr<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    rs_std: &RsStdTemplateSpecialization<'tcx>,
+    kind: SharedPtrKind,
+    pointee_ty: FormattedTy<'tcx>,
+) -> ApiSnippets<'tcx> {
+    let tcx = db.tcx();
+    let layout = rs_std.layout;
+    let mut prereqs = CcPrerequisites::default();
+    let pointee_ty_cc = pointee_ty.for_cc.clone().into_tokens(&mut prereqs);
+    let pointee_ty_rs = &pointee_ty.for_rs;
+
+    let rs_path = kind.rs_path();
+    let rs_fully_qualified_name = quote! { #rs_path<#pointee_ty_rs> };
+    let cc_short_name = kind.cc_short_name();
+    let cc_fully_qualified_name = quote! { rs_std::#cc_short_name<#pointee_ty_cc> };
+
+    let adt_def = rs_std.self_ty_rs.ty_adt_def().expect("Arc and Rc should be ADTs");
+
+    let core = Rc::new(AdtCoreBindings {
+        common: Rc::new(CoreBindingsCommon {
+            keyword: quote! { struct },
+            cc_short_name: cc_short_name.clone(),
+            cc_fully_qualified_name: cc_fully_qualified_name.clone(),
+            self_ty: rs_std.self_ty_rs,
+            alignment_in_bytes: layout.align().abi.bytes(),
+            size_in_bytes: layout.size().bytes(),
+        }),
+        def_id: Some(adt_def.did()),
+        rs_fully_qualified_name: rs_fully_qualified_name.clone(),
+    });
+
+    let default_ctor_snippets = db.generate_default_ctor(core.clone()).unwrap_or_else(|err| err);
+    let copy_ctor_and_assignment_snippets =
+        db.generate_copy_ctor_and_assignment_operator(core.clone()).unwrap_or_else(|err| err);
+    let move_ctor_and_assignment_snippets = db
+        .generate_move_ctor_and_assignment_operator(core.clone())
+        .unwrap_or_else(|err| err.explicitly_deleted);
+    let relocating_ctor_snippets = generate_relocating_ctor(
+        db,
+        &core.common.cc_short_name,
+        &core.common.cc_fully_qualified_name,
+    );
+
+    let qualified_name = cc_fully_qualified_name.to_string();
+    let name = escape_non_identifier_chars(&qualified_name);
+    let drop_trait = tcx.lang_items().drop_trait().expect("Could not find Drop trait");
+    let drop_thunk_name = trait_method_thunk_ident(db, drop_trait, rs_std.self_ty_rs);
+    let deref_trait = tcx.lang_items().deref_trait().expect("Could not find Deref trait");
+    let deref_thunk_name = trait_method_thunk_ident(db, deref_trait, rs_std.self_ty_rs);
+
+    let rs_thunks = quote! {
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #drop_thunk_name(ptr: *mut #rs_fully_qualified_name) {
+            // SAFETY: The caller guarantees `ptr` is a valid pointer to an initialized value.
+            unsafe { ::core::ptr::drop_in_place(ptr) };
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #deref_thunk_name(
+            ptr: *const #rs_fully_qualified_name,
+        ) -> *const #pointee_ty_rs {
+            // SAFETY: The caller guarantees `ptr` is a valid pointer to an initialized value.
+            #rs_path::as_ptr(unsafe { &*ptr })
+        }
+    };
+
+    let mut accessors_decl = quote! {
+        ~#cc_short_name() noexcept; __NEWLINE__
+
+        __NEWLINE__ __COMMENT__ "Returns a pointer to the shared value, which is never null."
+        #pointee_ty_cc const* get() const noexcept;
+        #pointee_ty_cc const& operator*() const noexcept;
+        #pointee_ty_cc const* operator->() const noexcept;
+    };
+    let mut accessors_impl = quote! {
+        extern "C" void #drop_thunk_name(void* ptr) noexcept;
+        extern "C" #pointee_ty_cc const* #deref_thunk_name(void const* ptr) noexcept;
+        inline #cc_fully_qualified_name::~#cc_short_name() noexcept {
+            #drop_thunk_name(this);
+        }
+        inline #pointee_ty_cc const* #cc_fully_qualified_name::get() const noexcept {
+            return #deref_thunk_name(this);
+        }
+        inline #pointee_ty_cc const& #cc_fully_qualified_name::operator*() const noexcept {
+            return *get();
+        }
+        inline #pointee_ty_cc const* #cc_fully_qualified_name::operator->() const noexcept {
+            return get();
+        }
+    };
+
+    if let SharedPtrKind::Arc = kind {
+        prereqs.includes.insert(CcInclude::memory());
+        prereqs.includes.insert(CcInclude::utility());
+        let shared_ptr = quote! { std::shared_ptr<#pointee_ty_cc const> };
+        accessors_decl.extend(quote! {
+            __NEWLINE__ __COMMENT__ "Converts to a `std::shared_ptr` whose deleter drops an `Arc` to the same value."
+            explicit operator #shared_ptr() const&;
+            explicit operator #shared_ptr() &&;
+        });
+        accessors_impl.extend(quote! {
+            inline #cc_fully_qualified_name::operator #shared_ptr() const& {
+                return static_cast<#shared_ptr>(#cc_fully_qualified_name(*this));
+            }
+            inline #cc_fully_qualified_name::operator #shared_ptr() && {
+                auto* arc = new #cc_fully_qualified_name(std::move(*this));
+                return #shared_ptr(arc->get(), [arc](#pointee_ty_cc const*) { delete arc; });
+            }
+        });
+    }
+
+    let ApiSnippets { main_api, cc_details, rs_details } = [
+        default_ctor_snippets,
+        copy_ctor_and_assignment_snippets,
+        move_ctor_and_assignment_snippets,
+        relocating_ctor_snippets,
+    ]
+    .into_iter()
+    .collect();
+
+    let mut rs_details = rs_details;
+    rs_details.tokens.extend(rs_thunks);
+
+    let main_api_tokens = main_api.into_tokens(&mut prereqs);
+    let guard_name = format_ident!("_CRUBIT_BINDINGS_FOR_{}", name);
+    let size_literal = Literal::u64_unsuffixed(layout.size().bytes());
+    let align_literal = Literal::u64_unsuffixed(layout.align().abi.bytes());
+    let internal_rust_type_string = rs_fully_qualified_name.to_string();
+
+    let main_api_tokens = quote! {
+        __HASH_TOKEN__ ifndef #guard_name __NEWLINE__
+        __HASH_TOKEN__ define #guard_name __NEWLINE__
+        template<> __NEWLINE__
+        struct alignas(#align_literal)
+        CRUBIT_INTERNAL_RUST_TYPE(#internal_rust_type_string)
+        #cc_fully_qualified_name { __NEWLINE__
+        public:
+            #main_api_tokens __NEWLINE__
+            #accessors_decl __NEWLINE__
+
+        private:
+            unsigned char storage_[#size_literal];
+            __NEWLINE__
+        }; __NEWLINE__
+
+        __HASH_TOKEN__ endif __NEWLINE__
+        __NEWLINE__
+    };
+
+    let guard_name = format_ident!("_CRUBIT_BINDINGS_FOR_IMPL_{}", name);
+    let cc_details_tokens = cc_details.into_tokens(&mut prereqs);
+    let cc_details_tokens = quote! {
+        __HASH_TOKEN__ ifndef #guard_name __NEWLINE__
+        __HASH_TOKEN__ define #guard_name __NEWLINE__
+        #cc_details_tokens __NEWLINE__
+        #accessors_impl __NEWLINE__
+        __HASH_TOKEN__ endif __NEWLINE__
+        __NEWLINE__
+    };
+
+    ApiSnippets {
+        main_api: CcSnippet { tokens: main_api_tokens, prereqs },
+        cc_details: CcSnippet::new(cc_details_tokens),
+        rs_details,
+    }
+}
+
+/// Generates the `rs_std::BoxDeleter<T>` specialization of `rs_std::Box<T>`, which is a
+/// `std::unique_ptr` that owns a `Box<T>`. The deleter drops the `Box` in Rust, which runs the
+/// destructor of `T` and deallocates it with the Rust allocator.
+fn specialize_box_deleter<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    rs_std: &RsStdTemplateSpecialization<'tcx>,
+    pointee_ty: FormattedTy<'tcx>,
+) -> ApiSnippets<'tcx> {
+    let tcx = db.tcx();
+    let mut prereqs = CcPrerequisites::default();
+    let pointee_ty_cc = pointee_ty.for_cc.clone().into_tokens(&mut prereqs);
+    let pointee_ty_rs = &pointee_ty.for_rs;
+    let cc_fully_qualified_name = quote! { rs_std::BoxDeleter<#pointee_ty_cc> };
+
+    let qualified_name = cc_fully_qualified_name.to_string();
+    let name = escape_non_identifier_chars(&qualified_name);
+    let drop_trait = tcx.lang_items().drop_trait().expect("Could not find Drop trait");
+    let drop_thunk_name = trait_method_thunk_ident(db, drop_trait, rs_std.self_ty_rs);
+
+    let rs_drop = quote! {
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #drop_thunk_name(ptr: *mut #pointee_ty_rs) {
+            // SAFETY: The caller guarantees `ptr` was produced by `Box::into_raw`.
+            ::core::mem::drop(unsafe { ::alloc::boxed::Box::from_raw(ptr) });
+        }
+    };
+
+    let guard_name = format_ident!("_CRUBIT_BINDINGS_FOR_{}", name);
+    let main_api_tokens = quote! {
+        __HASH_TOKEN__ ifndef #guard_name __NEWLINE__
+        __HASH_TOKEN__ define #guard_name __NEWLINE__
+        template<> __NEWLINE__
+        struct #cc_fully_qualified_name { __NEWLINE__
+            void operator()(#pointee_ty_cc* ptr) const noexcept;
+            __NEWLINE__
+        }; __NEWLINE__
+
+        __HASH_TOKEN__ endif __NEWLINE__
+        __NEWLINE__
+    };
+
+    let guard_name = format_ident!("_CRUBIT_BINDINGS_FOR_IMPL_{}", name);
+    let cc_details_tokens = quote! {
+        __HASH_TOKEN__ ifndef #guard_name __NEWLINE__
+        __HASH_TOKEN__ define #guard_name __NEWLINE__
+        extern "C" void #drop_thunk_name(void* ptr) noexcept;
+        inline void #cc_fully_qualified_name::operator()(#pointee_ty_cc* ptr) const noexcept {
+            #drop_thunk_name(ptr);
+        }
+        __HASH_TOKEN__ endif __NEWLINE__
+        __NEWLINE__
+    };
+
+    ApiSnippets {
+        main_api: CcSnippet { tokens: main_api_tokens, prereqs },
+        cc_details: CcSnippet::new(cc_details_tokens),
+        rs_details: RsSnippet::new(rs_drop),
+    }
+}
+
+/// Returns the name of a thunk of the `rs_std::HashMap<K, V>` specialization for `self_ty`.
+///
+/// The thunks call inherent methods of `HashMap`, so they are named after the `Drop` thunk of the
+/// map, which is unique for each `HashMap<K, V>`.
+fn hash_map_thunk_ident<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    self_ty: Ty<'tcx>,
+    method: &str,
+) -> Ident {
+    let drop_trait = db.tcx().lang_items().drop_trait().expect("Could not find Drop trait");
+    format_ident!("{}_{method}", trait_method_thunk_ident(db, drop_trait, self_ty))
+}
+
+/// Generates the opaque `rs_std::HashMap<K, V>` specialization.
+///
+/// The map stays in Rust's layout, and C++ accesses it through thunks which call `len`, `get`,
+/// `get_mut`, `insert` and `iter`, so nothing is copied. Iterating yields pointers to the keys
+/// and values through `rs_std::HashMapIter<K, V>`, which owns a boxed `hash_map::Iter`.
+fn specialize_hash_map<'tcx>(
+    db: &BindingsGenerator<'tcx>,
+    rs_std: &RsStdTemplateSpecialization<'tcx>,
+    key_ty: FormattedTy<'tcx>,
+    value_ty: FormattedTy<'tcx>,
+) -> ApiSnippets<'tcx> {
+    let layout = rs_std.layout;
+    let mut prereqs = CcPrerequisites::default();
+    let key_ty_cc = key_ty.for_cc.clone().into_tokens(&mut prereqs);
+    let value_ty_cc = value_ty.for_cc.clone().into_tokens(&mut prereqs);
+    let key_ty_rs = &key_ty.for_rs;
+    let value_ty_rs = &value_ty.for_rs;
+
+    let rs_fully_qualified_name = quote! { ::std::collections::HashMap<#key_ty_rs, #value_ty_rs> };
+    let cc_fully_qualified_name = quote! { rs_std::HashMap<#key_ty_cc, #value_ty_cc> };
+    let cc_iter_name = quote! { rs_std::HashMapIter<#key_ty_cc, #value_ty_cc> };
+
+    let adt_def = rs_std.self_ty_rs.ty_adt_def().expect("HashMap should be an ADT");
+
+    let core = Rc::new(AdtCoreBindings {
+        common: Rc::new(CoreBindingsCommon {
+            keyword: quote! { struct },
+            cc_short_name: format_ident!("HashMap"),
+            cc_fully_qualified_name: cc_fully_qualified_name.clone(),
+            self_ty: rs_std.self_ty_rs,
+            alignment_in_bytes: layout.align().abi.bytes(),
+            size_in_bytes: layout.size().bytes(),
+        }),
+        def_id: Some(adt_def.did()),
+        rs_fully_qualified_name: rs_fully_qualified_name.clone(),
+    });
+
+    let default_ctor_snippets = db.generate_default_ctor(core.clone()).unwrap_or_else(|err| err);
+    let copy_ctor_and_assignment_snippets =
+        db.generate_copy_ctor_and_assignment_operator(core.clone()).unwrap_or_else(|err| err);
+    let move_ctor_and_assignment_snippets = db
+        .generate_move_ctor_and_assignment_operator(core.clone())
+        .unwrap_or_else(|err| err.explicitly_deleted);
+    let relocating_ctor_snippets = generate_relocating_ctor(
+        db,
+        &core.common.cc_short_name,
+        &core.common.cc_fully_qualified_name,
+    );
+
+    let qualified_name = cc_fully_qualified_name.to_string();
+    let name = escape_non_identifier_chars(&qualified_name);
+    let self_ty = rs_std.self_ty_rs;
+    let drop_thunk_name = hash_map_thunk_ident(db, self_ty, "drop");
+    let len_thunk_name = hash_map_thunk_ident(db, self_ty, "len");
+    let get_thunk_name = hash_map_thunk_ident(db, self_ty, "get");
+    let get_mut_thunk_name = hash_map_thunk_ident(db, self_ty, "get_mut");
+    let insert_thunk_name = hash_map_thunk_ident(db, self_ty, "insert");
+    let iter_thunk_name = hash_map_thunk_ident(db, self_ty, "iter");
+    let iter_next_thunk_name = hash_map_thunk_ident(db, self_ty, "iter_next");
+    let iter_drop_thunk_name = hash_map_thunk_ident(db, self_ty, "iter_drop");
+    let rs_iter_name = quote! {
+        ::std::collections::hash_map::Iter<'static, #key_ty_rs, #value_ty_rs>
+    };
+
+    let rs_thunks = quote! {
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #drop_thunk_name(map: *mut #rs_fully_qualified_name) {
+            // SAFETY: The caller guarantees `map` is a valid pointer to an initialized map.
+            unsafe { ::core::ptr::drop_in_place(map) };
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #len_thunk_name(map: *const #rs_fully_qualified_name) -> usize {
+            // SAFETY: The caller guarantees `map` is a valid pointer to an initialized map.
+            unsafe { &*map }.len()
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #get_thunk_name(
+            map: *const #rs_fully_qualified_name,
+            key: *const #key_ty_rs,
+        ) -> *const #value_ty_rs {
+            // SAFETY: The caller guarantees `map` and `key` are valid pointers to initialized
+            // values.
+            match unsafe { &*map }.get(unsafe { &*key }) {
+                Some(value) => value,
+                None => ::core::ptr::null(),
+            }
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #get_mut_thunk_name(
+            map: *mut #rs_fully_qualified_name,
+            key: *const #key_ty_rs,
+        ) -> *mut #value_ty_rs {
+            // SAFETY: The caller guarantees `map` and `key` are valid pointers to initialized
+            // values, and that `map` isn't aliased.
+            match unsafe { &mut *map }.get_mut(unsafe { &*key }) {
+                Some(value) => value,
+                None => ::core::ptr::null_mut(),
+            }
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #insert_thunk_name(
+            map: *mut #rs_fully_qualified_name,
+            key: *mut #key_ty_rs,
+            value: *mut #value_ty_rs,
+        ) -> bool {
+            // SAFETY: The caller guarantees `map` is a valid pointer to an initialized map which
+            // isn't aliased, and moves the initialized `key` and `value` into the map.
+            let (key, value) = unsafe { (key.read(), value.read()) };
+            unsafe { &mut *map }.insert(key, value).is_none()
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #iter_thunk_name(
+            map: *const #rs_fully_qualified_name,
+        ) -> *mut ::core::ffi::c_void {
+            // SAFETY: The caller guarantees `map` is a valid pointer to an initialized map, which
+            // outlives the iterator.
+            let iter: #rs_iter_name = unsafe { &*map }.iter();
+            ::std::boxed::Box::into_raw(::std::boxed::Box::new(iter)).cast()
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #iter_next_thunk_name(
+            iter: *mut ::core::ffi::c_void,
+            key: *mut *const #key_ty_rs,
+            value: *mut *const #value_ty_rs,
+        ) -> bool {
+            // SAFETY: The caller guarantees `iter` was returned by the `iter` thunk and hasn't
+            // been dropped, and that `key` and `value` are valid for writes.
+            let iter = unsafe { &mut *iter.cast::<#rs_iter_name>() };
+            match iter.next() {
+                Some((next_key, next_value)) => {
+                    unsafe {
+                        key.write(next_key);
+                        value.write(next_value);
+                    }
+                    true
+                }
+                None => false,
+            }
+        }
+        #[unsafe(no_mangle)]
+        unsafe extern "C" fn #iter_drop_thunk_name(iter: *mut ::core::ffi::c_void) {
+            // SAFETY: The caller guarantees `iter` was returned by the `iter` thunk and hasn't
+            // been dropped.
+            ::core::mem::drop(unsafe { ::std::boxed::Box::from_raw(iter.cast::<#rs_iter_name>()) });
+        }
+    };
+
+    prereqs.includes.insert(CcInclude::cstddef());
+    prereqs.includes.insert(CcInclude::utility());
+    prereqs.includes.insert(db.support_header("internal/slot.h"));
+
+    let accessors_decl = quote! {
+        ~HashMap() noexcept; __NEWLINE__
+
+        __NEWLINE__ __COMMENT__ "Returns the number of entries in the map."
+        std::size_t size() const noexcept; __NEWLINE__
+
+        __NEWLINE__ __COMMENT__ "Returns a pointer to the value of `key`, or `nullptr` if there is none."
+        #value_ty_cc const* find(#key_ty_cc const& key) const noexcept;
+        #value_ty_cc* find(#key_ty_cc const& key) noexcept; __NEWLINE__
+
+        __NEWLINE__ __COMMENT__ "Inserts `value` at `key`, replacing any previous value.\nReturns whether `key` is new to the map."
+        bool insert(#key_ty_cc key, #value_ty_cc value) noexcept; __NEWLINE__
+
+        __NEWLINE__ __COMMENT__ "Iterates over pointers to the keys and values, in an unspecified order.\nThe map must not be modified while iterating."
+        rs::IteratorAdapter<#cc_iter_name> begin() const noexcept;
+        rs::IteratorEnd end() const noexcept;
+    };
+    let private_decl = quote! {
+        friend class #cc_iter_name;
+        friend struct rs_std::impl<#cc_iter_name, ::rs::core::iter::Iterator>;
+        static bool NextIter(void* iter, #key_ty_cc const** key, #value_ty_cc const** value) noexcept;
+        static void DropIter(void* iter) noexcept; __NEWLINE__
+    };
+    let accessors_impl = quote! {
+        extern "C" void #drop_thunk_name(void* map) noexcept;
+        extern "C" std::size_t #len_thunk_name(void const* map) noexcept;
+        extern "C" #value_ty_cc const* #get_thunk_name(void const* map, #key_ty_cc const* key) noexcept;
+        extern "C" #value_ty_cc* #get_mut_thunk_name(void* map, #key_ty_cc const* key) noexcept;
+        extern "C" bool #insert_thunk_name(void* map, #key_ty_cc* key, #value_ty_cc* value) noexcept;
+        extern "C" void* #iter_thunk_name(void const* map) noexcept;
+        extern "C" bool #iter_next_thunk_name(void* iter, #key_ty_cc const** key, #value_ty_cc const** value) noexcept;
+        extern "C" void #iter_drop_thunk_name(void* iter) noexcept;
+        inline #cc_fully_qualified_name::~HashMap() noexcept {
+            #drop_thunk_name(this);
+        }
+        inline std::size_t #cc_fully_qualified_name::size() const noexcept {
+            return #len_thunk_name(this);
+        }
+        inline #value_ty_cc const* #cc_fully_qualified_name::find(#key_ty_cc const& key) const noexcept {
+            return #get_thunk_name(this, &key);
+        }
+        inline #value_ty_cc* #cc_fully_qualified_name::find(#key_ty_cc const& key) noexcept {
+            return #get_mut_thunk_name(this, &key);
+        }
+        inline bool #cc_fully_qualified_name::insert(#key_ty_cc key, #value_ty_cc value) noexcept {
+            crubit::Slot key_slot((std::move(key)));
+            crubit::Slot value_slot((std::move(value)));
+            return #insert_thunk_name(this, key_slot.Get(), value_slot.Get());
+        }
+        inline rs::IteratorAdapter<#cc_iter_name> #cc_fully_qualified_name::begin() const noexcept {
+            return rs::IteratorAdapter<#cc_iter_name>(#cc_iter_name(#iter_thunk_name(this)));
+        }
+        inline rs::IteratorEnd #cc_fully_qualified_name::end() const noexcept {
+            return rs::IteratorEnd();
+        }
+        inline bool #cc_fully_qualified_name::NextIter(void* iter, #key_ty_cc const** key, #value_ty_cc const** value) noexcept {
+            return #iter_next_thunk_name(iter, key, value);
+        }
+        inline void #cc_fully_qualified_name::DropIter(void* iter) noexcept {
+            #iter_drop_thunk_name(iter);
+        }
+    };
+
+    let ApiSnippets { main_api, cc_details, rs_details } = [
+        default_ctor_snippets,
+        copy_ctor_and_assignment_snippets,
+        move_ctor_and_assignment_snippets,
+        relocating_ctor_snippets,
+    ]
+    .into_iter()
+    .collect();
+
+    let mut rs_details = rs_details;
+    rs_details.tokens.extend(rs_thunks);
+
+    let main_api_tokens = main_api.into_tokens(&mut prereqs);
+    let guard_name = format_ident!("_CRUBIT_BINDINGS_FOR_{}", name);
+    let size_literal = Literal::u64_unsuffixed(layout.size().bytes());
+    let align_literal = Literal::u64_unsuffixed(layout.align().abi.bytes());
+    let internal_rust_type_string = rs_fully_qualified_name.to_string();
+
+    let main_api_tokens = quote! {
+        __HASH_TOKEN__ ifndef #guard_name __NEWLINE__
+        __HASH_TOKEN__ define #guard_name __NEWLINE__
+        template<> __NEWLINE__
+        struct alignas(#align_literal)
+        CRUBIT_INTERNAL_RUST_TYPE(#internal_rust_type_string)
+        #cc_fully_qualified_name { __NEWLINE__
+        public:
+            #main_api_tokens __NEWLINE__
+            #accessors_decl __NEWLINE__
+
+        private:
+            #private_decl
+            unsigned char storage_[#size_literal];
+            __NEWLINE__
+        }; __NEWLINE__
+
+        __HASH_TOKEN__ endif __NEWLINE__
+        __NEWLINE__
+    };
+
+    let guard_name = format_ident!("_CRUBIT_BINDINGS_FOR_IMPL_{}", name);
+    let cc_details_tokens = cc_details.into_tokens(&mut prereqs);
+    let cc_details_tokens = quote! {
+        __HASH_TOKEN__ ifndef #guard_name __NEWLINE__
+        __HASH_TOKEN__ define #guard_name __NEWLINE__
+        #cc_details_tokens __NEWLINE__
+        #accessors_impl __NEWLINE__
+        __HASH_TOKEN__ endif __NEWLINE__
+        __NEWLINE__
+    };
+
+    ApiSnippets {
+        main_api: CcSnippet { tokens: main_api_tokens, prereqs },
+        cc_details: CcSnippet::new(cc_details_tokens),
+        rs_details,
+    }
+}
+
 fn specialize_result<'tcx>(
     db: &BindingsGenerator<'tcx>,
     rs_std: &RsStdTemplateSpecialization<'tcx>,
@@ -1600,6 +2246,33 @@ impl<'tcx> TemplateSpecializationExt<'tcx> for RsStdTemplateSpecialization<'tcx>
                 snippets.main_api.prereqs.forward_declare_type(inner_ty_ty);
                 snippets
             }
+            RsStdSpecializationArgs::Arc(pointee_ty) => {
+                let pointee_ty_ty = pointee_ty.ty;
+                let mut snippets =
+                    specialize_shared_ptr(db, &self, SharedPtrKind::Arc, pointee_ty.clone());
+                snippets.main_api.prereqs.forward_declare_type(pointee_ty_ty);
+                snippets
+            }
+            RsStdSpecializationArgs::Rc(pointee_ty) => {
+                let pointee_ty_ty = pointee_ty.ty;
+                let mut snippets =
+                    specialize_shared_ptr(db, &self, SharedPtrKind::Rc, pointee_ty.clone());
+                snippets.main_api.prereqs.forward_declare_type(pointee_ty_ty);
+                snippets
+            }
+            RsStdSpecializationArgs::Box(pointee_ty) => {
+                let pointee_ty_ty = pointee_ty.ty;
+                let mut snippets = specialize_box_deleter(db, &self, pointee_ty.clone());
+                snippets.main_api.prereqs.forward_declare_type(pointee_ty_ty);
+                snippets
+            }
+            RsStdSpecializationArgs::HashMap { key_ty, value_ty } => {
+                let (key_ty_ty, value_ty_ty) = (key_ty.ty, value_ty.ty);
+                let mut snippets = specialize_hash_map(db, &self, key_ty.clone(), value_ty.clone());
+                snippets.main_api.prereqs.forward_declare_type(key_ty_ty);
+                snippets.main_api.prereqs.forward_declare_type(value_ty_ty);
+                snippets
+            }
         }
     }
 }
@@ -1771,6 +2444,7 @@ fn generate_trait_impl_specialization<'tcx>(
     );
     let trait_name = canonical_trait_name.format_for_cc(db)?;
 
+    let features = db.bindings_features(Some(impl_def_id));
     let mut prereqs = CcPrerequisites::default();
     let trait_args: Vec<_> = trait_ref
         .args
@@ -1787,7 +2461,7 @@ fn generate_trait_impl_specialization<'tcx>(
                     "b/491106325 - isize and usize types are not yet supported as trait type arguments."
                 );
             }
-            db.format_ty_for_cc(arg, TypeLocation::TemplateArg)
+            db.format_ty_for_cc(arg, TypeLocation::TemplateArg, features)
                 .map(|snippet| snippet.into_tokens(&mut prereqs))
         })
         .collect::<Result<Vec<_>>>()?;
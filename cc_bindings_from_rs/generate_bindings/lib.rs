@@ -116,7 +116,11 @@ fn add_include_guard(db: &BindingsGenerator<'_>, cc_api: TokenStream) -> Result<
             #cc_api
         }),
         IncludeGuard::Guard(include_guard_str) => {
-            let include_guard = format_cc_ident(db, include_guard_str.as_str())?;
+            let include_guard = format_cc_ident(
+                db,
+                include_guard_str.as_str(),
+                db.crate_features(db.source_crate_num()),
+            )?;
             Ok(quote! {
                 __HASH_TOKEN__ ifndef #include_guard __NEWLINE__
                 __HASH_TOKEN__ define #include_guard __NEWLINE__
@@ -443,7 +447,9 @@ fn format_with_cc_body(
     if tokens.is_empty() {
         return Ok(quote! {});
     }
-    let mut namespaces = ns.parts().map(|s| format_cc_ident(db, s)).collect::<Result<Vec<_>>>()?;
+    let features = db.crate_features(db.source_crate_num());
+    let mut namespaces =
+        ns.parts().map(|s| format_cc_ident(db, s, features)).collect::<Result<Vec<_>>>()?;
 
     // Nested namespace syntax does not accept attributes (see b/445613694), so we have to split out
     // the with-attribute decl to contain only the trailing namespace.
@@ -784,7 +790,7 @@ fn symbol_unqualified_name(db: &BindingsGenerator<'_>, def_id: DefId) -> Option<
         // Hence, this unkeywording behavior only happens in the case where we implicitly
         // delegate to the Rust name.
         Symbol::intern(
-            code_gen_utils::unkeyword_cpp_ident(rs_name.as_str(), db.crate_features(def_id.krate))
+            code_gen_utils::unkeyword_cpp_ident(rs_name.as_str(), db.item_features(def_id))
                 .as_ref(),
         )
     });
@@ -866,7 +872,7 @@ fn symbol_canonical_name(db: &BindingsGenerator<'_>, def_id: DefId) -> Option<Fu
         }
     }
 
-    let features = db.crate_features(krate_num);
+    let features = db.item_features(def_id);
     let use_leading_colons =
         features.contains(crubit_feature::CrubitFeature::LeadingColonsForCppType);
 
@@ -1036,11 +1042,15 @@ fn generate_using<'tcx>(
                 .symbol_canonical_name(def_id)
                 .unwrap_or_else(|| panic!("Failed to get canonical name for {:?}", def_id));
             let formatted_fully_qualified_fn_name = fully_qualified_fn_name.format_for_cc(db)?;
-            let main_api_fn_name =
-                format_cc_ident(db, fully_qualified_fn_name.unqualified.cpp_name.as_str())
-                    .context("Error formatting function name")?;
-            let using_name_ident =
-                format_cc_ident(db, using_name.as_str()).context("Error formatting using name")?;
+            let features = db.item_features(def_id);
+            let main_api_fn_name = format_cc_ident(
+                db,
+                fully_qualified_fn_name.unqualified.cpp_name.as_str(),
+                features,
+            )
+            .context("Error formatting function name")?;
+            let using_name_ident = format_cc_ident(db, using_name.as_str(), features)
+                .context("Error formatting using name")?;
 
             prereqs.depend_on_def(db, def_id)?;
             let tokens = if using_name_ident == main_api_fn_name {
@@ -1089,7 +1099,8 @@ fn generate_using<'tcx>(
 
             let trait_name = canonical_name.format_for_cc(db)?;
             let using_name_ident =
-                format_cc_ident(db, using_name.as_str()).context("Error formatting using name")?;
+                format_cc_ident(db, using_name.as_str(), db.item_features(def_id))
+                    .context("Error formatting using name")?;
             let mut prereqs = CcPrerequisites::default();
             prereqs.depend_on_def(db, def_id)?;
             let tokens = if using_name_ident == canonical_name.unqualified.cpp_name.as_str() {
@@ -1128,10 +1139,11 @@ fn generate_const<'tcx>(db: &BindingsGenerator<'tcx>, def_id: DefId) -> Result<A
     }
     let ty = normalize_ty(tcx, tcx.param_env(def_id), tcx.type_of(def_id).instantiate_identity());
     let rust_type = ty;
-    let cc_type_snippet = db.format_ty_for_cc(rust_type, TypeLocation::Const)?;
+    let features = db.item_features(def_id);
+    let cc_type_snippet = db.format_ty_for_cc(rust_type, TypeLocation::Const, features)?;
 
     let cc_type = cc_type_snippet.tokens;
-    let cc_name = format_cc_ident(db, tcx.item_name(def_id).as_str())?;
+    let cc_name = format_cc_ident(db, tcx.item_name(def_id).as_str(), features)?;
 
     // Note that `&str` constants may appear as either `ConstValue::Slice` or
     // `ConstValue::Indirect`.
@@ -1279,7 +1291,11 @@ fn generate_trait<'tcx>(
         .expect("generate_trait was unexpectedly called on an item without a canonical name");
 
     let doc_comment = generate_doc_comment(db, trait_id);
-    let trait_name = format_cc_ident(db, canonical_name.unqualified.cpp_name.as_str())?;
+    let trait_name = format_cc_ident(
+        db,
+        canonical_name.unqualified.cpp_name.as_str(),
+        db.item_features(trait_id),
+    )?;
     let rs_type = canonical_name.format_for_rs().to_string();
     let attributes = vec![quote! {CRUBIT_INTERNAL_RUST_TYPE(#rs_type)}];
 
@@ -1353,12 +1369,14 @@ pub(crate) fn create_type_alias_with_rs_type<'tcx>(
     alias_type: Ty<'tcx>,
 ) -> Result<CcSnippet<'tcx>> {
     let doc_comment = generate_doc_comment(db, def_id);
-    let cc_bindings = db.format_ty_for_cc(alias_type, TypeLocation::Other)?;
+    let features = db.bindings_features(Some(def_id));
+    let cc_bindings = db.format_ty_for_cc(alias_type, TypeLocation::Other, features)?;
     let mut main_api_prereqs = CcPrerequisites::default();
     let actual_type_name = cc_bindings.into_tokens(&mut main_api_prereqs);
     main_api_prereqs.move_defs_to_fwd_decls();
 
-    let alias_name = format_cc_ident(db, alias_name).context("Error formatting type alias name")?;
+    let alias_name =
+        format_cc_ident(db, alias_name, features).context("Error formatting type alias name")?;
 
     main_api_prereqs.includes.insert(db.support_header("annotations_internal.h"));
     let mut attributes = vec![quote! {CRUBIT_INTERNAL_RUST_TYPE(#rs_type)}];
@@ -1941,7 +1959,7 @@ fn generate_item_impl<'tcx>(
     };
 
     if let Ok(Some(item)) = item {
-        Ok(Some(item.resolve_feature_requirements(db.crate_features(db.source_crate_num()))?))
+        Ok(Some(item.resolve_feature_requirements(db.item_features(def_id))?))
     } else {
         item
     }
@@ -2489,7 +2507,7 @@ fn generate_crate(db: &BindingsGenerator) -> Result<BindingsTokens> {
             .chain(ordered_main_apis)
             .map(|(node, tokens)| match node {
                 Node::Def(def_id) => {
-                    let features = db.crate_features(def_id.krate);
+                    let features = db.item_features(def_id);
                     let use_leading_colons =
                         features.contains(crubit_feature::CrubitFeature::LeadingColonsForCppType);
                     (
@@ -2521,7 +2539,7 @@ fn generate_crate(db: &BindingsGenerator) -> Result<BindingsTokens> {
             })
             .chain(cc_details.into_iter().map(|details| {
                 let use_leading_colons = db
-                    .crate_features(details.def_id.krate)
+                    .item_features(details.def_id)
                     .contains(crubit_feature::CrubitFeature::LeadingColonsForCppType);
                 (
                     tcx.opt_parent(details.def_id),
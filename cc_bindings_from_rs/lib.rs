@@ -15,8 +15,10 @@ extern crate rustc_span;
 extern crate rustc_target;
 
 use rustc_hir::attrs::AttributeKind;
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::config::OptionsTargetModifiers;
+use rustc_span::FileName;
 
 use arc_anyhow::{bail, Context, Result};
 use std::collections::{BTreeSet, HashMap, HashSet};
@@ -29,6 +31,7 @@ use error_report::{ErrorReport, ErrorReporting, FatalErrors, ReportFatalError, S
 use generate_bindings::{BindingsGenerator, IncludeGuard};
 use itertools::Itertools;
 use kythe_metadata::cc_embed_provenance_map;
+use output_cache::{CacheKey, CacheKeyBuilder, OutputCache};
 use run_compiler::{run_compiler, run_compiler_with_input};
 use token_stream_printer::{
     cc_tokens_to_formatted_string, cc_tokens_to_formatted_string_with_provenance,
@@ -114,7 +117,30 @@ fn new_db<'tcx>(
     )
 }
 
-fn run_with_tcx(cmdline: &Cmdline, tcx: TyCtxt) -> Result<()> {
+/// Returns the files read to generate the bindings: the source files of the crate being compiled,
+/// and the `.rlib` and `.rmeta` files of the crates it depends on.
+fn input_files(tcx: TyCtxt) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = tcx
+        .sess
+        .source_map()
+        .files()
+        .iter()
+        .filter(|file| file.cnum == LOCAL_CRATE)
+        .filter_map(|file| match &file.name {
+            FileName::Real(name) => name.local_path().map(Path::to_path_buf),
+            _ => None,
+        })
+        .collect();
+    for &cnum in tcx.crates(()) {
+        files.extend(tcx.used_crate_source(cnum).paths().cloned());
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Generates the bindings, and returns the files that were read to generate them.
+fn run_with_tcx(cmdline: &Cmdline, tcx: TyCtxt) -> Result<Vec<PathBuf>> {
     use generate_bindings::{generate_bindings, BindingsTokens};
 
     let generate_error_report = cmdline.error_report_out.is_some();
@@ -184,13 +210,13 @@ fn run_with_tcx(cmdline: &Cmdline, tcx: TyCtxt) -> Result<()> {
         write_file(error_report_out, &error_report.unwrap().to_json_string())?;
     }
 
-    Ok(())
+    Ok(input_files(tcx))
 }
 
 /// Generates bindings using rmeta input files rather than source files and rustc args.
 /// This function exists so we can guard it behind a feature flag and should be cleaned up once
 /// we've migrated to rmetas entirely.
-fn run_with_rmetas(cmdline: &Cmdline) -> Result<()> {
+fn run_with_rmetas(cmdline: &Cmdline) -> Result<Vec<PathBuf>> {
     let mut at_args = vec!["rustc".to_string()];
 
     for (crate_name, metadata) in &cmdline.r#extern {
@@ -277,26 +303,60 @@ fn run_with_rmetas(cmdline: &Cmdline) -> Result<()> {
         input_str.push_str(&format!("\n\nextern crate r#{};\n", crate_name));
     }
 
-    if let Err(e) =
-        run_compiler_with_input(&compiler_args, input_str, |tcx| run_with_tcx(cmdline, tcx))
-    {
-        let bold = "\x1B[1m";
-        let italic = "\x1B[3m";
-        let reset = "\x1B[0m";
-        let red = "\x1B[31m";
-        eprintln!(
-            "{bold}Crubit {italic}{red}failed{reset} to generate C++ bindings for Rust crate \
-            {bold}`{crate_name}`{reset} due to the following errors:\n{e}"
-        );
-        std::process::exit(1);
+    match run_compiler_with_input(&compiler_args, input_str, |tcx| run_with_tcx(cmdline, tcx)) {
+        Ok(input_files) => Ok(input_files),
+        Err(e) => {
+            let bold = "\x1B[1m";
+            let italic = "\x1B[3m";
+            let reset = "\x1B[0m";
+            let red = "\x1B[31m";
+            eprintln!(
+                "{bold}Crubit {italic}{red}failed{reset} to generate C++ bindings for Rust crate \
+                {bold}`{crate_name}`{reset} due to the following errors:\n{e}"
+            );
+            std::process::exit(1);
+        }
     }
-    Ok(())
 }
 
 /// Main entrypoint that (unlike `main`) doesn't do any intitializations that
 /// should only happen once for the binary (e.g. it doesn't call
 /// `init_env_logger`) and therefore can be used from the tests module below.
 pub fn run_with_cmdline_args(cmdline: &Cmdline) -> Result<()> {
+    let Some(cache_dir) = &cmdline.cache_dir else {
+        generate(cmdline)?;
+        return Ok(());
+    };
+    let cache = OutputCache::new(cache_dir);
+    let key = cache_key(cmdline);
+    let outputs: Vec<&Path> = [
+        Some(&cmdline.h_out),
+        cmdline.cpp_out.as_ref(),
+        Some(&cmdline.rs_out),
+        cmdline.error_report_out.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(PathBuf::as_path)
+    .collect();
+    if !cache.restore(&key, &outputs) {
+        let input_files = generate(cmdline)?;
+        if let Err(e) = cache.store(&key, &input_files, &outputs) {
+            eprintln!("warning: failed to store the bindings in {}: {e}", cache_dir.display());
+        }
+    }
+    if cmdline.cache_stats {
+        match cache.stats() {
+            Ok(stats) => eprintln!("{stats}"),
+            Err(e) => {
+                eprintln!("warning: failed to read the statistics of {}: {e}", cache_dir.display())
+            }
+        }
+    }
+    Ok(())
+}
+
+fn generate(cmdline: &Cmdline) -> Result<Vec<PathBuf>> {
     if cmdline.enable_rmeta_interface {
         run_with_rmetas(cmdline)
     } else {
@@ -304,6 +364,24 @@ pub fn run_with_cmdline_args(cmdline: &Cmdline) -> Result<()> {
     }
 }
 
+/// Returns the key of the `--cache-dir` cache for `cmdline`. It covers all the flags, except for
+/// the cache flags themselves, and the files named by them which are read before rustc runs,
+/// including the formatters run on the generated bindings.
+fn cache_key(cmdline: &Cmdline) -> CacheKey {
+    let mut key = CacheKeyBuilder::new("cc_bindings_from_rs");
+    let flags = Cmdline { cache_dir: None, cache_stats: false, ..cmdline.clone() };
+    key.add_str(&format!("{flags:?}"));
+    for (_, path) in &cmdline.r#extern {
+        key.add_file(Path::new(path));
+    }
+    let files =
+        [&cmdline.rustfmt_exe_path, &cmdline.rustfmt_config_path, &cmdline.clang_format_exe_path];
+    for path in files.into_iter().flatten().chain(&cmdline.ignore_symbols_from_files) {
+        key.add_file(path);
+    }
+    key.build()
+}
+
 pub fn run_rustc(args: &[String]) {
     struct Callbacks;
     impl rustc_driver::Callbacks for Callbacks {}
@@ -810,4 +888,27 @@ unsafe extern "C" fn __crubit_thunk_ANY_IDENTIFIER_CHARACTERS()
         );
         Ok(())
     }
+
+    #[test]
+    fn test_cache_dir() -> Result<()> {
+        let cache_dir = tempdir()?;
+        let cache_dir_arg = format!("--cache-dir={}", cache_dir.path().display());
+        let test_args = TestArgs::default_args()?.with_extra_crubit_args(&[&cache_dir_arg]);
+        let test_result = test_args.run()?;
+        let cc_api = std::fs::read_to_string(&test_result.h_path)?;
+
+        // The second run copies the outputs of the first one from the cache.
+        std::fs::remove_file(&test_result.h_path)?;
+        test_args.run()?;
+        assert_eq!(cc_api, std::fs::read_to_string(&test_result.h_path)?);
+
+        // Changing the source file of the crate invalidates the cached outputs.
+        let test_args = test_args.with_rs_input("pub fn other_function() {}");
+        test_args.run()?;
+        assert!(std::fs::read_to_string(&test_result.h_path)?.contains("other_function"));
+
+        let stats = OutputCache::new(cache_dir.path()).stats()?;
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 2));
+        Ok(())
+    }
 }
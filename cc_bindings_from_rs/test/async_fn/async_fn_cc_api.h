@@ -132,8 +132,8 @@ extern "C" void convert_rust_to_cpp_async_fn(const void* _rs_in,
 // `async_fn_golden::return_box_dyn_future` defined at
 // cc_bindings_from_rs/test/async_fn/async_fn.rs;l=129:
 // Error formatting function return type `std::boxed::Box<(dyn
-// std::future::Future<Output = i32> + 'static)>`: Generic types are not
-// supported yet (b/259749095)
+// std::future::Future<Output = i32> + 'static)>`: Box of dynamically sized
+// types is not supported yet
 
 // Error generating bindings for function
 // `async_fn_golden::return_bridged_convertible` defined at
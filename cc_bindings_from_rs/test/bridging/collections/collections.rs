@@ -0,0 +1,33 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+pub fn word_lengths() -> HashMap<u32, u64> {
+    HashMap::from([(1, 10), (2, 20), (3, 30)])
+}
+
+pub fn sum_values(map: HashMap<u32, u64>) -> u64 {
+    map.values().sum()
+}
+
+pub fn invert(map: BTreeMap<i32, i64>) -> BTreeMap<i64, i32> {
+    map.into_iter().map(|(k, v)| (v, k)).collect()
+}
+
+pub fn maybe_map(present: bool) -> Option<BTreeMap<i32, bool>> {
+    present.then(|| BTreeMap::from([(0, false), (1, true)]))
+}
+
+pub fn odd_numbers(set: HashSet<i32>) -> HashSet<i32> {
+    set.into_iter().filter(|x| x % 2 != 0).collect()
+}
+
+pub fn sorted(set: HashSet<u8>) -> BTreeSet<u8> {
+    set.into_iter().collect()
+}
+
+pub fn nested() -> BTreeMap<u8, BTreeSet<u8>> {
+    BTreeMap::from([(1, BTreeSet::from([1])), (2, BTreeSet::from([1, 2])), (3, BTreeSet::new())])
+}
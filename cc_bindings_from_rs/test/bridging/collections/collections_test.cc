@@ -0,0 +1,62 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include <cstdint>
+#include <map>
+#include <optional>
+#include <set>
+#include <type_traits>
+#include <unordered_map>
+#include <unordered_set>
+#include <utility>
+
+#include "gtest/gtest.h"
+#include "absl/container/flat_hash_map.h"
+#include "absl/container/flat_hash_set.h"
+#include "cc_bindings_from_rs/test/bridging/collections/collections.h"
+#include "cc_bindings_from_rs/test/bridging/collections/std_hash_collections.h"
+
+namespace crubit {
+namespace {
+
+TEST(CollectionsBridging, HashMap) {
+  absl::flat_hash_map<uint32_t, uint64_t> map = collections::word_lengths();
+  EXPECT_EQ(map, (absl::flat_hash_map<uint32_t, uint64_t>{
+                     {1, 10}, {2, 20}, {3, 30}}));
+  EXPECT_EQ(collections::sum_values(std::move(map)), 60);
+  EXPECT_EQ(collections::sum_values({}), 0);
+}
+
+TEST(CollectionsBridging, BTreeMap) {
+  EXPECT_EQ(collections::invert({{1, -1}, {2, -2}}),
+            (std::map<int64_t, int32_t>{{-2, 2}, {-1, 1}}));
+  EXPECT_EQ(collections::maybe_map(false), std::nullopt);
+  EXPECT_EQ(collections::maybe_map(true),
+            (std::map<int32_t, bool>{{0, false}, {1, true}}));
+}
+
+TEST(CollectionsBridging, Sets) {
+  EXPECT_EQ(collections::odd_numbers({1, 2, 3, 4}),
+            (absl::flat_hash_set<int32_t>{1, 3}));
+  EXPECT_EQ(collections::sorted({3, 1, 2}), (std::set<uint8_t>{1, 2, 3}));
+}
+
+TEST(CollectionsBridging, Nested) {
+  EXPECT_EQ(collections::nested(), (std::map<uint8_t, std::set<uint8_t>>{
+                                       {1, {1}}, {2, {1, 2}}, {3, {}}}));
+}
+
+TEST(CollectionsBridging, StdHashCollections) {
+  std::unordered_map<uint32_t, uint64_t> squares =
+      std_hash_collections::squares(3);
+  EXPECT_EQ(squares,
+            (std::unordered_map<uint32_t, uint64_t>{{0, 0}, {1, 1}, {2, 4}}));
+  static_assert(
+      std::is_same_v<decltype(std_hash_collections::len),
+                     uintptr_t(std::unordered_set<int64_t>)>);
+  EXPECT_EQ(std_hash_collections::len({-1, 0, 1}), 3);
+}
+
+}  // namespace
+}  // namespace crubit
@@ -0,0 +1,13 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use std::collections::{HashMap, HashSet};
+
+pub fn squares(n: u32) -> HashMap<u32, u64> {
+    (0..n).map(|i| (i, u64::from(i) * u64::from(i))).collect()
+}
+
+pub fn len(set: HashSet<i64>) -> usize {
+    set.len()
+}
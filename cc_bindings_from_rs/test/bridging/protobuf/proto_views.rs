@@ -0,0 +1,30 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use foo_rust_proto::{FooRequest, FooRequestStats, FooRequestStatsView};
+use protobuf::{MapView, ProtoString, RepeatedView};
+
+pub fn input_lengths(stats: FooRequestStatsView<'_>) -> RepeatedView<'_, i64> {
+    stats.input_lengths()
+}
+
+pub fn sum_input_lengths(input_lengths: RepeatedView<'_, i64>) -> i64 {
+    input_lengths.iter().sum()
+}
+
+pub fn total_input_len(requests: RepeatedView<'_, FooRequest>) -> usize {
+    requests.iter().map(|req| req.input().len()).sum()
+}
+
+pub fn requests_for(requests_by_input: MapView<'_, ProtoString, i32>, input: &str) -> i32 {
+    requests_by_input.get(input).unwrap_or(0)
+}
+
+pub fn nonempty_stats(stats: FooRequestStats) -> Option<FooRequestStats> {
+    (stats.num_requests() > 0).then_some(stats)
+}
+
+pub fn num_requests_or_zero(stats: Option<FooRequestStats>) -> i32 {
+    stats.map_or(0, |stats| stats.num_requests())
+}
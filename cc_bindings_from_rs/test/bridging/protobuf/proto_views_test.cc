@@ -0,0 +1,61 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "cc_bindings_from_rs/test/bridging/protobuf/proto_views.h"
+
+#include <cstdint>
+#include <optional>
+#include <utility>
+
+#include "gtest/gtest.h"
+#include "google/protobuf/map.h"
+#include "google/protobuf/repeated_field.h"
+#include "cc_bindings_from_rs/test/bridging/protobuf/foo.pb.h"
+
+namespace crubit {
+namespace {
+
+TEST(ProtoViewBridging, RepeatedScalarView) {
+  foo_service::FooRequestStats stats;
+  stats.add_input_lengths(3);
+  stats.add_input_lengths(4);
+
+  const google::protobuf::RepeatedField<int64_t>* input_lengths =
+      proto_views::input_lengths(&stats);
+  EXPECT_EQ(input_lengths, &stats.input_lengths());
+  EXPECT_EQ(proto_views::sum_input_lengths(input_lengths), 7);
+}
+
+TEST(ProtoViewBridging, RepeatedMessageView) {
+  foo_service::FooRequestStats stats;
+  stats.add_recent_requests()->set_input("ab");
+  stats.add_recent_requests()->set_input("cde");
+
+  EXPECT_EQ(proto_views::total_input_len(&stats.recent_requests()), 5);
+}
+
+TEST(ProtoViewBridging, MapView) {
+  foo_service::FooRequestStats stats;
+  (*stats.mutable_requests_by_input())["hello"] = 2;
+
+  EXPECT_EQ(proto_views::requests_for(&stats.requests_by_input(), "hello"), 2);
+  EXPECT_EQ(proto_views::requests_for(&stats.requests_by_input(), "bye"), 0);
+}
+
+TEST(ProtoViewBridging, OptionalMessage) {
+  foo_service::FooRequestStats stats;
+  EXPECT_EQ(proto_views::nonempty_stats(stats), std::nullopt);
+
+  stats.set_num_requests(2);
+  std::optional<foo_service::FooRequestStats> nonempty =
+      proto_views::nonempty_stats(stats);
+  ASSERT_TRUE(nonempty.has_value());
+  EXPECT_EQ(nonempty->num_requests(), 2);
+
+  EXPECT_EQ(proto_views::num_requests_or_zero(std::nullopt), 0);
+  EXPECT_EQ(proto_views::num_requests_or_zero(std::move(nonempty)), 2);
+}
+
+}  // namespace
+}  // namespace crubit
@@ -0,0 +1,35 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! End-to-end tests of `cc_bindings_from_rs`, focusing on the opaque `rs_std::HashMap`.
+
+use std::collections::HashMap;
+
+pub fn word_counts(text: &str) -> HashMap<String, u64> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub fn count_of(counts: &HashMap<String, u64>, word: &str) -> u64 {
+    counts.get(word).copied().unwrap_or(0)
+}
+
+pub fn total(counts: HashMap<String, u64>) -> u64 {
+    counts.values().sum()
+}
+
+pub fn squares(n: u32) -> HashMap<u32, i32> {
+    (0..n).map(|i| (i, (i * i) as i32)).collect()
+}
+
+pub struct Registry {
+    pub ids: HashMap<u32, i32>,
+}
+
+pub fn new_registry() -> Registry {
+    Registry { ids: HashMap::from([(1, -1)]) }
+}
@@ -0,0 +1,95 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "cc_bindings_from_rs/test/hash_map/hash_map.h"
+
+#include <cstdint>
+#include <map>
+#include <string>
+#include <utility>
+
+#include "gtest/gtest.h"
+#include "support/rs_std/hash_map.h"
+#include "support/rs_std/rs_std.h"
+
+namespace crubit {
+namespace {
+
+using Counts = rs_std::HashMap<rs::std::string::String, std::uint64_t>;
+
+TEST(HashMapTest, StringKeys) {
+  Counts counts = hash_map::word_counts("the cat saw the dog");
+  EXPECT_EQ(counts.size(), 4);
+
+  const std::uint64_t* the = counts.find(rs::std::string::String("the"));
+  ASSERT_NE(the, nullptr);
+  EXPECT_EQ(*the, 2);
+  EXPECT_EQ(counts.find(rs::std::string::String("bird")), nullptr);
+  EXPECT_EQ(hash_map::count_of(counts, "cat"), 1);
+}
+
+TEST(HashMapTest, Insert) {
+  Counts counts = hash_map::word_counts("a b");
+  EXPECT_TRUE(counts.insert(rs::std::string::String("c"), 3));
+  EXPECT_FALSE(counts.insert(rs::std::string::String("a"), 10));
+  EXPECT_EQ(counts.size(), 3);
+  EXPECT_EQ(hash_map::count_of(counts, "a"), 10);
+  EXPECT_EQ(hash_map::count_of(counts, "c"), 3);
+  EXPECT_EQ(hash_map::total(std::move(counts)), 14);
+}
+
+TEST(HashMapTest, FindMutable) {
+  Counts counts = hash_map::word_counts("x");
+  std::uint64_t* x = counts.find(rs::std::string::String("x"));
+  ASSERT_NE(x, nullptr);
+  *x = 5;
+  EXPECT_EQ(hash_map::count_of(counts, "x"), 5);
+}
+
+TEST(HashMapTest, Iterate) {
+  Counts counts = hash_map::word_counts("one two two");
+  std::map<std::string, std::uint64_t> entries;
+  for (auto [key, value] : counts) {
+    entries.emplace(std::string(key->as_str()), *value);
+  }
+  EXPECT_EQ(entries,
+            (std::map<std::string, std::uint64_t>{{"one", 1}, {"two", 2}}));
+}
+
+TEST(HashMapTest, IntegerKeys) {
+  rs_std::HashMap<std::uint32_t, std::int32_t> squares = hash_map::squares(4);
+  EXPECT_EQ(squares.size(), 4);
+  ASSERT_NE(squares.find(3), nullptr);
+  EXPECT_EQ(*squares.find(3), 9);
+  EXPECT_EQ(squares.find(4), nullptr);
+
+  std::int32_t sum = 0;
+  for (auto [key, value] : squares) {
+    EXPECT_EQ(*value, static_cast<std::int32_t>(*key * *key));
+    sum += *value;
+  }
+  EXPECT_EQ(sum, 14);
+}
+
+TEST(HashMapTest, CopyAndMove) {
+  rs_std::HashMap<std::uint32_t, std::int32_t> squares = hash_map::squares(2);
+  rs_std::HashMap<std::uint32_t, std::int32_t> copy = squares;
+  copy.insert(5, 25);
+  EXPECT_EQ(squares.size(), 2);
+  EXPECT_EQ(copy.size(), 3);
+
+  rs_std::HashMap<std::uint32_t, std::int32_t> moved = std::move(copy);
+  EXPECT_EQ(moved.size(), 3);
+  EXPECT_EQ(rs_std::HashMap<std::uint32_t, std::int32_t>().size(), 0);
+}
+
+TEST(HashMapTest, Field) {
+  hash_map::Registry registry = hash_map::new_registry();
+  registry.ids.insert(2, -2);
+  EXPECT_EQ(registry.ids.size(), 2);
+  EXPECT_EQ(*registry.ids.find(1), -1);
+}
+
+}  // namespace
+}  // namespace crubit
@@ -0,0 +1,38 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub struct Counter {
+    pub value: i32,
+}
+
+pub fn new_arc_counter(value: i32) -> Arc<Counter> {
+    Arc::new(Counter { value })
+}
+
+pub fn arc_strong_count(arc: &Arc<Counter>) -> usize {
+    Arc::strong_count(arc)
+}
+
+pub fn take_arc(arc: Arc<Counter>) -> i32 {
+    arc.value
+}
+
+pub fn new_rc_i32(value: i32) -> Rc<i32> {
+    Rc::new(value)
+}
+
+pub fn rc_strong_count(rc: &Rc<i32>) -> usize {
+    Rc::strong_count(rc)
+}
+
+pub fn new_box_counter(value: i32) -> Box<Counter> {
+    Box::new(Counter { value })
+}
+
+pub fn take_box(boxed: Box<Counter>) -> i32 {
+    boxed.value
+}
@@ -0,0 +1,99 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "cc_bindings_from_rs/test/smart_pointers/smart_pointers.h"
+
+#include <cstdint>
+#include <memory>
+#include <utility>
+
+#include "gtest/gtest.h"
+#include "support/rs_std/arc.h"
+#include "support/rs_std/box.h"
+#include "support/rs_std/rc.h"
+
+namespace crubit {
+namespace {
+
+using ::smart_pointers::Counter;
+
+TEST(SmartPointersTest, ArcAccessors) {
+  rs_std::Arc<Counter> arc = smart_pointers::new_arc_counter(42);
+  ASSERT_NE(arc.get(), nullptr);
+  EXPECT_EQ(arc->value, 42);
+  EXPECT_EQ((*arc).value, 42);
+}
+
+TEST(SmartPointersTest, ArcCopyIncrementsRefcount) {
+  rs_std::Arc<Counter> arc = smart_pointers::new_arc_counter(1);
+  EXPECT_EQ(smart_pointers::arc_strong_count(arc), 1);
+  {
+    rs_std::Arc<Counter> copy = arc;
+    EXPECT_EQ(copy.get(), arc.get());
+    EXPECT_EQ(smart_pointers::arc_strong_count(arc), 2);
+  }
+  EXPECT_EQ(smart_pointers::arc_strong_count(arc), 1);
+}
+
+TEST(SmartPointersTest, PassArcByValue) {
+  rs_std::Arc<Counter> arc = smart_pointers::new_arc_counter(7);
+  EXPECT_EQ(smart_pointers::take_arc(arc), 7);
+  EXPECT_EQ(smart_pointers::arc_strong_count(arc), 1);
+}
+
+TEST(SmartPointersTest, ArcToSharedPtr) {
+  rs_std::Arc<Counter> arc = smart_pointers::new_arc_counter(3);
+  std::shared_ptr<const Counter> shared =
+      static_cast<std::shared_ptr<const Counter>>(arc);
+  EXPECT_EQ(shared.get(), arc.get());
+  EXPECT_EQ(smart_pointers::arc_strong_count(arc), 2);
+
+  std::shared_ptr<const Counter> copy = shared;
+  EXPECT_EQ(smart_pointers::arc_strong_count(arc), 2);
+
+  shared.reset();
+  copy.reset();
+  EXPECT_EQ(smart_pointers::arc_strong_count(arc), 1);
+}
+
+TEST(SmartPointersTest, ArcMovedIntoSharedPtr) {
+  std::shared_ptr<const Counter> shared =
+      static_cast<std::shared_ptr<const Counter>>(
+          smart_pointers::new_arc_counter(5));
+  EXPECT_EQ(shared->value, 5);
+}
+
+TEST(SmartPointersTest, Rc) {
+  rs_std::Rc<std::int32_t> rc = smart_pointers::new_rc_i32(10);
+  EXPECT_EQ(*rc, 10);
+  rs_std::Rc<std::int32_t> copy = rc;
+  EXPECT_EQ(copy.get(), rc.get());
+  EXPECT_EQ(smart_pointers::rc_strong_count(rc), 2);
+}
+
+TEST(SmartPointersTest, Box) {
+  rs_std::Box<Counter> boxed = smart_pointers::new_box_counter(11);
+  ASSERT_NE(boxed, nullptr);
+  EXPECT_EQ(boxed->value, 11);
+  boxed->value = 12;
+  EXPECT_EQ(smart_pointers::take_box(std::move(boxed)), 12);
+}
+
+TEST(SmartPointersTest, BoxDroppedByRust) {
+  rs_std::Box<Counter> boxed = smart_pointers::new_box_counter(1);
+  // Runs the deleter, which drops the `Box` in Rust.
+  boxed.reset();
+  EXPECT_EQ(boxed, nullptr);
+}
+
+TEST(SmartPointersTest, MovedFromBoxPassedToRustAborts) {
+  rs_std::Box<Counter> boxed = smart_pointers::new_box_counter(1);
+  rs_std::Box<Counter> other = std::move(boxed);
+  EXPECT_DEATH(smart_pointers::take_box(std::move(boxed)),
+               "Passing a null rs_std::Box to Rust");
+  EXPECT_EQ(smart_pointers::take_box(std::move(other)), 1);
+}
+
+}  // namespace
+}  // namespace crubit
@@ -52,8 +52,9 @@ struct CRUBIT_INTERNAL_RUST_TYPE(
   CloneNoDefault(::crubit::UnsafeRelocateTag, CloneNoDefault&& value);
 
  private:
-  // Field type has been replaced with a blob of bytes: Generic types are not
-  // supported yet (b/259749095)
+  // Field type has been replaced with a blob of bytes: Field is a bridged type
+  // and might not be layout-compatible
+  //                 with the C++ type (b/400633609)
   ::std::array<unsigned char, 8> value;
 
  private:
@@ -169,8 +170,9 @@ DontMoveMe final {
   DontMoveMe(::crubit::UnsafeRelocateTag, DontMoveMe&& value);
 
  private:
-  // Field type has been replaced with a blob of bytes: Generic types are not
-  // supported yet (b/259749095)
+  // Field type has been replaced with a blob of bytes: Field is a bridged type
+  // and might not be layout-compatible
+  //                 with the C++ type (b/400633609)
   ::std::array<unsigned char, 8> value;
 
  private:
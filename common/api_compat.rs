@@ -0,0 +1,1555 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Compares two versions of the API generated by Crubit.
+//!
+//! An [`ApiSnapshot`] lists the items of the generated bindings: whether each item could be bound,
+//! whether it is `unsafe`, its C++ signature, and its layout. Items are keyed by their C++
+//! qualified name (e.g. `ns::Point::Norm`), whichever generator produced them. Snapshots are built
+//! from:
+//!
+//! * the IR dumped by `rs_bindings_from_cc --ir_out`, which provides the signatures and layouts of
+//!   the C++ items of the target, as well as the items that could not be imported;
+//! * the C++ header generated by `cc_bindings_from_rs --h-out`, which provides the signatures and
+//!   layouts of the bound Rust items, as well as the items that could not be bound;
+//! * the JSON error report of `rs_bindings_from_cc --error_report_out`, which lists every item
+//!   that the generator saw, whether it could be bound, and whether it is `unsafe`.
+//!
+//! [`compare`] then reports how the API changed, along with a semver-style [`Verdict`].
+
+use anyhow::{bail, ensure, Context, Result};
+use error_report::{Category, ErrorReportEntry, SourceLanguage};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{self, Display, Formatter};
+
+/// The kind of an item of the generated API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Function,
+    Type,
+    Alias,
+    Constant,
+    Variable,
+    Namespace,
+    /// The kind is unknown, e.g. because the item comes from an error report without a category.
+    #[default]
+    Unknown,
+}
+
+/// An item of the generated API.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiItem {
+    pub kind: ItemKind,
+    /// The C++ signature of the item, without attributes, parameter names or bodies. The
+    /// signatures of overloads are sorted and separated by newlines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_unsafe: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub align: Option<u64>,
+    /// The reasons why the item could not be bound. Empty if the item is bound.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl ApiItem {
+    pub fn is_bound(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn merge(&mut self, other: ApiItem) {
+        if self.kind == ItemKind::Unknown {
+            self.kind = other.kind;
+        }
+        self.signature = match (self.signature.take(), other.signature) {
+            (Some(signature), Some(other_signature)) => {
+                // Overloads share a name, so their signatures are listed together.
+                let overloads: BTreeSet<&str> =
+                    signature.lines().chain(other_signature.lines()).collect();
+                Some(overloads.into_iter().collect::<Vec<_>>().join("\n"))
+            }
+            (signature, other_signature) => signature.or(other_signature),
+        };
+        self.is_unsafe |= other.is_unsafe;
+        self.size = self.size.or(other.size);
+        self.align = self.align.or(other.align);
+        self.errors.extend(other.errors);
+        // An item that has a signature is bound, even if some of its parts (e.g. some fields of a
+        // struct, or another overload) reported errors.
+        if self.signature.is_some() {
+            self.errors.clear();
+        }
+    }
+}
+
+/// The items of the generated API, keyed by their C++ qualified name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiSnapshot {
+    pub items: BTreeMap<String, ApiItem>,
+}
+
+impl ApiSnapshot {
+    /// Builds a snapshot from the text-format IR dumped by `rs_bindings_from_cc --ir_out`.
+    ///
+    /// Only the items of the current target are included. The IR does not know which functions
+    /// the generator will consider `unsafe`, unless they are annotated with `CRUBIT_UNSAFE`, so it
+    /// should be merged with the error report of the same run.
+    pub fn from_ir(textproto: &str) -> Result<Self> {
+        let ir = TextProto::parse(textproto).context("Failed to parse the IR")?;
+        let current_target = ir.scalar("current_target").unwrap_or_default();
+        let all_items = || {
+            ir.messages("top_level_items").flat_map(|target_items| {
+                let items = target_items.message("value").map(|value| value.messages("items"));
+                items.into_iter().flatten().map(move |item| (target_items.scalar("key"), item))
+            })
+        };
+        let mut decl_names = HashMap::new();
+        for (_, item) in all_items() {
+            collect_ir_decl_names(item, "", &mut decl_names);
+        }
+        let mut builder = IrSnapshotBuilder { decl_names, snapshot: ApiSnapshot::default() };
+        for (target, item) in all_items() {
+            if target == Some(current_target) {
+                builder.add_item(item, "", None);
+            }
+        }
+        Ok(builder.snapshot)
+    }
+
+    /// Builds a snapshot from the C++ header generated by `cc_bindings_from_rs`.
+    ///
+    /// The header lists the items that could not be bound by their Rust path, which is mapped to
+    /// the C++ namespace of the crate.
+    pub fn from_cc_header(source: &str) -> Result<Self> {
+        let mut parser = HeaderParser {
+            tokens: tokenize_cc(source),
+            pos: 0,
+            snapshot: ApiSnapshot::default(),
+            layouts: vec![],
+            rust_types: vec![],
+        };
+        parser.parse_scope("", None);
+        ensure!(parser.pos == parser.tokens.len(), "Unbalanced braces in the C++ header");
+        let HeaderParser { mut snapshot, layouts, rust_types, .. } = parser;
+        for (name, is_size, value) in layouts {
+            if let Some(item) = snapshot.items.get_mut(&name) {
+                if is_size {
+                    item.size = Some(value);
+                } else {
+                    item.align = Some(value);
+                }
+            }
+        }
+
+        let crate_regex =
+            Regex::new(r"bindings for the following Rust crate:\n// (\w+)\n").unwrap();
+        let Some(crate_name) = crate_regex.captures(source).map(|captures| captures[1].to_string())
+        else {
+            return Ok(snapshot);
+        };
+        // The crate's top-level namespace defaults to the crate name, but may be overridden with
+        // `--crate-namespace`. A type of the crate tells which one it is.
+        let crate_namespace = rust_types
+            .iter()
+            .find_map(|(rust_path, cc_name)| {
+                let rust_suffix = rust_path.strip_prefix(&format!("{crate_name}::"))?;
+                cc_name.strip_suffix(rust_suffix)?.strip_suffix("::").map(str::to_string)
+            })
+            .unwrap_or_else(|| crate_name.clone());
+        for (kind, rust_path, errors) in unsupported_item_comments(source) {
+            // Trait implementations, e.g. `<Foo as Trait>`, have no C++ name. Their bound
+            // counterparts are specializations of `rs_std::impl`, so losing bindings is still
+            // reported as a removal.
+            if rust_path.starts_with('<') {
+                continue;
+            }
+            let rust_path =
+                rust_path.strip_prefix(&format!("{crate_name}::")).unwrap_or(&rust_path);
+            snapshot.add(
+                format!("{crate_namespace}::{rust_path}"),
+                ApiItem { kind, errors: vec![errors], ..Default::default() },
+            );
+        }
+        Ok(snapshot)
+    }
+
+    /// Builds a snapshot from the JSON error report of `rs_bindings_from_cc`.
+    ///
+    /// Items with errors are considered unbound. The error reports of `cc_bindings_from_rs` are
+    /// not supported, because they name items by their Rust path rather than their C++ name: use
+    /// the generated header instead.
+    pub fn from_error_report(json: &str) -> Result<Self> {
+        let entries: Vec<ErrorReportEntry> =
+            serde_json::from_str(json).context("Failed to parse the error report")?;
+        let mut snapshot = ApiSnapshot::default();
+        for entry in entries {
+            if entry.source_language == Some(SourceLanguage::Rust) {
+                bail!(
+                    "Error reports of `cc_bindings_from_rs` are not supported, use the generated \
+                     C++ header instead"
+                );
+            }
+            // Errors that are not attributed to any item have an empty name.
+            if entry.name.is_empty() {
+                continue;
+            }
+            let has_category = |category: Category| entry.category & category as u32 != 0;
+            let kind = if has_category(Category::Function) {
+                ItemKind::Function
+            } else if has_category(Category::Type) {
+                ItemKind::Type
+            } else if has_category(Category::Alias) {
+                ItemKind::Alias
+            } else if has_category(Category::Constant) {
+                ItemKind::Constant
+            } else if has_category(Category::Variable) {
+                ItemKind::Variable
+            } else if has_category(Category::Namespace) {
+                ItemKind::Namespace
+            } else {
+                ItemKind::Unknown
+            };
+            snapshot.add(
+                entry.name.to_string(),
+                ApiItem {
+                    kind,
+                    is_unsafe: has_category(Category::Unsafe),
+                    errors: entry.errors.iter().map(ToString::to_string).collect(),
+                    ..Default::default()
+                },
+            );
+        }
+        Ok(snapshot)
+    }
+
+    /// Adds the items of `other` to this snapshot, combining the information about the items that
+    /// are in both.
+    pub fn merge(&mut self, other: ApiSnapshot) {
+        for (name, item) in other.items {
+            self.add(name, item);
+        }
+    }
+
+    fn add(&mut self, name: String, item: ApiItem) {
+        match self.items.get_mut(&name) {
+            Some(existing) => existing.merge(item),
+            None => {
+                self.items.insert(name, item);
+            }
+        }
+    }
+}
+
+/// A message of a protobuf in text format, e.g. the IR dumped by `rs_bindings_from_cc`.
+///
+/// Fields are kept in order, and repeated fields appear once per element.
+#[derive(Debug, Default)]
+struct TextProto {
+    fields: Vec<(String, TextProtoValue)>,
+}
+
+#[derive(Debug)]
+enum TextProtoValue {
+    /// A number, enumerator, boolean, or unescaped string.
+    Scalar(String),
+    Message(TextProto),
+}
+
+#[derive(Debug, PartialEq)]
+enum TextProtoToken {
+    Word(String),
+    String(Vec<u8>),
+    Punct(char),
+}
+
+impl TextProto {
+    fn parse(text: &str) -> Result<Self> {
+        let mut tokens = tokenize_text_proto(text)?.into_iter().peekable();
+        Self::parse_fields(&mut tokens, None)
+    }
+
+    fn parse_fields(
+        tokens: &mut std::iter::Peekable<std::vec::IntoIter<TextProtoToken>>,
+        close: Option<char>,
+    ) -> Result<Self> {
+        let mut message = TextProto::default();
+        loop {
+            let name = match tokens.next() {
+                None if close.is_none() => return Ok(message),
+                None => bail!("Unterminated message"),
+                Some(TextProtoToken::Punct(c)) if Some(c) == close => return Ok(message),
+                Some(TextProtoToken::Word(name)) => name,
+                Some(token) => bail!("Expected a field name, got {token:?}"),
+            };
+            tokens.next_if_eq(&TextProtoToken::Punct(':'));
+            if tokens.next_if_eq(&TextProtoToken::Punct('[')).is_some() {
+                while tokens.next_if_eq(&TextProtoToken::Punct(']')).is_none() {
+                    let value = Self::parse_value(tokens)?;
+                    message.fields.push((name.clone(), value));
+                    tokens.next_if_eq(&TextProtoToken::Punct(','));
+                }
+            } else {
+                let value = Self::parse_value(tokens)?;
+                message.fields.push((name, value));
+            }
+            tokens.next_if(|token| matches!(token, TextProtoToken::Punct(',' | ';')));
+        }
+    }
+
+    fn parse_value(
+        tokens: &mut std::iter::Peekable<std::vec::IntoIter<TextProtoToken>>,
+    ) -> Result<TextProtoValue> {
+        Ok(match tokens.next() {
+            Some(TextProtoToken::Punct('{')) => {
+                TextProtoValue::Message(Self::parse_fields(tokens, Some('}'))?)
+            }
+            Some(TextProtoToken::Punct('<')) => {
+                TextProtoValue::Message(Self::parse_fields(tokens, Some('>'))?)
+            }
+            Some(TextProtoToken::Word(word)) => TextProtoValue::Scalar(word),
+            Some(TextProtoToken::String(mut bytes)) => {
+                // Adjacent string literals are concatenated.
+                while let Some(TextProtoToken::String(more)) =
+                    tokens.next_if(|token| matches!(token, TextProtoToken::String(_)))
+                {
+                    bytes.extend(more);
+                }
+                TextProtoValue::Scalar(String::from_utf8_lossy(&bytes).into_owned())
+            }
+            token => bail!("Expected a value, got {token:?}"),
+        })
+    }
+
+    fn scalar(&self, name: &str) -> Option<&str> {
+        self.fields.iter().find_map(|(field_name, value)| match value {
+            TextProtoValue::Scalar(scalar) if field_name == name => Some(scalar.as_str()),
+            _ => None,
+        })
+    }
+
+    fn message(&self, name: &str) -> Option<&TextProto> {
+        self.fields.iter().find_map(|(field_name, value)| match value {
+            TextProtoValue::Message(message) if field_name == name => Some(message),
+            _ => None,
+        })
+    }
+
+    fn messages<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a TextProto> + 'a {
+        self.fields.iter().filter_map(move |(field_name, value)| match value {
+            TextProtoValue::Message(message) if field_name == name => Some(message),
+            _ => None,
+        })
+    }
+
+    /// Returns the set field of a message that has a single one, like a `oneof`.
+    fn variant(&self) -> Option<(&str, &TextProtoValue)> {
+        self.fields.first().map(|(name, value)| (name.as_str(), value))
+    }
+
+    fn is_true(&self, name: &str) -> bool {
+        self.scalar(name) == Some("true")
+    }
+
+    /// Returns `identifier` of the `Identifier` message `name`.
+    fn identifier(&self, name: &str) -> Option<&str> {
+        self.message(name)?.scalar("identifier")
+    }
+}
+
+fn tokenize_text_proto(text: &str) -> Result<Vec<TextProtoToken>> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '"' | '\'' => {
+                let mut bytes = vec![];
+                loop {
+                    let Some(next) = chars.next() else { bail!("Unterminated string") };
+                    if next == c {
+                        break;
+                    }
+                    if next != '\\' {
+                        bytes.extend(next.encode_utf8(&mut [0; 4]).as_bytes());
+                        continue;
+                    }
+                    let Some(escaped) = chars.next() else { bail!("Unterminated string") };
+                    let byte = match escaped {
+                        'n' => b'\n',
+                        'r' => b'\r',
+                        't' => b'\t',
+                        'a' => 0x07,
+                        'b' => 0x08,
+                        'f' => 0x0c,
+                        'v' => 0x0b,
+                        '0'..='7' => {
+                            let mut value = escaped.to_digit(8).unwrap();
+                            for _ in 0..2 {
+                                let Some(digit) = chars.peek().and_then(|c| c.to_digit(8)) else {
+                                    break;
+                                };
+                                value = value * 8 + digit;
+                                chars.next();
+                            }
+                            value as u8
+                        }
+                        'x' => {
+                            let mut value = 0;
+                            for _ in 0..2 {
+                                let Some(digit) = chars.peek().and_then(|c| c.to_digit(16)) else {
+                                    break;
+                                };
+                                value = value * 16 + digit;
+                                chars.next();
+                            }
+                            value as u8
+                        }
+                        other => {
+                            bytes.extend(other.encode_utf8(&mut [0; 4]).as_bytes());
+                            continue;
+                        }
+                    };
+                    bytes.push(byte);
+                }
+                tokens.push(TextProtoToken::String(bytes));
+            }
+            c if c.is_alphanumeric() || matches!(c, '_' | '-' | '+' | '.') => {
+                let mut word = c.to_string();
+                while let Some(c) = chars
+                    .next_if(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '+' | '.' | '/'))
+                {
+                    word.push(c);
+                }
+                tokens.push(TextProtoToken::Word(word));
+            }
+            c => tokens.push(TextProtoToken::Punct(c)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Records the C++ qualified names of the IR items that types can refer to, by item id.
+fn collect_ir_decl_names(item: &TextProto, qualifier: &str, names: &mut HashMap<String, String>) {
+    let Some((kind, TextProtoValue::Message(decl))) = item.variant() else {
+        return;
+    };
+    let name = match kind {
+        "existing_rust_type" => decl.scalar("cc_name").map(str::to_string),
+        _ => decl.identifier("cc_name").map(|cc_name| format!("{qualifier}{cc_name}")),
+    };
+    let (Some(name), Some(id)) = (name, decl.scalar("id")) else {
+        return;
+    };
+    if matches!(
+        kind,
+        "record" | "incomplete_record" | "enum_decl" | "type_alias" | "existing_rust_type"
+    ) {
+        names.insert(id.to_string(), name.clone());
+    }
+    if matches!(kind, "record" | "namespace_decl") {
+        for child in decl.messages("children") {
+            collect_ir_decl_names(child, &format!("{name}::"), names);
+        }
+    }
+}
+
+struct IrSnapshotBuilder {
+    /// The C++ qualified names of the IR items, by item id.
+    decl_names: HashMap<String, String>,
+    snapshot: ApiSnapshot,
+}
+
+impl IrSnapshotBuilder {
+    /// Adds an IR item, which is nested in the namespaces and records of `qualifier`, e.g.
+    /// `ns::Point::`. `record` is the name of the enclosing record, if any.
+    fn add_item(&mut self, item: &TextProto, qualifier: &str, record: Option<&str>) {
+        let Some((kind, TextProtoValue::Message(decl))) = item.variant() else {
+            return;
+        };
+        let cc_name = decl.identifier("cc_name").unwrap_or_default();
+        let name = format!("{qualifier}{cc_name}");
+        let (kind, signature) = match kind {
+            "func" => return self.add_func(decl, qualifier, record),
+            "record" => {
+                let fields = decl
+                    .messages("fields")
+                    .filter(|field| field.scalar("access") == Some("PUBLIC"))
+                    .filter_map(|field| {
+                        let field_name = field.identifier("cpp_identifier")?;
+                        let ty = self.format_type(field.message("type"));
+                        Some(if field.is_true("is_bitfield") {
+                            format!(" {ty} {field_name} : {};", field.scalar("size")?)
+                        } else {
+                            format!(" {ty} {field_name};")
+                        })
+                    })
+                    .collect::<String>();
+                let size_align = decl.message("size_align");
+                let layout = |name| size_align?.scalar(name)?.parse().ok();
+                self.snapshot.add(
+                    name.clone(),
+                    ApiItem {
+                        kind: ItemKind::Type,
+                        signature: Some(format!(
+                            "{} {cc_name} {{{fields} }}",
+                            record_keyword(decl.scalar("record_type"))
+                        )),
+                        size: layout("size"),
+                        align: layout("alignment"),
+                        ..Default::default()
+                    },
+                );
+                for child in decl.messages("children") {
+                    self.add_item(child, &format!("{name}::"), Some(cc_name));
+                }
+                return;
+            }
+            "incomplete_record" => (
+                ItemKind::Type,
+                format!("{} {cc_name};", record_keyword(decl.scalar("record_type"))),
+            ),
+            "enum_decl" => {
+                let underlying_type = self.format_type(decl.message("underlying_type"));
+                if decl.is_true("is_incomplete") {
+                    (ItemKind::Type, format!("enum {cc_name} : {underlying_type};"))
+                } else {
+                    let enumerators = decl
+                        .messages("enumerators")
+                        .map(|enumerator| {
+                            format!(
+                                "{} = {}",
+                                enumerator.identifier("identifier").unwrap_or_default(),
+                                format_integer_constant(enumerator.message("value"))
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    (
+                        ItemKind::Type,
+                        format!("enum {cc_name} : {underlying_type} {{ {enumerators} }}"),
+                    )
+                }
+            }
+            "constant" => (
+                ItemKind::Constant,
+                format!(
+                    "constexpr {} {cc_name} = {}",
+                    self.format_type(decl.message("type")),
+                    format_integer_constant(decl.message("value"))
+                ),
+            ),
+            "type_alias" => (
+                ItemKind::Alias,
+                format!("using {cc_name} = {}", self.format_type(decl.message("underlying_type"))),
+            ),
+            "global_var" => (
+                ItemKind::Variable,
+                format!("extern {} {cc_name}", self.format_type(decl.message("type"))),
+            ),
+            "existing_rust_type" => {
+                let cc_name = decl.scalar("cc_name").unwrap_or_default();
+                let size_align = decl.message("size_align");
+                let layout = |name| size_align?.scalar(name)?.parse().ok();
+                self.snapshot.add(
+                    cc_name.to_string(),
+                    ApiItem {
+                        kind: ItemKind::Type,
+                        signature: Some(format!(
+                            "{cc_name} = {}",
+                            decl.scalar("rs_name").unwrap_or_default()
+                        )),
+                        size: layout("size"),
+                        align: layout("alignment"),
+                        ..Default::default()
+                    },
+                );
+                return;
+            }
+            "namespace_decl" => {
+                self.snapshot.add(
+                    name.clone(),
+                    ApiItem {
+                        kind: ItemKind::Namespace,
+                        signature: Some(format!("namespace {cc_name}")),
+                        ..Default::default()
+                    },
+                );
+                for child in decl.messages("children") {
+                    self.add_item(child, &format!("{name}::"), None);
+                }
+                return;
+            }
+            "unsupported_item" => {
+                let kind = match decl.scalar("kind") {
+                    Some("FUNC" | "CONSTRUCTOR") => ItemKind::Function,
+                    Some("GLOBAL_VAR") => ItemKind::Variable,
+                    Some("STRUCT" | "UNION" | "CLASS" | "ENUM") => ItemKind::Type,
+                    Some("TYPE_ALIAS") => ItemKind::Alias,
+                    Some("NAMESPACE") => ItemKind::Namespace,
+                    _ => ItemKind::Unknown,
+                };
+                let mut errors: Vec<String> = decl
+                    .messages("errors")
+                    .filter_map(|error| error.scalar("message"))
+                    .map(str::to_string)
+                    .collect();
+                if errors.is_empty() {
+                    errors.push("The item could not be imported".to_string());
+                }
+                self.snapshot.add(
+                    decl.scalar("name").unwrap_or_default().to_string(),
+                    ApiItem { kind, errors, ..Default::default() },
+                );
+                return;
+            }
+            // Comments and `use` of extra Rust modules are not part of the API.
+            _ => return,
+        };
+        self.snapshot.add(name, ApiItem { kind, signature: Some(signature), ..Default::default() });
+    }
+
+    fn add_func(&mut self, func: &TextProto, qualifier: &str, record: Option<&str>) {
+        let return_type = self.format_type(func.message("return_type"));
+        let cc_name = func.message("cc_name").and_then(TextProto::variant);
+        let (name, return_type) = match cc_name {
+            Some(("ident", TextProtoValue::Message(ident))) => {
+                (ident.scalar("identifier").unwrap_or_default().to_string(), Some(return_type))
+            }
+            Some(("oper", TextProtoValue::Message(operator))) => {
+                let operator = operator.scalar("name").unwrap_or_default();
+                let separator = if operator.starts_with(char::is_alphabetic) { " " } else { "" };
+                (format!("operator{separator}{operator}"), Some(return_type))
+            }
+            Some(("special_name", TextProtoValue::Scalar(special_name))) => {
+                let record = record.unwrap_or_default();
+                match special_name.as_str() {
+                    "DESTRUCTOR" => (format!("~{record}"), None),
+                    _ => (record.to_string(), None),
+                }
+            }
+            Some(("conversion_operator", _)) => ("operator [conversion]".to_string(), None),
+            _ => return,
+        };
+        let mut params: Vec<String> = func
+            .messages("params")
+            .filter(|param| param.identifier("identifier") != Some("__this"))
+            .map(|param| self.format_type(param.message("type")))
+            .collect();
+        if func.is_true("is_variadic") {
+            params.push("...".to_string());
+        }
+        let mut signature = String::new();
+        let instance_method = func.message("instance_method_metadata");
+        if record.is_some() && instance_method.is_none() {
+            signature.push_str("static ");
+        }
+        if let Some(return_type) = return_type {
+            signature.push_str(&format!("{return_type} "));
+        }
+        signature.push_str(&format!("{name}({})", params.join(", ")));
+        if let Some(instance_method) = instance_method {
+            if instance_method.is_true("is_const") {
+                signature.push_str(" const");
+            }
+            match instance_method.scalar("reference") {
+                Some("L_VALUE") => signature.push_str(" &"),
+                Some("R_VALUE") => signature.push_str(" &&"),
+                _ => {}
+            }
+        }
+        self.snapshot.add(
+            format!("{qualifier}{name}"),
+            ApiItem {
+                kind: ItemKind::Function,
+                signature: Some(signature),
+                is_unsafe: func.scalar("safety_annotation") == Some("SAFETY_ANNOTATION_UNSAFE"),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Spells a `CcType`, e.g. `const ns::Point*`.
+    fn format_type(&self, ty: Option<&TextProto>) -> String {
+        let Some(ty) = ty else {
+            return "void".to_string();
+        };
+        let message = |name| ty.message(name);
+        let base = if let Some(primitive) = message("primitive") {
+            primitive.scalar("spelling").unwrap_or_default().to_string()
+        } else if let Some(pointer) = message("pointer") {
+            let sigil = match pointer.scalar("kind") {
+                Some("L_VALUE_REF") => "&",
+                Some("R_VALUE_REF") => "&&",
+                Some("NON_NULL") => "* _Nonnull",
+                _ => "*",
+            };
+            format!("{}{sigil}", self.format_type(pointer.message("pointee_type")))
+        } else if let Some(func_pointer) = message("func_pointer") {
+            let mut types: Vec<String> = func_pointer
+                .messages("param_and_return_types")
+                .map(|ty| self.format_type(Some(ty)))
+                .collect();
+            let return_type = types.pop().unwrap_or_default();
+            let sigil = if func_pointer.is_true("non_null") { "&" } else { "*" };
+            format!("{return_type} ({sigil})({})", types.join(", "))
+        } else if let Some(id) = ty.scalar("decl") {
+            self.decl_names.get(id).cloned().unwrap_or_else(|| "<unknown type>".to_string())
+        } else if let Some(error) = message("error") {
+            format!("<error: {}>", error.scalar("message").unwrap_or_default())
+        } else {
+            "void".to_string()
+        };
+        match (ty.is_true("is_const"), message("pointer").is_some()) {
+            (false, _) => base,
+            (true, true) => format!("{base} const"),
+            (true, false) => format!("const {base}"),
+        }
+    }
+}
+
+fn record_keyword(record_type: Option<&str>) -> &'static str {
+    match record_type {
+        Some("UNION") => "union",
+        Some("CLASS") => "class",
+        _ => "struct",
+    }
+}
+
+/// Formats an `IntegerConstant`, which stores negative values as `i64` and others as `u64`.
+fn format_integer_constant(constant: Option<&TextProto>) -> String {
+    let Some(constant) = constant else {
+        return "0".to_string();
+    };
+    let wrapped_value: i64 =
+        constant.scalar("wrapped_value").and_then(|value| value.parse().ok()).unwrap_or_default();
+    if constant.is_true("is_negative") {
+        wrapped_value.to_string()
+    } else {
+        (wrapped_value as u64).to_string()
+    }
+}
+
+/// Splits C++ source into tokens, skipping comments and preprocessor directives.
+fn tokenize_cc(source: &str) -> Vec<String> {
+    const PUNCTS: [&str; 9] = ["::", "->", "&&", "...", "==", "!=", "<=", ">=", "||"];
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = vec![];
+    let mut at_line_start = true;
+    let mut i = 0;
+    let rest = |i: usize| chars[i..].iter().take(3).collect::<String>();
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            at_line_start = true;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if (c == '#' && at_line_start) || rest(i).starts_with("//") {
+            while i < chars.len() && chars[i] != '\n' {
+                // Directives may continue on the next line.
+                if chars[i] == '\\' && c == '#' {
+                    i += 1;
+                }
+                i += 1;
+            }
+        } else if rest(i).starts_with("/*") {
+            i += 2;
+            while i < chars.len() && !rest(i).starts_with("*/") {
+                i += 1;
+            }
+            i += 2;
+        } else {
+            at_line_start = false;
+            let start = i;
+            if c == '"' || c == '\'' {
+                i += 1;
+                while i < chars.len() && chars[i] != c {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i += 1;
+            } else if c.is_alphanumeric() || matches!(c, '_' | '$') {
+                // `$` starts lifetime annotations, e.g. `const& $a`.
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '$'))
+                {
+                    // Exponents of floating-point literals, e.g. `1E+38f`.
+                    if c.is_ascii_digit()
+                        && matches!(chars[i], 'e' | 'E')
+                        && matches!(chars.get(i + 1), Some('+' | '-'))
+                    {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            } else {
+                i += PUNCTS.iter().find(|punct| rest(i).starts_with(*punct)).map_or(1, |p| p.len());
+            }
+            tokens.push(chars[start..i.min(chars.len())].iter().collect());
+        }
+    }
+    tokens
+}
+
+fn is_identifier(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_alphabetic() || matches!(c, '_' | '$'))
+}
+
+/// Returns whether `token` is a keyword that can't be the name of a declaration.
+fn is_keyword(token: &str) -> bool {
+    matches!(
+        token,
+        "auto"
+            | "bool"
+            | "char"
+            | "class"
+            | "const"
+            | "constexpr"
+            | "double"
+            | "enum"
+            | "explicit"
+            | "extern"
+            | "float"
+            | "inline"
+            | "int"
+            | "long"
+            | "short"
+            | "signed"
+            | "static"
+            | "struct"
+            | "typename"
+            | "union"
+            | "unsigned"
+            | "virtual"
+            | "void"
+            | "volatile"
+    )
+}
+
+/// Returns whether `token`, followed by parentheses, is not a function name, e.g. `decltype`, a
+/// macro like `CRUBIT_INTERNAL_RUST_TYPE`, or the `$` of an unnamed lifetime like `$(__anon1)`.
+fn is_call_like(token: &str) -> bool {
+    matches!(
+        token,
+        "$" | "alignas" | "alignof" | "decltype" | "noexcept" | "sizeof" | "__attribute__"
+    ) || (token.len() > 1 && token.chars().all(|c| c.is_ascii_uppercase() || c == '_'))
+}
+
+/// Joins C++ tokens, with spaces only where they help readability: `const ::ns::Foo& (int)`.
+fn join_cc_tokens(tokens: &[String]) -> String {
+    let mut result = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(prev) = i.checked_sub(1).map(|i| tokens[i].as_str()) {
+            let prev_is_name = (is_identifier(prev) && !is_keyword(prev)) || prev == ">";
+            let no_space = matches!(prev, "::" | "(" | "[" | "<" | "~")
+                || matches!(token.as_str(), "," | ")" | "]" | ">" | "*" | "&" | "&&")
+                || (matches!(token.as_str(), "::" | "(" | "<") && prev_is_name);
+            if !no_space {
+                result.push(' ');
+            }
+        }
+        result.push_str(token);
+    }
+    result
+}
+
+/// A record of the generated header, whose members are being parsed.
+struct RecordScope {
+    name: String,
+    is_public: bool,
+    fields: Vec<String>,
+}
+
+struct HeaderParser {
+    tokens: Vec<String>,
+    pos: usize,
+    snapshot: ApiSnapshot,
+    /// Layouts from `static_assert(sizeof(T) == N)`: the qualified name of `T`, whether it's the
+    /// size rather than the alignment, and `N`.
+    layouts: Vec<(String, bool, u64)>,
+    /// The Rust paths of the types of the header, from `CRUBIT_INTERNAL_RUST_TYPE`, and their C++
+    /// qualified names.
+    rust_types: Vec<(String, String)>,
+}
+
+impl HeaderParser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    /// Parses declarations until the end of the current scope, and consumes its closing brace.
+    /// `qualifier` is the qualified name of the scope followed by `::`, e.g. `ns::Foo::`.
+    fn parse_scope(&mut self, qualifier: &str, mut record: Option<&mut RecordScope>) {
+        while let Some(token) = self.peek() {
+            if token == "}" {
+                self.pos += 1;
+                return;
+            }
+            if matches!(token, "public" | "private" | "protected")
+                && self.tokens.get(self.pos + 1).map(String::as_str) == Some(":")
+            {
+                if let Some(record) = record.as_deref_mut() {
+                    record.is_public = token == "public";
+                }
+                self.pos += 2;
+                continue;
+            }
+            let start = self.pos;
+            let mut depth = 0;
+            while let Some(token) = self.peek() {
+                match token {
+                    "(" | "[" => depth += 1,
+                    ")" | "]" => depth -= 1,
+                    ";" | "{" | "}" if depth == 0 => break,
+                    _ => {}
+                }
+                self.pos += 1;
+            }
+            let decl = self.tokens[start..self.pos].to_vec();
+            let has_body = self.peek() == Some("{");
+            if has_body || self.peek() == Some(";") {
+                self.pos += 1;
+            }
+            self.parse_decl(&decl, has_body, qualifier, record.as_deref_mut());
+        }
+    }
+
+    /// Skips the rest of a block whose opening brace was consumed.
+    fn skip_block(&mut self) {
+        let mut depth = 1;
+        while let Some(token) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            match token.as_str() {
+                "{" => depth += 1,
+                "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Consumes the `;` after the body of a struct or enum, if any.
+    fn skip_semicolon(&mut self) {
+        if self.peek() == Some(";") {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_decl(
+        &mut self,
+        decl: &[String],
+        has_body: bool,
+        qualifier: &str,
+        record: Option<&mut RecordScope>,
+    ) {
+        let decl = strip_attributes(decl);
+        let first = decl.first().map(String::as_str);
+        match first {
+            Some("namespace") if has_body => {
+                let name = join_cc_tokens(&decl[1..]);
+                if name.is_empty() || name.starts_with("__") {
+                    self.skip_block();
+                } else {
+                    self.parse_scope(&format!("{qualifier}{name}::"), None);
+                }
+            }
+            Some("struct" | "class" | "union" | "enum") if has_body => {
+                self.parse_record(&decl, qualifier, record);
+            }
+            Some("static_assert") => {
+                // `static_assert(sizeof(T) == N, "...")`
+                let [_, _, layout, open, ..] = decl.as_slice() else {
+                    return;
+                };
+                if !matches!(layout.as_str(), "sizeof" | "alignof") || open != "(" {
+                    return;
+                }
+                let close = matching_paren(&decl, 3);
+                let (Some("=="), Some(Ok(value))) = (
+                    decl.get(close + 1).map(String::as_str),
+                    decl.get(close + 2).map(|value| value.parse()),
+                ) else {
+                    return;
+                };
+                let ty = join_cc_tokens(&decl[4..close]);
+                let name = match ty.strip_prefix("::") {
+                    Some(name) => name.to_string(),
+                    None => format!("{qualifier}{ty}"),
+                };
+                self.layouts.push((name, layout == "sizeof", value));
+            }
+            Some("using") => {
+                if let [_, name, eq, ty @ ..] = decl.as_slice()
+                    && eq == "="
+                    && record.as_ref().is_none_or(|record| record.is_public)
+                {
+                    self.snapshot.add(
+                        format!("{qualifier}{name}"),
+                        ApiItem {
+                            kind: ItemKind::Alias,
+                            signature: Some(format!("using {name} = {}", join_cc_tokens(ty))),
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+            // Traits are templates, and so are their implementations, e.g.
+            // `template <> struct rs_std::impl<Foo, Trait> { ... }`.
+            Some("template") => {
+                let mut depth = 0;
+                let params_end = decl
+                    .iter()
+                    .position(|token| {
+                        match token.as_str() {
+                            "<" => depth += 1,
+                            ">" => depth -= 1,
+                            _ => {}
+                        }
+                        token == ">" && depth == 0
+                    })
+                    .map_or(decl.len(), |end| end + 1);
+                let templated = &decl[params_end..];
+                let is_record = templated.first().is_some_and(|keyword| {
+                    matches!(keyword.as_str(), "struct" | "class" | "union")
+                });
+                if has_body && is_record {
+                    self.parse_record(templated, qualifier, None);
+                } else if has_body {
+                    self.skip_block();
+                }
+            }
+            // `extern "C"` blocks hold thunks.
+            Some("extern" | "friend" | "typedef") | None => {
+                if has_body {
+                    self.skip_block();
+                    self.skip_semicolon();
+                }
+            }
+            Some(_) => {
+                if has_body {
+                    self.skip_block();
+                }
+                if record.as_ref().is_none_or(|record| record.is_public) {
+                    self.parse_member_or_function(&decl, qualifier, record);
+                }
+            }
+        }
+    }
+
+    fn parse_record(&mut self, decl: &[String], qualifier: &str, record: Option<&mut RecordScope>) {
+        let keyword = decl[0].as_str();
+        // Drops macros like `CRUBIT_INTERNAL_RUST_TYPE(...)` and `alignas(...)`, which leaves the
+        // name (e.g. `Foo`, or `rs_std::impl<Foo, Trait>` for a specialization) and the base
+        // clause.
+        let mut name_and_base = vec![];
+        let mut rust_path = None;
+        let mut i = 1;
+        while i < decl.len() {
+            if decl[i] == "(" {
+                if name_and_base.pop().as_deref() == Some("CRUBIT_INTERNAL_RUST_TYPE") {
+                    rust_path = decl.get(i + 1).map(|path| path.trim_matches('"').replace(' ', ""));
+                }
+                i = matching_paren(decl, i);
+            } else if !matches!(decl[i].as_str(), "class" | "final") {
+                name_and_base.push(decl[i].clone());
+            }
+            i += 1;
+        }
+        let base_start = name_and_base.iter().position(|token| token == ":");
+        let (name, base) = name_and_base.split_at(base_start.unwrap_or(name_and_base.len()));
+        let name = join_cc_tokens(name);
+        let name = name.strip_prefix("::").unwrap_or(&name).to_string();
+        if name.is_empty() {
+            // An anonymous union holds fields of the enclosing record.
+            match record {
+                Some(record) => self.parse_scope(qualifier, Some(record)),
+                None => self.skip_block(),
+            }
+            self.skip_semicolon();
+            return;
+        }
+        let key = format!("{qualifier}{name}");
+        if keyword == "enum" {
+            let start = self.pos;
+            self.skip_block();
+            let body = join_cc_tokens(&self.tokens[start..self.pos - 1]);
+            self.skip_semicolon();
+            self.snapshot.add(
+                key,
+                ApiItem {
+                    kind: ItemKind::Type,
+                    signature: Some(format!(
+                        "{} {name}{} {{ {body} }}",
+                        if decl.get(1).is_some_and(|token| token == "class") {
+                            "enum class"
+                        } else {
+                            "enum"
+                        },
+                        join_cc_tokens(base)
+                            .strip_prefix(':')
+                            .map_or(String::new(), |base| { format!(" :{base}") }),
+                    )),
+                    ..Default::default()
+                },
+            );
+            return;
+        }
+        let mut scope = RecordScope { name, is_public: keyword != "class", fields: vec![] };
+        self.parse_scope(&format!("{key}::"), Some(&mut scope));
+        self.skip_semicolon();
+        if let Some(rust_path) = rust_path {
+            self.rust_types.push((rust_path.trim_start_matches("::").to_string(), key.clone()));
+        }
+        let fields: String = scope.fields.iter().map(|field| format!(" {field};")).collect();
+        self.snapshot.add(
+            key,
+            ApiItem {
+                kind: ItemKind::Type,
+                signature: Some(format!("{keyword} {} {{{fields} }}", scope.name)),
+                ..Default::default()
+            },
+        );
+    }
+
+    fn parse_member_or_function(
+        &mut self,
+        decl: &[String],
+        qualifier: &str,
+        record: Option<&mut RecordScope>,
+    ) {
+        // Finds the parameter list, if this is a function.
+        let mut params_start = None;
+        let mut initializer_start = None;
+        let mut i = 0;
+        while i < decl.len() {
+            match decl[i].as_str() {
+                "=" => {
+                    initializer_start = Some(i);
+                    break;
+                }
+                "operator" => {
+                    params_start = decl[i + 1..].iter().skip(1).position(|t| t == "(");
+                    params_start = params_start.map(|position| position + i + 2);
+                    break;
+                }
+                "(" if i > 0 && is_identifier(&decl[i - 1]) && !is_call_like(&decl[i - 1]) => {
+                    params_start = Some(i);
+                    break;
+                }
+                "(" => i = matching_paren(decl, i),
+                _ => {}
+            }
+            i += 1;
+        }
+        let is_static = decl.iter().any(|token| token == "static");
+        let specifiers = ["static", "inline", "explicit", "virtual", "constexpr", "extern"];
+        let Some(params_start) = params_start else {
+            // A field, variable or constant.
+            let (declarator, initializer) = decl.split_at(initializer_start.unwrap_or(decl.len()));
+            let Some((name, ty)) = declarator.split_last() else {
+                return;
+            };
+            if !is_identifier(name) || name.starts_with("__crubit") {
+                return;
+            }
+            let ty: Vec<String> =
+                ty.iter().filter(|token| !specifiers.contains(&token.as_str())).cloned().collect();
+            let ty = join_cc_tokens(&ty);
+            let (kind, signature) = match (record, is_static) {
+                (Some(record), false) => {
+                    record.fields.push(format!("{ty} {name}"));
+                    return;
+                }
+                (_, true) if decl.iter().any(|token| token == "constexpr") => (
+                    ItemKind::Constant,
+                    format!("constexpr {ty} {name} {}", join_cc_tokens(initializer)),
+                ),
+                _ => (ItemKind::Variable, format!("extern {ty} {name}")),
+            };
+            self.snapshot.add(
+                format!("{qualifier}{name}"),
+                ApiItem { kind, signature: Some(signature), ..Default::default() },
+            );
+            return;
+        };
+
+        // The name is either an identifier, a destructor, or an operator.
+        let mut name_start = params_start - 1;
+        if let Some(operator) = decl[..params_start].iter().rposition(|token| token == "operator") {
+            name_start = operator;
+        } else if name_start > 0 && decl[name_start - 1] == "~" {
+            name_start -= 1;
+        }
+        // Out-of-line definitions of members have qualified names.
+        if name_start > 0 && decl[name_start - 1] == "::" {
+            return;
+        }
+        let name = match &decl[name_start..params_start] {
+            // Conversion operators, e.g. `operator ::std::int32_t const&`.
+            [operator, ty @ ..]
+                if operator == "operator"
+                    && ty.first().is_some_and(|token| token == "::" || is_identifier(token)) =>
+            {
+                format!("operator {}", join_cc_tokens(ty))
+            }
+            name => name.concat(),
+        };
+        if name.starts_with("__crubit") {
+            return;
+        }
+        let params_end = matching_paren(decl, params_start);
+        let mut trailing = vec![];
+        for token in &decl[params_end + 1..] {
+            match token.as_str() {
+                "const" | "&" | "&&" | "noexcept" | "volatile" => trailing.push(token.clone()),
+                "=" | ":" | "->" => break,
+                _ => {}
+            }
+        }
+        let is_deleted = decl[params_end + 1..].windows(2).any(|tokens| tokens == ["=", "delete"]);
+        if is_deleted {
+            return;
+        }
+        let return_type: Vec<String> = decl[..name_start]
+            .iter()
+            .filter(|token| !specifiers.contains(&token.as_str()))
+            .cloned()
+            .collect();
+        let params = split_params(&decl[params_start + 1..params_end])
+            .iter()
+            .map(|param| join_cc_tokens(strip_param_name(param)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut signature = String::new();
+        if is_static {
+            signature.push_str("static ");
+        }
+        if !return_type.is_empty() {
+            signature.push_str(&format!("{} ", join_cc_tokens(&return_type)));
+        }
+        signature.push_str(&format!("{name}({params})"));
+        if !trailing.is_empty() {
+            signature.push_str(&format!(" {}", join_cc_tokens(&trailing)));
+        }
+        self.snapshot.add(
+            format!("{qualifier}{name}"),
+            ApiItem { kind: ItemKind::Function, signature: Some(signature), ..Default::default() },
+        );
+    }
+}
+
+/// Removes attributes like `[[deprecated]]`, which don't affect callers.
+fn strip_attributes(decl: &[String]) -> Vec<String> {
+    let mut result = vec![];
+    let mut depth = 0;
+    for (i, token) in decl.iter().enumerate() {
+        let next = decl.get(i + 1).map(String::as_str);
+        let prev = i.checked_sub(1).map(|i| decl[i].as_str());
+        if token == "[" && (next == Some("[") || prev == Some("[") && depth > 0) {
+            depth += 1;
+        } else if depth > 0 {
+            if token == "]" {
+                depth -= 1;
+            }
+        } else {
+            result.push(token.clone());
+        }
+    }
+    result
+}
+
+/// Returns the index of the parenthesis that closes the one at `open`.
+fn matching_paren(tokens: &[String], open: usize) -> usize {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.as_str() {
+            "(" => depth += 1,
+            ")" => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len() - 1
+}
+
+/// Splits a parameter list at the commas that are not nested in template arguments.
+fn split_params(tokens: &[String]) -> Vec<&[String]> {
+    let mut params = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match token.as_str() {
+            "(" | "<" | "[" => depth += 1,
+            ")" | ">" | "]" => depth -= 1,
+            "," if depth == 0 => {
+                params.push(&tokens[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if start < tokens.len() {
+        params.push(&tokens[start..]);
+    }
+    params
+}
+
+/// Removes the name and default value of a parameter, which don't affect callers.
+fn strip_param_name(param: &[String]) -> &[String] {
+    let param = match param.iter().position(|token| token == "=") {
+        Some(default) => &param[..default],
+        None => param,
+    };
+    match param {
+        [.., prev, name]
+            if is_identifier(name)
+                && !is_keyword(name)
+                && prev != "::"
+                && !matches!(prev.as_str(), "const" | "volatile" | "struct" | "class" | "enum") =>
+        {
+            &param[..param.len() - 1]
+        }
+        _ => param,
+    }
+}
+
+/// Returns the items that could not be bound, from the comments that `cc_bindings_from_rs`
+/// generates in their place, e.g.:
+///
+/// ```text
+/// // Error generating bindings for struct
+/// // `crate_name::SomeType` defined at
+/// // crate_name/lib.rs;l=26:
+/// // Zero-sized types (ZSTs) are not supported (b/258259459)
+/// ```
+///
+/// Returns the kind of each item, its Rust path, and the error.
+fn unsupported_item_comments(source: &str) -> Vec<(ItemKind, String, String)> {
+    let error_regex =
+        Regex::new(r"^Error generating bindings for (.+?) `([^`]+)` defined at \S+?: (.*)$")
+            .unwrap();
+    let mut comments = vec![];
+    let mut comment = String::new();
+    for line in source.lines().chain([""]) {
+        if let Some(text) = line.trim().strip_prefix("//") {
+            if !comment.is_empty() {
+                comment.push(' ');
+            }
+            comment.push_str(text.trim());
+            continue;
+        }
+        if let Some(captures) = error_regex.captures(&comment) {
+            let kind = match &captures[1] {
+                kind if kind.ends_with("function") => ItemKind::Function,
+                "struct" | "enum" | "union" | "trait" | "variant" => ItemKind::Type,
+                "associated type" => ItemKind::Alias,
+                "constant" | "associated constant" => ItemKind::Constant,
+                "type alias" => ItemKind::Alias,
+                "static" => ItemKind::Variable,
+                "module" => ItemKind::Namespace,
+                _ => ItemKind::Unknown,
+            };
+            comments.push((kind, captures[2].to_string(), captures[3].to_string()));
+        }
+        comment.clear();
+    }
+    comments
+}
+
+/// A semver-style verdict on a change of the API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// The API did not change.
+    #[default]
+    Patch,
+    /// The API changed in a backwards-compatible way, e.g. items were added.
+    Minor,
+    /// The API changed in a way that can break callers.
+    Major,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Verdict::Patch => write!(f, "patch"),
+            Verdict::Minor => write!(f, "minor"),
+            Verdict::Major => write!(f, "major"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    BecameBound,
+    BecameUnbound,
+    SignatureChanged,
+    BecameUnsafe,
+    BecameSafe,
+    LayoutChanged,
+}
+
+/// A change of a single item of the API.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub item: String,
+    pub kind: ChangeKind,
+    pub verdict: Verdict,
+    /// A description of the item before the change, e.g. its old signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old: Option<String>,
+    /// A description of the item after the change, e.g. its new signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new: Option<String>,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let item = &self.item;
+        write!(f, "{}: ", self.verdict)?;
+        match self.kind {
+            ChangeKind::Added => write!(f, "`{item}` was added")?,
+            ChangeKind::Removed => write!(f, "`{item}` was removed")?,
+            ChangeKind::BecameBound => write!(f, "`{item}` now has bindings")?,
+            ChangeKind::BecameUnbound => write!(f, "`{item}` no longer has bindings")?,
+            ChangeKind::SignatureChanged => write!(f, "the signature of `{item}` changed")?,
+            ChangeKind::BecameUnsafe => write!(f, "`{item}` became `unsafe`")?,
+            ChangeKind::BecameSafe => write!(f, "`{item}` is no longer `unsafe`")?,
+            ChangeKind::LayoutChanged => write!(f, "the layout of `{item}` changed")?,
+        }
+        if let Some(old) = &self.old {
+            write!(f, "\n  old: {old}")?;
+        }
+        if let Some(new) = &self.new {
+            write!(f, "\n  new: {new}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The changes between two versions of the API.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatReport {
+    /// The most severe verdict of all the changes.
+    pub verdict: Verdict,
+    pub changes: Vec<Change>,
+}
+
+impl Display for CompatReport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        write!(f, "verdict: {}", self.verdict)
+    }
+}
+
+/// Compares two versions of the API.
+pub fn compare(old: &ApiSnapshot, new: &ApiSnapshot) -> CompatReport {
+    let mut changes = vec![];
+    let mut change = |item: &str, kind, verdict, old, new| {
+        changes.push(Change { item: item.to_string(), kind, verdict, old, new })
+    };
+    for (name, old_item) in &old.items {
+        let Some(new_item) = new.items.get(name) else {
+            if old_item.is_bound() {
+                change(name, ChangeKind::Removed, Verdict::Major, old_item.signature.clone(), None);
+            }
+            continue;
+        };
+        match (old_item.is_bound(), new_item.is_bound()) {
+            (true, false) => change(
+                name,
+                ChangeKind::BecameUnbound,
+                Verdict::Major,
+                None,
+                Some(new_item.errors.join("; ")),
+            ),
+            (false, true) => change(
+                name,
+                ChangeKind::BecameBound,
+                Verdict::Minor,
+                None,
+                new_item.signature.clone(),
+            ),
+            (false, false) => {}
+            (true, true) => {
+                if let (Some(old_signature), Some(new_signature)) =
+                    (&old_item.signature, &new_item.signature)
+                    && old_signature != new_signature
+                {
+                    change(
+                        name,
+                        ChangeKind::SignatureChanged,
+                        Verdict::Major,
+                        Some(old_signature.clone()),
+                        Some(new_signature.clone()),
+                    );
+                }
+                match (old_item.is_unsafe, new_item.is_unsafe) {
+                    (false, true) => {
+                        change(name, ChangeKind::BecameUnsafe, Verdict::Major, None, None)
+                    }
+                    (true, false) => {
+                        change(name, ChangeKind::BecameSafe, Verdict::Minor, None, None)
+                    }
+                    _ => {}
+                }
+                let old_layout = (old_item.size, old_item.align);
+                let new_layout = (new_item.size, new_item.align);
+                if old_layout != (None, None)
+                    && new_layout != (None, None)
+                    && old_layout != new_layout
+                {
+                    change(
+                        name,
+                        ChangeKind::LayoutChanged,
+                        Verdict::Major,
+                        Some(format_layout(old_layout)),
+                        Some(format_layout(new_layout)),
+                    );
+                }
+            }
+        }
+    }
+    for (name, new_item) in &new.items {
+        if !old.items.contains_key(name) && new_item.is_bound() {
+            change(name, ChangeKind::Added, Verdict::Minor, None, new_item.signature.clone());
+        }
+    }
+    let verdict = changes.iter().map(|change| change.verdict).max().unwrap_or_default();
+    CompatReport { verdict, changes }
+}
+
+fn format_layout((size, align): (Option<u64>, Option<u64>)) -> String {
+    let unknown = || "?".to_string();
+    format!(
+        "size {}, align {}",
+        size.map_or_else(unknown, |size| size.to_string()),
+        align.map_or_else(unknown, |align| align.to_string())
+    )
+}
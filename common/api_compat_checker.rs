@@ -0,0 +1,79 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Reports whether a new version of Crubit-generated bindings is compatible with an old one.
+//!
+//! ```shell
+//! $ api_compat_checker \
+//!     --old old/foo_ir.textproto --old old/foo_error_report.json \
+//!     --new new/foo_ir.textproto --new new/foo_error_report.json \
+//!     --format=json --fail-on-breaking
+//! $ api_compat_checker --old old/bar_cc_api.h --new new/bar_cc_api.h
+//! ```
+
+use anyhow::{bail, Context, Result};
+use api_compat::{compare, ApiSnapshot, Verdict};
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Parser)]
+#[clap(name = "api_compat_checker")]
+#[clap(about = "Compares two versions of Crubit-generated bindings", long_about = None)]
+struct Cmdline {
+    /// IR dumps (`.textproto`), generated C++ headers (`.h`) or JSON error reports (`.json`) of
+    /// the old version.
+    #[clap(long, value_parser, value_name = "FILE", required = true)]
+    old: Vec<PathBuf>,
+
+    /// IR dumps (`.textproto`), generated C++ headers (`.h`) or JSON error reports (`.json`) of
+    /// the new version.
+    #[clap(long, value_parser, value_name = "FILE", required = true)]
+    new: Vec<PathBuf>,
+
+    /// Output format of the report.
+    #[clap(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Exit with status 1 if the new version can break callers of the old one.
+    #[clap(long)]
+    fail_on_breaking: bool,
+}
+
+fn load_snapshot(paths: &[PathBuf]) -> Result<ApiSnapshot> {
+    let mut snapshot = ApiSnapshot::default();
+    for path in paths {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file_snapshot = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("textproto" | "textpb" | "txtpb" | "pbtxt") => ApiSnapshot::from_ir(&contents),
+            Some("h") => ApiSnapshot::from_cc_header(&contents),
+            Some("json") => ApiSnapshot::from_error_report(&contents),
+            _ => bail!("Expected a `.textproto`, `.h` or `.json` file, got {}", path.display()),
+        }
+        .with_context(|| format!("Failed to load {}", path.display()))?;
+        snapshot.merge(file_snapshot);
+    }
+    Ok(snapshot)
+}
+
+fn main() -> Result<()> {
+    let cmdline = Cmdline::parse();
+    let old = load_snapshot(&cmdline.old)?;
+    let new = load_snapshot(&cmdline.new)?;
+    let report = compare(&old, &new);
+    match cmdline.format {
+        Format::Text => println!("{report}"),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+    if cmdline.fail_on_breaking && report.verdict == Verdict::Major {
+        std::process::exit(1);
+    }
+    Ok(())
+}
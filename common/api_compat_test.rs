@@ -0,0 +1,613 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use api_compat::{compare, ApiItem, ApiSnapshot, ChangeKind, ItemKind, Verdict};
+use googletest::prelude::*;
+
+/// The IR of `rs_bindings_from_cc` for:
+///
+/// ```c++
+/// struct Point {
+///   int x;
+///   int y;
+///   int Norm() const;
+/// };
+/// CRUBIT_UNSAFE int Deref(int* p);
+/// namespace ns {
+/// int Add(int a, int b);
+/// }
+/// volatile int Volatile();
+/// enum Color : int { kRed, kBlue };
+/// void Take(const Other& other);  // `Other` is defined by `//bar`.
+/// ```
+const IR: &str = r#"
+public_headers { name: "foo/foo.h" }
+current_target: "//foo"
+top_level_items {
+  key: "//bar"
+  value {
+    items {
+      record {
+        rs_name { identifier: "Other" }
+        cc_name { identifier: "Other" }
+        id: 4
+        size_align { size: 1 alignment: 1 }
+        record_type: STRUCT
+      }
+    }
+  }
+}
+top_level_items {
+  key: "//foo"
+  value {
+    items {
+      record {
+        rs_name { identifier: "Point" }
+        cc_name { identifier: "Point" }
+        id: 1
+        fields {
+          rust_identifier { identifier: "x" }
+          cpp_identifier { identifier: "x" }
+          doc_comment: "Generated from: foo/foo.h;l=2"
+          type { primitive { spelling: "int" } }
+          access: PUBLIC
+          size: 32
+        }
+        fields {
+          rust_identifier { identifier: "y" }
+          cpp_identifier { identifier: "y" }
+          type { primitive { spelling: "int" } }
+          access: PUBLIC
+          offset: 32
+          size: 32
+        }
+        size_align { size: 8 alignment: 4 }
+        record_type: STRUCT
+        children {
+          func {
+            cc_name { ident { identifier: "Norm" } }
+            rs_name { ident { identifier: "Norm" } }
+            return_type { primitive { spelling: "int" } }
+            params {
+              type {
+                pointer {
+                  kind: NON_NULL
+                  pointee_type { decl: 1 is_const: true }
+                }
+              }
+              identifier { identifier: "__this" }
+            }
+            instance_method_metadata { reference: UNQUALIFIED is_const: true }
+            source_loc: "foo/foo.h;l=4"
+            id: 5
+            enclosing_item_id: 1
+          }
+        }
+      }
+    }
+    items {
+      func {
+        cc_name { ident { identifier: "Deref" } }
+        return_type { primitive { spelling: "int" } }
+        params {
+          type { pointer { kind: NULLABLE pointee_type { primitive { spelling: "int" } } } }
+          identifier { identifier: "p" }
+        }
+        safety_annotation: SAFETY_ANNOTATION_UNSAFE
+        id: 6
+      }
+    }
+    items {
+      namespace_decl {
+        cc_name { identifier: "ns" }
+        id: 2
+        children {
+          func {
+            cc_name { ident { identifier: "Add" } }
+            return_type { primitive { spelling: "int" } }
+            params { type { primitive { spelling: "int" } } identifier { identifier: "a" } }
+            params { type { primitive { spelling: "int" } } identifier { identifier: "b" } }
+            id: 7
+            enclosing_item_id: 2
+          }
+        }
+      }
+    }
+    items {
+      unsupported_item {
+        name: "Volatile"
+        kind: FUNC
+        errors {
+          fmt: "Return type is not supported: {}"
+          message: "Return type is not supported: Unsupported `volatile` qualifier: volatile int"
+        }
+        id: 8
+      }
+    }
+    items {
+      enum_decl {
+        cc_name { identifier: "Color" }
+        id: 3
+        underlying_type { primitive { spelling: "int" } }
+        enumerators { identifier { identifier: "kRed" } value { } }
+        enumerators { identifier { identifier: "kBlue" } value { wrapped_value: 1 } }
+      }
+    }
+    items {
+      func {
+        cc_name { ident { identifier: "Take" } }
+        return_type { primitive { spelling: "void" } }
+        params {
+          type { pointer { kind: L_VALUE_REF pointee_type { decl: 4 is_const: true } } }
+          identifier { identifier: "other" }
+        }
+        id: 9
+      }
+    }
+    items { comment { text: "Not part of the API." } }
+  }
+}
+"#;
+
+/// The C++ header that `cc_bindings_from_rs` generates for:
+///
+/// ```rust
+/// #[derive(Clone, Copy, Default)]
+/// pub struct Point { pub x: i32, pub y: i32 }
+/// impl Point {
+///     pub fn norm(&self) -> i32 { ... }
+/// }
+/// pub fn add(a: i32, b: i32) -> i32 { ... }
+/// pub struct Zst;
+/// ```
+const HEADER: &str = r#"// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// Automatically @generated C++ bindings for the following Rust crate:
+// foo_golden
+
+// clang-format off
+#ifndef THIRD_PARTY_CRUBIT_FOO_GOLDEN
+#define THIRD_PARTY_CRUBIT_FOO_GOLDEN
+
+#include "support/annotations_internal.h"
+
+#include <cstdint>
+
+namespace foo {
+
+// Generated from: foo/foo.rs;l=2
+struct CRUBIT_INTERNAL_RUST_TYPE(":: foo_golden :: Point") alignas(4)
+    [[clang::trivial_abi]] Point final {
+ public:
+  // Default::default
+  Point();
+
+  // No custom `Drop` impl and no custom "drop glue" required
+  ~Point() = default;
+  Point(Point&&) = default;
+  Point& operator=(Point&&) = default;
+
+  // Generated from: foo/foo.rs;l=4
+  std::int32_t norm() const;
+
+  union {
+    // Generated from: foo/foo.rs;l=2
+    std::int32_t x;
+  };
+  union {
+    std::int32_t y;
+  };
+
+ private:
+  static void __crubit_field_offset_assertions();
+};
+
+// Generated from: foo/foo.rs;l=7
+std::int32_t add(std::int32_t a, std::int32_t b);
+
+// Error generating bindings for struct
+// `foo_golden::Zst` defined at
+// foo/foo.rs;l=8:
+// Zero-sized types (ZSTs) are not supported (b/258259459)
+
+}  // namespace foo
+
+template <>
+struct rs_std::impl<::foo::Point, ::rs_std::Default> {
+  static constexpr bool kIsImplemented = true;
+};
+
+namespace foo {
+
+static_assert(
+    sizeof(Point) == 8,
+    "Verify that ADT layout didn't change since this header got generated");
+static_assert(
+    alignof(Point) == 4,
+    "Verify that ADT layout didn't change since this header got generated");
+namespace __crubit_internal {
+extern "C" std::int32_t __crubit_thunk_norm(::foo::Point const&);
+}
+inline std::int32_t Point::norm() const {
+  auto&& self = *this;
+  return __crubit_internal::__crubit_thunk_norm(self);
+}
+
+namespace __crubit_internal {
+extern "C" std::int32_t __crubit_thunk_add(std::int32_t, std::int32_t);
+}
+inline std::int32_t add(std::int32_t a, std::int32_t b) {
+  return __crubit_internal::__crubit_thunk_add(a, b);
+}
+
+}  // namespace foo
+
+#endif  // THIRD_PARTY_CRUBIT_FOO_GOLDEN
+"#;
+
+fn snapshot(ir: &str) -> ApiSnapshot {
+    ApiSnapshot::from_ir(ir).unwrap()
+}
+
+fn header_snapshot(header: &str) -> ApiSnapshot {
+    ApiSnapshot::from_cc_header(header).unwrap()
+}
+
+#[gtest]
+fn test_snapshot_from_ir() {
+    let snapshot = snapshot(IR);
+    expect_eq!(
+        snapshot.items.keys().collect::<Vec<_>>(),
+        ["Color", "Deref", "Point", "Point::Norm", "Take", "Volatile", "ns", "ns::Add"]
+    );
+    expect_eq!(
+        snapshot.items["Point"],
+        ApiItem {
+            kind: ItemKind::Type,
+            signature: Some("struct Point { int x; int y; }".to_string()),
+            size: Some(8),
+            align: Some(4),
+            ..Default::default()
+        }
+    );
+    expect_eq!(
+        snapshot.items["Deref"],
+        ApiItem {
+            kind: ItemKind::Function,
+            signature: Some("int Deref(int*)".to_string()),
+            is_unsafe: true,
+            ..Default::default()
+        }
+    );
+    expect_eq!(snapshot.items["Point::Norm"].signature.as_deref(), Some("int Norm() const"));
+    expect_eq!(snapshot.items["ns::Add"].signature.as_deref(), Some("int Add(int, int)"));
+    expect_eq!(
+        snapshot.items["Color"].signature.as_deref(),
+        Some("enum Color : int { kRed = 0, kBlue = 1 }")
+    );
+    // Types of other targets are named, but their items are not part of the snapshot.
+    expect_eq!(snapshot.items["Take"].signature.as_deref(), Some("void Take(const Other&)"));
+    expect_that!(snapshot.items["Point::Norm"].is_bound(), eq(true));
+    expect_that!(snapshot.items["Volatile"].is_bound(), eq(false));
+    expect_eq!(
+        snapshot.items["Volatile"],
+        ApiItem {
+            kind: ItemKind::Function,
+            errors: vec![
+                "Return type is not supported: Unsupported `volatile` qualifier: volatile int"
+                    .to_string()
+            ],
+            ..Default::default()
+        }
+    );
+}
+
+#[gtest]
+fn test_snapshot_from_cc_header() {
+    let snapshot = header_snapshot(HEADER);
+    expect_eq!(
+        snapshot.items.keys().collect::<Vec<_>>(),
+        [
+            "foo::Point",
+            "foo::Point::Point",
+            "foo::Point::norm",
+            "foo::Point::operator=",
+            "foo::Point::~Point",
+            "foo::Zst",
+            "foo::add",
+            "rs_std::impl<::foo::Point, ::rs_std::Default>",
+            "rs_std::impl<::foo::Point, ::rs_std::Default>::kIsImplemented",
+        ]
+    );
+    expect_eq!(
+        snapshot.items["foo::Point"],
+        ApiItem {
+            kind: ItemKind::Type,
+            signature: Some("struct Point { std::int32_t x; std::int32_t y; }".to_string()),
+            size: Some(8),
+            align: Some(4),
+            ..Default::default()
+        }
+    );
+    expect_eq!(
+        snapshot.items["foo::Point::Point"].signature.as_deref(),
+        Some("Point()\nPoint(Point&&)")
+    );
+    expect_eq!(
+        snapshot.items["foo::Point::norm"].signature.as_deref(),
+        Some("std::int32_t norm() const")
+    );
+    expect_eq!(
+        snapshot.items["foo::add"],
+        ApiItem {
+            kind: ItemKind::Function,
+            signature: Some("std::int32_t add(std::int32_t, std::int32_t)".to_string()),
+            ..Default::default()
+        }
+    );
+    expect_eq!(
+        snapshot.items["foo::Zst"],
+        ApiItem {
+            kind: ItemKind::Type,
+            errors: vec!["Zero-sized types (ZSTs) are not supported (b/258259459)".to_string()],
+            ..Default::default()
+        }
+    );
+}
+
+#[gtest]
+fn test_snapshot_from_cc_header_with_crate_namespace() {
+    let header = HEADER.replace("namespace foo {", "namespace bar {").replace("::foo::", "::bar::");
+    let snapshot = header_snapshot(&header);
+    expect_that!(snapshot.items.get("bar::Zst"), some(anything()));
+    expect_that!(snapshot.items.get("foo::Zst"), none());
+}
+
+#[gtest]
+fn test_snapshot_from_error_report() {
+    let snapshot = ApiSnapshot::from_error_report(
+        r#"[
+            {"source_language": "Cpp", "name": "Point", "category": 4},
+            {"source_language": "Cpp", "name": "Deref", "category": 513},
+            {
+                "source_language": "Cpp",
+                "name": "Volatile",
+                "errors": [{"fmt": "Unsupported `volatile` qualifier: {}"}],
+                "category": 1
+            }
+        ]"#,
+    )
+    .unwrap();
+    expect_eq!(snapshot.items["Point"], ApiItem { kind: ItemKind::Type, ..Default::default() });
+    expect_eq!(
+        snapshot.items["Deref"],
+        ApiItem { kind: ItemKind::Function, is_unsafe: true, ..Default::default() }
+    );
+    expect_eq!(
+        snapshot.items["Volatile"],
+        ApiItem {
+            kind: ItemKind::Function,
+            errors: vec!["Unsupported `volatile` qualifier: {}".to_string()],
+            ..Default::default()
+        }
+    );
+}
+
+#[gtest]
+fn test_snapshot_from_error_report_of_cc_bindings_from_rs() {
+    let result = ApiSnapshot::from_error_report(
+        r#"[{"source_language": "Rust", "name": "foo_golden::Zst", "category": 4}]"#,
+    );
+    expect_that!(result, err(displays_as(contains_substring("use the generated C++ header"))));
+}
+
+#[gtest]
+fn test_merge_ir_and_error_report() {
+    let mut merged = snapshot(IR);
+    merged.merge(
+        ApiSnapshot::from_error_report(
+            r#"[{"source_language": "Cpp", "name": "ns::Add", "category": 513}]"#,
+        )
+        .unwrap(),
+    );
+    // Both inputs name items by their C++ qualified name, so they describe the same item.
+    expect_eq!(merged.items.len(), snapshot(IR).items.len());
+    expect_eq!(
+        merged.items["ns::Add"],
+        ApiItem {
+            kind: ItemKind::Function,
+            signature: Some("int Add(int, int)".to_string()),
+            is_unsafe: true,
+            ..Default::default()
+        }
+    );
+}
+
+#[gtest]
+fn test_merge_overloads() {
+    let ir = IR.replace(
+        r#"items { comment { text: "Not part of the API." } }"#,
+        r#"items {
+          func {
+            cc_name { ident { identifier: "Deref" } }
+            return_type { primitive { spelling: "long" } }
+            params {
+              type { pointer { kind: NULLABLE pointee_type { primitive { spelling: "long" } } } }
+              identifier { identifier: "p" }
+            }
+            id: 10
+          }
+        }"#,
+    );
+    expect_eq!(
+        snapshot(&ir).items["Deref"].signature.as_deref(),
+        Some("int Deref(int*)\nlong Deref(long*)")
+    );
+}
+
+#[gtest]
+fn test_compare_identical() {
+    let report = compare(&snapshot(IR), &snapshot(IR));
+    expect_eq!(report.verdict, Verdict::Patch);
+    expect_that!(report.changes, is_empty());
+}
+
+#[gtest]
+fn test_compare_ignores_docs_and_parameter_names() {
+    let new = HEADER.replace("// Generated from: foo/foo.rs;l=7\n", "// Adds.\n");
+    let new =
+        new.replace("add(std::int32_t a, std::int32_t b);", "add(std::int32_t x, std::int32_t y);");
+    let report = compare(&header_snapshot(HEADER), &header_snapshot(&new));
+    expect_eq!(report.verdict, Verdict::Patch);
+}
+
+#[gtest]
+fn test_compare_added() {
+    let new = HEADER.replace(
+        "}  // namespace foo\n\ntemplate",
+        "void new_function();\n\n}  // namespace foo\n\ntemplate",
+    );
+    let report = compare(&header_snapshot(HEADER), &header_snapshot(&new));
+    expect_eq!(report.verdict, Verdict::Minor);
+    expect_that!(
+        report.changes,
+        elements_are![all![
+            field!(api_compat::Change.item, eq("foo::new_function")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::Added)),
+        ]]
+    );
+}
+
+#[gtest]
+fn test_compare_removed() {
+    let new =
+        HEADER.replace("struct rs_std::impl<::foo::Point, ::rs_std::Default>", "struct Removed");
+    let report = compare(&header_snapshot(HEADER), &header_snapshot(&new));
+    expect_eq!(report.verdict, Verdict::Major);
+    expect_that!(
+        report.changes,
+        contains(all![
+            field!(api_compat::Change.item, eq("rs_std::impl<::foo::Point, ::rs_std::Default>")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::Removed)),
+        ])
+    );
+}
+
+#[gtest]
+fn test_compare_became_unbound() {
+    let new = HEADER.replace(
+        "// Generated from: foo/foo.rs;l=7\nstd::int32_t add(std::int32_t a, std::int32_t b);",
+        "// Error generating bindings for function\n\
+         // `foo_golden::add` defined at\n\
+         // foo/foo.rs;l=7:\n\
+         // Reasons.",
+    );
+    let new = new.replace("inline std::int32_t add", "inline std::int32_t other_add");
+    let report = compare(&header_snapshot(HEADER), &header_snapshot(&new));
+    expect_eq!(report.verdict, Verdict::Major);
+    expect_that!(
+        report.changes,
+        contains(all![
+            field!(api_compat::Change.item, eq("foo::add")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::BecameUnbound)),
+            field!(api_compat::Change.new, some(eq("Reasons."))),
+        ])
+    );
+
+    let report = compare(&header_snapshot(&new), &header_snapshot(HEADER));
+    expect_that!(
+        report.changes,
+        contains(all![
+            field!(api_compat::Change.item, eq("foo::add")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::BecameBound)),
+            field!(api_compat::Change.verdict, eq(&Verdict::Minor)),
+        ])
+    );
+}
+
+#[gtest]
+fn test_compare_signature_changed() {
+    let new = IR.replace(
+        r#"params { type { primitive { spelling: "int" } } identifier { identifier: "a" } }"#,
+        r#"params { type { primitive { spelling: "long" } } identifier { identifier: "a" } }"#,
+    );
+    let report = compare(&snapshot(IR), &snapshot(&new));
+    expect_eq!(report.verdict, Verdict::Major);
+    expect_that!(
+        report.changes,
+        elements_are![all![
+            field!(api_compat::Change.item, eq("ns::Add")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::SignatureChanged)),
+            field!(api_compat::Change.old, some(eq("int Add(int, int)"))),
+            field!(api_compat::Change.new, some(eq("int Add(long, int)"))),
+        ]]
+    );
+}
+
+#[gtest]
+fn test_compare_safety_changed() {
+    let new =
+        IR.replace("source_loc: \"foo/foo.h;l=4\"", "safety_annotation: SAFETY_ANNOTATION_UNSAFE");
+    let report = compare(&snapshot(IR), &snapshot(&new));
+    expect_eq!(report.verdict, Verdict::Major);
+    expect_that!(
+        report.changes,
+        elements_are![all![
+            field!(api_compat::Change.item, eq("Point::Norm")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::BecameUnsafe)),
+        ]]
+    );
+
+    let report = compare(&snapshot(&new), &snapshot(IR));
+    expect_eq!(report.verdict, Verdict::Minor);
+    expect_that!(
+        report.changes,
+        elements_are![field!(api_compat::Change.kind, eq(&ChangeKind::BecameSafe))]
+    );
+}
+
+#[gtest]
+fn test_compare_layout_changed() {
+    let new =
+        IR.replace("size_align { size: 8 alignment: 4 }", "size_align { size: 12 alignment: 4 }");
+    let report = compare(&snapshot(IR), &snapshot(&new));
+    expect_eq!(report.verdict, Verdict::Major);
+    expect_that!(
+        report.changes,
+        elements_are![all![
+            field!(api_compat::Change.item, eq("Point")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::LayoutChanged)),
+            field!(api_compat::Change.old, some(eq("size 8, align 4"))),
+            field!(api_compat::Change.new, some(eq("size 12, align 4"))),
+        ]]
+    );
+
+    let new = HEADER.replace("sizeof(Point) == 8", "sizeof(Point) == 12");
+    let report = compare(&header_snapshot(HEADER), &header_snapshot(&new));
+    expect_that!(
+        report.changes,
+        elements_are![all![
+            field!(api_compat::Change.item, eq("foo::Point")),
+            field!(api_compat::Change.kind, eq(&ChangeKind::LayoutChanged)),
+        ]]
+    );
+}
+
+#[gtest]
+fn test_report_json() {
+    let new =
+        IR.replace("source_loc: \"foo/foo.h;l=4\"", "safety_annotation: SAFETY_ANNOTATION_UNSAFE");
+    let report = compare(&snapshot(IR), &snapshot(&new));
+    expect_eq!(
+        serde_json::to_value(&report).unwrap(),
+        serde_json::json!({
+            "verdict": "major",
+            "changes": [
+                {"item": "Point::Norm", "kind": "became_unsafe", "verdict": "major"},
+            ],
+        })
+    );
+}
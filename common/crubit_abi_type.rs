@@ -65,6 +65,26 @@ pub enum CrubitAbiType {
         rust_type: TokenStream,
         cpp_type: TokenStream,
     },
+    /// An owned Rust `Box<T>`, bridged to `rs_std::Box<T>` by passing ownership of the pointer.
+    RustBox {
+        rust_type: TokenStream,
+        cpp_type: TokenStream,
+    },
+    /// `absl::Duration` or a `std::chrono::duration`, bridged to `bridge_rust::SignedDuration`.
+    Duration {
+        cpp_type: TokenStream,
+    },
+    /// `absl::Time` or a `std::chrono::time_point`, bridged to `std::time::SystemTime`.
+    Time {
+        cpp_type: TokenStream,
+    },
+    /// `std::filesystem::path`, bridged to `std::path::PathBuf`, or to `&std::path::Path` for a
+    /// `const path&` parameter.
+    StdPath {
+        /// If true, use `crate::...` instead of `::cc_std::...` in Rust tokens.
+        in_cc_std: bool,
+        is_const_ref: bool,
+    },
     /// A proto message type. This is a special case of CrubitAbiType::Type, where the Rust type is
     /// a ::foo_proto::ProtoMessageRustBridge<M>, and the C++ type is a
     /// ::crubit::BoxedAbi<::foo_proto::Message>.
@@ -179,6 +199,27 @@ impl ToTokens for CrubitAbiTypeToRustTokens<'_> {
             CrubitAbiType::Transmute { rust_type, .. } => {
                 quote! { ::bridge_rust::TransmuteAbi<#rust_type> }.to_tokens(tokens);
             }
+            CrubitAbiType::RustBox { rust_type, .. } => {
+                quote! { ::bridge_rust::BoxAbi<#rust_type> }.to_tokens(tokens);
+            }
+            CrubitAbiType::Duration { .. } => {
+                quote! { ::bridge_rust::DurationAbi }.to_tokens(tokens);
+            }
+            CrubitAbiType::Time { .. } => {
+                quote! { ::bridge_rust::SystemTimeAbi }.to_tokens(tokens);
+            }
+            CrubitAbiType::StdPath { in_cc_std, is_const_ref } => {
+                let root = if *in_cc_std {
+                    quote! { crate }
+                } else {
+                    quote! { ::cc_std }
+                };
+                if *is_const_ref {
+                    quote! { #root::std::PathRefAbi<'_> }.to_tokens(tokens)
+                } else {
+                    quote! { #root::std::PathBufAbi }.to_tokens(tokens)
+                }
+            }
             CrubitAbiType::ProtoMessage { proto_message_rust_bridge, rust_proto_path, .. } => {
                 quote! { #proto_message_rust_bridge<#rust_proto_path> }.to_tokens(tokens);
             }
@@ -232,6 +273,28 @@ impl ToTokens for CrubitAbiTypeToRustExprTokens<'_> {
             CrubitAbiType::Transmute { rust_type, .. } => {
                 quote! { ::bridge_rust::transmute_abi::<#rust_type>() }.to_tokens(tokens);
             }
+            CrubitAbiType::RustBox { rust_type, .. } => {
+                quote! { ::bridge_rust::BoxAbi::<#rust_type>(::core::marker::PhantomData) }
+                    .to_tokens(tokens);
+            }
+            CrubitAbiType::Duration { .. } => {
+                quote! { ::bridge_rust::DurationAbi }.to_tokens(tokens);
+            }
+            CrubitAbiType::Time { .. } => {
+                quote! { ::bridge_rust::SystemTimeAbi }.to_tokens(tokens);
+            }
+            CrubitAbiType::StdPath { in_cc_std, is_const_ref } => {
+                let root = if *in_cc_std {
+                    quote! { crate }
+                } else {
+                    quote! { ::cc_std }
+                };
+                if *is_const_ref {
+                    quote! { #root::std::PathRefAbi(::core::marker::PhantomData) }.to_tokens(tokens)
+                } else {
+                    quote! { #root::std::PathBufAbi }.to_tokens(tokens)
+                }
+            }
             CrubitAbiType::ProtoMessage { proto_message_rust_bridge, .. } => {
                 quote! { #proto_message_rust_bridge(::core::marker::PhantomData) }
                     .to_tokens(tokens);
@@ -285,6 +348,16 @@ impl ToTokens for CrubitAbiTypeToCppTokens<'_> {
             CrubitAbiType::Transmute { cpp_type, .. } => {
                 quote! { ::crubit::TransmuteAbi<#cpp_type> }.to_tokens(tokens);
             }
+            CrubitAbiType::RustBox { cpp_type, .. } => {
+                quote! { ::crubit::BoxAbi<#cpp_type> }.to_tokens(tokens);
+            }
+            CrubitAbiType::Duration { cpp_type } => {
+                quote! { ::crubit::DurationAbi<#cpp_type> }.to_tokens(tokens);
+            }
+            CrubitAbiType::Time { cpp_type } => {
+                quote! { ::crubit::TimeAbi<#cpp_type> }.to_tokens(tokens);
+            }
+            CrubitAbiType::StdPath { .. } => quote! { ::crubit::PathAbi }.to_tokens(tokens),
             CrubitAbiType::ProtoMessage { cpp_proto_path, .. } => {
                 quote! { ::crubit::BoxedAbi<#cpp_proto_path> }.to_tokens(tokens);
             }
@@ -339,6 +412,16 @@ impl ToTokens for CrubitAbiTypeToCppExprTokens<'_> {
             CrubitAbiType::Transmute { cpp_type, .. } => {
                 quote! { ::crubit::TransmuteAbi<#cpp_type>() }.to_tokens(tokens);
             }
+            CrubitAbiType::RustBox { cpp_type, .. } => {
+                quote! { ::crubit::BoxAbi<#cpp_type>() }.to_tokens(tokens);
+            }
+            CrubitAbiType::Duration { cpp_type } => {
+                quote! { ::crubit::DurationAbi<#cpp_type>() }.to_tokens(tokens);
+            }
+            CrubitAbiType::Time { cpp_type } => {
+                quote! { ::crubit::TimeAbi<#cpp_type>() }.to_tokens(tokens);
+            }
+            CrubitAbiType::StdPath { .. } => quote! { ::crubit::PathAbi() }.to_tokens(tokens),
             CrubitAbiType::ProtoMessage { cpp_proto_path, .. } => {
                 quote! { ::crubit::BoxedAbi<#cpp_proto_path>() }.to_tokens(tokens);
             }
@@ -423,4 +506,75 @@ mod tests {
             .to_string()
         );
     }
+    #[gtest]
+    fn duration_and_time_test() {
+        let duration = CrubitAbiType::Duration { cpp_type: quote! { absl::Duration } };
+        expect_eq!(
+            CrubitAbiTypeToRustExprTokens(&duration).to_token_stream().to_string(),
+            quote! { ::bridge_rust::DurationAbi }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToCppExprTokens(&duration).to_token_stream().to_string(),
+            quote! { ::crubit::DurationAbi<absl::Duration>() }.to_string()
+        );
+
+        let time = CrubitAbiType::Time { cpp_type: quote! { absl::Time } };
+        expect_eq!(
+            CrubitAbiTypeToRustTokens(&time).to_token_stream().to_string(),
+            quote! { ::bridge_rust::SystemTimeAbi }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToCppTokens(&time).to_token_stream().to_string(),
+            quote! { ::crubit::TimeAbi<absl::Time> }.to_string()
+        );
+    }
+
+    #[gtest]
+    fn std_path_test() {
+        let path_buf = CrubitAbiType::StdPath { in_cc_std: false, is_const_ref: false };
+        expect_eq!(
+            CrubitAbiTypeToRustTokens(&path_buf).to_token_stream().to_string(),
+            quote! { ::cc_std::std::PathBufAbi }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToCppExprTokens(&path_buf).to_token_stream().to_string(),
+            quote! { ::crubit::PathAbi() }.to_string()
+        );
+
+        let path_ref = CrubitAbiType::StdPath { in_cc_std: true, is_const_ref: true };
+        expect_eq!(
+            CrubitAbiTypeToRustTokens(&path_ref).to_token_stream().to_string(),
+            quote! { crate::std::PathRefAbi<'_> }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToRustExprTokens(&path_ref).to_token_stream().to_string(),
+            quote! { crate::std::PathRefAbi(::core::marker::PhantomData) }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToCppTokens(&path_ref).to_token_stream().to_string(),
+            quote! { ::crubit::PathAbi }.to_string()
+        );
+    }
+
+    #[gtest]
+    fn rust_box_test() {
+        let rust_box =
+            CrubitAbiType::RustBox { rust_type: quote! { crate::Foo }, cpp_type: quote! { Foo } };
+        expect_eq!(
+            CrubitAbiTypeToRustTokens(&rust_box).to_token_stream().to_string(),
+            quote! { ::bridge_rust::BoxAbi<crate::Foo> }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToRustExprTokens(&rust_box).to_token_stream().to_string(),
+            quote! { ::bridge_rust::BoxAbi::<crate::Foo>(::core::marker::PhantomData) }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToCppTokens(&rust_box).to_token_stream().to_string(),
+            quote! { ::crubit::BoxAbi<Foo> }.to_string()
+        );
+        expect_eq!(
+            CrubitAbiTypeToCppExprTokens(&rust_box).to_token_stream().to_string(),
+            quote! { ::crubit::BoxAbi<Foo>() }.to_string()
+        );
+    }
 }
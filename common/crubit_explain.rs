@@ -0,0 +1,62 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Explains why Crubit did not generate bindings for an item.
+//!
+//! ```shell
+//! $ crubit_explain \
+//!     --error-report bazel-bin/foo/foo_rust_api_error_report.json \
+//!     ns::MyFunction
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use explain_bindings::ErrorReports;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Parser)]
+#[clap(name = "crubit_explain")]
+#[clap(about = "Explains why Crubit did not generate bindings for an item", long_about = None)]
+struct Cmdline {
+    /// The C++ qualified name (`ns::Foo`) or Rust path (`my_crate::Foo`) of the item.
+    symbol: String,
+
+    /// The JSON error report of the target that defines the item. Error reports of its
+    /// dependencies can be added to explain failures caused by them.
+    #[clap(long, value_parser, value_name = "FILE", required = true)]
+    error_report: Vec<PathBuf>,
+
+    /// Output format of the explanation.
+    #[clap(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+fn main() -> Result<()> {
+    let cmdline = Cmdline::parse();
+    let mut reports = ErrorReports::default();
+    for path in &cmdline.error_report {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        reports.merge(
+            ErrorReports::from_json(&json)
+                .with_context(|| format!("Failed to load {}", path.display()))?,
+        );
+    }
+    let explanations = reports.explain(&cmdline.symbol)?;
+    match cmdline.format {
+        Format::Text => {
+            for explanation in &explanations {
+                print!("{explanation}");
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&explanations)?),
+    }
+    Ok(())
+}
@@ -74,6 +74,15 @@ flagset::flags! {
 
         /// Check if async fn return type implements Send modulo regions.
         AsyncFnSendModuloRegions,
+
+        /// Bridge `HashMap`, `HashSet`, `BTreeMap` and `BTreeSet` to C++ containers by value in
+        /// cpp_api_from_rust.
+        BridgeCollections,
+
+        /// Bridge `HashMap` and `HashSet` to `std::unordered_map` and `std::unordered_set` instead
+        /// of `absl::flat_hash_map` and `absl::flat_hash_set`. Only has an effect together with
+        /// `BridgeCollections`.
+        StdHashCollections,
     }
 }
 
@@ -106,6 +115,8 @@ impl CrubitFeature {
             Self::ThunklessAccessors => "thunkless_accessors",
             Self::OoCasting => "oo_casting",
             Self::AsyncFnSendModuloRegions => "async_fn_send_modulo_regions",
+            Self::BridgeCollections => "bridge_collections",
+            Self::StdHashCollections => "std_hash_collections",
         }
     }
 
@@ -141,6 +152,8 @@ impl CrubitFeature {
             Self::AsyncFnSendModuloRegions => {
                 "//features:async_fn_send_modulo_regions"
             }
+            Self::BridgeCollections => "//features:bridge_collections",
+            Self::StdHashCollections => "//features:std_hash_collections",
         }
     }
 }
@@ -155,6 +168,8 @@ pub fn named_features(name: &[u8]) -> Option<flagset::FlagSet<CrubitFeature>> {
                 - CrubitFeature::LayoutCompatTuple
                 - CrubitFeature::AlwaysSpecializeGenericsInCppApiFromRust
                 - CrubitFeature::OoCasting
+                - CrubitFeature::BridgeCollections
+                - CrubitFeature::StdHashCollections
         }
         // `supported` automatically implies `types`.
         b"supported" => CrubitFeature::Supported | CrubitFeature::Types,
@@ -178,6 +193,12 @@ pub fn named_features(name: &[u8]) -> Option<flagset::FlagSet<CrubitFeature>> {
         b"thunkless_accessors" => CrubitFeature::ThunklessAccessors.into(),
         b"oo_casting" => CrubitFeature::OoCasting.into(),
         b"async_fn_send_modulo_regions" => CrubitFeature::AsyncFnSendModuloRegions.into(),
+        b"bridge_collections" => CrubitFeature::BridgeCollections.into(),
+        // `std_hash_collections` only selects the C++ containers, so it implies
+        // `bridge_collections`.
+        b"std_hash_collections" => {
+            CrubitFeature::BridgeCollections | CrubitFeature::StdHashCollections
+        }
         _ => return None,
         // importer.cc: make sure the logic for the "all" feature still makes sense: b/530193579
         // LINT.ThenChange(//depot/rs_bindings_from_cc/importer.cc, //depot/features/BUILD)
@@ -309,6 +330,13 @@ mod tests {
         );
     }
 
+    #[gtest]
+    fn test_serialized_crubit_feature_std_hash_collections() {
+        let SerializedCrubitFeature(features) =
+            serde_json::from_str("\"std_hash_collections\"").unwrap();
+        assert_eq!(features, CrubitFeature::BridgeCollections | CrubitFeature::StdHashCollections);
+    }
+
     #[gtest]
     fn test_serialized_crubit_features_empty() {
         let SerializedCrubitFeatures(features) = serde_json::from_str("[]").unwrap();
@@ -0,0 +1,61 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Exports the fixes suggested by Crubit error reports, for bulk application.
+//!
+//! ```shell
+//! $ crubit_fix_its --format=clang-apply-replacements \
+//!     --error-report bazel-bin/foo/foo_rust_api_error_report.json \
+//!     > fixes/foo.yaml
+//! $ clang-apply-replacements fixes/
+//! ```
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use error_report::ErrorReportEntry;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    /// Source edits, as a YAML file for `clang-apply-replacements`.
+    ClangApplyReplacements,
+    /// Source edits, as rustc JSON diagnostics for `rustfix`.
+    Rustfix,
+    /// BUILD file edits, as a command file for `buildozer -f`.
+    Buildozer,
+}
+
+#[derive(Debug, Parser)]
+#[clap(name = "crubit_fix_its")]
+#[clap(about = "Exports the fixes suggested by Crubit error reports", long_about = None)]
+struct Cmdline {
+    /// A JSON error report produced by Crubit.
+    #[clap(long, value_parser, value_name = "FILE", required = true)]
+    error_report: Vec<PathBuf>,
+
+    /// Which fixes to export, and in which format.
+    #[clap(long, value_enum)]
+    format: Format,
+}
+
+fn main() -> Result<()> {
+    let cmdline = Cmdline::parse();
+    let mut entries = Vec::<ErrorReportEntry>::new();
+    for path in &cmdline.error_report {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        entries.extend(
+            serde_json::from_str::<Vec<ErrorReportEntry>>(&json)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+        );
+    }
+    let fix_its = fix_its::collect(&entries);
+    let output = match cmdline.format {
+        Format::ClangApplyReplacements => fix_its::to_clang_apply_replacements(&fix_its),
+        Format::Rustfix => fix_its::to_rustfix_diagnostics(&fix_its),
+        Format::Buildozer => fix_its::to_buildozer_commands(&fix_its),
+    };
+    print!("{output}");
+    Ok(())
+}
@@ -0,0 +1,75 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Generates a `rust-project.json` file for rust-analyzer, which includes the binding crates
+//! generated by Crubit.
+//!
+//! ```shell
+//! $ bazel build //foo:bar
+//! $ bazel aquery 'mnemonic("Rustc", deps(//foo:bar))' --output=jsonproto > aquery.json
+//! $ crubit_rust_project --aquery=aquery.json --execroot="$(bazel info execution_root)" \
+//!     > rust-project.json
+//! ```
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use rust_project::{invocations_from_aquery, rust_project, RustcInvocation};
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[clap(name = "crubit_rust_project")]
+#[clap(about = "Generates a rust-project.json file that includes Crubit's binding crates",
+       long_about = None)]
+struct Cmdline {
+    /// The output of `bazel aquery --output=jsonproto` for the rustc actions of the build.
+    #[clap(long, value_parser, value_name = "FILE")]
+    aquery: Vec<PathBuf>,
+
+    /// A file with the command line of a rustc invocation, one argument per line, as in Bazel
+    /// params files.
+    #[clap(long, value_parser, value_name = "FILE")]
+    rustc_args: Vec<PathBuf>,
+
+    /// The directory the rustc invocations ran in. Relative paths are resolved against it.
+    #[clap(long, value_parser, value_name = "DIR", default_value = "")]
+    execroot: String,
+
+    /// The Rust sysroot, to be used by rust-analyzer.
+    #[clap(long, value_parser, value_name = "DIR")]
+    sysroot: Option<String>,
+
+    /// The source code of the Rust standard library, to be used by rust-analyzer.
+    #[clap(long, value_parser, value_name = "DIR")]
+    sysroot_src: Option<String>,
+}
+
+fn main() -> Result<()> {
+    let cmdline = Cmdline::parse();
+    let mut invocations = Vec::<RustcInvocation>::new();
+    for path in &cmdline.aquery {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        invocations.extend(
+            invocations_from_aquery(&json)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+        );
+    }
+    for path in &cmdline.rustc_args {
+        let params = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let args: Vec<String> = params.lines().map(str::to_string).collect();
+        invocations.extend(RustcInvocation::parse(&args));
+    }
+    let (project, unresolved) = rust_project(
+        &invocations,
+        &cmdline.execroot,
+        cmdline.sysroot.as_deref(),
+        cmdline.sysroot_src.as_deref(),
+    );
+    for unresolved in unresolved {
+        eprintln!("warning: no crate matches {unresolved}");
+    }
+    println!("{}", serde_json::to_string_pretty(&project)?);
+    Ok(())
+}
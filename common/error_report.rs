@@ -177,6 +177,9 @@ pub trait ErrorReporting {
 
     /// Adds the provided category metadata bits to the current item.
     fn add_category(&self, category: Category);
+
+    /// Attaches a suggested fix to the current item.
+    fn suggest(&self, suggestion: Suggestion);
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -273,6 +276,7 @@ impl ErrorReporting for IgnoreErrors {
     fn assert_in_item(&self, _: ItemName) {}
     fn exit_item(&self, _: ItemName, _: Option<ItemName>) {}
     fn add_category(&self, _: Category) {}
+    fn suggest(&self, _: Suggestion) {}
 }
 
 fn hide_unstable_details(input: &str) -> String {
@@ -442,6 +446,20 @@ impl ErrorReporting for ErrorReport {
             })
             .category |= category as u32;
     }
+
+    fn suggest(&self, suggestion: Suggestion) {
+        let mut map = self.map.borrow_mut();
+        let suggestions = &mut map
+            .entry(self.current_item().id)
+            .or_insert_with(|| ErrorReportEntry {
+                source_language: Some(self.source_language),
+                ..Default::default()
+            })
+            .suggestions;
+        if !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+    }
 }
 
 /// An entry in an error report.
@@ -470,6 +488,78 @@ pub struct ErrorReportEntry {
     /// The Bazel label or Rust crate that defines this item, if it's not the current one.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub defining_target: Option<Rc<str>>,
+
+    /// Mechanical fixes that would let this item receive bindings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// A fix for an error, which can be applied without human judgement.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Suggestion {
+    /// What the fix does, e.g. "give this overload a distinct Rust name".
+    pub message: Rc<str>,
+    pub edit: Edit,
+}
+
+/// A change to a source file or to a build target.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Edit {
+    /// Replaces `length` bytes at `offset` of `file` with `replacement`. An insertion has a
+    /// `length` of 0.
+    Replace {
+        /// The path of the file, as passed to the compiler.
+        file: Rc<str>,
+        /// The byte offset of the replaced text in the file.
+        offset: u64,
+        /// The number of replaced bytes.
+        length: u64,
+        /// The 1-based line of `offset`.
+        line: u32,
+        /// The 1-based column of `offset`.
+        column: u32,
+        replacement: Rc<str>,
+    },
+    /// Adds `aspect_hint` (e.g. `//features:supported`) to the `aspect_hints` of the Bazel
+    /// target `target`.
+    AddAspectHint { target: Rc<str>, aspect_hint: Rc<str> },
+}
+
+impl Suggestion {
+    /// A suggestion to insert `text` before the given position of `file`.
+    pub fn insert(
+        message: impl Into<Rc<str>>,
+        file: impl Into<Rc<str>>,
+        offset: u64,
+        line: u32,
+        column: u32,
+        text: impl Into<Rc<str>>,
+    ) -> Self {
+        Suggestion {
+            message: message.into(),
+            edit: Edit::Replace {
+                file: file.into(),
+                offset,
+                length: 0,
+                line,
+                column,
+                replacement: text.into(),
+            },
+        }
+    }
+
+    /// A suggestion to add `aspect_hint` to the `aspect_hints` of `target`.
+    pub fn add_aspect_hint(
+        message: impl Into<Rc<str>>,
+        target: impl Into<Rc<str>>,
+        aspect_hint: impl Into<Rc<str>>,
+    ) -> Self {
+        Suggestion {
+            message: message.into(),
+            edit: Edit::AddAspectHint { target: target.into(), aspect_hint: aspect_hint.into() },
+        }
+    }
 }
 
 fn is_default<T: Default + PartialEq>(value: &T) -> bool {
@@ -837,4 +927,68 @@ mod tests {
             ]),
         );
     }
+
+    #[gtest]
+    fn test_suggestions_are_reported() {
+        let report = ErrorReport::new(SourceLanguage::Cpp);
+        {
+            let _scope = ItemScope::new(
+                &report,
+                ItemName { name: "Foo".into(), id: 1, unique_name: None, defining_target: None },
+            );
+            report.report(&anyhow!("Cannot generate bindings for overloaded function"));
+            let suggestion = Suggestion::insert(
+                "give this overload a distinct Rust name",
+                "foo.h",
+                12,
+                2,
+                1,
+                "CRUBIT_RUST_NAME(\"Foo_int\") ",
+            );
+            report.suggest(suggestion.clone());
+            // Duplicates, e.g. from revisiting an item, are dropped.
+            report.suggest(suggestion);
+            report.suggest(Suggestion::add_aspect_hint(
+                "enable Crubit on the defining target",
+                "//other:target",
+                "//features:supported",
+            ));
+        }
+        expect_eq!(
+            serde_json::from_str::<serde_json::Value>(&report.to_json_string()).unwrap(),
+            serde_json::json!([
+                {
+                    "source_language": "Cpp",
+                    "name": "Foo",
+                    "errors": [
+                        {
+                            "fmt": "Cannot generate bindings for overloaded function",
+                        },
+                    ],
+                    "suggestions": [
+                        {
+                            "message": "give this overload a distinct Rust name",
+                            "edit": {
+                                "kind": "replace",
+                                "file": "foo.h",
+                                "offset": 12,
+                                "length": 0,
+                                "line": 2,
+                                "column": 1,
+                                "replacement": "CRUBIT_RUST_NAME(\"Foo_int\") ",
+                            },
+                        },
+                        {
+                            "message": "enable Crubit on the defining target",
+                            "edit": {
+                                "kind": "add_aspect_hint",
+                                "target": "//other:target",
+                                "aspect_hint": "//features:supported",
+                            },
+                        },
+                    ],
+                },
+            ]),
+        );
+    }
 }
@@ -0,0 +1,401 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Explains why Crubit did not generate bindings for an item, and how to fix it.
+//!
+//! The explanation is built from the JSON error reports of `rs_bindings_from_cc` or
+//! `cc_bindings_from_rs`. Each error is split into its causal chain (e.g. "Parameter #0 is not
+//! supported" caused by "Unsupported type 'int[2]'"), and is matched against known errors to
+//! suggest fixes: a Crubit feature to enable, an annotation to add, or a page of
+//! crubit.rs/errors to read. When an item fails because another item failed, the explanation
+//! follows the dependency.
+
+use anyhow::{bail, Context, Result};
+use crubit_feature::CrubitFeature;
+use error_report::{Edit, ErrorReportEntry, SourceLanguage, Suggestion};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+/// The items of one or more error reports.
+#[derive(Debug, Default)]
+pub struct ErrorReports {
+    entries: Vec<ErrorReportEntry>,
+}
+
+impl ErrorReports {
+    /// Parses a JSON error report.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let entries = serde_json::from_str(json).context("Failed to parse the error report")?;
+        Ok(ErrorReports { entries })
+    }
+
+    /// Adds the items of `other`, e.g. the error report of a dependency.
+    pub fn merge(&mut self, other: ErrorReports) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Returns the items named `symbol`.
+    ///
+    /// `symbol` is a C++ qualified name (`ns::Foo`) or a Rust path (`my_crate::Foo`), and matches
+    /// the items whose name is `symbol`, or ends with `::` followed by `symbol`. Overloaded C++
+    /// functions share a name, so more than one item can match.
+    pub fn find(&self, symbol: &str) -> Vec<&ErrorReportEntry> {
+        let symbol = normalize_name(symbol);
+        let suffix = format!("::{symbol}");
+        let exact: Vec<_> =
+            self.entries.iter().filter(|entry| normalize_name(&entry.name) == symbol).collect();
+        if !exact.is_empty() {
+            return exact;
+        }
+        self.entries.iter().filter(|entry| normalize_name(&entry.name).ends_with(&suffix)).collect()
+    }
+
+    /// Explains why the items named `symbol` did or did not receive bindings.
+    ///
+    /// Returns an error listing similarly named items if no item is named `symbol`.
+    pub fn explain(&self, symbol: &str) -> Result<Vec<Explanation>> {
+        let entries = self.find(symbol);
+        if entries.is_empty() {
+            let suggestions = self.similar_names(symbol);
+            if suggestions.is_empty() {
+                bail!("`{symbol}` is not in the error report");
+            }
+            bail!(
+                "`{symbol}` is not in the error report. Did you mean:\n{}",
+                suggestions.iter().map(|name| format!("  {name}")).collect::<Vec<_>>().join("\n")
+            );
+        }
+        Ok(entries
+            .into_iter()
+            .map(|entry| self.explain_entry(entry, &mut HashSet::new()))
+            .collect())
+    }
+
+    fn explain_entry(
+        &self,
+        entry: &ErrorReportEntry,
+        visited: &mut HashSet<String>,
+    ) -> Explanation {
+        visited.insert(normalize_name(&entry.name));
+        let reasons = entry
+            .errors
+            .iter()
+            .map(|error| {
+                let message = error.to_string();
+                let chain = causal_chain(&message);
+                let fixes = suggest_fixes(&chain, entry);
+                let dependency = chain
+                    .iter()
+                    .find_map(|step| dependency_name(step))
+                    .filter(|name| !visited.contains(&normalize_name(name)))
+                    .and_then(|name| {
+                        self.find(&name).into_iter().find(|dep| !dep.errors.is_empty())
+                    })
+                    .map(|dep| Box::new(self.explain_entry(dep, visited)));
+                Reason { chain, fixes, dependency }
+            })
+            .collect();
+        Explanation {
+            item: entry.name.to_string(),
+            source_language: entry.source_language,
+            defining_target: entry.defining_target.as_deref().map(str::to_string),
+            reasons,
+            suggestions: entry.suggestions.clone(),
+        }
+    }
+
+    /// Returns up to 5 item names that look like `symbol`, for "did you mean" suggestions.
+    fn similar_names(&self, symbol: &str) -> Vec<String> {
+        let symbol = normalize_name(symbol).to_lowercase();
+        let last_segment = symbol.rsplit("::").next().unwrap_or_default();
+        let mut names: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| entry.name.to_string())
+            .filter(|name| {
+                let name = normalize_name(name).to_lowercase();
+                name.contains(&symbol) || name.rsplit("::").next() == Some(last_segment)
+            })
+            .collect();
+        names.sort();
+        names.dedup();
+        names.truncate(5);
+        names
+    }
+}
+
+/// Why an item did or did not receive bindings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    /// The name of the item, as it appears in the error report.
+    pub item: String,
+    pub source_language: Option<SourceLanguage>,
+    /// The Bazel label or Rust crate that defines the item, if it's not the current one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defining_target: Option<String>,
+    /// Why bindings could not be generated. Empty if the item received bindings.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<Reason>,
+    /// Machine-applicable edits suggested by the binding generator. These can be applied in bulk
+    /// with `crubit_fix_its`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Explanation {
+    /// Returns whether the item received bindings.
+    pub fn is_bound(&self) -> bool {
+        self.reasons.is_empty()
+    }
+}
+
+/// One of the errors that prevented an item from receiving bindings.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Reason {
+    /// The error, followed by its causes, e.g.
+    /// `["Parameter #0 is not supported", "Unsupported type 'int[2]'", ...]`.
+    pub chain: Vec<String>,
+    /// Changes that would resolve the error.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+    /// The explanation for the item that this error is caused by, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependency: Option<Box<Explanation>>,
+}
+
+/// A change that would resolve an error.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Fix {
+    /// Enable a Crubit feature (crubit.rs/features) on a target.
+    EnableFeature {
+        /// The aspect hint that enables the feature, e.g. `//features:experimental`.
+        aspect_hint: String,
+        /// The target to enable the feature on, or `None` for the target being bound.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        target: Option<String>,
+    },
+    /// Add an annotation to the item.
+    Annotate {
+        /// The annotation, e.g. `CRUBIT_RUST_NAME("...")`.
+        annotation: String,
+        /// What the annotation does.
+        note: String,
+    },
+    /// Read a page of the documentation, e.g. `crubit.rs/errors/unsupported_type`.
+    ReadDocs { page: String },
+    /// Change the item itself.
+    Change { note: String },
+}
+
+impl Display for Fix {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Fix::EnableFeature { aspect_hint, target: Some(target) } => {
+                write!(f, "add `{aspect_hint}` to the `aspect_hints` of `{target}`")
+            }
+            Fix::EnableFeature { aspect_hint, target: None } => {
+                write!(f, "add `{aspect_hint}` to the `aspect_hints` of the target")
+            }
+            Fix::Annotate { annotation, note } => {
+                write!(f, "annotate it with `{annotation}`: {note}")
+            }
+            Fix::ReadDocs { page } => write!(f, "see {page}"),
+            Fix::Change { note } => write!(f, "{note}"),
+        }
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl Explanation {
+    fn fmt_indented(&self, f: &mut Formatter, indent: usize) -> fmt::Result {
+        let pad = " ".repeat(indent);
+        let defined_in = match &self.defining_target {
+            Some(target) => format!(" (defined in `{target}`)"),
+            None => String::new(),
+        };
+        if self.is_bound() {
+            return writeln!(f, "{pad}`{}`{defined_in} has bindings.", self.item);
+        }
+        writeln!(f, "{pad}`{}`{defined_in} has no bindings:", self.item)?;
+        for (i, reason) in self.reasons.iter().enumerate() {
+            let (first, causes) = reason.chain.split_first().expect("chains are never empty");
+            writeln!(f, "{pad}  {}. {first}", i + 1)?;
+            for cause in causes {
+                writeln!(f, "{pad}     caused by: {cause}")?;
+            }
+            for fix in &reason.fixes {
+                writeln!(f, "{pad}     fix: {fix}")?;
+            }
+            if let Some(dependency) = &reason.dependency {
+                dependency.fmt_indented(f, indent + 5)?;
+            }
+        }
+        for suggestion in &self.suggestions {
+            let location = match &suggestion.edit {
+                Edit::Replace { file, line, column, .. } => format!("{file}:{line}:{column}"),
+                Edit::AddAspectHint { target, .. } => target.to_string(),
+            };
+            writeln!(f, "{pad}  suggested edit ({location}): {}", suggestion.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares names regardless of whitespace (the error report contains names like
+/// `c9 :: Co < int >`) and of leading `::`.
+fn normalize_name(name: &str) -> String {
+    let name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+    name.trim_start_matches("::").to_string()
+}
+
+/// Splits an error message into the error, followed by its causes.
+///
+/// Errors with context are printed either as `context: cause`, or, for lists of errors, as
+/// `context:\n  cause\n  cause`. A `: ` only separates two steps if the cause looks like a
+/// sentence, so that e.g. "Unsupported `volatile` qualifier: volatile int" remains a single
+/// step.
+fn causal_chain(message: &str) -> Vec<String> {
+    let mut chain: Vec<String> = vec![];
+    let mut previous_line_is_context = false;
+    for line in message.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (line, is_context) = match line.strip_suffix(':') {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+        // A lone word after a context line is a detail of the context, like the target in
+        // "Crubit is not enabled on defining target:\n  //foo:bar".
+        if previous_line_is_context
+            && !is_context
+            && !line.contains(' ')
+            && let Some(context) = chain.last_mut()
+        {
+            *context = format!("{context}: {line}");
+            previous_line_is_context = false;
+            continue;
+        }
+        let mut step = String::new();
+        for part in line.split(": ") {
+            let is_sentence = part.starts_with(|c: char| c.is_ascii_uppercase() || c == '`')
+                && part.contains(' ');
+            if step.is_empty() {
+                step = part.to_string();
+            } else if step.starts_with("crubit.rs/errors/") && !step.contains(": ") {
+                // Keep a link to the documentation together with the error that it documents.
+                step = format!("{step}: {part}");
+            } else if is_sentence || part.starts_with("crubit.rs/errors/") {
+                chain.push(std::mem::take(&mut step));
+                step = part.to_string();
+            } else {
+                step = format!("{step}: {part}");
+            }
+        }
+        chain.push(step);
+        previous_line_is_context = is_context;
+    }
+    if chain.is_empty() {
+        chain.push(message.to_string());
+    }
+    chain
+}
+
+/// Returns the name of the item that `step` says the error is caused by, if any.
+fn dependency_name(step: &str) -> Option<String> {
+    let between_backticks = |prefix: &str| {
+        let rest = step.split_once(prefix)?.1;
+        Some(rest.split_once('`')?.0.to_string())
+    };
+    between_backticks("depends on `")
+        .or_else(|| between_backticks("Cannot use an error type `"))
+        .or_else(|| between_backticks("is unsupported because `"))
+}
+
+/// Returns the fixes for the error with the causal chain `chain`, of the item `entry`.
+fn suggest_fixes(chain: &[String], entry: &ErrorReportEntry) -> Vec<Fix> {
+    let mut fixes = vec![];
+    let mut add = |fix: Fix| {
+        if !fixes.contains(&fix) {
+            fixes.push(fix);
+        }
+    };
+    let enable_feature = |feature: CrubitFeature, target: Option<&str>| Fix::EnableFeature {
+        aspect_hint: feature.aspect_hint().to_string(),
+        target: target.or(entry.defining_target.as_deref()).map(str::to_string),
+    };
+    let is_rust = entry.source_language == Some(SourceLanguage::Rust);
+
+    for step in chain {
+        if let Some(page) = step.split(": ").find(|part| part.starts_with("crubit.rs/errors/")) {
+            add(Fix::ReadDocs { page: page.to_string() });
+        }
+        // Some errors name the aspect hint that they require.
+        for feature in flagset::FlagSet::<CrubitFeature>::full() {
+            if step.contains(&format!("requires {}", feature.aspect_hint())) {
+                add(enable_feature(feature, None));
+            }
+        }
+        if let Some(target) = step.strip_prefix("Crubit is not enabled on defining target: ") {
+            add(enable_feature(CrubitFeature::Supported, Some(target)));
+        }
+        if [
+            "references are not yet supported",
+            "non-C calling convention",
+            "function pointers using a non-`C` calling convention",
+            "variadic function",
+            "[[noreturn]] attribute",
+            "unknown function attributes are only supported with experimental features",
+        ]
+        .iter()
+        .any(|pattern| step.contains(pattern))
+            || step.ends_with("is experimental.")
+        {
+            add(enable_feature(CrubitFeature::Experimental, None));
+        }
+        if step == "incomplete type"
+            || step.contains("forward declared types are not yet supported")
+        {
+            add(enable_feature(CrubitFeature::Wrapper, None));
+        }
+        if step.contains("template instantiation is not yet supported") {
+            add(enable_feature(CrubitFeature::TemplateInstantiation, None));
+        }
+        if step == "destructors" {
+            add(enable_feature(CrubitFeature::Types, None));
+        }
+        if let Some((_, attributes)) = step.split_once("unknown attribute(s): ") {
+            add(Fix::Annotate {
+                annotation: format!("CRUBIT_UNSAFE_IGNORE_ATTR(\"{attributes}\")"),
+                note: "ignores the attribute, if it doesn't affect the ABI".to_string(),
+            });
+        }
+        if step.contains("Cannot generate bindings for overloaded function") {
+            add(Fix::Annotate {
+                annotation: "CRUBIT_RUST_NAME(\"...\")".to_string(),
+                note: "gives each overload a distinct Rust name".to_string(),
+            });
+        }
+        if step.contains("because it has a leading `__`") {
+            add(Fix::Change {
+                note: "rename it, or expose it through an alias without a leading `__`".to_string(),
+            });
+        }
+        if is_rust
+            && (step.contains("is a C++ reserved word")
+                || step.contains("is a C++ reserved keyword")
+                || step.starts_with("Conflicting member function name"))
+        {
+            add(Fix::Annotate {
+                annotation: "#[crubit_annotate::cpp_name(\"...\")]".to_string(),
+                note: "gives it a different C++ name".to_string(),
+            });
+        }
+    }
+    fixes
+}
@@ -0,0 +1,336 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use explain_bindings::{ErrorReports, Fix};
+use googletest::prelude::*;
+
+const CPP_REPORT: &str = r#"[
+    {"source_language": "Cpp", "name": "Point", "category": 4},
+    {
+        "source_language": "Cpp",
+        "name": "ns::TakesArray",
+        "errors": [{
+            "fmt": "Parameter #{} is not supported: {}",
+            "full_error": "Parameter #0 is not supported: Unsupported type 'int[2]': Unsupported clang::Type class 'ConstantArray'"
+        }],
+        "category": 1
+    },
+    {
+        "source_language": "Cpp",
+        "name": "Volatile",
+        "errors": [{
+            "fmt": "Return type is not supported: {}",
+            "full_error": "Return type is not supported: Unsupported `volatile` qualifier: volatile int"
+        }],
+        "category": 1
+    },
+    {
+        "source_language": "Cpp",
+        "name": "UsesExternal",
+        "errors": [{
+            "fmt": "{}",
+            "full_error": "Unsupported parameter type `External e`:\n  Crubit is not enabled on defining target:\n    //external:lib"
+        }],
+        "category": 1
+    },
+    {
+        "source_language": "Cpp",
+        "name": "Overloaded",
+        "errors": [{"fmt": "Cannot generate bindings for overloaded function"}],
+        "category": 1,
+        "suggestions": [{
+            "message": "give this overload the distinct Rust name `Overloaded_int`",
+            "edit": {
+                "kind": "replace",
+                "file": "foo.h",
+                "offset": 40,
+                "length": 0,
+                "line": 3,
+                "column": 1,
+                "replacement": "CRUBIT_RUST_NAME(\"Overloaded_int\") "
+            }
+        }]
+    },
+    {
+        "source_language": "Cpp",
+        "name": "Overloaded",
+        "errors": [{"fmt": "Cannot generate bindings for overloaded function"}],
+        "category": 1
+    },
+    {
+        "source_language": "Cpp",
+        "name": "Cold",
+        "errors": [{
+            "fmt": "crubit.rs/errors/unknown_attribute: unknown attribute(s): {}",
+            "full_error": "crubit.rs/errors/unknown_attribute: unknown attribute(s): gnu::abi_tag"
+        }],
+        "category": 1
+    },
+    {
+        "source_language": "Cpp",
+        "name": "__DunderType",
+        "errors": [{
+            "fmt": "Skipping generating bindings for '{}' because it has a leading `__`",
+            "full_error": "Skipping generating bindings for '__DunderType' because it has a leading `__`"
+        }],
+        "category": 4
+    },
+    {
+        "source_language": "Cpp",
+        "name": "AliasToDunderType",
+        "errors": [{
+            "fmt": "depends on `{}` which cannot be bound because {}",
+            "full_error": "depends on `__DunderType` which cannot be bound because Skipping generating bindings for '__DunderType' because it has a leading `__`"
+        }],
+        "category": 8
+    },
+    {
+        "source_language": "Cpp",
+        "name": "TakesReference",
+        "errors": [{
+            "fmt": "{}",
+            "full_error": "Unsupported parameter type `int & x`:\n  references are not yet supported"
+        }],
+        "category": 1
+    }
+]"#;
+
+const RUST_REPORT: &str = r#"[
+    {
+        "source_language": "Rust",
+        "name": "my_crate::inner::delete",
+        "errors": [{
+            "fmt": "Error formatting function name: {}",
+            "full_error": "Error formatting function name: `delete` is a C++ reserved word and can't be used as a C++ identifier"
+        }],
+        "category": 1
+    },
+    {
+        "source_language": "Rust",
+        "name": "my_crate::generic",
+        "errors": [{
+            "fmt": "crubit.rs/errors/unsupported_type: Generic type parameter `{}` is not supported without monomorphization",
+            "full_error": "Error handling parameter #0: crubit.rs/errors/unsupported_type: Generic type parameter `T` is not supported without monomorphization"
+        }],
+        "category": 1
+    }
+]"#;
+
+fn reports() -> ErrorReports {
+    let mut reports = ErrorReports::from_json(CPP_REPORT).unwrap();
+    reports.merge(ErrorReports::from_json(RUST_REPORT).unwrap());
+    reports
+}
+
+#[gtest]
+fn test_find() {
+    let reports = reports();
+    expect_that!(reports.find("ns::TakesArray"), len(eq(1)));
+    expect_that!(reports.find("::ns::TakesArray"), len(eq(1)));
+    expect_that!(reports.find("TakesArray"), len(eq(1)));
+    expect_that!(reports.find("inner::delete"), len(eq(1)));
+    expect_that!(reports.find("Overloaded"), len(eq(2)));
+    expect_that!(reports.find("akesArray"), is_empty());
+}
+
+#[gtest]
+fn test_not_found() {
+    let error = reports().explain("other::TakesArray").unwrap_err();
+    expect_eq!(
+        error.to_string(),
+        "`other::TakesArray` is not in the error report. Did you mean:\n  ns::TakesArray"
+    );
+}
+
+#[gtest]
+fn test_bound() {
+    let explanations = reports().explain("Point").unwrap();
+    expect_that!(explanations, len(eq(1)));
+    expect_that!(explanations[0].is_bound(), eq(true));
+    expect_eq!(explanations[0].to_string(), "`Point` has bindings.\n");
+}
+
+#[gtest]
+fn test_causal_chain() {
+    let explanations = reports().explain("TakesArray").unwrap();
+    expect_eq!(
+        explanations[0].reasons[0].chain,
+        [
+            "Parameter #0 is not supported",
+            "Unsupported type 'int[2]'",
+            "Unsupported clang::Type class 'ConstantArray'"
+        ]
+    );
+    expect_that!(explanations[0].reasons[0].fixes, is_empty());
+    expect_eq!(
+        explanations[0].to_string(),
+        "`ns::TakesArray` has no bindings:\n\
+         \x20 1. Parameter #0 is not supported\n\
+         \x20    caused by: Unsupported type 'int[2]'\n\
+         \x20    caused by: Unsupported clang::Type class 'ConstantArray'\n"
+    );
+}
+
+#[gtest]
+fn test_causal_chain_does_not_split_details() {
+    let explanations = reports().explain("Volatile").unwrap();
+    expect_eq!(
+        explanations[0].reasons[0].chain,
+        ["Return type is not supported", "Unsupported `volatile` qualifier: volatile int"]
+    );
+}
+
+#[gtest]
+fn test_feature_on_defining_target() {
+    let explanations = reports().explain("UsesExternal").unwrap();
+    expect_eq!(
+        explanations[0].reasons[0].chain,
+        [
+            "Unsupported parameter type `External e`",
+            "Crubit is not enabled on defining target: //external:lib"
+        ]
+    );
+    expect_eq!(
+        explanations[0].reasons[0].fixes,
+        [Fix::EnableFeature {
+            aspect_hint: "//features:supported".to_string(),
+            target: Some("//external:lib".to_string()),
+        }]
+    );
+    expect_that!(
+        explanations[0].to_string(),
+        contains_substring(
+            "fix: add `//features:supported` to the `aspect_hints` of `//external:lib`"
+        )
+    );
+}
+
+#[gtest]
+fn test_feature_on_current_target() {
+    let explanations = reports().explain("TakesReference").unwrap();
+    expect_eq!(
+        explanations[0].reasons[0].fixes,
+        [Fix::EnableFeature { aspect_hint: "//features:experimental".to_string(), target: None }]
+    );
+}
+
+#[gtest]
+fn test_overloads() {
+    let explanations = reports().explain("Overloaded").unwrap();
+    expect_that!(explanations, len(eq(2)));
+    expect_that!(
+        explanations[0].reasons[0].fixes,
+        elements_are![matches_pattern!(Fix::Annotate {
+            annotation: eq("CRUBIT_RUST_NAME(\"...\")"),
+            ..
+        })]
+    );
+    expect_that!(explanations[0].suggestions, len(eq(1)));
+    expect_that!(explanations[1].suggestions, is_empty());
+    expect_eq!(
+        explanations[0].to_string(),
+        "`Overloaded` has no bindings:\n\
+         \x20 1. Cannot generate bindings for overloaded function\n\
+         \x20    fix: annotate it with `CRUBIT_RUST_NAME(\"...\")`: gives each overload a distinct \
+         Rust name\n\
+         \x20 suggested edit (foo.h:3:1): give this overload the distinct Rust name \
+         `Overloaded_int`\n"
+    );
+}
+
+#[gtest]
+fn test_unknown_attribute() {
+    let explanations = reports().explain("Cold").unwrap();
+    expect_eq!(
+        explanations[0].reasons[0].chain,
+        ["crubit.rs/errors/unknown_attribute: unknown attribute(s): gnu::abi_tag"]
+    );
+    expect_that!(
+        explanations[0].reasons[0].fixes,
+        elements_are![
+            eq(&Fix::ReadDocs { page: "crubit.rs/errors/unknown_attribute".to_string() }),
+            matches_pattern!(Fix::Annotate {
+                annotation: eq("CRUBIT_UNSAFE_IGNORE_ATTR(\"gnu::abi_tag\")"),
+                ..
+            }),
+        ]
+    );
+}
+
+#[gtest]
+fn test_dependency() {
+    let explanations = reports().explain("AliasToDunderType").unwrap();
+    let dependency = explanations[0].reasons[0].dependency.as_ref().unwrap();
+    expect_eq!(dependency.item, "__DunderType");
+    expect_that!(dependency.reasons[0].fixes, elements_are![matches_pattern!(Fix::Change { .. })]);
+    expect_eq!(
+        explanations[0].to_string(),
+        "`AliasToDunderType` has no bindings:\n\
+         \x20 1. depends on `__DunderType` which cannot be bound because Skipping generating \
+         bindings for '__DunderType' because it has a leading `__`\n\
+         \x20    fix: rename it, or expose it through an alias without a leading `__`\n\
+         \x20    `__DunderType` has no bindings:\n\
+         \x20      1. Skipping generating bindings for '__DunderType' because it has a leading `__`\n\
+         \x20         fix: rename it, or expose it through an alias without a leading `__`\n"
+    );
+}
+
+#[gtest]
+fn test_rust_reserved_word() {
+    let explanations = reports().explain("my_crate::inner::delete").unwrap();
+    expect_eq!(
+        explanations[0].reasons[0].chain,
+        [
+            "Error formatting function name",
+            "`delete` is a C++ reserved word and can't be used as a C++ identifier"
+        ]
+    );
+    expect_that!(
+        explanations[0].reasons[0].fixes,
+        elements_are![matches_pattern!(Fix::Annotate {
+            annotation: eq("#[crubit_annotate::cpp_name(\"...\")]"),
+            ..
+        })]
+    );
+}
+
+#[gtest]
+fn test_docs_link() {
+    let explanations = reports().explain("my_crate::generic").unwrap();
+    expect_eq!(
+        explanations[0].reasons[0].chain,
+        [
+            "Error handling parameter #0",
+            "crubit.rs/errors/unsupported_type: Generic type parameter `T` is not supported \
+             without monomorphization"
+        ]
+    );
+    expect_eq!(
+        explanations[0].reasons[0].fixes,
+        [Fix::ReadDocs { page: "crubit.rs/errors/unsupported_type".to_string() }]
+    );
+}
+
+#[gtest]
+fn test_json() {
+    let explanations = reports().explain("UsesExternal").unwrap();
+    expect_eq!(
+        serde_json::to_value(&explanations).unwrap(),
+        serde_json::json!([{
+            "item": "UsesExternal",
+            "source_language": "Cpp",
+            "reasons": [{
+                "chain": [
+                    "Unsupported parameter type `External e`",
+                    "Crubit is not enabled on defining target: //external:lib",
+                ],
+                "fixes": [{
+                    "kind": "enable_feature",
+                    "aspect_hint": "//features:supported",
+                    "target": "//external:lib",
+                }],
+            }],
+        }])
+    );
+}
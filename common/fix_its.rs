@@ -0,0 +1,164 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Exports the suggestions of Crubit error reports for bulk application by other tools.
+//!
+//! Both binding generators attach machine-applicable suggestions to the items of their error
+//! report (see `error_report::Suggestion`). Source edits are exported as a
+//! `clang-apply-replacements` YAML file, or as rustc JSON diagnostics that `rustfix` can apply.
+//! Edits of BUILD files are exported as `buildozer` commands.
+
+use error_report::{Edit, ErrorReportEntry, Suggestion};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// A suggestion, along with the item it would fix.
+#[derive(Clone, Copy, Debug)]
+pub struct FixIt<'a> {
+    pub entry: &'a ErrorReportEntry,
+    pub suggestion: &'a Suggestion,
+}
+
+/// Returns the suggestions of `entries`.
+///
+/// An edit is only returned once, even if it's suggested for several items, or in the error
+/// reports of several targets (e.g. a header shared by two `cc_library`s).
+pub fn collect(entries: &[ErrorReportEntry]) -> Vec<FixIt<'_>> {
+    let mut seen = HashSet::new();
+    entries
+        .iter()
+        .flat_map(|entry| {
+            entry.suggestions.iter().map(move |suggestion| FixIt { entry, suggestion })
+        })
+        .filter(|fix_it| seen.insert(&fix_it.suggestion.edit))
+        .collect()
+}
+
+/// Formats the source edits of `fix_its` as a `clang-apply-replacements` YAML file.
+pub fn to_clang_apply_replacements(fix_its: &[FixIt]) -> String {
+    let mut yaml = String::from("---\nMainSourceFile: ''\nReplacements:\n");
+    for fix_it in fix_its {
+        let Edit::Replace { file, offset, length, replacement, .. } = &fix_it.suggestion.edit
+        else {
+            continue;
+        };
+        writeln!(yaml, "  - FilePath: {}", yaml_quote(file)).unwrap();
+        writeln!(yaml, "    Offset: {offset}").unwrap();
+        writeln!(yaml, "    Length: {length}").unwrap();
+        writeln!(yaml, "    ReplacementText: {}", yaml_quote(replacement)).unwrap();
+    }
+    yaml.push_str("...\n");
+    yaml
+}
+
+/// Quotes `s` as a double-quoted YAML scalar, which is the only style that preserves newlines.
+fn yaml_quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A rustc JSON diagnostic, as read by `rustfix`.
+#[derive(Serialize)]
+struct Diagnostic<'a> {
+    #[serde(rename = "$message_type")]
+    message_type: &'static str,
+    message: String,
+    code: Option<()>,
+    level: &'static str,
+    spans: Vec<DiagnosticSpan<'a>>,
+    children: Vec<Diagnostic<'a>>,
+    rendered: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DiagnosticSpan<'a> {
+    file_name: &'a str,
+    byte_start: u64,
+    byte_end: u64,
+    line_start: u32,
+    line_end: u32,
+    column_start: u32,
+    column_end: u32,
+    is_primary: bool,
+    text: Vec<()>,
+    label: Option<()>,
+    suggested_replacement: &'a str,
+    suggestion_applicability: &'static str,
+    expansion: Option<()>,
+}
+
+/// Formats the source edits of `fix_its` as rustc JSON diagnostics, one per line, as produced
+/// by `rustc --error-format=json`.
+pub fn to_rustfix_diagnostics(fix_its: &[FixIt]) -> String {
+    let mut json = String::new();
+    for fix_it in fix_its {
+        let Edit::Replace { file, offset, length, line, column, replacement } =
+            &fix_it.suggestion.edit
+        else {
+            continue;
+        };
+        let message = match fix_it.entry.errors.first() {
+            Some(error) => format!("`{}` has no bindings: {error}", fix_it.entry.name),
+            None => format!("`{}` has no bindings", fix_it.entry.name),
+        };
+        let span = DiagnosticSpan {
+            file_name: file,
+            byte_start: *offset,
+            byte_end: offset + length,
+            line_start: *line,
+            // The end of a replaced range isn't recorded. `rustfix` only uses the byte range.
+            line_end: *line,
+            column_start: *column,
+            column_end: *column,
+            is_primary: true,
+            text: vec![],
+            label: None,
+            suggested_replacement: replacement,
+            suggestion_applicability: "MachineApplicable",
+            expansion: None,
+        };
+        let diagnostic = Diagnostic {
+            message_type: "diagnostic",
+            rendered: Some(format!("warning: {message}\n")),
+            message,
+            code: None,
+            level: "warning",
+            spans: vec![],
+            children: vec![Diagnostic {
+                message_type: "diagnostic",
+                message: fix_it.suggestion.message.to_string(),
+                code: None,
+                level: "help",
+                spans: vec![span],
+                children: vec![],
+                rendered: None,
+            }],
+        };
+        json.push_str(&serde_json::to_string(&diagnostic).expect("diagnostics are serializable"));
+        json.push('\n');
+    }
+    json
+}
+
+/// Formats the BUILD file edits of `fix_its` as a `buildozer -f` command file.
+pub fn to_buildozer_commands(fix_its: &[FixIt]) -> String {
+    let mut commands = String::new();
+    for fix_it in fix_its {
+        if let Edit::AddAspectHint { target, aspect_hint } = &fix_it.suggestion.edit {
+            writeln!(commands, "add aspect_hints {aspect_hint}|{target}").unwrap();
+        }
+    }
+    commands
+}
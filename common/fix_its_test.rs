@@ -0,0 +1,182 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use error_report::{Edit, ErrorReportEntry};
+use fix_its::{
+    collect, to_buildozer_commands, to_clang_apply_replacements, to_rustfix_diagnostics,
+};
+use googletest::prelude::*;
+
+const CPP_REPORT: &str = r#"[
+    {"source_language": "Cpp", "name": "Point", "category": 4},
+    {
+        "source_language": "Cpp",
+        "name": "Overloaded",
+        "errors": [{"fmt": "Cannot generate bindings for overloaded function"}],
+        "suggestions": [{
+            "message": "give this overload the distinct Rust name `Overloaded_int`",
+            "edit": {
+                "kind": "replace",
+                "file": "foo/foo.h",
+                "offset": 40,
+                "length": 0,
+                "line": 3,
+                "column": 1,
+                "replacement": "CRUBIT_RUST_NAME(\"Overloaded_int\") "
+            }
+        }]
+    },
+    {
+        "source_language": "Cpp",
+        "name": "UsesExternal",
+        "errors": [{"fmt": "{}", "full_error": "Crubit is not enabled on defining target"}],
+        "suggestions": [{
+            "message": "enable Crubit on `//external:lib`",
+            "edit": {
+                "kind": "add_aspect_hint",
+                "target": "//external:lib",
+                "aspect_hint": "//features:supported"
+            }
+        }]
+    },
+    {
+        "source_language": "Cpp",
+        "name": "AlsoUsesExternal",
+        "errors": [{"fmt": "{}", "full_error": "Crubit is not enabled on defining target"}],
+        "suggestions": [{
+            "message": "enable Crubit on `//external:lib`",
+            "edit": {
+                "kind": "add_aspect_hint",
+                "target": "//external:lib",
+                "aspect_hint": "//features:supported"
+            }
+        }]
+    }
+]"#;
+
+const RUST_REPORT: &str = r##"[
+    {
+        "source_language": "Rust",
+        "name": "my_crate::delete",
+        "errors": [{
+            "fmt": "Error formatting function name: {}",
+            "full_error": "Error formatting function name: `delete` is a C++ reserved word and can't be used as a C++ identifier"
+        }],
+        "suggestions": [{
+            "message": "name this function `delete_` in C++",
+            "edit": {
+                "kind": "replace",
+                "file": "my_crate/lib.rs",
+                "offset": 104,
+                "length": 0,
+                "line": 5,
+                "column": 5,
+                "replacement": "#[crubit_annotate::cpp_name(\"delete_\")]\n    "
+            }
+        }]
+    }
+]"##;
+
+fn entries() -> Vec<ErrorReportEntry> {
+    let mut entries: Vec<ErrorReportEntry> = serde_json::from_str(CPP_REPORT).unwrap();
+    entries.extend(serde_json::from_str::<Vec<ErrorReportEntry>>(RUST_REPORT).unwrap());
+    entries
+}
+
+#[gtest]
+fn test_collect_deduplicates_edits() {
+    let entries = entries();
+    let fix_its = collect(&entries);
+    expect_eq!(
+        fix_its.iter().map(|fix_it| &*fix_it.entry.name).collect::<Vec<_>>(),
+        ["Overloaded", "UsesExternal", "my_crate::delete"]
+    );
+    expect_that!(fix_its[1].suggestion.edit, matches_pattern!(Edit::AddAspectHint { .. }));
+}
+
+#[gtest]
+fn test_clang_apply_replacements() {
+    let entries = entries();
+    expect_eq!(
+        to_clang_apply_replacements(&collect(&entries)),
+        r##"---
+MainSourceFile: ''
+Replacements:
+  - FilePath: "foo/foo.h"
+    Offset: 40
+    Length: 0
+    ReplacementText: "CRUBIT_RUST_NAME(\"Overloaded_int\") "
+  - FilePath: "my_crate/lib.rs"
+    Offset: 104
+    Length: 0
+    ReplacementText: "#[crubit_annotate::cpp_name(\"delete_\")]\n    "
+...
+"##
+    );
+}
+
+#[gtest]
+fn test_rustfix_diagnostics() {
+    let entries = entries();
+    let diagnostics = to_rustfix_diagnostics(&collect(&entries));
+    let lines: Vec<serde_json::Value> =
+        diagnostics.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    expect_that!(lines, len(eq(2)));
+    expect_eq!(
+        lines[1],
+        serde_json::json!({
+            "$message_type": "diagnostic",
+            "message": "`my_crate::delete` has no bindings: Error formatting function name: \
+                        `delete` is a C++ reserved word and can't be used as a C++ identifier",
+            "code": null,
+            "level": "warning",
+            "spans": [],
+            "children": [{
+                "$message_type": "diagnostic",
+                "message": "name this function `delete_` in C++",
+                "code": null,
+                "level": "help",
+                "spans": [{
+                    "file_name": "my_crate/lib.rs",
+                    "byte_start": 104,
+                    "byte_end": 104,
+                    "line_start": 5,
+                    "line_end": 5,
+                    "column_start": 5,
+                    "column_end": 5,
+                    "is_primary": true,
+                    "text": [],
+                    "label": null,
+                    "suggested_replacement": "#[crubit_annotate::cpp_name(\"delete_\")]\n    ",
+                    "suggestion_applicability": "MachineApplicable",
+                    "expansion": null,
+                }],
+                "children": [],
+                "rendered": null,
+            }],
+            "rendered": "warning: `my_crate::delete` has no bindings: Error formatting function \
+                         name: `delete` is a C++ reserved word and can't be used as a C++ \
+                         identifier\n",
+        })
+    );
+}
+
+#[gtest]
+fn test_buildozer_commands() {
+    let entries = entries();
+    expect_eq!(
+        to_buildozer_commands(&collect(&entries)),
+        "add aspect_hints //features:supported|//external:lib\n"
+    );
+}
+
+#[gtest]
+fn test_no_suggestions() {
+    let entries: Vec<ErrorReportEntry> =
+        serde_json::from_str(r#"[{"source_language": "Cpp", "name": "Point"}]"#).unwrap();
+    let fix_its = collect(&entries);
+    expect_that!(fix_its, is_empty());
+    expect_eq!(to_buildozer_commands(&fix_its), "");
+    expect_eq!(to_rustfix_diagnostics(&fix_its), "");
+}
@@ -4,6 +4,8 @@
 
 use base64::{prelude::BASE64_STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{btree_map::Entry, BTreeMap, HashMap};
 use token_stream_printer::{fix_provenance_map_postformatting, SubstringProvenanceMap};
 
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
@@ -100,6 +102,189 @@ fn embed_provenance_map(
     text
 }
 
+/// A symbol shared by a C++ declaration and the Rust items that bind it.
+struct LsifSymbol {
+    result_set: usize,
+    cc_document: usize,
+    cc_range: usize,
+    rs_ranges: Vec<usize>,
+}
+
+/// Given a `provenance_map` and the Rust `source_file` at `source_path` that has possibly been
+/// formatted, returns an LSIF index (as JSON lines) that links each annotated Rust item to the C++
+/// declaration it was generated from, and back.
+///
+/// The C++ declaration and the Rust item share a result set, so "go to definition" on the Rust
+/// item jumps to the C++ declaration and "find references" on the C++ declaration includes the
+/// Rust item. The result set also carries a `crubit` moniker naming the C++ declaration, which
+/// links the symbol to other indexes. `read_header` returns the contents of the C++ file at a
+/// path from the provenance map, and entries for unreadable files are skipped. Document URIs are
+/// relative to `project_root`.
+pub fn rs_provenance_map_to_lsif(
+    provenance_map: &SubstringProvenanceMap,
+    project_root: &str,
+    source_path: &str,
+    source_file: &str,
+    read_header: &mut dyn FnMut(&str) -> Option<String>,
+) -> String {
+    let project_root = project_root.trim_end_matches('/');
+    let mut lsif = LsifWriter::default();
+    lsif.vertex(
+        "metaData",
+        json!({
+            "version": "0.4.3",
+            "projectRoot": project_root,
+            "positionEncoding": "utf-16",
+            "toolInfo": {"name": "crubit"},
+        }),
+    );
+    let project = lsif.vertex("project", json!({"kind": "rust"}));
+    let rs_document = lsif.vertex(
+        "document",
+        json!({"uri": format!("{project_root}/{source_path}"), "languageId": "rust"}),
+    );
+
+    // NB: the formatter might break the line after the colon.
+    let fixed_map =
+        fix_provenance_map_postformatting(source_file, "/// Generated from:", provenance_map);
+    let mut sorted_map: Vec<_> = fixed_map.values().collect();
+    sorted_map.sort_unstable();
+    // C++ files, by path: their document and contents (or `None` if they can't be read).
+    let mut cc_documents = HashMap::<&str, Option<(usize, String)>>::new();
+    let mut symbols = BTreeMap::<(&str, usize, usize), LsifSymbol>::new();
+    let mut document_ranges = BTreeMap::<usize, Vec<usize>>::new();
+    for entry in sorted_map {
+        let (Ok(cc_start), Ok(cc_end)) =
+            (entry.original_start.parse::<usize>(), entry.original_end.parse::<usize>())
+        else {
+            continue;
+        };
+        let symbol = match symbols.entry((&entry.original_path, cc_start, cc_end)) {
+            Entry::Occupied(symbol) => symbol.into_mut(),
+            Entry::Vacant(symbol) => {
+                let cc_document = cc_documents.entry(&entry.original_path).or_insert_with(|| {
+                    let text = read_header(&entry.original_path)?;
+                    let uri = format!("{project_root}/{}", entry.original_path);
+                    let document =
+                        lsif.vertex("document", json!({"uri": uri, "languageId": "cpp"}));
+                    Some((document, text))
+                });
+                let Some((cc_document, cc_text)) = cc_document else {
+                    continue;
+                };
+                let Some(cc_range) = lsif.range(cc_text, cc_start, cc_end) else {
+                    continue;
+                };
+                let result_set = lsif.vertex("resultSet", json!({}));
+                lsif.edge("next", cc_range, result_set);
+                let moniker = lsif.vertex(
+                    "moniker",
+                    json!({
+                        "scheme": "crubit",
+                        "identifier": format!("{}:{cc_start}:{cc_end}", entry.original_path),
+                        "unique": "scheme",
+                        "kind": "export",
+                    }),
+                );
+                lsif.edge("moniker", result_set, moniker);
+                document_ranges.entry(*cc_document).or_default().push(cc_range);
+                symbol.insert(LsifSymbol {
+                    result_set,
+                    cc_document: *cc_document,
+                    cc_range,
+                    rs_ranges: vec![],
+                })
+            }
+        };
+        let Some(rs_range) = lsif.range(source_file, entry.formatted_start, entry.formatted_end)
+        else {
+            continue;
+        };
+        lsif.edge("next", rs_range, symbol.result_set);
+        symbol.rs_ranges.push(rs_range);
+        document_ranges.entry(rs_document).or_default().push(rs_range);
+    }
+
+    for symbol in symbols.values() {
+        let definition_result = lsif.vertex("definitionResult", json!({}));
+        lsif.edge("textDocument/definition", symbol.result_set, definition_result);
+        lsif.items(definition_result, &[symbol.cc_range], symbol.cc_document, None);
+        let reference_result = lsif.vertex("referenceResult", json!({}));
+        lsif.edge("textDocument/references", symbol.result_set, reference_result);
+        lsif.items(reference_result, &[symbol.cc_range], symbol.cc_document, Some("definitions"));
+        lsif.items(reference_result, &symbol.rs_ranges, rs_document, Some("references"));
+    }
+    for (document, ranges) in &document_ranges {
+        lsif.edges("contains", *document, ranges);
+    }
+    let mut documents = vec![rs_document];
+    documents.extend(cc_documents.values().flatten().map(|(document, _)| *document));
+    documents.sort_unstable();
+    lsif.edges("contains", project, &documents);
+    lsif.text
+}
+
+/// Writes LSIF vertices and edges as JSON lines, assigning consecutive ids.
+#[derive(Default)]
+struct LsifWriter {
+    text: String,
+    next_id: usize,
+}
+
+impl LsifWriter {
+    /// Writes an element of type `typ` and `label`, with additional `properties`, and returns its
+    /// id.
+    fn element(&mut self, typ: &str, label: &str, properties: Value) -> usize {
+        self.next_id += 1;
+        let mut element = json!({"id": self.next_id, "type": typ, "label": label});
+        if let (Value::Object(element), Value::Object(properties)) = (&mut element, properties) {
+            element.extend(properties);
+        }
+        self.text.push_str(&element.to_string());
+        self.text.push('\n');
+        self.next_id
+    }
+
+    fn vertex(&mut self, label: &str, properties: Value) -> usize {
+        self.element("vertex", label, properties)
+    }
+
+    fn edge(&mut self, label: &str, out_v: usize, in_v: usize) -> usize {
+        self.element("edge", label, json!({"outV": out_v, "inV": in_v}))
+    }
+
+    fn edges(&mut self, label: &str, out_v: usize, in_vs: &[usize]) -> usize {
+        self.element("edge", label, json!({"outV": out_v, "inVs": in_vs}))
+    }
+
+    /// Writes an `item` edge from a definition or reference result to ranges of `document`.
+    fn items(&mut self, out_v: usize, in_vs: &[usize], document: usize, property: Option<&str>) {
+        let mut properties = json!({"outV": out_v, "inVs": in_vs, "document": document});
+        if let Some(property) = property {
+            properties["property"] = property.into();
+        }
+        self.element("edge", "item", properties);
+    }
+
+    /// Writes a range vertex for the bytes `start..end` of `text`. Returns `None` if these aren't
+    /// valid offsets into `text`.
+    fn range(&mut self, text: &str, start: usize, end: usize) -> Option<usize> {
+        let start = lsif_position(text, start)?;
+        let end = lsif_position(text, end)?;
+        Some(self.vertex("range", json!({"start": start, "end": end})))
+    }
+}
+
+/// Returns the LSIF position (zero-based line, and UTF-16 column) of the byte `offset` of `text`.
+fn lsif_position(text: &str, offset: usize) -> Option<Value> {
+    let before = text.get(..offset)?;
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    Some(json!({
+        "line": before.matches('\n').count(),
+        "character": before[line_start..].encode_utf16().count(),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,4 +391,82 @@ bar
         );
         Ok(())
     }
+
+    #[gtest]
+    fn test_rs_provenance_map_to_lsif() -> Result<()> {
+        let header = "// Comment\nvoid Foo();\n";
+        let rs_api = r#"/// Generated from: foo.h;l=2
+pub fn Foo() {}
+/// Generated from: missing.h;l=1
+pub fn Bar() {}
+"#;
+        let mut provenance_map = SubstringProvenanceMap::new();
+        provenance_map.insert(
+            1,
+            SubstringWithProvenance {
+                substring: "Foo".to_owned(),
+                original_path: "foo.h".to_owned(),
+                original_start: "16".to_owned(),
+                original_end: "19".to_owned(),
+                index: 0,
+            },
+        );
+        provenance_map.insert(
+            2,
+            SubstringWithProvenance {
+                substring: "Bar".to_owned(),
+                original_path: "missing.h".to_owned(),
+                original_start: "0".to_owned(),
+                original_end: "3".to_owned(),
+                index: 0,
+            },
+        );
+        let lsif = rs_provenance_map_to_lsif(
+            &provenance_map,
+            "file:///root/",
+            "foo_rs_api.rs",
+            rs_api,
+            &mut |path| (path == "foo.h").then(|| header.to_owned()),
+        );
+        let elements: Vec<Value> =
+            lsif.lines().map(serde_json::from_str).collect::<serde_json::Result<_>>()?;
+        let expected = [
+            json!({"id": 1, "type": "vertex", "label": "metaData", "version": "0.4.3",
+                   "projectRoot": "file:///root", "positionEncoding": "utf-16",
+                   "toolInfo": {"name": "crubit"}}),
+            json!({"id": 2, "type": "vertex", "label": "project", "kind": "rust"}),
+            json!({"id": 3, "type": "vertex", "label": "document",
+                   "uri": "file:///root/foo_rs_api.rs", "languageId": "rust"}),
+            json!({"id": 4, "type": "vertex", "label": "document",
+                   "uri": "file:///root/foo.h", "languageId": "cpp"}),
+            json!({"id": 5, "type": "vertex", "label": "range",
+                   "start": {"line": 1, "character": 5}, "end": {"line": 1, "character": 8}}),
+            json!({"id": 6, "type": "vertex", "label": "resultSet"}),
+            json!({"id": 7, "type": "edge", "label": "next", "outV": 5, "inV": 6}),
+            json!({"id": 8, "type": "vertex", "label": "moniker", "scheme": "crubit",
+                   "identifier": "foo.h:16:19", "unique": "scheme", "kind": "export"}),
+            json!({"id": 9, "type": "edge", "label": "moniker", "outV": 6, "inV": 8}),
+            json!({"id": 10, "type": "vertex", "label": "range",
+                   "start": {"line": 1, "character": 7}, "end": {"line": 1, "character": 10}}),
+            json!({"id": 11, "type": "edge", "label": "next", "outV": 10, "inV": 6}),
+            // `Bar` is skipped, since `missing.h` can't be read.
+            json!({"id": 12, "type": "vertex", "label": "definitionResult"}),
+            json!({"id": 13, "type": "edge", "label": "textDocument/definition",
+                   "outV": 6, "inV": 12}),
+            json!({"id": 14, "type": "edge", "label": "item", "outV": 12, "inVs": [5],
+                   "document": 4}),
+            json!({"id": 15, "type": "vertex", "label": "referenceResult"}),
+            json!({"id": 16, "type": "edge", "label": "textDocument/references",
+                   "outV": 6, "inV": 15}),
+            json!({"id": 17, "type": "edge", "label": "item", "outV": 15, "inVs": [5],
+                   "document": 4, "property": "definitions"}),
+            json!({"id": 18, "type": "edge", "label": "item", "outV": 15, "inVs": [10],
+                   "document": 3, "property": "references"}),
+            json!({"id": 19, "type": "edge", "label": "contains", "outV": 3, "inVs": [10]}),
+            json!({"id": 20, "type": "edge", "label": "contains", "outV": 4, "inVs": [5]}),
+            json!({"id": 21, "type": "edge", "label": "contains", "outV": 2, "inVs": [3, 4]}),
+        ];
+        assert_eq!(elements, expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,287 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! A local, content-addressed cache of the outputs of Crubit's binding generators.
+//!
+//! Generating bindings requires an expensive analysis (rustc's, or Clang's), but its result only
+//! depends on the generator, its flags, and the contents of the files it reads. Some of these files
+//! are known up front (e.g. the `.rmeta` files passed to `cc_bindings_from_rs`), but others are
+//! only discovered by the analysis (e.g. the headers transitively included by a public header).
+//! So, like `ccache`'s "direct mode", the cache has two levels:
+//!
+//! * A [`CacheKey`] hashes everything known before the analysis. It maps to a manifest: the list
+//!   of files that the last analysis with this key read.
+//! * The key and the contents of the files in the manifest are hashed again, to find the outputs.
+//!
+//! Cache entries are written to a temporary directory and then renamed into place, so several
+//! builds can share a cache directory.
+
+use rustc_stable_hash::{FromStableHash, SipHasher128Hash, StableSipHasher128};
+use std::fmt;
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Version of the layout of the cache directory. Bumping it invalidates existing entries.
+const CACHE_FORMAT_VERSION: &str = "1";
+
+struct Hash128([u64; 2]);
+
+impl FromStableHash for Hash128 {
+    type Hash = SipHasher128Hash;
+    fn from(SipHasher128Hash(hash): Self::Hash) -> Self {
+        Hash128(hash)
+    }
+}
+
+/// Hashes the inputs of a binding generator that are known before it runs.
+pub struct CacheKeyBuilder {
+    hasher: StableSipHasher128,
+}
+
+impl CacheKeyBuilder {
+    /// Starts a key for an invocation of `tool`, the name of the binding generator.
+    ///
+    /// The key covers the generator executable (by path, size and modification time, which
+    /// changes whenever Crubit is rebuilt or upgraded) and the current directory, against which
+    /// relative paths are resolved.
+    pub fn new(tool: &str) -> Self {
+        let mut builder = CacheKeyBuilder { hasher: StableSipHasher128::new() };
+        builder.add_str(CACHE_FORMAT_VERSION);
+        builder.add_str(tool);
+        match std::env::current_exe().and_then(|exe| Ok((fs::metadata(&exe)?, exe))) {
+            Ok((metadata, exe)) => {
+                builder.add_str(&exe.to_string_lossy());
+                builder.add_u64(metadata.len());
+                builder.add_u64(modified_nanos(&metadata));
+            }
+            Err(_) => builder.add_str("<unknown executable>"),
+        }
+        builder.add_str(&std::env::current_dir().unwrap_or_default().to_string_lossy());
+        builder
+    }
+
+    pub fn add_str(&mut self, s: &str) {
+        self.add_bytes(s.as_bytes());
+    }
+
+    /// Adds the path and contents of `path`. A missing file is hashed as such.
+    pub fn add_file(&mut self, path: &Path) {
+        self.add_str(&path.to_string_lossy());
+        match fs::read(path) {
+            Ok(contents) => {
+                self.add_u64(1);
+                self.add_bytes(&contents);
+            }
+            Err(_) => self.add_u64(0),
+        }
+    }
+
+    fn add_u64(&mut self, n: u64) {
+        self.hasher.write_u64(n);
+    }
+
+    fn add_bytes(&mut self, bytes: &[u8]) {
+        // Length-prefixed, so that e.g. `["ab", "c"]` and `["a", "bc"]` hash differently.
+        self.add_u64(bytes.len() as u64);
+        self.hasher.write(bytes);
+    }
+
+    pub fn build(self) -> CacheKey {
+        let Hash128([low, high]) = self.hasher.finish();
+        CacheKey(format!("{high:016x}{low:016x}"))
+    }
+}
+
+fn modified_nanos(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
+
+/// The hash of the inputs of a binding generator that are known before it runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CacheKey(String);
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A cache directory.
+pub struct OutputCache {
+    dir: PathBuf,
+}
+
+/// Hit and miss counts of a cache directory, and its size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+    pub size_bytes: u64,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lookups = self.hits + self.misses;
+        let hit_rate = if lookups == 0 { 0.0 } else { 100.0 * self.hits as f64 / lookups as f64 };
+        write!(
+            f,
+            "Crubit cache: {} hits, {} misses ({hit_rate:.1}% hit rate), {} entries, {} bytes",
+            self.hits, self.misses, self.entries, self.size_bytes
+        )
+    }
+}
+
+impl OutputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        OutputCache { dir: dir.into() }
+    }
+
+    /// Copies the outputs stored for `key` to `outputs`, and returns whether they were found.
+    ///
+    /// The outputs are found if, for the files read by the last invocation with this `key`,
+    /// there are outputs stored for their current contents. The lookup is recorded in the
+    /// cache's statistics.
+    pub fn restore(&self, key: &CacheKey, outputs: &[&Path]) -> bool {
+        let hit = self.try_restore(key, outputs).unwrap_or(false);
+        // Each lookup appends a byte to a file, so that concurrent builds don't lose counts.
+        let _ = self.record(if hit { "hits" } else { "misses" });
+        hit
+    }
+
+    fn try_restore(&self, key: &CacheKey, outputs: &[&Path]) -> io::Result<bool> {
+        let manifest = match fs::read_to_string(self.manifest_path(key)) {
+            Ok(manifest) => manifest,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(err) => return Err(err),
+        };
+        let inputs: Vec<PathBuf> = manifest.lines().map(PathBuf::from).collect();
+        let entry = self.entry_path(key, &inputs);
+        if !entry.is_dir() {
+            return Ok(false);
+        }
+        for (index, output) in outputs.iter().enumerate() {
+            fs::copy(entry.join(index.to_string()), output)?;
+        }
+        Ok(true)
+    }
+
+    /// Stores `outputs`, which were generated for `key` by reading `inputs`.
+    pub fn store(&self, key: &CacheKey, inputs: &[PathBuf], outputs: &[&Path]) -> io::Result<()> {
+        let entry = self.entry_path(key, inputs);
+        if !entry.is_dir() {
+            let tmp = self.tmp_path()?;
+            for (index, output) in outputs.iter().enumerate() {
+                fs::copy(output, tmp.join(index.to_string()))?;
+            }
+            fs::create_dir_all(entry.parent().unwrap())?;
+            if fs::rename(&tmp, &entry).is_err() {
+                // Another build stored the same entry concurrently.
+                fs::remove_dir_all(&tmp)?;
+            }
+        }
+        let mut manifest = String::new();
+        for input in inputs {
+            manifest.push_str(&input.to_string_lossy());
+            manifest.push('\n');
+        }
+        let tmp = self.tmp_path()?.join("manifest");
+        fs::write(&tmp, manifest)?;
+        let manifest_path = self.manifest_path(key);
+        fs::create_dir_all(manifest_path.parent().unwrap())?;
+        fs::rename(&tmp, manifest_path)?;
+        fs::remove_dir(tmp.parent().unwrap())
+    }
+
+    /// Returns the statistics of the cache.
+    pub fn stats(&self) -> io::Result<CacheStats> {
+        let count = |name| match fs::metadata(self.dir.join("stats").join(name)) {
+            Ok(metadata) => Ok(metadata.len()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
+            Err(err) => Err(err),
+        };
+        let mut stats =
+            CacheStats { hits: count("hits")?, misses: count("misses")?, ..Default::default() };
+        let entries = match fs::read_dir(self.dir.join("entries")) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(stats),
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            stats.entries += 1;
+            for output in fs::read_dir(entry?.path())? {
+                stats.size_bytes += output?.metadata()?.len();
+            }
+        }
+        Ok(stats)
+    }
+
+    fn record(&self, event: &str) -> io::Result<()> {
+        let stats = self.dir.join("stats");
+        fs::create_dir_all(&stats)?;
+        fs::OpenOptions::new().create(true).append(true).open(stats.join(event))?.write_all(b".")
+    }
+
+    fn manifest_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join("manifests").join(&key.0)
+    }
+
+    fn entry_path(&self, key: &CacheKey, inputs: &[PathBuf]) -> PathBuf {
+        let mut builder = CacheKeyBuilder { hasher: StableSipHasher128::new() };
+        builder.add_str(&key.0);
+        for input in inputs {
+            builder.add_file(input);
+        }
+        self.dir.join("entries").join(builder.build().0)
+    }
+
+    /// Creates a new, uniquely named directory for writing files before renaming them into place.
+    fn tmp_path(&self) -> io::Result<PathBuf> {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+        let tmp = self.dir.join("tmp").join(format!("{}-{nanos}", std::process::id()));
+        fs::create_dir_all(&tmp)?;
+        Ok(tmp)
+    }
+}
+
+/// Returns the prerequisites listed in a Make-style dependency file, as written by the `-MD` flag of
+/// Clang and GCC: the files read by a compilation.
+pub fn parse_depfile(contents: &str) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    for rule in contents.replace("\\\r\n", " ").replace("\\\n", " ").lines() {
+        // The targets end at the first `:` followed by whitespace. A `:` followed by something
+        // else is part of a Windows path, like `C:\foo.h`.
+        let Some((separator, _)) = rule
+            .match_indices(':')
+            .find(|(i, _)| rule[i + 1..].chars().next().is_none_or(char::is_whitespace))
+        else {
+            continue;
+        };
+        let mut path = String::new();
+        let mut chars = rule[separator + 1..].chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' if matches!(chars.peek(), Some(' ' | '#')) => path.push(chars.next().unwrap()),
+                '$' if chars.peek() == Some(&'$') => path.push(chars.next().unwrap()),
+                c if c.is_whitespace() => {
+                    if !path.is_empty() {
+                        paths.push(PathBuf::from(std::mem::take(&mut path)));
+                    }
+                }
+                c => path.push(c),
+            }
+        }
+        if !path.is_empty() {
+            paths.push(PathBuf::from(path));
+        }
+    }
+    paths
+}
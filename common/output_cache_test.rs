@@ -0,0 +1,129 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use googletest::prelude::*;
+use output_cache::{CacheKey, CacheKeyBuilder, CacheStats, OutputCache};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn key(args: &[&str]) -> CacheKey {
+    let mut builder = CacheKeyBuilder::new("test_tool");
+    for arg in args {
+        builder.add_str(arg);
+    }
+    builder.build()
+}
+
+#[gtest]
+fn test_key_depends_on_args() {
+    expect_eq!(key(&["--a"]), key(&["--a"]));
+    expect_ne!(key(&["--a"]), key(&["--b"]));
+    expect_ne!(key(&["ab", "c"]), key(&["a", "bc"]));
+}
+
+#[gtest]
+fn test_key_depends_on_file_contents() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("input.rmeta");
+    let file_key = || {
+        let mut builder = CacheKeyBuilder::new("test_tool");
+        builder.add_file(&file);
+        builder.build()
+    };
+    let missing = file_key();
+    fs::write(&file, "v1").unwrap();
+    let v1 = file_key();
+    fs::write(&file, "v2").unwrap();
+    expect_ne!(missing, v1);
+    expect_ne!(v1, file_key());
+}
+
+#[gtest]
+fn test_restore_after_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = OutputCache::new(dir.path().join("cache"));
+    let header = dir.path().join("foo.h");
+    let output = dir.path().join("foo_rust_api.rs");
+    let key = key(&["--public_headers=foo.h"]);
+
+    expect_false!(cache.restore(&key, &[&output]));
+    fs::write(&header, "struct Foo {};").unwrap();
+    fs::write(&output, "pub struct Foo {}").unwrap();
+    cache.store(&key, &[header.clone()], &[&output]).unwrap();
+
+    fs::remove_file(&output).unwrap();
+    expect_true!(cache.restore(&key, &[&output]));
+    expect_eq!(fs::read_to_string(&output).unwrap(), "pub struct Foo {}");
+    expect_eq!(
+        cache.stats().unwrap(),
+        CacheStats { hits: 1, misses: 1, entries: 1, size_bytes: "pub struct Foo {}".len() as u64 }
+    );
+}
+
+#[gtest]
+fn test_discovered_input_changes_miss() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = OutputCache::new(dir.path().join("cache"));
+    let header = dir.path().join("foo.h");
+    let output = dir.path().join("foo_rust_api.rs");
+    let key = key(&["--public_headers=foo.h"]);
+    fs::write(&header, "struct Foo {};").unwrap();
+    fs::write(&output, "pub struct Foo {}").unwrap();
+    cache.store(&key, &[header.clone()], &[&output]).unwrap();
+
+    fs::write(&header, "struct Bar {};").unwrap();
+    expect_false!(cache.restore(&key, &[&output]));
+
+    // Changing the header back finds the original outputs again.
+    fs::write(&header, "struct Foo {};").unwrap();
+    fs::write(&output, "").unwrap();
+    expect_true!(cache.restore(&key, &[&output]));
+    expect_eq!(fs::read_to_string(&output).unwrap(), "pub struct Foo {}");
+}
+
+#[gtest]
+fn test_multiple_outputs() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = OutputCache::new(dir.path().join("cache"));
+    let outputs: Vec<PathBuf> =
+        ["foo.h", "foo.rs"].iter().map(|name| dir.path().join(name)).collect();
+    let outputs: Vec<&Path> = outputs.iter().map(PathBuf::as_path).collect();
+    fs::write(outputs[0], "header").unwrap();
+    fs::write(outputs[1], "source").unwrap();
+    let key = key(&[]);
+    cache.store(&key, &[], &outputs).unwrap();
+
+    fs::write(outputs[0], "").unwrap();
+    fs::write(outputs[1], "").unwrap();
+    expect_true!(cache.restore(&key, &outputs));
+    expect_eq!(fs::read_to_string(outputs[0]).unwrap(), "header");
+    expect_eq!(fs::read_to_string(outputs[1]).unwrap(), "source");
+}
+
+#[gtest]
+fn test_stats_of_empty_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let stats = OutputCache::new(dir.path().join("cache")).stats().unwrap();
+    expect_eq!(stats, CacheStats::default());
+    expect_eq!(
+        stats.to_string(),
+        "Crubit cache: 0 hits, 0 misses (0.0% hit rate), 0 entries, 0 bytes"
+    );
+}
+
+#[gtest]
+fn test_parse_depfile() {
+    let depfile = "rs_api_header.h: foo/foo.h \\\n  foo/bar\\ baz.h  /usr/include/a$$b.h \\\r\n\
+                   C:\\include\\win.h third_party/c\\#.h\n\nfoo/foo.h:\n";
+    expect_eq!(
+        output_cache::parse_depfile(depfile),
+        [
+            PathBuf::from("foo/foo.h"),
+            PathBuf::from("foo/bar baz.h"),
+            PathBuf::from("/usr/include/a$b.h"),
+            PathBuf::from("C:\\include\\win.h"),
+            PathBuf::from("third_party/c#.h"),
+        ]
+    );
+}
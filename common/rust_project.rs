@@ -0,0 +1,335 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Describes the crates of a build, including Crubit's generated binding crates, as a
+//! `rust-project.json` file for rust-analyzer.
+//!
+//! The generated `..._rust_api.rs` and `..._cc_api_impl.rs` crates live in the output tree of the
+//! build, and rust-analyzer can't discover them (or the Crubit support crates they depend on,
+//! like `ctor`, `forward_declare`, `oops`, and the `cc_std` bindings) on its own. Instead, their
+//! crates are reconstructed from the rustc invocations that compile them: the crate root, edition,
+//! `--cfg`s (which include the enabled crate features) and `--extern` dependencies.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env::consts::{DLL_EXTENSION, DLL_PREFIX};
+
+/// A single rustc invocation, parsed from its command line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RustcInvocation {
+    pub crate_name: String,
+    pub root_module: String,
+    pub edition: String,
+    pub crate_type: String,
+    /// The `--cfg` options, in the form used by `rust-project.json`, e.g. `feature="unstable"`.
+    pub cfgs: Vec<String>,
+    /// The `--extern` dependencies, as (name, path) pairs.
+    pub externs: Vec<(String, String)>,
+    /// The paths of the crate's compiled outputs (`.rlib`, `.rmeta`, or `.so`).
+    pub outputs: Vec<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+/// rustc options that take a value, which may be passed as a separate argument.
+const OPTIONS_WITH_VALUES: &[&str] = &[
+    "--cap-lints",
+    "--cfg",
+    "--check-cfg",
+    "--codegen",
+    "--color",
+    "--crate-name",
+    "--crate-type",
+    "--edition",
+    "--emit",
+    "--error-format",
+    "--explain",
+    "--extern",
+    "--json",
+    "--out-dir",
+    "--print",
+    "--remap-path-prefix",
+    "--sysroot",
+    "--target",
+    "-A",
+    "-C",
+    "-D",
+    "-F",
+    "-L",
+    "-W",
+    "-Z",
+    "-l",
+    "-o",
+];
+
+impl RustcInvocation {
+    /// Parses the command line of a rustc invocation. Arguments before the first `--` are
+    /// skipped if there is one, as they belong to a wrapper like Bazel's `process_wrapper`.
+    ///
+    /// Returns `None` if the command line doesn't compile a crate root.
+    pub fn parse(args: &[String]) -> Option<Self> {
+        let args = match args.iter().position(|arg| arg == "--") {
+            Some(separator) => &args[separator + 1..],
+            None => args,
+        };
+        let mut invocation = RustcInvocation::default();
+        let mut emits = Vec::<String>::new();
+        let mut out_dir = None;
+        let mut extra_filename = String::new();
+        let mut args = args.iter().map(|arg| arg.strip_prefix("${pwd}/").unwrap_or(arg));
+        while let Some(arg) = args.next() {
+            let (option, value) = match arg.split_once('=') {
+                Some((option, value)) if option.starts_with("--") => (option, value.to_string()),
+                _ if OPTIONS_WITH_VALUES.contains(&arg) => match args.next() {
+                    Some(value) => (arg, value.to_string()),
+                    None => break,
+                },
+                // Short options may also be followed directly by their value, as in `-Copt-level=0`.
+                _ if !arg.starts_with("--")
+                    && arg.len() > 2
+                    && arg.get(..2).is_some_and(|short| OPTIONS_WITH_VALUES.contains(&short)) =>
+                {
+                    (&arg[..2], arg[2..].to_string())
+                }
+                _ => {
+                    if arg.ends_with(".rs") && invocation.root_module.is_empty() {
+                        invocation.root_module = arg.to_string();
+                    }
+                    continue;
+                }
+            };
+            match option {
+                "--crate-name" => invocation.crate_name = value,
+                "--crate-type" => invocation.crate_type = value,
+                "--edition" => invocation.edition = value,
+                "--cfg" => invocation.cfgs.push(value),
+                "--extern" => {
+                    if let Some((name, path)) = value.split_once('=') {
+                        let path = path.strip_prefix("${pwd}/").unwrap_or(path);
+                        invocation.externs.push((name.to_string(), path.to_string()));
+                    }
+                }
+                "--emit" => emits.extend(value.split(',').map(str::to_string)),
+                "--out-dir" => out_dir = Some(value),
+                "-o" => invocation.outputs.push(value),
+                "-C" | "--codegen" => {
+                    if let Some(extra) = value.strip_prefix("extra-filename=") {
+                        extra_filename = extra.to_string();
+                    }
+                }
+                _ => {}
+            }
+        }
+        if invocation.root_module.is_empty() {
+            return None;
+        }
+        if invocation.crate_name.is_empty() {
+            let file_name = invocation.root_module.rsplit('/').next().unwrap_or_default();
+            invocation.crate_name = file_name.trim_end_matches(".rs").replace('-', "_");
+        }
+        if invocation.edition.is_empty() {
+            invocation.edition = "2015".to_string();
+        }
+        if invocation.crate_type.is_empty() {
+            invocation.crate_type = "bin".to_string();
+        }
+        for emit in emits {
+            match emit.split_once('=') {
+                Some(("link" | "metadata", path)) => invocation.outputs.push(path.to_string()),
+                None if emit == "link" || emit == "metadata" => {
+                    let Some(out_dir) = &out_dir else { continue };
+                    let extension = match (emit.as_str(), invocation.crate_type.as_str()) {
+                        ("metadata", _) => "rmeta",
+                        (_, "proc-macro") => DLL_EXTENSION,
+                        _ => "rlib",
+                    };
+                    let prefix = if extension == DLL_EXTENSION { DLL_PREFIX } else { "lib" };
+                    invocation.outputs.push(format!(
+                        "{out_dir}/{prefix}{}{extra_filename}.{extension}",
+                        invocation.crate_name
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Some(invocation)
+    }
+
+    pub fn is_proc_macro(&self) -> bool {
+        self.crate_type == "proc-macro"
+    }
+}
+
+#[derive(Deserialize)]
+struct AqueryOutput {
+    #[serde(default)]
+    actions: Vec<AqueryAction>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AqueryAction {
+    mnemonic: String,
+    #[serde(default)]
+    arguments: Vec<String>,
+    #[serde(default)]
+    environment_variables: Vec<AqueryKeyValue>,
+}
+
+#[derive(Deserialize)]
+struct AqueryKeyValue {
+    key: String,
+    #[serde(default)]
+    value: String,
+}
+
+/// Returns the rustc invocations of the output of `bazel aquery --output=jsonproto`.
+pub fn invocations_from_aquery(json: &str) -> Result<Vec<RustcInvocation>> {
+    let aquery: AqueryOutput =
+        serde_json::from_str(json).context("Failed to parse `bazel aquery` output")?;
+    Ok(aquery
+        .actions
+        .into_iter()
+        .filter(|action| matches!(action.mnemonic.as_str(), "Rustc" | "RustcMetadata"))
+        .filter_map(|action| {
+            let mut invocation = RustcInvocation::parse(&action.arguments)?;
+            invocation.env = action
+                .environment_variables
+                .into_iter()
+                .map(|variable| (variable.key, variable.value))
+                .collect();
+            Some(invocation)
+        })
+        .collect())
+}
+
+/// A `rust-project.json` file.
+#[derive(Debug, Serialize)]
+pub struct ProjectJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sysroot: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sysroot_src: Option<String>,
+    pub crates: Vec<CrateJson>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CrateJson {
+    pub display_name: String,
+    pub root_module: String,
+    pub edition: String,
+    pub deps: Vec<DepJson>,
+    pub cfg: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    pub is_workspace_member: bool,
+    pub is_proc_macro: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proc_macro_dylib_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepJson {
+    #[serde(rename = "crate")]
+    pub krate: usize,
+    pub name: String,
+}
+
+/// Builds a `rust-project.json` file from rustc `invocations`.
+///
+/// Invocations which compile the same crate (e.g. the metadata and the full build of a pipelined
+/// compilation) are merged. Relative paths are resolved against `execroot`, the directory the
+/// invocations ran in. Crates in the output tree (`bazel-out/`) or in external repositories are
+/// not workspace members.
+///
+/// Returns the file, along with the `--extern`s that didn't match any crate.
+pub fn rust_project(
+    invocations: &[RustcInvocation],
+    execroot: &str,
+    sysroot: Option<&str>,
+    sysroot_src: Option<&str>,
+) -> (ProjectJson, Vec<String>) {
+    let mut crates = Vec::<RustcInvocation>::new();
+    let mut crate_indices = HashMap::<(&str, &str, Vec<&str>), usize>::new();
+    for invocation in invocations {
+        let mut cfgs: Vec<&str> = invocation.cfgs.iter().map(String::as_str).collect();
+        cfgs.sort_unstable();
+        let key = (invocation.root_module.as_str(), invocation.crate_name.as_str(), cfgs);
+        let index = *crate_indices.entry(key).or_insert_with(|| {
+            crates.push(RustcInvocation { outputs: vec![], ..invocation.clone() });
+            crates.len() - 1
+        });
+        crates[index].outputs.extend(invocation.outputs.iter().cloned());
+    }
+
+    let mut by_output = HashMap::<&str, usize>::new();
+    let mut by_name = HashMap::<&str, Vec<usize>>::new();
+    for (index, krate) in crates.iter().enumerate() {
+        for output in &krate.outputs {
+            by_output.insert(without_extension(output), index);
+        }
+        by_name.entry(&krate.crate_name).or_default().push(index);
+    }
+    let mut unresolved = vec![];
+    let resolve = |name: &str, path: &str| {
+        if let Some(index) = by_output.get(without_extension(path)) {
+            return Some(*index);
+        }
+        match by_name.get(name).map(Vec::as_slice) {
+            Some([index]) => Some(*index),
+            _ => None,
+        }
+    };
+
+    let path = |path: &str| {
+        if path.starts_with('/') || execroot.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{path}", execroot.trim_end_matches('/'))
+        }
+    };
+    let mut crate_jsons = vec![];
+    for krate in &crates {
+        let mut deps = vec![];
+        for (name, extern_path) in &krate.externs {
+            match resolve(name, extern_path) {
+                Some(index) => deps.push(DepJson { krate: index, name: name.clone() }),
+                None => {
+                    unresolved.push(format!("{}: --extern {name}={extern_path}", krate.crate_name))
+                }
+            }
+        }
+        let proc_macro_dylib_path = krate
+            .outputs
+            .iter()
+            .find(|output| krate.is_proc_macro() && output.ends_with(DLL_EXTENSION))
+            .map(|output| path(output));
+        crate_jsons.push(CrateJson {
+            display_name: krate.crate_name.clone(),
+            root_module: path(&krate.root_module),
+            edition: krate.edition.clone(),
+            deps,
+            cfg: krate.cfgs.clone(),
+            env: krate.env.clone(),
+            is_workspace_member: !krate.root_module.starts_with("bazel-out/")
+                && !krate.root_module.starts_with("external/"),
+            is_proc_macro: krate.is_proc_macro(),
+            proc_macro_dylib_path,
+        });
+    }
+    let project = ProjectJson {
+        sysroot: sysroot.map(str::to_string),
+        sysroot_src: sysroot_src.map(str::to_string),
+        crates: crate_jsons,
+    };
+    (project, unresolved)
+}
+
+/// Returns `path` without its extension, so that the `.rlib` and `.rmeta` outputs of a crate
+/// match each other.
+fn without_extension(path: &str) -> &str {
+    match path.rsplit_once('.') {
+        Some((stem, extension)) if !extension.contains('/') => stem,
+        _ => path,
+    }
+}
@@ -0,0 +1,188 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use googletest::prelude::*;
+use rust_project::{invocations_from_aquery, rust_project, RustcInvocation};
+use std::env::consts::{DLL_EXTENSION, DLL_PREFIX};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[gtest]
+fn test_parse_rustc_invocation() {
+    let invocation = RustcInvocation::parse(&args(&[
+        "process_wrapper",
+        "--subst",
+        "pwd=${pwd}",
+        "--",
+        "rustc",
+        "bazel-out/bin/foo/bar_rust_api.rs",
+        "--crate-name=bar",
+        "--crate-type=rlib",
+        "--edition",
+        "2021",
+        "--cfg=feature=\"unstable\"",
+        "--extern=ctor=bazel-out/bin/support/libctor-123.rlib",
+        "--extern",
+        "oops=${pwd}/bazel-out/bin/support/liboops-456.rmeta",
+        "-Copt-level=0",
+        "--codegen=extra-filename=-789",
+        "--out-dir=bazel-out/bin/foo",
+        "--emit=dep-info,link,metadata",
+        "-L",
+        "dependency=bazel-out/bin/support",
+    ]))
+    .unwrap();
+    expect_eq!(
+        invocation,
+        RustcInvocation {
+            crate_name: "bar".to_string(),
+            root_module: "bazel-out/bin/foo/bar_rust_api.rs".to_string(),
+            edition: "2021".to_string(),
+            crate_type: "rlib".to_string(),
+            cfgs: vec!["feature=\"unstable\"".to_string()],
+            externs: vec![
+                ("ctor".to_string(), "bazel-out/bin/support/libctor-123.rlib".to_string()),
+                ("oops".to_string(), "bazel-out/bin/support/liboops-456.rmeta".to_string()),
+            ],
+            outputs: vec![
+                "bazel-out/bin/foo/libbar-789.rlib".to_string(),
+                "bazel-out/bin/foo/libbar-789.rmeta".to_string(),
+            ],
+            env: Default::default(),
+        }
+    );
+}
+
+#[gtest]
+fn test_parse_rustc_invocation_defaults() {
+    let invocation =
+        RustcInvocation::parse(&args(&["rustc", "src/my-crate.rs", "-o", "out/my_crate"])).unwrap();
+    expect_eq!(invocation.crate_name, "my_crate");
+    expect_eq!(invocation.edition, "2015");
+    expect_eq!(invocation.crate_type, "bin");
+    expect_eq!(invocation.outputs, ["out/my_crate"]);
+}
+
+#[gtest]
+fn test_parse_without_crate_root() {
+    expect_that!(RustcInvocation::parse(&args(&["rustc", "--version"])), none());
+}
+
+const AQUERY: &str = r#"{
+  "actions": [
+    {
+      "mnemonic": "Rustc",
+      "arguments": ["rustc", "support/ctor.rs", "--crate-name=ctor", "--crate-type=rlib",
+                    "--edition=2021", "--out-dir=bazel-out/bin/support", "--emit=link",
+                    "--extern=ctor_proc_macros=bazel-out/bin/support/ctor_proc_macros.so"],
+      "environmentVariables": [{"key": "CARGO_PKG_NAME", "value": "ctor"}]
+    },
+    {
+      "mnemonic": "RustcMetadata",
+      "arguments": ["rustc", "support/ctor.rs", "--crate-name=ctor", "--crate-type=rlib",
+                    "--edition=2021", "--out-dir=bazel-out/bin/support", "--emit=metadata",
+                    "--extern=ctor_proc_macros=bazel-out/bin/support/ctor_proc_macros.so"]
+    },
+    {
+      "mnemonic": "Rustc",
+      "arguments": ["rustc", "support/ctor_proc_macros.rs", "--crate-name=ctor_proc_macros",
+                    "--crate-type=proc-macro", "--edition=2021",
+                    "-o", "bazel-out/bin/support/ctor_proc_macros.so"]
+    },
+    {
+      "mnemonic": "Rustc",
+      "arguments": ["rustc", "bazel-out/bin/foo/bar_rust_api.rs", "--crate-name=bar",
+                    "--crate-type=rlib", "--edition=2021",
+                    "--extern=ctor=bazel-out/bin/support/libctor.rmeta",
+                    "--extern=missing=bazel-out/bin/libmissing.rlib"]
+    },
+    {
+      "mnemonic": "Rustc",
+      "arguments": ["rustc", "foo/main.rs", "--crate-name=main", "--edition=2021",
+                    "--extern=bar=/abs/libbar.rlib"]
+    },
+    {
+      "mnemonic": "Clippy",
+      "arguments": ["clippy-driver", "foo/main.rs", "--crate-name=main"]
+    }
+  ]
+}"#;
+
+#[gtest]
+fn test_rust_project_from_aquery() {
+    let invocations = invocations_from_aquery(AQUERY).unwrap();
+    expect_that!(invocations, len(eq(5)));
+    let (project, unresolved) = rust_project(
+        &invocations,
+        "/execroot/",
+        None,
+        Some("/sysroot/lib/rustlib/src/rust/library"),
+    );
+    expect_eq!(unresolved, ["bar: --extern missing=bazel-out/bin/libmissing.rlib"]);
+    let json = serde_json::to_value(&project).unwrap();
+    expect_eq!(
+        json,
+        serde_json::json!({
+            "sysroot_src": "/sysroot/lib/rustlib/src/rust/library",
+            "crates": [
+                {
+                    "display_name": "ctor",
+                    "root_module": "/execroot/support/ctor.rs",
+                    "edition": "2021",
+                    "deps": [{"crate": 1, "name": "ctor_proc_macros"}],
+                    "cfg": [],
+                    "env": {"CARGO_PKG_NAME": "ctor"},
+                    "is_workspace_member": true,
+                    "is_proc_macro": false,
+                },
+                {
+                    "display_name": "ctor_proc_macros",
+                    "root_module": "/execroot/support/ctor_proc_macros.rs",
+                    "edition": "2021",
+                    "deps": [],
+                    "cfg": [],
+                    "env": {},
+                    "is_workspace_member": true,
+                    "is_proc_macro": true,
+                    "proc_macro_dylib_path": "/execroot/bazel-out/bin/support/ctor_proc_macros.so",
+                },
+                {
+                    "display_name": "bar",
+                    "root_module": "/execroot/bazel-out/bin/foo/bar_rust_api.rs",
+                    "edition": "2021",
+                    "deps": [{"crate": 0, "name": "ctor"}],
+                    "cfg": [],
+                    "env": {},
+                    "is_workspace_member": false,
+                    "is_proc_macro": false,
+                },
+                {
+                    "display_name": "main",
+                    "root_module": "/execroot/foo/main.rs",
+                    "edition": "2021",
+                    "deps": [{"crate": 2, "name": "bar"}],
+                    "cfg": [],
+                    "env": {},
+                    "is_workspace_member": true,
+                    "is_proc_macro": false,
+                },
+            ],
+        })
+    );
+}
+
+#[gtest]
+fn test_proc_macro_out_dir() {
+    let invocation = RustcInvocation::parse(&args(&[
+        "rustc",
+        "support/ctor_proc_macros.rs",
+        "--crate-type=proc-macro",
+        "--out-dir=out",
+        "--emit=link",
+    ]))
+    .unwrap();
+    expect_eq!(invocation.outputs, [format!("out/{DLL_PREFIX}ctor_proc_macros.{DLL_EXTENSION}")]);
+}
@@ -347,6 +347,12 @@ bool IsProto2Message(const clang::Decl& decl) {
   });
 }
 
+bool IsAnonymousNestedRecord(const clang::RecordDecl& record_decl) {
+  return record_decl.getName().empty() &&
+         record_decl.getTypedefNameForAnonDecl() == nullptr &&
+         clang::isa<clang::RecordDecl>(record_decl.getDeclContext());
+}
+
 const clang::TagDecl* StripCStyleNameIntroducingTypedef(
     const clang::TypedefNameDecl* alias_decl) {
   if (alias_decl == nullptr) {
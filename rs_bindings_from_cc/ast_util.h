@@ -108,6 +108,18 @@ absl::StatusOr<CallingConv> ConvertCcCallConvToSupportedCallingConv(
 // message.
 bool IsProto2Message(const clang::Decl& decl);
 
+// Returns true if `record_decl` is a struct or union that has neither a name
+// nor a typedef name for linkage purposes, and is nested directly in another
+// record. For example, both unions below are anonymous nested records:
+//
+// ```c++
+// struct S {
+//   union { int i; float f; };     // An anonymous member.
+//   union { int i; float f; } u;   // A named field of an unnamed type.
+// };
+// ```
+bool IsAnonymousNestedRecord(const clang::RecordDecl& record_decl);
+
 // If `alias_decl` is a C-style name-introducing typedef (e.g.,
 // `typedef struct Foo Foo;` or `typedef struct { ... } Foo;`), returns the
 // underlying `TagDecl` representing the C type. Otherwise, returns `nullptr`.
@@ -131,6 +131,10 @@ ABSL_FLAG(bool, kythe_annotations, false,
 
 ABSL_FLAG(std::string, kythe_default_corpus, "corpus",
           "Default corpus to use for Kythe vnames.");
+ABSL_FLAG(std::string, lsif_out, "",
+          "(optional) output path for an LSIF index linking the generated "
+          "Rust items to the C++ declarations they were generated from. "
+          "Requires --kythe_annotations.");
 ABSL_FLAG(std::string, template_blocklist_path_regex, "",
           "If nonempty, do not instantiate templates defined in files with "
           "paths matching this llvm::Regex.");
@@ -278,6 +282,7 @@ absl::StatusOr<Cmdline> Cmdline::FromFlags() {
       .is_golden_test = absl::GetFlag(FLAGS_is_golden_test),
       .kythe_annotations = absl::GetFlag(FLAGS_kythe_annotations),
       .kythe_default_corpus = absl::GetFlag(FLAGS_kythe_default_corpus),
+      .lsif_out = absl::GetFlag(FLAGS_lsif_out),
       .public_headers = PublicHeaders(),
       .extra_rs_srcs = absl::GetFlag(FLAGS_extra_rs_srcs),
       .extra_cpp_srcs = absl::GetFlag(FLAGS_extra_cpp_srcs),
@@ -333,6 +338,9 @@ absl::StatusOr<Cmdline> Cmdline::Create(CmdlineArgs args) {
         "please specify both --rust_sources and --instantiations_out when "
         "requesting a template instantiation mode\n");
   }
+  if (!args.lsif_out.empty() && !args.kythe_annotations) {
+    absl::StrAppend(&error, "--lsif_out requires --kythe_annotations\n");
+  }
   for (const HeaderName& header : args.public_headers) {
     if (auto it = args.headers_to_targets.find(header);
         it == args.headers_to_targets.end()) {
@@ -40,6 +40,7 @@ struct CmdlineArgs {
   bool is_golden_test = false;
   bool kythe_annotations = false;
   std::string kythe_default_corpus;
+  std::string lsif_out;
 
   std::vector<HeaderName> public_headers;
   absl::flat_hash_map<HeaderName, BazelLabel> headers_to_targets;
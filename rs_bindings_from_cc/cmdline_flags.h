@@ -31,6 +31,8 @@ ABSL_DECLARE_FLAG(std::string, instantiations_out);
 ABSL_DECLARE_FLAG(std::string, namespaces_out);
 ABSL_DECLARE_FLAG(std::string, error_report_out);
 ABSL_DECLARE_FLAG(bool, is_golden_test);
+ABSL_DECLARE_FLAG(bool, kythe_annotations);
+ABSL_DECLARE_FLAG(std::string, lsif_out);
 
 namespace crubit {
 
@@ -95,6 +95,8 @@ TEST(CmdlineTest, BasicCorrectInput) {
   absl::SetFlag(&FLAGS_namespaces_out, "namespaces_out");
   absl::SetFlag(&FLAGS_error_report_out, "error_report_out");
   absl::SetFlag(&FLAGS_is_golden_test, true);
+  absl::SetFlag(&FLAGS_kythe_annotations, true);
+  absl::SetFlag(&FLAGS_lsif_out, "lsif_out");
   ASSERT_OK_AND_ASSIGN(Cmdline cmdline, Cmdline::FromFlags());
   const CmdlineArgs& args = cmdline.args();
   EXPECT_EQ(args.cc_out, "cc_out");
@@ -109,6 +111,8 @@ TEST(CmdlineTest, BasicCorrectInput) {
   EXPECT_EQ(args.rustfmt_config_path, "rustfmt_config_path");
   EXPECT_EQ(args.instantiations_out, "instantiations_out");
   EXPECT_EQ(args.error_report_out, "error_report_out");
+  EXPECT_EQ(args.kythe_annotations, true);
+  EXPECT_EQ(args.lsif_out, "lsif_out");
   EXPECT_EQ(args.do_nothing, false);
   EXPECT_EQ(args.current_target.value(), "//:t1");
   EXPECT_THAT(args.public_headers, ElementsAre(HeaderName("h1")));
@@ -319,6 +323,14 @@ TEST(CmdlineTest, RustSourcesEmpty) {
               "when requesting a template instantiation mode")));
 }
 
+TEST(CmdlineTest, LsifOutWithoutKytheAnnotations) {
+  ASSERT_OK_AND_ASSIGN(CmdlineArgs args, TestCmdlineArgs());
+  args.lsif_out = "lsif_out";
+  EXPECT_THAT(Cmdline::Create(std::move(args)),
+              StatusIs(absl::StatusCode::kInvalidArgument,
+                       HasSubstr("--lsif_out requires --kythe_annotations")));
+}
+
 TEST(CmdlineTest, CcOutEmpty) {
   ASSERT_OK_AND_ASSIGN(CmdlineArgs args, TestCmdlineArgs());
   args.cc_out = "";
@@ -22,7 +22,7 @@ extern "C" crubit::FfiU8SliceBox CollectInstantiationsImpl(
 
 namespace crubit {
 
-absl::StatusOr<std::vector<std::string>> CollectInstantiations(
+absl::StatusOr<RequestedInstantiations> CollectInstantiations(
     absl::Span<const std::string> rust_sources) {
   llvm::json::Value rust_sources_json = llvm::json::Array(rust_sources);
   std::string json = llvm::formatv("{0}", rust_sources_json);
@@ -36,10 +36,14 @@ absl::StatusOr<std::vector<std::string>> CollectInstantiations(
 
   llvm::json::Value instantiations = *expected_instantiations;
   FreeFfiU8SliceBox(result);
-  std::vector<std::string> instantiations_vector;
+  RequestedInstantiations requested_instantiations;
   llvm::json::Path::Root root;
-  if (llvm::json::fromJSON(instantiations, instantiations_vector, root)) {
-    return instantiations_vector;
+  llvm::json::ObjectMapper mapper(instantiations, root);
+  if (mapper &&
+      mapper.map("class_templates", requested_instantiations.class_templates) &&
+      mapper.map("function_templates",
+                 requested_instantiations.function_templates)) {
+    return requested_instantiations;
   }
   return absl::InternalError(llvm::toString(root.getError()));
 }
@@ -13,10 +13,20 @@
 
 namespace crubit {
 
-// Parses Rust source files given their filenames and returns a vector with all
-// C++ class template instantiations requested by calls to the `cc_template!`
-// macro.
-absl::StatusOr<std::vector<std::string>> CollectInstantiations(
+// C++ template instantiations requested from Rust source files.
+struct RequestedInstantiations {
+  // Class template instantiations requested by calls to the `cc_template!`
+  // macro.
+  std::vector<std::string> class_templates;
+  // Function template instantiations requested by calls to the
+  // `cc_template_fn!` macro.
+  std::vector<std::string> function_templates;
+};
+
+// Parses Rust source files given their filenames and returns all C++ template
+// instantiations requested by calls to the `cc_template!` and
+// `cc_template_fn!` macros.
+absl::StatusOr<RequestedInstantiations> CollectInstantiations(
     absl::Span<const std::string> rust_sources);
 
 }  // namespace crubit
@@ -13,8 +13,11 @@ use std::panic::catch_unwind;
 use std::path::PathBuf;
 use std::process;
 
-/// Parses given files and returns a Json list with all  C++ class
-/// template instantiations requested by calls to the `cc_template!` macro.
+/// Parses given files and returns a Json object with all C++ class template
+/// instantiations requested by calls to the `cc_template!` macro (under the
+/// `"class_templates"` key) and all C++ function template instantiations
+/// requested by calls to the `cc_template_fn!` macro (under the
+/// `"function_templates"` key).
 ///
 /// This function panics on error.
 ///
@@ -39,54 +42,86 @@ pub unsafe extern "C" fn CollectInstantiationsImpl(json: FfiU8Slice) -> FfiU8Sli
             })
             .unwrap();
         let instantiations = collect_instantiations_impl(filenames).unwrap();
-        let result_json = serde_json::to_string(&instantiations).unwrap();
+        let result_json = serde_json::json!({
+            "class_templates": instantiations.class_templates,
+            "function_templates": instantiations.function_templates,
+        })
+        .to_string();
         FfiU8SliceBox::from_boxed_slice(result_json.into_bytes().into_boxed_slice())
     })
     .unwrap_or_else(|_| process::abort())
 }
 
-fn collect_instantiations_impl(filenames: Vec<PathBuf>) -> Result<Vec<String>> {
-    let mut result = HashSet::<String>::new();
+/// Template instantiations requested from Rust, each list sorted and free of
+/// duplicates.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Instantiations {
+    /// Class template instantiations requested by `cc_template!`.
+    class_templates: Vec<String>,
+    /// Function template instantiations requested by `cc_template_fn!`.
+    function_templates: Vec<String>,
+}
+
+fn collect_instantiations_impl(filenames: Vec<PathBuf>) -> Result<Instantiations> {
+    let mut class_templates = HashSet::<String>::new();
+    let mut function_templates = HashSet::<String>::new();
     for filename in filenames {
         let content = fs::read_to_string(&filename)
             .with_context(|| format!("Couldn't read '{}'", filename.display()))?;
         let token_stream = syn::parse_str(&content)
             .with_context(|| format!("Couldn't parse the file '{}'", filename.display()))?;
-        find_cc_template_calls(token_stream, &mut result);
+        find_cc_template_calls(token_stream, &mut class_templates, &mut function_templates);
     }
-    let mut result_vec = result.into_iter().collect::<Vec<_>>();
-    result_vec.sort();
-    Ok(result_vec)
+    let into_sorted_vec = |set: HashSet<String>| {
+        let mut result_vec = set.into_iter().collect::<Vec<_>>();
+        result_vec.sort();
+        result_vec
+    };
+    Ok(Instantiations {
+        class_templates: into_sorted_vec(class_templates),
+        function_templates: into_sorted_vec(function_templates),
+    })
 }
 
-fn find_cc_template_calls(input: TokenStream, results: &mut HashSet<String>) {
+fn find_cc_template_calls(
+    input: TokenStream,
+    class_templates: &mut HashSet<String>,
+    function_templates: &mut HashSet<String>,
+) {
     let mut iter = input.into_iter();
     while let Some(next) = iter.next() {
         // 3 token trees starting at the current 'next' ('cc_template', '!', 'group with
         // the macro body').
         let macro_tokens = std::iter::once(next.clone()).chain(iter.clone().take(2)).collect();
-        if let Ok(m) = syn::parse2::<syn::Macro>(macro_tokens)
-            && m.path.is_ident("cc_template")
-        {
-            // In theory `TokenStream` -> `instantiation_name` translation could go through
-            // `token_stream_printer::tokens_to_string`.  This route is not used because:
-            // - The dependencies it would bring would run into b/216638047
-            // - Extra functionality from that route is not needed (e.g. no need for
-            //   `__COMMENT__`-aware or `__SPACE__`-aware processing, nor for special
-            //   handling of `TokenTree::Group`).
-            //
-            // TODO(lukasza, hlopko): In the future, extra canonicalization might be
-            // considered, so that `std::vector<int>`, and `std::vector<(int)>`, and
-            // `std::vector<int32_t>` are treated as equivalent.
-            //
-            // TODO(lukasza, hlopko): More explicitly ensure that the same canonicalization
-            // (e.g. TokenStream->String transformation) is used here and in
-            // `cc_template/cc_template_impl.rs`.
-            let instantiation_name = m.tokens.to_string().replace(' ', "");
-            results.insert(instantiation_name);
+        if let Ok(m) = syn::parse2::<syn::Macro>(macro_tokens) {
+            let results = if m.path.is_ident("cc_template") {
+                Some(&mut *class_templates)
+            } else if m.path.is_ident("cc_template_fn") {
+                Some(&mut *function_templates)
+            } else {
+                None
+            };
+            if let Some(results) = results {
+                // In theory `TokenStream` -> `instantiation_name` translation could go through
+                // `token_stream_printer::tokens_to_string`.  This route is not used because:
+                // - The dependencies it would bring would run into b/216638047
+                // - Extra functionality from that route is not needed (e.g. no need for
+                //   `__COMMENT__`-aware or `__SPACE__`-aware processing, nor for special
+                //   handling of `TokenTree::Group`).
+                //
+                // TODO(lukasza, hlopko): In the future, extra canonicalization might be
+                // considered, so that `std::vector<int>`, and `std::vector<(int)>`, and
+                // `std::vector<int32_t>` are treated as equivalent.
+                //
+                // TODO(lukasza, hlopko): More explicitly ensure that the same canonicalization
+                // (e.g. TokenStream->String transformation) is used here and in
+                // `cc_template/cc_template_impl.rs`.
+                let instantiation_name = m.tokens.to_string().replace(' ', "");
+                results.insert(instantiation_name);
+            }
         }
         if let TokenTree::Group(group) = next {
-            find_cc_template_calls(group.stream(), results);
+            find_cc_template_calls(group.stream(), class_templates, function_templates);
         }
     }
 }
@@ -100,7 +135,7 @@ mod tests {
 
     #[gtest]
     fn test_noop() {
-        assert!(collect_instantiations_impl(vec![]).unwrap().is_empty());
+        assert_eq!(collect_instantiations_impl(vec![]).unwrap(), Instantiations::default());
     }
 
     #[gtest]
@@ -119,7 +154,7 @@ mod tests {
         result_file
     }
 
-    fn write_file_and_collect_instantiations(input: TokenStream) -> Result<Vec<String>> {
+    fn write_file_and_collect_instantiations(input: TokenStream) -> Result<Instantiations> {
         let file = make_tmp_input_file("file", &input.to_string());
         collect_instantiations_impl(vec![file])
     }
@@ -138,7 +173,8 @@ mod tests {
     fn test_single_template_parens() {
         let result =
             write_file_and_collect_instantiations(quote! { cc_template!(MyTemplate<int>) })
-                .unwrap();
+                .unwrap()
+                .class_templates;
         assert_eq!(result, vec!["MyTemplate<int>".to_string()]);
     }
 
@@ -146,7 +182,8 @@ mod tests {
     fn test_single_template_brackets() {
         let result =
             write_file_and_collect_instantiations(quote! { cc_template![MyTemplate<int>] })
-                .unwrap();
+                .unwrap()
+                .class_templates;
         assert_eq!(result, vec!["MyTemplate<int>".to_string()]);
     }
 
@@ -154,7 +191,8 @@ mod tests {
     fn test_single_template_curlies() {
         let result =
             write_file_and_collect_instantiations(quote! { cc_template!{MyTemplate<int>} })
-                .unwrap();
+                .unwrap()
+                .class_templates;
         assert_eq!(result, vec!["MyTemplate<int>".to_string()]);
     }
 
@@ -165,7 +203,8 @@ mod tests {
             cc_template!{MyTemplate<int>};
             cc_template![MyTemplate<long>];
         })
-        .unwrap();
+        .unwrap()
+        .class_templates;
         assert_eq!(
             result,
             vec![
@@ -184,7 +223,8 @@ mod tests {
                 <cc_template!(MyTemplate<42>)>::new()
             }
         })
-        .unwrap();
+        .unwrap()
+        .class_templates;
         assert_eq!(
             result,
             vec![
@@ -203,10 +243,33 @@ mod tests {
                 <cc_template!(std::vector<Foo>)>::new()
             }
         })
-        .unwrap();
+        .unwrap()
+        .class_templates;
         assert_eq!(result, vec!["std::vector<Foo>".to_string(),]);
     }
 
+    #[gtest]
+    fn test_function_template_instantiations() {
+        let result = write_file_and_collect_instantiations(quote! {
+            fn my_rust_func(input: cc_template!(std::vector<Foo>)) -> String {
+                let s = cc_template_fn!(absl::StrCat<int, std::string>)(&input);
+                cc_template_fn![absl::StrJoin<std::vector<Foo>>](&input, ",");
+                cc_template_fn!{absl::StrCat<int, std::string>}(&input)
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            result,
+            Instantiations {
+                class_templates: vec!["std::vector<Foo>".to_string()],
+                function_templates: vec![
+                    "absl::StrCat<int,std::string>".to_string(),
+                    "absl::StrJoin<std::vector<Foo>>".to_string(),
+                ],
+            }
+        );
+    }
+
     fn collect_instantiations_from_json(json: &str) -> String {
         let u8_slice = unsafe {
             CollectInstantiationsImpl(FfiU8Slice::from_slice(json.as_bytes())).into_boxed_slice()
@@ -218,11 +281,13 @@ mod tests {
     fn test_collect_instantiations_json() {
         let filename = make_tmp_input_file(
             "json",
-            "cc_template!(std::vector<int>); cc_template!(std::vector<bool>);",
+            "cc_template!(std::vector<int>); cc_template!(std::vector<bool>); \
+             cc_template_fn!(std::max<int>);",
         );
         assert_eq!(
             collect_instantiations_from_json(&format!("[\"{}\"]", filename.display())),
-            "[\"std::vector<bool>\",\"std::vector<int>\"]"
+            "{\"class_templates\":[\"std::vector<bool>\",\"std::vector<int>\"],\
+             \"function_templates\":[\"std::max<int>\"]}"
         );
     }
 }
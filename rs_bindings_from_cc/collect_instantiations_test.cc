@@ -15,13 +15,19 @@
 namespace crubit {
 namespace {
 
+using ::testing::AllOf;
 using ::testing::ElementsAre;
+using ::testing::Field;
 using ::testing::IsEmpty;
 using ::testing::StrEq;
 
 // A minimal test showing that C++ and Rust link and talk to each other.
 TEST(CollectInstantiationsTest, EmptyRustFileReturnsEmptyCollectionTest) {
-  EXPECT_THAT(CollectInstantiations({}), IsOkAndHolds(IsEmpty()));
+  EXPECT_THAT(
+      CollectInstantiations({}),
+      IsOkAndHolds(AllOf(
+          Field(&RequestedInstantiations::class_templates, IsEmpty()),
+          Field(&RequestedInstantiations::function_templates, IsEmpty()))));
 }
 
 // A minimal test showing that C++ and Rust link and talk to each other.
@@ -29,7 +35,19 @@ TEST(CollectInstantiationsTest, ReturnInstantiationsFromRustTest) {
   std::string path =
       WriteFileForCurrentTest("a.rs", "cc_template!(std::vector<bool>);");
   EXPECT_THAT(CollectInstantiations({std::move(path)}),
-              IsOkAndHolds(ElementsAre(StrEq("std::vector<bool>"))));
+              IsOkAndHolds(Field(&RequestedInstantiations::class_templates,
+                                 ElementsAre(StrEq("std::vector<bool>")))));
+}
+
+TEST(CollectInstantiationsTest, ReturnFunctionInstantiationsFromRustTest) {
+  std::string path = WriteFileForCurrentTest(
+      "a.rs", "cc_template_fn!(absl::StrCat<int, std::string>);");
+  EXPECT_THAT(
+      CollectInstantiations({std::move(path)}),
+      IsOkAndHolds(AllOf(
+          Field(&RequestedInstantiations::class_templates, IsEmpty()),
+          Field(&RequestedInstantiations::function_templates,
+                ElementsAre(StrEq("absl::StrCat<int,std::string>"))))));
 }
 
 }  // namespace
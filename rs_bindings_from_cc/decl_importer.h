@@ -269,6 +269,19 @@ class ImportContext {
   GetUnsupportedItemPathForTemplateDecl(
       clang::RedeclarableTemplateDecl* absl_nonnull template_decl) = 0;
 
+  // Imports a function template specialization requested by `cc_template_fn!`
+  // (see support/cc_template/cc_template.rs). `spelling` is the argument of
+  // the macro. Like class template specializations, the specialization is
+  // owned by the current target and emitted as a top-level item.
+  virtual void ImportFunctionTemplateInstantiation(
+      clang::FunctionDecl* absl_nonnull function_decl,
+      std::string spelling) = 0;
+
+  // Returns the `cc_template_fn!` argument that requested `function_decl`, or
+  // `std::nullopt` if `function_decl` wasn't requested by `cc_template_fn!`.
+  virtual std::optional<absl::string_view> GetFunctionTemplateInstantiation(
+      const clang::FunctionDecl& function_decl) const = 0;
+
   // Returns the label of the target that contains a decl.
   virtual BazelLabel GetOwningTarget(const clang::Decl& decl) const = 0;
 
@@ -288,26 +301,24 @@ class ImportContext {
   // Returns true iff `label` has opted in to crubit support.
   virtual bool IsCrubitEnabledForTarget(const BazelLabel& label) const = 0;
 
-  // Returns true iff lifetime annotations in `label` should be recorded as
+  // Returns true iff lifetime annotations in `decl` should be recorded as
   // raw in the IR for later processing.
-  virtual bool AreAssumedLifetimesEnabledForTarget(
-      const BazelLabel& label) const = 0;
+  virtual bool AreAssumedLifetimesEnabledForDecl(
+      const clang::Decl& decl) const = 0;
 
-  // Returns true iff `label` has opted in to marking classes with
+  // Returns true iff `decl` has opted in to marking classes with
   // `[[gsl::Pointer]]` as unsafe.
-  virtual bool IsUnsafeViewEnabledForTarget(const BazelLabel& label) const = 0;
+  virtual bool IsUnsafeViewEnabledForDecl(const clang::Decl& decl) const = 0;
 
-  // Returns true iff `label` has opted in to generate `impl Debug` bindings for
+  // Returns true iff `decl` has opted in to generate `impl Debug` bindings for
   // records.
-  virtual bool IsRecordImplDebugEnabledForTarget(
-      const BazelLabel& label) const = 0;
-
-  virtual bool IsFeatureEnabledForTarget(const BazelLabel& label,
-                                         absl::string_view feature) const = 0;
+  virtual bool IsRecordImplDebugEnabledForDecl(
+      const clang::Decl& decl) const = 0;
 
-  bool IsFeatureEnabledForCurrentTarget(absl::string_view feature) {
-    return IsFeatureEnabledForTarget(invocation_.target_, feature);
-  }
+  // Returns true iff `feature` is enabled for `decl`, either by its owning
+  // target or by `CRUBIT_ENABLE_FEATURE` on `decl` or an enclosing item.
+  virtual bool IsFeatureEnabledForDecl(const clang::Decl& decl,
+                                       absl::string_view feature) const = 0;
 
   // Returns whether the given `decl`'s type has a detectable formatter.
   //
@@ -361,6 +372,12 @@ class ImportContext {
       clang::SourceLocation loc,
       clang::DeclarationNameInfo* absl_nullable name_info) const = 0;
 
+  // Converts a Clang source location to the position in the file where an edit
+  // to the declaration would be made, i.e. the expansion location for macros.
+  // Returns nullopt if the location isn't in a file.
+  virtual std::optional<ir_proto::FileLocation> ConvertFileLocation(
+      clang::SourceLocation loc) const = 0;
+
   // Converts the Clang type `qual_type` into an equivalent `CcType`.
   // Lifetimes for the type can optionally be specified using `lifetimes` (pass
   // null otherwise).
@@ -10,6 +10,7 @@
 #include "rs_bindings_from_cc/ast_consumer.h"
 #include "clang/AST/ASTConsumer.h"
 #include "clang/Frontend/CompilerInstance.h"
+#include "clang/Frontend/DependencyOutputOptions.h"
 #include "llvm/ADT/StringRef.h"
 
 namespace crubit {
@@ -21,4 +22,17 @@ std::unique_ptr<clang::ASTConsumer> FrontendAction::CreateASTConsumer(
   return std::make_unique<AstConsumer>(instance, invocation_);
 }
 
+bool FrontendAction::PrepareToExecuteAction(clang::CompilerInstance& instance) {
+  // This runs before the preprocessor is created, which is when Clang attaches
+  // the dependency file generator.
+  if (!dependency_file_.empty()) {
+    clang::DependencyOutputOptions& options =
+        instance.getDependencyOutputOpts();
+    options.OutputFile = dependency_file_;
+    options.Targets = {"rs_bindings_from_cc"};
+    options.IncludeSystemHeaders = true;
+  }
+  return true;
+}
+
 }  // namespace crubit
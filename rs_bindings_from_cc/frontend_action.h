@@ -6,6 +6,8 @@
 #define CRUBIT_RS_BINDINGS_FROM_CC_FRONTEND_ACTION_H_
 
 #include <memory>
+#include <string>
+#include <utility>
 
 #include "rs_bindings_from_cc/decl_importer.h"
 #include "clang/AST/ASTConsumer.h"
@@ -17,15 +19,24 @@ namespace crubit {
 
 // Creates an `ASTConsumer` that generates the intermediate representation
 // (`IR`) into the invocation object.
+//
+// If `dependency_file` is nonempty, a Make-style dependency file listing the
+// headers that were read is written to it, as with Clang's `-MD -MF` flags.
 class FrontendAction : public clang::ASTFrontendAction {
  public:
-  explicit FrontendAction(Invocation& invocation) : invocation_(invocation) {}
+  explicit FrontendAction(Invocation& invocation,
+                          std::string dependency_file = "")
+      : invocation_(invocation), dependency_file_(std::move(dependency_file)) {}
 
   std::unique_ptr<clang::ASTConsumer> CreateASTConsumer(
       clang::CompilerInstance& instance, llvm::StringRef) override;
 
+ protected:
+  bool PrepareToExecuteAction(clang::CompilerInstance& instance) override;
+
  private:
   Invocation& invocation_;
+  std::string dependency_file_;
 };
 
 }  // namespace crubit
@@ -51,6 +51,8 @@ pub fn generate_bindings(
         .kythe_default_corpus()
         .to_str()
         .expect("kythe_default_corpus is not valid UTF-8");
+    let lsif_rs_api_path: &str =
+        request_view.lsif_rs_api_path().to_str().expect("lsif_rs_api_path is not valid UTF-8");
     let generate_error_report = request_view.generate_error_report();
     let is_golden_test = request_view.is_golden_test();
     let kythe_annotations = request_view.kythe_annotations();
@@ -65,7 +67,7 @@ pub fn generate_bindings(
             &error_report::IgnoreErrors
         };
         let fatal_errors = FatalErrors::new();
-        let Bindings { rs_api, rs_api_impl } = inner_generate_bindings(
+        let Bindings { rs_api, rs_api_impl, lsif_index } = inner_generate_bindings(
             &ir,
             crubit_support_path_format,
             crubit_support_versioned_path_format,
@@ -77,11 +79,13 @@ pub fn generate_bindings(
             is_golden_test,
             kythe_annotations,
             kythe_default_corpus,
+            lsif_rs_api_path,
         )
         .unwrap();
 
         response.set_rs_api(rs_api);
         response.set_rs_api_impl(rs_api_impl);
+        response.set_lsif_index(lsif_index);
         if let Some(report) = error_report {
             response.set_error_report(report.to_json_string());
         }
@@ -40,7 +40,13 @@ pub fn cpp_tagless_type_name_for_record(
     record: &Record,
     db: &BindingsGenerator<'_>,
 ) -> Result<TokenStream> {
-    let features = db.ir().target_crubit_features(record.owning_target());
+    if record.is_anonymous() {
+        bail!(
+            "`{}` is an anonymous struct or union, which can't be named in C++",
+            record.rs_name()
+        );
+    }
+    let features = db.ir().item_crubit_features(record.id(), record.owning_target());
     let ident = format_nonportable_cc_type_name(record.cc_name().as_str())?;
     let namespace_qualifier = db.namespace_qualifier(record).format_for_cc(features)?;
     Ok(quote! { #namespace_qualifier #ident })
@@ -150,8 +156,10 @@ pub fn tagless_cpp_type_name_for_item(
     item: &ir::Item,
     db: &BindingsGenerator<'_>,
 ) -> Result<TokenStream> {
-    let features =
-        item.owning_target().map(|t| db.ir().target_crubit_features(&t)).unwrap_or_default();
+    let features = item
+        .owning_target()
+        .map(|t| db.ir().item_crubit_features(item.id(), &t))
+        .unwrap_or_default();
     match item {
         Item::IncompleteRecord(incomplete_record) => {
             let ident = format_nonportable_cc_type_name(incomplete_record.cc_name().as_str())?;
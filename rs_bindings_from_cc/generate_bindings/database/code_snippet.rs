@@ -94,6 +94,8 @@ pub struct Bindings {
     pub rs_api: String,
     // C++ source code.
     pub rs_api_impl: String,
+    // LSIF index, if requested.
+    pub lsif_index: String,
 }
 
 /// Source code for generated bindings, as tokens.
@@ -130,16 +132,18 @@ pub fn missing_feature_descriptions<'a>(
         .defining_target(item.id())
         .into_iter()
         .chain(item.owning_target())
-        .map(|target| TargetAndFeatures { features: ir.target_crubit_features(&target), target })
+        .map(|target| TargetAndFeatures {
+            features: ir.item_crubit_features(item.id(), &target),
+            target,
+        })
         .collect();
 
     let have_feature = |feature: CrubitFeature| -> bool {
         // We refuse to generate bindings if either the definition of an item, or
         // instantiation (if it is a template) of an item are in a translation unit
         // which doesn't have the required Crubit features.
-        for TargetAndFeatures { target, .. } in &defining_and_owning_target {
-            let enabled_features = ir.target_crubit_features(target);
-            if !enabled_features.contains(feature) {
+        for TargetAndFeatures { features, .. } in &defining_and_owning_target {
+            if !features.contains(feature) {
                 return false;
             }
         }
@@ -544,6 +548,8 @@ pub fn generated_items_to_tokens<'db>(
                     display_impl,
                     debug_impl,
                     no_unique_address_accessors,
+                    anonymous_member_accessors,
+                    bridge_field_accessors,
                     items,
                     nested_items,
                     indirect_functions,
@@ -636,9 +642,39 @@ pub fn generated_items_to_tokens<'db>(
                     None
                 };
 
+                let anonymous_member_accessors_impl = if !anonymous_member_accessors.is_empty() {
+                    Some(quote! {
+                        impl #type_param_tokens #ident #type_param_tokens {
+                            #( #anonymous_member_accessors )*
+                        }
+                    })
+                } else {
+                    None
+                };
+
+                let bridge_field_accessors_impl = if !bridge_field_accessors.is_empty() {
+                    Some(quote! {
+                        impl #type_param_tokens #ident #type_param_tokens {
+                            #( #bridge_field_accessors )*
+                        }
+                    })
+                } else {
+                    None
+                };
+
                 let owned_type_def = owned_ptr_config.as_ref().map(|cfg| {
                     let owned_type_name = &cfg.owned_type_name;
                     let drop_meth = &cfg.drop_impl;
+                    let deref_mut_impl = cfg.impl_deref_mut.then(|| {
+                        quote! {
+                            impl ::core::ops::DerefMut for #owned_type_name {
+                                #[inline(always)]
+                                fn deref_mut(&mut self) -> &mut Self::Target {
+                                    unsafe { self.0.as_mut() }
+                                }
+                            }
+                        }
+                    });
                     let doc_comment = format!(
                         "Wrapper for a C++ {} owned by Rust. \n\n Style guide: The C++ type to which this refers should be wrapped in an `Arc` or `Mutex` if it is not already thread-safe. \n\n THIS TYPE REQUIRES A MANUAL DROP IMPLEMENTATION. \n You MUST provide an `impl {} {{ pub fn {}(&mut self) {{ ... }} }}` block in a separate Rust file (e.g., via `additional_rust_srcs`). Failure to do so will result in a compile-time error: `method not found in `{}``.",
                         ident, owned_type_name, drop_meth, owned_type_name
@@ -658,6 +694,16 @@ pub fn generated_items_to_tokens<'db>(
                                 self.#drop_meth();
                             }
                         }
+
+                        impl ::core::ops::Deref for #owned_type_name {
+                            type Target = #ident;
+                            #[inline(always)]
+                            fn deref(&self) -> &Self::Target {
+                                unsafe { self.0.as_ref() }
+                            }
+                        }
+
+                        #deref_mut_impl
                     }
                 });
 
@@ -694,6 +740,10 @@ pub fn generated_items_to_tokens<'db>(
 
                     #no_unique_address_accessors_impl
 
+                    #anonymous_member_accessors_impl
+
+                    #bridge_field_accessors_impl
+
                     #member_methods_impl
 
                     #owned_type_def
@@ -1016,6 +1066,9 @@ impl GeneratedItem {
 pub struct OwnedPtrConfig {
     pub owned_type_name: Ident,
     pub drop_impl: Ident,
+    /// Whether the owned type also gets a `DerefMut` impl, which requires the pointee to be
+    /// `Unpin`.
+    pub impl_deref_mut: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -1043,6 +1096,8 @@ pub struct Record {
     pub display_impl: Option<DisplayImpl>,
     pub debug_impl: Option<DebugImpl>,
     pub no_unique_address_accessors: Vec<NoUniqueAddressAccessor>,
+    pub anonymous_member_accessors: Vec<AnonymousMemberAccessor>,
+    pub bridge_field_accessors: Vec<BridgeFieldAccessor>,
     pub items: Vec<ItemId>,
     pub nested_items: Vec<ItemId>,
     /// Functions that get attached either by a trait or from a base class.
@@ -1428,6 +1483,159 @@ impl ToTokens for NoUniqueAddressAccessor {
     }
 }
 
+/// Accessors for a member of an anonymous struct or union, which C++ makes accessible directly
+/// on the enclosing record.
+///
+/// For example, given `struct S { union { int i; float f; }; };`, C++ code can write `s.i`. In
+/// Rust, the anonymous union is an unnamed field (e.g. `__unnamed_field0`) of a synthesized type,
+/// and `i` is instead accessed through `s.i()` and `s.i_mut()`.
+#[derive(Clone, Debug)]
+pub struct AnonymousMemberAccessor {
+    pub doc_comment: Option<DocCommentAttr>,
+    /// The path of Rust fields from the enclosing record to the member, e.g.
+    /// `[__unnamed_field0, i]`.
+    pub field_path: Vec<Ident>,
+    /// The name of the accessor, which is the name of the member in C++.
+    pub ident: Ident,
+    pub type_: TokenStream,
+    /// Whether the member is (transitively) nested in an anonymous union, which makes reading it
+    /// unsafe.
+    pub is_in_union: bool,
+    /// Whether to generate a `&mut` accessor in addition to the `&` accessor.
+    pub has_mut_accessor: bool,
+}
+
+impl ToTokens for AnonymousMemberAccessor {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { doc_comment, field_path, ident, type_, is_in_union, has_mut_accessor } = self;
+        let mut_ident = format_ident!("{}_mut", ident.to_string().trim_start_matches("r#"));
+        if *is_in_union {
+            quote! {
+                #doc_comment
+                ///
+                /// # Safety
+                ///
+                /// The member must be the active member of the enclosing anonymous union.
+                pub unsafe fn #ident(&self) -> &#type_ {
+                    unsafe { &self.#( #field_path ).* }
+                }
+            }
+            .to_tokens(tokens);
+            if *has_mut_accessor {
+                quote! {
+                    #doc_comment
+                    ///
+                    /// # Safety
+                    ///
+                    /// The member must be the active member of the enclosing anonymous union.
+                    pub unsafe fn #mut_ident(&mut self) -> &mut #type_ {
+                        unsafe { &mut self.#( #field_path ).* }
+                    }
+                }
+                .to_tokens(tokens);
+            }
+        } else {
+            quote! {
+                #doc_comment
+                pub fn #ident(&self) -> &#type_ {
+                    &self.#( #field_path ).*
+                }
+            }
+            .to_tokens(tokens);
+            if *has_mut_accessor {
+                quote! {
+                    #doc_comment
+                    pub fn #mut_ident(&mut self) -> &mut #type_ {
+                        &mut self.#( #field_path ).*
+                    }
+                }
+                .to_tokens(tokens);
+            }
+        }
+    }
+}
+
+/// A getter and/or setter for a field of a bridge type, which is represented as an opaque blob of
+/// bytes in the Rust struct.
+///
+/// For example, given `struct S { std::optional<int> foo; };`, the field is read with
+/// `s.foo() -> Option<i32>` and written with `s.set_foo(Some(1))`. The conversion runs in C++
+/// thunks that encode or decode the field through its `CrubitAbiType`.
+#[derive(Clone, Debug)]
+pub struct BridgeFieldAccessor {
+    pub doc_comment: Option<DocCommentAttr>,
+    pub visibility: Visibility,
+    /// The Rust type of the field, e.g. `Option<i32>`.
+    pub type_: TokenStream,
+    pub crubit_abi_type_tokens: TokenStream,
+    pub crubit_abi_type_expr_tokens: TokenStream,
+    pub crate_root_path: TokenStream,
+    /// The name of the getter, and of the thunk that encodes the field, if the field can be read.
+    pub getter: Option<(Ident, Ident)>,
+    /// The name of the setter, and of the thunk that decodes into the field, if the field can be
+    /// written.
+    pub setter: Option<(Ident, Ident)>,
+    /// Whether the setter takes `self: Pin<&mut Self>`, because the record is not `Unpin`.
+    pub is_pinned: bool,
+}
+
+impl ToTokens for BridgeFieldAccessor {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            doc_comment,
+            visibility,
+            type_,
+            crubit_abi_type_tokens,
+            crubit_abi_type_expr_tokens,
+            crate_root_path,
+            getter,
+            setter,
+            is_pinned,
+        } = self;
+        if let Some((getter_ident, thunk_ident)) = getter {
+            quote! {
+                #doc_comment
+                #[inline(always)]
+                #visibility fn #getter_ident(&self) -> #type_ {
+                    unsafe {
+                        ::bridge_rust::unstable_return!(@ #crubit_abi_type_expr_tokens, #crubit_abi_type_tokens, |__crubit_return_abi_buffer| {
+                            #crate_root_path::detail::#thunk_ident(
+                                __crubit_return_abi_buffer,
+                                self as *const Self as *const ::core::ffi::c_void,
+                            );
+                        })
+                    }
+                }
+            }
+            .to_tokens(tokens);
+        }
+        if let Some((setter_ident, thunk_ident)) = setter {
+            let (self_param, self_ptr) = if *is_pinned {
+                (
+                    quote! { self: ::core::pin::Pin<&mut Self> },
+                    quote! { self.get_unchecked_mut() as *mut Self as *mut ::core::ffi::c_void },
+                )
+            } else {
+                (quote! { &mut self }, quote! { self as *mut Self as *mut ::core::ffi::c_void })
+            };
+            quote! {
+                #doc_comment
+                #[inline(always)]
+                #visibility fn #setter_ident(#self_param, value: #type_) {
+                    unsafe {
+                        #crate_root_path::detail::#thunk_ident(
+                            #self_ptr,
+                            ::bridge_rust::unstable_encode!(@ #crubit_abi_type_expr_tokens, #crubit_abi_type_tokens, value)
+                                .as_ptr() as *const u8,
+                        )
+                    }
+                }
+            }
+            .to_tokens(tokens);
+        }
+    }
+}
+
 flagset::flags! {
     #[allow(non_camel_case_types)]
     pub enum Feature: u32 {
@@ -666,7 +666,7 @@ impl<'db> BindingsGenerator<'db> {
         let use_leading_colons =
             if let Some(target) = self.find_untyped_decl(item_id).owning_target() {
                 self.ir()
-                    .target_crubit_features(&target)
+                    .item_crubit_features(item_id, &target)
                     .contains(crubit_feature::CrubitFeature::LeadingColonsForCppType)
             } else {
                 // We default to true here because the final change will always be to add `::` to
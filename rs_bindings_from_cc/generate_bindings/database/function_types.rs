@@ -69,6 +69,14 @@ pub enum TraitName<'a> {
         index_type: Rc<RsTypeKind<'a>>,
         output_type: Rc<RsTypeKind<'a>>,
     },
+    /// The `core::ops::Deref` trait, for a const C++ `operator*` or `operator->`.
+    Deref {
+        target_type: Rc<RsTypeKind<'a>>,
+    },
+    /// The `core::ops::DerefMut` trait, for a non-const C++ `operator*` or `operator->`.
+    DerefMut {
+        target_type: Rc<RsTypeKind<'a>>,
+    },
     /// The operator::Delete trait.
     Delete,
     /// Any other trait, e.g. Eq.
@@ -98,6 +106,8 @@ impl<'a> TraitName<'a> {
             TraitName::PartialOrd { .. } => "PartialOrd",
             TraitName::CcIndex { .. } => "CcIndex",
             TraitName::CcIndexMut { .. } => "CcIndexMut",
+            TraitName::Deref { .. } => "Deref",
+            TraitName::DerefMut { .. } => "DerefMut",
             TraitName::Delete => "::operator::Delete",
             TraitName::Other { name, .. } => name,
         }
@@ -106,7 +116,11 @@ impl<'a> TraitName<'a> {
     /// Returns the generic parameters in this trait name.
     fn params(&self) -> &[RsTypeKind<'a>] {
         match self {
-            Self::Clone | Self::Default | Self::Delete => &[],
+            Self::Clone
+            | Self::Default
+            | Self::Deref { .. }
+            | Self::DerefMut { .. }
+            | Self::Delete => &[],
             Self::CtorNew(params)
             | Self::From(params)
             | Self::UnsafeCtorNew(params)
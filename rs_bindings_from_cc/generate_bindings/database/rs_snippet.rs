@@ -13,7 +13,7 @@ use crubit_feature::CrubitFeature;
 use error_report::{bail, ensure};
 use flagset::FlagSet;
 use ir::*;
-use proc_macro2::{Delimiter, Group, Ident, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Group, Ident, Literal, TokenStream, TokenTree};
 use quote::{format_ident, quote, ToTokens};
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
@@ -313,6 +313,18 @@ pub enum UniformReprTemplateType<'a> {
         // No lifetime here: owned by the shared_ptr
         element_type: RsTypeKind<'a>,
     },
+    /// std::span<T, std::dynamic_extent>, as `cc_std::std::span<'a, T>` or `span_mut<'a, T>` if
+    /// `lifetime` is set, and as `raw_span<T>` or `raw_span_mut<T>` otherwise.
+    ///
+    /// These are `#[repr(C)]` wrappers with the layout of a C++ span, rather than Rust slice
+    /// pointers, whose layout Rust does not guarantee.
+    StdSpan { is_const: bool, element_type: RsTypeKind<'a>, lifetime: Option<Lifetime> },
+    /// std::array<T, N>, as `[T; N]`
+    StdArray {
+        // No lifetime here: owned by the array
+        element_type: RsTypeKind<'a>,
+        size: u64,
+    },
     AbslSpan {
         is_const: bool,
         include_lifetime: bool,
@@ -322,6 +334,9 @@ pub enum UniformReprTemplateType<'a> {
     },
     /// cc_std::string_view<'a>
     StdStringView { in_cc_std: bool, lifetime: Lifetime },
+    /// absl::Cord, as `absl_cord::Cord`. This isn't a template, but is likewise mapped to a
+    /// handwritten Rust type.
+    AbslCord,
 }
 
 fn choose_one_type(t: &CcType, ts: &Option<Rc<[CcType]>>) -> Result<CcType> {
@@ -453,6 +468,37 @@ impl<'a> UniformReprTemplateType<'a> {
                     lifetime: if lifetimes.is_empty() { None } else { Some(lifetimes[0].clone()) },
                 })))
             }
+            Some(TemplateSpecializationKind::StdSpan { raw_element_type }) => {
+                let element_type = choose_one_type(raw_element_type, template_args)?;
+                let element_type_kind = type_arg(&element_type)?;
+                if !element_type_kind.allowed_behind_multi_element_ptr() {
+                    return Ok(None);
+                }
+                if lifetimes.len() > 1 {
+                    bail!("Internal error: span was given too many lifetimes.")
+                }
+                Ok(Some(Rc::new(UniformReprTemplateType::StdSpan {
+                    is_const: element_type.is_const(),
+                    // Like absl::Span, parameters are always lifetime-bound. A returned span is a
+                    // raw span, because its lifetime is unclear.
+                    lifetime: if options.is_return_type {
+                        None
+                    } else {
+                        Some(lifetimes.first().cloned().unwrap_or_else(Lifetime::elided))
+                    },
+                    element_type: element_type_kind,
+                })))
+            }
+            Some(TemplateSpecializationKind::StdArray { raw_element_type, size }) => {
+                let element_type = choose_one_type(raw_element_type, template_args)?;
+                let element_type = type_arg(&element_type)?;
+                // `std::array<T, 0>` is not empty in C++, and `[T; N]` can only hold elements
+                // which Rust can move.
+                if *size == 0 || !element_type.is_complete() || !element_type.is_unpin() {
+                    return Ok(None);
+                }
+                Ok(Some(Rc::new(UniformReprTemplateType::StdArray { element_type, size: *size })))
+            }
             Some(TemplateSpecializationKind::StdStringView) if lifetimes.len() == 1 => {
                 Ok(Some(Rc::new(UniformReprTemplateType::StdStringView {
                     in_cc_std,
@@ -555,6 +601,24 @@ impl<'a> UniformReprTemplateType<'a> {
                 let element_type_tokens = element_type.to_token_stream(db);
                 quote! { ::cc_std::std::shared_ptr::<#element_type_tokens> }
             }
+            Self::StdSpan { is_const, element_type, lifetime } => {
+                let element_type_tokens = element_type.to_token_stream(db);
+                match (*is_const, lifetime) {
+                    (true, Some(lifetime)) => {
+                        quote! { ::cc_std::std::span<#lifetime, #element_type_tokens> }
+                    }
+                    (false, Some(lifetime)) => {
+                        quote! { ::cc_std::std::span_mut<#lifetime, #element_type_tokens> }
+                    }
+                    (true, None) => quote! { ::cc_std::std::raw_span<#element_type_tokens> },
+                    (false, None) => quote! { ::cc_std::std::raw_span_mut<#element_type_tokens> },
+                }
+            }
+            Self::StdArray { element_type, size } => {
+                let element_type_tokens = element_type.to_token_stream(db);
+                let size = Literal::u64_unsuffixed(*size);
+                quote! { [#element_type_tokens; #size] }
+            }
             Self::AbslSpan { is_const, include_lifetime, element_type, lifetime } => {
                 let element_type_tokens = element_type.to_token_stream(db);
                 // Use the custom name of the span crate, if configured (e.g. if
@@ -593,6 +657,7 @@ impl<'a> UniformReprTemplateType<'a> {
                     quote! { ::cc_std::std::string_view<#lifetime> }
                 }
             }
+            Self::AbslCord => quote! { ::absl_cord::Cord },
         }
     }
 
@@ -602,9 +667,12 @@ impl<'a> UniformReprTemplateType<'a> {
             Self::StdUniquePtr { .. } => None,
             Self::StdAtomic { .. } => None,
             Self::StdSharedPtr { .. } => None,
+            Self::StdSpan { lifetime, .. } => lifetime.clone(),
+            Self::StdArray { .. } => None,
             Self::AbslSpan { include_lifetime: true, .. } => Some(Lifetime::elided()),
             Self::AbslSpan { include_lifetime: false, .. } => None,
             Self::StdStringView { lifetime, .. } => Some(lifetime.clone()),
+            Self::AbslCord => None,
         }
     }
 }
@@ -765,6 +833,8 @@ impl<'a> CustomizeMethodsKind<'a> {
                 | TemplateSpecializationKind::StdAtomic { .. }
                 | TemplateSpecializationKind::C9Co { .. }
                 | TemplateSpecializationKind::AbslFlatHashSet { .. }
+                | TemplateSpecializationKind::StdSpan { .. }
+                | TemplateSpecializationKind::StdArray { .. }
                 | TemplateSpecializationKind::AbslSpan { .. }
                 | TemplateSpecializationKind::NonSpecial,
             )
@@ -782,6 +852,15 @@ pub enum BackingType {
         /// `extern "C" fn(*mut RawAnyInvocable, ...) -> ...`
         invoke_any_invocable_ident: Ident,
     },
+    MoveOnlyFunction {
+        /// The name of an extern "C" function that knows how to invoke this
+        /// `std::move_only_function`. It is declared in Rust and defined in C++. It has the
+        /// signature `extern "C" fn(*mut c_void, ...) -> ...`
+        invoke_move_only_function_ident: Ident,
+    },
+    /// `absl::FunctionRef`, which borrows the callable for the duration of a call instead of
+    /// owning it. It can only be passed from Rust to C++, as a function parameter.
+    FunctionRef,
 }
 
 /// Information about how the owned function object may be called.
@@ -824,6 +903,15 @@ pub struct Callable<'a> {
     pub rust_fn_trait: FnTrait,
     pub return_type: Rc<RsTypeKind<'a>>,
     pub param_types: Rc<[RsTypeKind<'a>]>,
+    /// Whether the Rust function object must be `Send`.
+    ///
+    /// Owned callables may be moved to, and invoked on, other threads by C++, so they must be
+    /// `Send`. A borrowed `absl::FunctionRef` only lives for the duration of a call, so it is not:
+    /// instead, the function it is passed to is `unsafe`, and must only call it from the calling
+    /// thread.
+    pub is_send: bool,
+    /// Whether the Rust function object must be `Sync`, for the same reasons as `is_send`.
+    pub is_sync: bool,
     pub invoker_ident: Ident,
     pub manager_ident: Ident,
 }
@@ -839,14 +927,34 @@ impl<'a> Callable<'a> {
         }
     }
 
-    /// Returns a `TokenStream` in the shape of `dyn Trait(Inputs) -> Output`.
+    /// Returns true if the callable is borrowed rather than owned, i.e. an `absl::FunctionRef`.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.backing_type, BackingType::FunctionRef)
+    }
+
+    /// Returns a `TokenStream` in the shape of `dyn Trait(Inputs) -> Output`, followed by the
+    /// `Send`, `Sync`, and `'static` bounds that apply to this callable.
     pub fn dyn_fn_spelling(&self, db: &BindingsGenerator<'a>) -> TokenStream {
         let rust_return_type_fragment = self.rust_return_type_fragment(db);
         let param_type_tokens =
             self.param_types.iter().map(|param_ty| param_ty.to_token_stream(db));
         let fn_trait = self.rust_fn_trait;
+        let send_bound = self.is_send.then(|| quote! { + ::core::marker::Send });
+        let sync_bound = self.is_sync.then(|| quote! { + ::core::marker::Sync });
+        let static_bound = (!self.is_borrowed()).then(|| quote! { + 'static });
         quote! {
-            dyn #fn_trait(#(#param_type_tokens),*) #rust_return_type_fragment + ::core::marker::Send + ::core::marker::Sync + 'static
+            dyn #fn_trait(#(#param_type_tokens),*) #rust_return_type_fragment #send_bound #sync_bound #static_bound
+        }
+    }
+
+    /// Returns the Rust type of the function object that the invoker thunk is handed a pointer
+    /// to: `Box<dyn Trait>` for owned callables, and `&mut dyn Trait` for borrowed ones.
+    pub fn rust_storage_type(&self, db: &BindingsGenerator<'a>) -> TokenStream {
+        let dyn_fn_spelling = self.dyn_fn_spelling(db);
+        if self.is_borrowed() {
+            quote! { &mut #dyn_fn_spelling }
+        } else {
+            quote! { ::alloc::boxed::Box<#dyn_fn_spelling> }
         }
     }
 
@@ -877,6 +985,19 @@ pub enum BridgeRsTypeKind<'a> {
     StdString {
         in_cc_std: bool,
     },
+    /// `absl::Duration` or a `std::chrono::duration`, bridged to `bridge_rust::SignedDuration`.
+    Duration,
+    /// `absl::Time` or a `std::chrono::time_point` of `std::chrono::system_clock`, bridged to
+    /// `std::time::SystemTime`.
+    Time,
+    /// `std::filesystem::path`, bridged to `std::path::PathBuf`, or, for a `const path&`
+    /// parameter, to `&std::path::Path`.
+    StdPath {
+        in_cc_std: bool,
+        is_const_ref: bool,
+    },
+    /// `std::unique_ptr<T, rs_std::BoxDeleter<T>>`, bridged to `Box<T>`.
+    RustBox(Rc<RsTypeKind<'a>>),
     Callable(Rc<Callable<'a>>),
     /// c9::Co<T>
     C9Co {
@@ -990,6 +1111,15 @@ impl<'a> BridgeRsTypeKind<'a> {
 
                 BridgeRsTypeKind::StdString { in_cc_std }
             }
+            BridgeType::Duration => BridgeRsTypeKind::Duration,
+            BridgeType::Time => BridgeRsTypeKind::Time,
+            BridgeType::StdPath => {
+                let in_cc_std = db.ir().is_current_target(record.owning_target())
+                    && record.owning_target().target_name_escaped() == "cc_std";
+
+                BridgeRsTypeKind::StdPath { in_cc_std, is_const_ref: false }
+            }
+            BridgeType::RustBox(t) => BridgeRsTypeKind::RustBox(Rc::new(db.rs_type_kind(t)?)),
             BridgeType::Callable { backing_type, fn_trait, return_type, param_types } => {
                 let target_identifier = record.owning_target().convert_to_cc_identifier();
                 let cpp_fn_trait = match fn_trait {
@@ -999,7 +1129,43 @@ impl<'a> BridgeRsTypeKind<'a> {
                     _ => bail!("unspecified FnTrait"),
                 };
 
-                let rust_fn_trait = if cpp_fn_trait == FnTrait::FnMut {
+                let backing_type = match backing_type {
+                    ir::BackingType::DynCallable => BackingType::DynCallable,
+                    ir::BackingType::AnyInvocable => BackingType::AnyInvocable {
+                        invoke_any_invocable_ident: format_ident!(
+                            "__crubit_invoke_any_invocable_{}{target_identifier}",
+                            record.rs_name().as_str(),
+                        ),
+                    },
+                    ir::BackingType::MoveOnlyFunction => BackingType::MoveOnlyFunction {
+                        invoke_move_only_function_ident: format_ident!(
+                            "__crubit_invoke_move_only_function_{}{target_identifier}",
+                            record.rs_name().as_str(),
+                        ),
+                    },
+                    ir::BackingType::FunctionRef => BackingType::FunctionRef,
+                    _ => bail!("unspecified BackingType"),
+                };
+
+                // An `absl::FunctionRef` borrows a Rust closure for the duration of a single call,
+                // so the closure needs neither `Send` nor `Sync`. Instead, functions that take one
+                // are `unsafe` (see `rs_type_kind_safety`), because nothing stops C++ from calling
+                // it concurrently. Owned callables may be moved to and shared between threads by
+                // C++.
+                let is_borrowed = backing_type == BackingType::FunctionRef;
+                let (is_send, is_sync) = (!is_borrowed, !is_borrowed);
+                if is_borrowed {
+                    ensure!(
+                        !options.is_return_type,
+                        "absl::FunctionRef is only supported as a function parameter"
+                    );
+                    ensure!(
+                        cpp_fn_trait == FnTrait::FnMut,
+                        "absl::FunctionRef with a `const` or `&&` qualified signature is not supported"
+                    );
+                }
+
+                let rust_fn_trait = if cpp_fn_trait == FnTrait::FnMut && is_sync {
                     // C++ doesn't require exclusive access to invoke a mutable function,
                     // so to be safe we use the more conservative `Fn` trait in Rust, which
                     // because we mark it with Send + Sync, is safe to call anywhere in
@@ -1008,24 +1174,28 @@ impl<'a> BridgeRsTypeKind<'a> {
                 } else {
                     cpp_fn_trait
                 };
+
+                let return_type = Rc::new(db.rs_type_kind(return_type.clone())?);
+                let param_types = param_types
+                    .iter()
+                    .map(|param_type| db.rs_type_kind(param_type.clone()))
+                    .collect::<Result<Rc<[_]>>>()?;
+                ensure!(
+                    !param_types
+                        .iter()
+                        .chain([return_type.as_ref()])
+                        .any(RsTypeKind::is_function_ref),
+                    "absl::FunctionRef cannot be passed to or returned from a callable"
+                );
+
                 BridgeRsTypeKind::Callable(Rc::new(Callable {
-                    backing_type: match backing_type {
-                        ir::BackingType::DynCallable => BackingType::DynCallable,
-                        ir::BackingType::AnyInvocable => BackingType::AnyInvocable {
-                            invoke_any_invocable_ident: format_ident!(
-                                "__crubit_invoke_any_invocable_{}{target_identifier}",
-                                record.rs_name().as_str(),
-                            ),
-                        },
-                        _ => bail!("unspecified BackingType"),
-                    },
+                    backing_type,
                     cpp_fn_trait,
                     rust_fn_trait,
-                    return_type: Rc::new(db.rs_type_kind(return_type.clone())?),
-                    param_types: param_types
-                        .iter()
-                        .map(|param_type| db.rs_type_kind(param_type.clone()))
-                        .collect::<Result<_>>()?,
+                    return_type,
+                    param_types,
+                    is_send,
+                    is_sync,
                     invoker_ident: format_ident!(
                         "__crubit_invoker_{}{}",
                         record.rs_name().as_str(),
@@ -1190,14 +1360,18 @@ impl<'a> RsTypeKind<'a> {
         let in_cc_std = db.ir().is_current_target(record.as_ref().owning_target())
             && record.as_ref().owning_target().target_name_escaped() == "cc_std";
 
-        let uniform_repr_template_type = UniformReprTemplateType::new(
-            db,
-            record.template_specialization().as_ref().map(|ts| ts.kind()),
-            options,
-            template_args,
-            lifetimes,
-            in_cc_std,
-        )?;
+        let uniform_repr_template_type = if record.is_absl_cord() {
+            Some(Rc::new(UniformReprTemplateType::AbslCord))
+        } else {
+            UniformReprTemplateType::new(
+                db,
+                record.template_specialization().as_ref().map(|ts| ts.kind()),
+                options,
+                template_args,
+                lifetimes,
+                in_cc_std,
+            )?
+        };
         let customize_methods = CustomizeMethodsKind::new(
             db,
             record.template_specialization().as_ref().map(|ts| ts.kind()),
@@ -1317,7 +1491,11 @@ impl<'a> RsTypeKind<'a> {
         match self.unalias() {
             RsTypeKind::Error { .. } | RsTypeKind::IncompleteRecord { .. } => false,
             RsTypeKind::Record { record, uniform_repr_template_type, .. } => {
-                uniform_repr_template_type.is_some() || record.is_unpin()
+                // `absl_cord::Cord`, like `absl::Cord`, is not Rust-movable.
+                match uniform_repr_template_type.as_deref() {
+                    Some(UniformReprTemplateType::AbslCord) | None => record.is_unpin(),
+                    Some(_) => true,
+                }
             }
             RsTypeKind::BridgeType { .. } => true,
             _ => true,
@@ -1351,6 +1529,15 @@ impl<'a> RsTypeKind<'a> {
         matches!(self.unalias(), RsTypeKind::BridgeType { .. })
     }
 
+    /// Returns true if this is an `absl::FunctionRef`, which borrows a Rust callable.
+    pub fn is_function_ref(&self) -> bool {
+        matches!(
+            self.unalias(),
+            RsTypeKind::BridgeType { bridge_type: BridgeRsTypeKind::Callable(callable), .. }
+                if callable.is_borrowed()
+        )
+    }
+
     pub fn is_proto_message_bridge_type(&self) -> bool {
         matches!(
             self.unalias(),
@@ -1663,6 +1850,17 @@ impl<'a> RsTypeKind<'a> {
             RsTypeKind::Reference { mutability: Mutability::Mut, .. } => false,
             RsTypeKind::RvalueReference { .. } => false,
             RsTypeKind::IncompleteRecord { .. } => false,
+            RsTypeKind::Record { uniform_repr_template_type: Some(template), record, .. } => {
+                match template.as_ref() {
+                    UniformReprTemplateType::StdSpan { is_const, lifetime: Some(_), .. } => {
+                        *is_const
+                    }
+                    UniformReprTemplateType::StdArray { element_type, .. } => {
+                        element_type.implements_copy()
+                    }
+                    _ => record.should_derive_copy(),
+                }
+            }
             RsTypeKind::Record { record, .. } => record.should_derive_copy(),
             RsTypeKind::Enum { .. } => true,
             RsTypeKind::TypeAlias { underlying_type, .. } => underlying_type.implements_copy(),
@@ -1674,8 +1872,11 @@ impl<'a> RsTypeKind<'a> {
                 BridgeRsTypeKind::StdOptional(t) => t.implements_copy(),
                 BridgeRsTypeKind::StdPair(t1, t2) => t1.implements_copy() && t2.implements_copy(),
                 BridgeRsTypeKind::StdString { .. } => false,
+                BridgeRsTypeKind::Duration | BridgeRsTypeKind::Time => true,
+                BridgeRsTypeKind::StdPath { is_const_ref, .. } => *is_const_ref,
+                BridgeRsTypeKind::RustBox(_) => false,
                 BridgeRsTypeKind::Callable { .. } => {
-                    // Callables represent an owned function object, so they are not copyable.
+                    // Owned callables are not copyable, and borrowed ones are `&mut`.
                     false
                 }
                 BridgeRsTypeKind::C9Co { .. } => false,
@@ -2072,6 +2273,29 @@ fn all_static_lifetimes_internal<'a>(
                             .clone(),
                         }
                     }
+                    UniformReprTemplateType::StdSpan { is_const, element_type, lifetime } => {
+                        UniformReprTemplateType::StdSpan {
+                            is_const: *is_const,
+                            element_type: all_static_lifetimes_internal(
+                                element_type,
+                                strip_aliases,
+                            )
+                            .as_ref()
+                            .clone(),
+                            lifetime: lifetime.as_ref().map(|_| Lifetime::new("static")),
+                        }
+                    }
+                    UniformReprTemplateType::StdArray { element_type, size } => {
+                        UniformReprTemplateType::StdArray {
+                            element_type: all_static_lifetimes_internal(
+                                element_type,
+                                strip_aliases,
+                            )
+                            .as_ref()
+                            .clone(),
+                            size: *size,
+                        }
+                    }
                     UniformReprTemplateType::AbslSpan {
                         is_const,
                         include_lifetime,
@@ -2091,6 +2315,7 @@ fn all_static_lifetimes_internal<'a>(
                             lifetime: Lifetime::new("static"),
                         }
                     }
+                    UniformReprTemplateType::AbslCord => UniformReprTemplateType::AbslCord,
                 })
             }),
             owned_ptr_type: owned_ptr_type.clone(),
@@ -2154,7 +2379,13 @@ fn all_static_lifetimes_internal<'a>(
                     all_static_lifetimes_internal(first, strip_aliases),
                     all_static_lifetimes_internal(second, strip_aliases),
                 ),
-                BridgeRsTypeKind::StdString { .. } => bridge_type.clone(),
+                BridgeRsTypeKind::RustBox(pointee) => {
+                    BridgeRsTypeKind::RustBox(all_static_lifetimes_internal(pointee, strip_aliases))
+                }
+                BridgeRsTypeKind::StdString { .. }
+                | BridgeRsTypeKind::Duration
+                | BridgeRsTypeKind::Time
+                | BridgeRsTypeKind::StdPath { .. } => bridge_type.clone(),
                 BridgeRsTypeKind::Callable(k) => BridgeRsTypeKind::Callable(Rc::new(Callable {
                     return_type: all_static_lifetimes_internal(&k.return_type, strip_aliases),
                     param_types: k
@@ -2519,10 +2750,19 @@ impl<'a> RsTypeKind<'a> {
                             quote! { ::cc_std::std::string_wrapper }
                         }
                     }
-                    BridgeRsTypeKind::Callable(callable) => {
-                        let callable_spelling = callable.dyn_fn_spelling(&db);
-                        quote! { ::alloc::boxed::Box<#callable_spelling> }
+                    BridgeRsTypeKind::Duration => quote! { ::bridge_rust::SignedDuration },
+                    BridgeRsTypeKind::Time => quote! { ::std::time::SystemTime },
+                    BridgeRsTypeKind::StdPath { is_const_ref: false, .. } => {
+                        quote! { ::std::path::PathBuf }
                     }
+                    BridgeRsTypeKind::StdPath { is_const_ref: true, .. } => {
+                        quote! { &::std::path::Path }
+                    }
+                    BridgeRsTypeKind::RustBox(pointee) => {
+                        let pointee = pointee.to_token_stream(db);
+                        quote! { ::alloc::boxed::Box< #pointee > }
+                    }
+                    BridgeRsTypeKind::Callable(callable) => callable.rust_storage_type(db),
                     BridgeRsTypeKind::C9Co { has_reference_param, result_type, lifetime, .. } => {
                         let co_crate = db
                             .ir()
@@ -2701,12 +2941,17 @@ impl<'a, 'ty> Iterator for RsTypeKindIter<'a, 'ty> {
                     RsTypeKind::BridgeType { bridge_type, .. } => match bridge_type {
                         BridgeRsTypeKind::ProtoMessageBridge { .. }
                         | BridgeRsTypeKind::Bridge { .. } => {}
-                        BridgeRsTypeKind::StdOptional(t) => self.todo.push(t),
+                        BridgeRsTypeKind::StdOptional(t) | BridgeRsTypeKind::RustBox(t) => {
+                            self.todo.push(t)
+                        }
                         BridgeRsTypeKind::StdPair(t1, t2) => {
                             self.todo.push(t2);
                             self.todo.push(t1);
                         }
-                        BridgeRsTypeKind::StdString { .. } => {}
+                        BridgeRsTypeKind::StdString { .. }
+                        | BridgeRsTypeKind::Duration
+                        | BridgeRsTypeKind::Time
+                        | BridgeRsTypeKind::StdPath { .. } => {}
                         BridgeRsTypeKind::Callable(callable) => {
                             self.todo.push(&callable.return_type);
                             self.todo.extend(callable.param_types.iter().rev());
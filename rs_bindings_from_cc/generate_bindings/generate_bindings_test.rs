@@ -14,7 +14,9 @@ use multiplatform_ir_testing::{
 };
 use quote::quote;
 use static_assertions::{assert_impl_all, assert_not_impl_any};
-use test_generators::{generate_bindings_tokens_for_test, TestDbFactory};
+use test_generators::{
+    generate_bindings_tokens_for_test, generate_error_report_for_test, TestDbFactory,
+};
 use token_stream_matchers::{
     assert_cc_matches, assert_cc_not_matches, assert_rs_matches, assert_rs_not_matches,
 };
@@ -1219,6 +1221,24 @@ fn test_default_crubit_features_disabled_wrapper() -> Result<()> {
     Ok(())
 }
 
+/// Features enabled for a single item (by `CRUBIT_ENABLE_FEATURE`) apply to that item only.
+#[gtest]
+fn test_item_crubit_features() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        "[[noreturn]] void WithFeature();
+         [[noreturn]] void WithoutFeature();",
+    )?;
+
+    let mut ir = make_test_ir(&proto)?;
+    enable_supported(&mut ir);
+    let func_id = ir.functions().find(|func| *func.rs_name() == "WithFeature").unwrap().id();
+    *ir.item_crubit_features_mut(func_id) = crubit_feature::CrubitFeature::Experimental.into();
+    let BindingsTokens { rs_api, .. } = generate_bindings_tokens_for_test(ir)?;
+    assert_rs_matches!(rs_api, quote! {pub fn WithFeature});
+    assert_rs_not_matches!(rs_api, quote! {pub fn WithoutFeature});
+    Ok(())
+}
+
 #[gtest]
 fn test_default_crubit_features_disabled_dependency_supported_function_parameter() -> Result<()> {
     let proto = ir_proto_from_cc_dependency(
@@ -1626,3 +1646,90 @@ fn test_nested_ir_end_to_end() -> Result<()> {
     );
     Ok(())
 }
+
+/// Returns the edits suggested by the error report, as `(item name, edit)` pairs.
+fn suggested_edits(ir: IR<'_>) -> Result<Vec<(String, serde_json::Value)>> {
+    let report = generate_error_report_for_test(ir)?;
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&report.to_json_string())?;
+    let mut edits = vec![];
+    for entry in &entries {
+        for suggestion in entry["suggestions"].as_array().into_iter().flatten() {
+            edits.push((entry["name"].as_str().unwrap().to_string(), suggestion["edit"].clone()));
+        }
+    }
+    Ok(edits)
+}
+
+#[gtest]
+fn test_overloaded_function_suggests_rust_name() -> Result<()> {
+    let proto = ir_proto_from_cc("void Overloaded(int x);\nvoid Overloaded(const char* s);")?;
+    let ir = make_test_ir(&proto)?;
+    let edits = suggested_edits(ir)?;
+    expect_eq!(edits.len(), 2);
+    expect_eq!(edits[0].1["kind"], "replace");
+    expect_eq!(edits[0].1["length"], 0);
+    expect_eq!(edits[0].1["replacement"], "CRUBIT_RUST_NAME(\"Overloaded_int\") ");
+    expect_eq!(edits[1].1["replacement"], "CRUBIT_RUST_NAME(\"Overloaded_const_char_ptr\") ");
+    expect_eq!(edits[1].1["line"], edits[0].1["line"].as_u64().unwrap() + 1);
+    expect_eq!(edits[1].1["column"], 1);
+    Ok(())
+}
+
+#[gtest]
+fn test_overloads_differing_in_const_get_distinct_rust_names() -> Result<()> {
+    let proto = ir_proto_from_cc("struct S {\n  int Get();\n  int Get() const;\n};")?;
+    let ir = make_test_ir(&proto)?;
+    let mut replacements = suggested_edits(ir)?
+        .into_iter()
+        .map(|(_, edit)| edit["replacement"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    replacements.sort();
+    expect_eq!(
+        replacements,
+        ["CRUBIT_RUST_NAME(\"Get_const\") ", "CRUBIT_RUST_NAME(\"Get_void\") "]
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_overload_rust_name_avoids_existing_names() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        "void Overloaded(int x);\nvoid Overloaded(const char* s);\nvoid Overloaded_int();",
+    )?;
+    let ir = make_test_ir(&proto)?;
+    let mut replacements = suggested_edits(ir)?
+        .into_iter()
+        .map(|(_, edit)| edit["replacement"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    replacements.sort();
+    // `Overloaded_int` is already taken by another function.
+    expect_eq!(
+        replacements,
+        [
+            "CRUBIT_RUST_NAME(\"Overloaded_const_char_ptr\") ",
+            "CRUBIT_RUST_NAME(\"Overloaded_int_1\") ",
+        ]
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_missing_feature_on_dependency_suggests_aspect_hint() -> Result<()> {
+    let proto = ir_proto_from_cc_dependency("void Func(NotPresent);", "struct NotPresent {};")?;
+    let mut ir = make_test_ir_dependency(&proto, None)?;
+    ir.target_crubit_features_mut(&ir::BazelLabel::from("//test:dependency")).clear();
+    enable_supported(&mut ir);
+    let edits = suggested_edits(ir)?;
+    expect_eq!(
+        edits,
+        [(
+            "Func".to_string(),
+            serde_json::json!({
+                "kind": "add_aspect_hint",
+                "target": "//test:dependency",
+                "aspect_hint": "//features:supported",
+            })
+        )]
+    );
+    Ok(())
+}
@@ -41,6 +41,12 @@ pub fn callable_crubit_abi_type<'a>(
         BackingType::AnyInvocable { .. } => quote! {
             #any_invocable_crate::AnyInvocableAbi<#dyn_fn_spelling>
         },
+        BackingType::MoveOnlyFunction { .. } => quote! {
+            ::dyn_callable_rs::MoveOnlyFunctionAbi<#dyn_fn_spelling>
+        },
+        BackingType::FunctionRef => quote! {
+            ::dyn_callable_rs::FunctionRefAbi<'_, #dyn_fn_spelling>
+        },
     };
 
     let on_empty_tokens = {
@@ -71,6 +77,19 @@ pub fn callable_crubit_abi_type<'a>(
                 )
             }
         }
+        BackingType::MoveOnlyFunction { invoke_move_only_function_ident } => {
+            let make_cpp_invoker_tokens =
+                generate_make_cpp_invoker_tokens(db, callable, invoke_move_only_function_ident)?;
+            quote! {
+                ::dyn_callable_rs::MoveOnlyFunctionAbi::<#dyn_fn_spelling>::new(
+                    #on_empty_tokens,
+                    #make_cpp_invoker_tokens,
+                )
+            }
+        }
+        BackingType::FunctionRef => quote! {
+            ::dyn_callable_rs::FunctionRefAbi::<'_, #dyn_fn_spelling>(::core::marker::PhantomData)
+        },
     };
 
     let qualifier = match callable.cpp_fn_trait {
@@ -96,18 +115,31 @@ pub fn callable_crubit_abi_type<'a>(
         BackingType::AnyInvocable { .. } => quote! {
             ::crubit::AnyInvocableAbi<#cpp_fn_sig>
         },
+        BackingType::MoveOnlyFunction { .. } => quote! {
+            ::rs_std::internal_dyn_callable::MoveOnlyFunctionAbi<#cpp_fn_sig>
+        },
+        BackingType::FunctionRef => quote! {
+            ::rs_std::internal_dyn_callable::FunctionRefAbi<#cpp_fn_sig>
+        },
     };
 
     let cpp_expr_tokens = {
         let invoker_function_pointer =
             generate_invoker_function_pointer(db, callable, &cpp_param_types, &cpp_return_type)?;
-        let manager_ident = &callable.manager_ident;
 
-        quote! {
-            #cpp_type_tokens(
-                #manager_ident,
-                #invoker_function_pointer
-            )
+        if callable.is_borrowed() {
+            // A borrowed callable is never relocated or disposed of by C++, so it has no manager.
+            quote! {
+                #cpp_type_tokens(#invoker_function_pointer)
+            }
+        } else {
+            let manager_ident = &callable.manager_ident;
+            quote! {
+                #cpp_type_tokens(
+                    #manager_ident,
+                    #invoker_function_pointer
+                )
+            }
         }
     };
 
@@ -259,22 +291,39 @@ fn generate_invoker_function_pointer<'a>(
     })
 }
 
-/// Generates the `make_cpp_invoker` function for AnyInvocable.
+/// Generates the `make_cpp_invoker` function for AnyInvocable and `std::move_only_function`.
 ///
-/// It's a closure that takes a manager and an invoker, and produces a boxed dyn fn that uses the
-/// manager and invoker to do the actual work.
+/// It's a closure that takes an owned C++ function object, and produces a boxed dyn fn that
+/// invokes it through `invoke_cpp_callable_ident` to do the actual work.
 ///
 /// The produced function needs to know how to convert values to and from C++.
 fn generate_make_cpp_invoker_tokens<'a>(
     db: &BindingsGenerator<'a>,
     callable: &Callable<'a>,
-    invoke_any_invocable_ident: &Ident,
+    invoke_cpp_callable_ident: &Ident,
 ) -> Result<TokenStream> {
-    let any_invocable_crate = db
-        .ir()
-        .crate_name(&BazelLabel::from("@abseil-cpp//absl/functional:any_invocable"))
-        .map(|ident| quote! { ::#ident })
-        .unwrap_or_else(|| quote! { ::any_invocable });
+    let (raw_callable_param, raw_callable_ptr) = match &callable.backing_type {
+        BackingType::AnyInvocable { .. } => {
+            let any_invocable_crate = db
+                .ir()
+                .crate_name(&BazelLabel::from("@abseil-cpp//absl/functional:any_invocable"))
+                .map(|ident| quote! { ::#ident })
+                .unwrap_or_else(|| quote! { ::any_invocable });
+            (
+                quote! {
+                    raw_any_invocable: ::cc_std::std::unique_ptr<#any_invocable_crate::RawAnyInvocable>
+                },
+                quote! { raw_any_invocable.get() },
+            )
+        }
+        BackingType::MoveOnlyFunction { .. } => (
+            quote! { raw_move_only_function: ::dyn_callable_rs::RawCppCallable },
+            quote! { raw_move_only_function.as_ptr() },
+        ),
+        BackingType::DynCallable | BackingType::FunctionRef => {
+            bail!("crubit.rs-bug: only C++ function objects can be invoked from Rust")
+        }
+    };
 
     let rust_return_type_fragment = callable.rust_return_type_fragment(db);
 
@@ -339,7 +388,7 @@ fn generate_make_cpp_invoker_tokens<'a>(
 
     let mut invoke_ffi_and_transform_to_rust = quote! {
         unsafe {
-            crate::detail::#invoke_any_invocable_ident(raw_any_invocable.get() #(, #arg_exprs)*)
+            crate::detail::#invoke_cpp_callable_ident(#raw_callable_ptr #(, #arg_exprs)*)
         }
     };
 
@@ -384,8 +433,7 @@ fn generate_make_cpp_invoker_tokens<'a>(
     let dyn_fn_spelling = callable.dyn_fn_spelling(db);
 
     Ok(quote! {
-        |raw_any_invocable: ::cc_std::std::unique_ptr<#any_invocable_crate::RawAnyInvocable>|
-            -> ::alloc::boxed::Box<#dyn_fn_spelling>
+        |#raw_callable_param| -> ::alloc::boxed::Box<#dyn_fn_spelling>
         {
             ::alloc::boxed::Box::new(
                 move |#(#params),*|
@@ -23,7 +23,7 @@ use std::rc::Rc;
 #[cfg_attr(enable_heap_profiling, inline(never))]
 pub fn generate_enum(db: &BindingsGenerator, enum_: Rc<Enum>) -> Result<ApiSnippets> {
     db.errors().add_category(error_report::Category::Type);
-    let features = db.ir().target_crubit_features(enum_.owning_target());
+    let features = db.ir().item_crubit_features(enum_.id(), enum_.owning_target());
     let ident = format_nonportable_cc_ident(enum_.cc_name().as_str())?;
     let namespace_qualifier = db.namespace_qualifier(enum_.as_ref()).format_for_cc(features)?;
     let fully_qualified_cc_name = quote! { #namespace_qualifier #ident };
@@ -0,0 +1,182 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Machine-applicable fixes for items that could not receive bindings.
+//!
+//! These are attached to the item's entry in the error report, and can be exported in bulk by
+//! `crubit_fix_its`.
+
+use cpp_type_name::format_cpp_type;
+use crubit_feature::CrubitFeature;
+use database::db::BindingsGenerator;
+use error_report::Suggestion;
+use ir::{
+    BazelLabel, CcType, CcTypeVariant, Func, GenericItem, Item, ReferenceQualification,
+    UnqualifiedIdentifier,
+};
+use std::collections::{BTreeSet, HashSet};
+use std::rc::Rc;
+
+/// Suggests a `CRUBIT_RUST_NAME` annotation which gives the overload `func` a distinct Rust name.
+pub fn suggest_rust_name_for_overload(db: &BindingsGenerator<'_>, func: &Func<'_>) {
+    let UnqualifiedIdentifier::Identifier(name) = func.rs_name() else {
+        // Constructors and operators map to traits, not names.
+        return;
+    };
+    let Some(decl_begin) = func.decl_begin() else {
+        return;
+    };
+    let rust_name = distinct_overload_name(db, name.as_str(), func);
+    db.errors().suggest(Suggestion::insert(
+        format!("give this overload the distinct Rust name `{rust_name}`"),
+        decl_begin.file,
+        decl_begin.offset,
+        decl_begin.line,
+        decl_begin.column,
+        format!("CRUBIT_RUST_NAME(\"{rust_name}\") "),
+    ));
+}
+
+/// Returns a Rust name for the overload `func` of `name` that differs from the names suggested
+/// for the other overloads, and from the names of the other functions in the same scope.
+///
+/// The name is spelled from the parameter types and the qualifiers of `this` (see
+/// [`overload_name`]). Overloads that would still get the same name, e.g. because their types
+/// can't be spelled, are numbered in declaration order.
+fn distinct_overload_name(db: &BindingsGenerator<'_>, name: &str, func: &Func<'_>) -> String {
+    let scope_functions = db
+        .ir()
+        .functions()
+        .filter(|other| other.enclosing_item_id() == func.enclosing_item_id())
+        .collect::<Vec<_>>();
+    let overload_names = scope_functions
+        .iter()
+        .filter(|other| other.rs_name() == func.rs_name())
+        .map(|overload| (overload.id(), overload_name(db, name, overload)))
+        .collect::<Vec<_>>();
+    let rust_name = overload_name(db, name, func);
+
+    let mut taken_names = scope_functions
+        .iter()
+        .filter(|other| other.rs_name() != func.rs_name())
+        .filter_map(|other| other.rs_name().as_identifier().map(|name| name.as_str().to_string()))
+        .collect::<HashSet<_>>();
+    let colliding_overloads = overload_names
+        .iter()
+        .filter(|(_, overload_name)| *overload_name == rust_name)
+        .map(|(id, _)| *id)
+        .collect::<Vec<_>>();
+    if colliding_overloads.len() <= 1 && !taken_names.contains(&rust_name) {
+        return rust_name;
+    }
+    taken_names.extend(overload_names.into_iter().map(|(_, overload_name)| overload_name));
+    let mut candidates = (1..).map(|index| format!("{rust_name}_{index}"));
+    for id in colliding_overloads {
+        let candidate = candidates
+            .by_ref()
+            .find(|candidate| !taken_names.contains(candidate))
+            .expect("the candidate names are unbounded");
+        if id == func.id() {
+            return candidate;
+        }
+    }
+    unreachable!("`func` is one of its own overloads")
+}
+
+/// Spells the name of the overload `func` of `name`, e.g. `Get_int_const` for
+/// `Get(int) const`, or `Get_void` for `Get()`.
+fn overload_name(db: &BindingsGenerator<'_>, name: &str, func: &Func<'_>) -> String {
+    let mut words = func
+        .params()
+        .iter()
+        .filter(|param| param.identifier().as_str() != "__this")
+        .map(|param| type_name_for_identifier(db, param.type_()))
+        .collect::<Vec<_>>();
+    if let Some(metadata) = func.instance_method_metadata() {
+        if metadata.is_const() {
+            words.push("const".to_string());
+        }
+        match metadata.reference() {
+            ReferenceQualification::LValue => words.push("lvalue".to_string()),
+            ReferenceQualification::RValue => words.push("rvalue".to_string()),
+            ReferenceQualification::Unqualified => {}
+        }
+    }
+    if words.is_empty() {
+        words.push("void".to_string());
+    }
+    format!("{name}_{}", words.join("_"))
+}
+
+/// Spells `cc_type` as a snake_case identifier fragment, e.g. `const char*` as `const_char_ptr`.
+fn type_name_for_identifier(db: &BindingsGenerator<'_>, cc_type: &CcType) -> String {
+    let Ok(spelling) = db.rs_type_kind(cc_type.clone()).and_then(|ty| format_cpp_type(&ty, db))
+    else {
+        return "unknown".to_string();
+    };
+    let spelling = spelling.to_string().replace('*', " ptr ").replace('&', " ref ");
+    spelling
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase()
+}
+
+/// Suggests enabling Crubit on the targets whose missing `//features:supported` prevents
+/// bindings for `item`: the targets of the item itself, and those of the types in its signature.
+pub fn suggest_aspect_hints<'a>(db: &BindingsGenerator<'a>, item: &Item<'a>) {
+    let mut targets = BTreeSet::<Rc<str>>::new();
+    targets.extend(db.defining_target(item.id()).map(|target| target.0));
+    targets.extend(item.owning_target().map(|target| target.0));
+    if let Item::Func(func) = item {
+        collect_type_targets(db, func.return_type(), &mut targets);
+        for param in func.params() {
+            collect_type_targets(db, param.type_(), &mut targets);
+        }
+    }
+    for target in targets {
+        if db
+            .ir()
+            .target_crubit_features(&BazelLabel(target.clone()))
+            .contains(CrubitFeature::Types)
+        {
+            continue;
+        }
+        db.errors().suggest(Suggestion::add_aspect_hint(
+            format!("enable Crubit on `{target}`"),
+            target,
+            CrubitFeature::Supported.aspect_hint(),
+        ));
+    }
+}
+
+/// Adds the owning targets of the declarations referenced by `cc_type` to `targets`.
+fn collect_type_targets(
+    db: &BindingsGenerator<'_>,
+    cc_type: &CcType,
+    targets: &mut BTreeSet<Rc<str>>,
+) {
+    match cc_type.variant() {
+        CcTypeVariant::Decl { id, template_args } => {
+            let decl = db.find_untyped_decl(*id);
+            if let Item::TypeAlias(alias) = decl {
+                collect_type_targets(db, alias.underlying_type(), targets);
+            }
+            targets.extend(decl.owning_target().map(|target| target.0));
+            for arg in template_args.iter().flat_map(|args| args.iter()) {
+                collect_type_targets(db, arg, targets);
+            }
+        }
+        CcTypeVariant::Pointer(pointer) => {
+            collect_type_targets(db, pointer.pointee_type(), targets)
+        }
+        CcTypeVariant::FuncPointer { param_and_return_types, .. } => {
+            for cc_type in param_and_return_types.iter() {
+                collect_type_targets(db, cc_type, targets);
+            }
+        }
+        CcTypeVariant::Primitive(_) | CcTypeVariant::Error(_) => {}
+    }
+}
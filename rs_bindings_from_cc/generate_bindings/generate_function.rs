@@ -119,6 +119,12 @@ fn trait_name_to_token_stream_removing_trait_record<'a>(
                 format_generic_params_replacing_by_self(db, &**params, self_type);
             quote! {#name_as_token_stream #formatted_params}
         }
+        Deref { .. } => {
+            quote! { ::core::ops::Deref }
+        }
+        DerefMut { .. } => {
+            quote! { ::core::ops::DerefMut }
+        }
         Delete => {
             quote! { ::operator::Delete }
         }
@@ -552,6 +558,119 @@ fn generate_cc_operator_index_mut_impls<'a>(
     Ok((func_name, impl_kind))
 }
 
+/// Returns the shape of the bindings for a member `operator*` or `operator->` of a
+/// smart-pointer-like class, i.e. one whose result is `[[clang::lifetimebound]]` to `*this`.
+///
+/// - The const overload becomes `core::ops::Deref`.
+/// - The non-const overload becomes `core::ops::DerefMut` if the class and the pointee are both
+///   `Unpin`, and there is a `Deref` impl with the same target. If either is not `Unpin`, it
+///   instead becomes a `deref_pin_mut` method, which returns a pinned mutable reference if the
+///   pointee is not `Unpin`.
+///
+/// Both operators map to the same traits, so a class that defines both only receives bindings for
+/// `operator*`. `Ok(None)` is returned for `operator->` in that case.
+fn api_func_shape_for_operator_deref<'a>(
+    db: &BindingsGenerator<'a>,
+    func: &Func<'a>,
+    maybe_record: Option<&Rc<Record<'a>>>,
+    return_type: &RsTypeKind<'a>,
+    op_name: &str,
+    errors: &Errors,
+) -> ErrorsOr<Option<(Ident, ImplKind<'a>)>> {
+    let (Some(record), Some(instance_method_metadata)) =
+        (maybe_record, func.instance_method_metadata())
+    else {
+        bail_to_errors!(
+            errors,
+            "operator{op_name} must be a member function to be bound as `Deref`"
+        );
+    };
+    if instance_method_metadata.reference() == ReferenceQualification::RValue {
+        bail_to_errors!(errors, "Rvalue-qualified operator{op_name} is not supported");
+    }
+    if !func.params().first().is_some_and(|this| this.clang_lifetimebound()) {
+        bail_to_errors!(
+            errors,
+            "operator{op_name} must be annotated with [[clang::lifetimebound]] to be bound as `Deref`"
+        );
+    }
+    let has_expected_return_kind = match func.return_type().variant() {
+        CcTypeVariant::Pointer(pointer_data) if op_name == "*" => {
+            matches!(pointer_data.kind(), PointerTypeKind::LValueRef)
+        }
+        CcTypeVariant::Pointer(pointer_data) => {
+            matches!(pointer_data.kind(), PointerTypeKind::Nullable | PointerTypeKind::NonNull)
+        }
+        _ => false,
+    };
+    let target_type = match return_type.referent() {
+        Some(target_type)
+            if has_expected_return_kind
+                && !matches!(target_type.unalias(), RsTypeKind::Primitive(Primitive::Void)) =>
+        {
+            Rc::new(target_type.clone())
+        }
+        _ => bail_to_errors!(
+            errors,
+            "operator{op_name} must return {} to be bound as `Deref`, found {}",
+            if op_name == "*" { "an lvalue reference" } else { "a pointer" },
+            db.cc_type_debug_name(func.return_type())
+        ),
+    };
+
+    let is_const = instance_method_metadata.is_const();
+    if op_name == "->" && deref_operator_shape(db, record, "*", is_const).is_some() {
+        return Ok(None);
+    }
+    if is_const {
+        let impl_kind = ImplKind::Trait {
+            record: record.clone(),
+            trait_name: TraitName::Deref { target_type },
+            impl_for: ImplFor::T,
+            format_first_param_as_self: true,
+            drop_return: false,
+            associated_return_type: Some(make_rs_ident("Target")),
+            force_const_reference_params: false,
+            always_public: false,
+        };
+        return Ok(Some((make_rs_ident("deref"), impl_kind)));
+    }
+    if !record.is_unpin() || !target_type.is_unpin() {
+        let impl_kind = ImplKind::Struct {
+            is_unsafe: false,
+            record: record.clone(),
+            format_first_param_as_self: true,
+            is_renamed_unpin_constructor: false,
+        };
+        return Ok(Some((make_rs_ident("deref_pin_mut"), impl_kind)));
+    }
+    let has_matching_deref = ["*", "->"].into_iter().any(|op_name| {
+        matches!(
+            deref_operator_shape(db, record, op_name, /* is_const= */ true),
+            Some((_, ImplKind::Trait { trait_name: TraitName::Deref { target_type: const_target }, .. }))
+                if const_target.all_static_lifetimes(true) == target_type.all_static_lifetimes(true)
+        )
+    });
+    if !has_matching_deref {
+        bail_to_errors!(
+            errors,
+            "Non-const operator{op_name} requires a const operator* or operator-> with the same \
+            pointee type, so that `DerefMut` can be implemented"
+        );
+    }
+    let impl_kind = ImplKind::Trait {
+        record: record.clone(),
+        trait_name: TraitName::DerefMut { target_type },
+        impl_for: ImplFor::T,
+        format_first_param_as_self: true,
+        drop_return: false,
+        associated_return_type: None,
+        force_const_reference_params: false,
+        always_public: false,
+    };
+    Ok(Some((make_rs_ident("deref_mut"), impl_kind)))
+}
+
 fn api_func_shape_for_operator_lt<'a>(
     db: &BindingsGenerator<'a>,
     func: &Func<'a>,
@@ -803,6 +922,16 @@ fn api_func_shape_for_operator<'a>(
             api_func_shape_for_operator_unary_plus(db, &param_types[0], errors).ok()
         }
         "[]" => api_func_shape_for_operator_index(db, func, param_types, return_type, errors).ok(),
+        "*" | "->" if param_types.len() == 1 => api_func_shape_for_operator_deref(
+            db,
+            func,
+            maybe_record,
+            return_type,
+            op.name(),
+            errors,
+        )
+        .ok()
+        .flatten(),
         _ => {
             let Some(op_metadata) =
                 OPERATOR_METADATA.by_cc_name_and_params.get(&(op.name(), param_types.len()))
@@ -1284,6 +1413,8 @@ fn api_func_shape<'a>(
     let maybe_record = match func.enclosing_item_id().map(|id| db.find_untyped_decl(id)) {
         None => None,
         Some(ir::Item::Namespace(_)) => None,
+        // `absl::Cord` is mapped to `absl_cord::Cord`, whose API is handwritten.
+        Some(ir::Item::Record(record)) if record.is_absl_cord() => return None,
         Some(ir::Item::Record(record)) => Some(record),
         // If the record was replaced by an existing Rust type using `crubit_internal_rust_type`,
         // don't generate any bindings for its functions. (That can't work!)
@@ -1518,7 +1649,7 @@ fn generate_func_body<'a>(
 
     if db
         .ir()
-        .target_crubit_features(func.owning_target())
+        .item_crubit_features(func.id(), func.owning_target())
         .contains(crubit_feature::CrubitFeature::ThunklessAccessors)
     {
         if let Some(MemberFuncSemantic::Getter(getter)) = func.semantic()
@@ -1652,6 +1783,21 @@ fn generate_func_body<'a>(
                     }
                 }
             };
+            // `operator->` returns a pointer into `*this`, which is bound as a reference.
+            if is_deref_operator(func)
+                && let CcTypeVariant::Pointer(pointer_data) = func.return_type().variant()
+                && matches!(
+                    pointer_data.kind(),
+                    PointerTypeKind::Nullable | PointerTypeKind::NonNull
+                )
+                && let RsTypeKind::Reference { referent, mutability, .. } = return_type
+            {
+                body = match mutability {
+                    Mutability::Const => quote! { &*#body },
+                    Mutability::Mut if referent.is_unpin() => quote! { &mut *#body },
+                    Mutability::Mut => quote! { ::core::pin::Pin::new_unchecked(&mut *#body) },
+                };
+            }
             // Discard the return value if requested (for example, when calling a C++
             // operator that returns a value from a Rust trait that returns
             // unit).
@@ -1742,7 +1888,7 @@ fn func_should_infer_lifetimes_of_references(func: &Func) -> bool {
         Constructor | ConversionOperator => true,
         Operator(op_name) => {
             match op_name.name() {
-                "==" | "!=" | "<=>" | "<" | "=" | "[]" => true,
+                "==" | "!=" | "<=>" | "<" | "=" | "[]" | "->" => true,
                 // TODO(b/333759161): Temporarily disable inference for `<<` and `>>`, as they
                 // creates conflicting libc++ impls for `long` and `long long`.
                 "<<" | ">>" => false,
@@ -1791,7 +1937,7 @@ fn rs_type_kinds_for_func<'a>(
     let infer_lifetimes = func_should_infer_lifetimes_of_references(func);
     let assume_lifetimes = db
         .ir()
-        .target_crubit_features(func.owning_target())
+        .item_crubit_features(func.id(), func.owning_target())
         .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
     // We can only reliably generate conversion operators when `AssumeThisLifetimes` is enabled,
     // which allows the implicit `this` pointer to be mapped as a reference rather than a raw
@@ -1839,7 +1985,7 @@ fn rs_type_kinds_for_func<'a>(
                 };
                 let lifetime = ptr.lifetime();
                 if db.ir()
-                    .target_crubit_features(func.owning_target())
+                    .item_crubit_features(func.id(), func.owning_target())
                     .contains(crubit_feature::CrubitFeature::AssumeThisLifetimes) {
                     infer_param_lifetimes = true;
                 }
@@ -1956,7 +2102,7 @@ pub fn generate_function<'a>(
     if matches!(func.cc_name(), ir::UnqualifiedIdentifier::ConversionOperator)
         && !db
             .ir()
-            .target_crubit_features(func.as_ref().owning_target())
+            .item_crubit_features(func.id(), func.as_ref().owning_target())
             .contains(crubit_feature::CrubitFeature::AssumeThisLifetimes)
     {
         bail!("Conversion operators are only supported when AssumeThisLifetimes is enabled");
@@ -1995,7 +2141,7 @@ pub fn generate_function<'a>(
     // a direct Rust getter or setter implementation.
     let is_direct_access = db
         .ir()
-        .target_crubit_features(func.as_ref().owning_target())
+        .item_crubit_features(func.id(), func.as_ref().owning_target())
         .contains(crubit_feature::CrubitFeature::ThunklessAccessors)
         && match func.semantic() {
             Some(MemberFuncSemantic::Getter(getter)) => {
@@ -2070,7 +2216,7 @@ pub fn generate_function<'a>(
     if let ImplKind::Trait { drop_return: true, ref record, .. } = impl_kind {
         let assume_lifetimes = db
             .ir()
-            .target_crubit_features(func.as_ref().owning_target())
+            .item_crubit_features(func.id(), func.as_ref().owning_target())
             .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
         let mut self_type = db.rs_type_kind((&**record).into())?;
         if assume_lifetimes {
@@ -2425,7 +2571,7 @@ pub fn generate_function<'a>(
 
             let assume_lifetimes = db
                 .ir()
-                .target_crubit_features(trait_record.as_ref().owning_target())
+                .item_crubit_features(trait_record.id(), trait_record.as_ref().owning_target())
                 .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
             // TODO(b/454627672): is it worth caching this?
             let transformed_trait_record;
@@ -2455,7 +2601,17 @@ pub fn generate_function<'a>(
 
             let mut extra_body = if let Some(name) = associated_return_type {
                 let associated_type;
-                if matches!(trait_name, TraitName::CcIndex { .. } | TraitName::CcIndexMut { .. }) {
+                if let TraitName::Deref { target_type } = &trait_name {
+                    // `Deref::deref` returns `&Self::Target`, rather than the associated type
+                    // itself.
+                    let container_lifetime = Lifetime::new(CONTAINER_LIFETIME_NAME);
+                    let target_type_tokens = target_type.to_token_stream(db);
+                    associated_type = quote! { type #name = #target_type_tokens; };
+                    quoted_return_type = quote! { &#container_lifetime Self::#name };
+                } else if matches!(
+                    trait_name,
+                    TraitName::CcIndex { .. } | TraitName::CcIndexMut { .. }
+                ) {
                     let is_rvalue = match func.instance_method_metadata() {
                         Some(metadata) => metadata.reference() == ReferenceQualification::RValue,
                         None => false,
@@ -2796,7 +2952,7 @@ fn collect_parent_lifetime_bindings(
             if let Item::Record(r) = decl {
                 let assume_lifetimes = db
                     .ir()
-                    .target_crubit_features(r.as_ref().owning_target())
+                    .item_crubit_features(r.id(), r.as_ref().owning_target())
                     .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
                 if assume_lifetimes {
                     let r = lifetime_defaults_transform_record(db, r)?;
@@ -2839,7 +2995,7 @@ fn function_signature<'a>(
     if let Some(derived_record) = derived_record.as_deref() {
         ensure!(
             db.ir()
-                .target_crubit_features(derived_record.owning_target())
+                .item_crubit_features(derived_record.id(), derived_record.owning_target())
                 .contains(crubit_feature::CrubitFeature::OoCasting),
             "upcasting requires //features:oo_casting, see b/216195042"
         );
@@ -2847,7 +3003,7 @@ fn function_signature<'a>(
 
     let assume_lifetimes = db
         .ir()
-        .target_crubit_features(func.owning_target())
+        .item_crubit_features(func.id(), func.owning_target())
         .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
 
     // TODO(b/454627672): is it worth caching this?
@@ -2861,6 +3017,8 @@ fn function_signature<'a>(
         }
     ) {
         adjust_signature_for_indexing_traits(db, func, return_type, param_types)?
+    } else if is_deref_operator(func) {
+        adjust_signature_for_deref_operators(db, func, return_type, param_types)?
     } else {
         None
     };
@@ -2980,9 +3138,10 @@ fn function_signature<'a>(
             trait_name: TraitName::CcIndex { .. } | TraitName::CcIndexMut { .. },
             ..
         }
-    ) {
-        // We manually set the container lifetime for indexing trait methods,
-        // so remove the `'__this` lifetime in this case.
+    ) || is_deref_operator(func)
+    {
+        // We manually set the container lifetime for indexing trait methods and dereference
+        // operators, so remove the `'__this` lifetime in this case.
         lifetimes.retain(|l| &*l.0 != "__this");
     }
 
@@ -3074,6 +3233,8 @@ fn function_signature<'a>(
         Some(
             TraitName::CcIndex { .. }
             | TraitName::CcIndexMut { .. }
+            | TraitName::Deref { .. }
+            | TraitName::DerefMut { .. }
             | TraitName::Other { .. }
             | TraitName::Delete,
         )
@@ -3198,7 +3359,7 @@ fn function_signature<'a>(
     // can be annotated around with `CRUBIT_UNSAFE_IGNORE_ATTR()`
     if let Some(unknown_attr) = func.unknown_attr() {
         let target = func.owning_target();
-        let enabled_features = db.ir().target_crubit_features(target);
+        let enabled_features = db.ir().item_crubit_features(func.id(), target);
         ensure!(
             enabled_features.contains(crubit_feature::CrubitFeature::Experimental),
             "crubit.rs/errors/unknown_attribute: unknown function attributes are only supported with experimental features enabled on \
@@ -3465,6 +3626,34 @@ fn has_matching_cc_index<'a>(
     })
 }
 
+/// Returns the shape of the bindings for the member `operator{op_name}` of `record` with the given
+/// constness, if it receives any.
+fn deref_operator_shape<'a>(
+    db: &BindingsGenerator<'a>,
+    record: &Record<'a>,
+    op_name: &str,
+    is_const: bool,
+) -> Option<(Ident, ImplKind<'a>)> {
+    record.children().iter().find_map(|item| {
+        if let Item::Func(f) = item
+            && let Some(metadata) = f.instance_method_metadata()
+            && metadata.is_const() == is_const
+            && let UnqualifiedIdentifier::Operator(op) = f.cc_name()
+            && op.name() == op_name
+            && f.params().len() == 1
+        {
+            let Ok((mut param_types, return_type)) = rs_type_kinds_for_func(db, f) else {
+                return None;
+            };
+            let errors = Errors::new();
+            let shape = api_func_shape(db, f, &mut param_types, &return_type, &errors);
+            errors.discard();
+            return shape;
+        }
+        None
+    })
+}
+
 /// Generates standard `std::ops::Index` and `std::ops::IndexMut`
 /// implementations that forward to the GAT-based `CcIndex` and `CcIndexMut`
 /// traits.
@@ -3610,6 +3799,37 @@ pub fn adjust_signature_for_indexing_traits<'a>(
     Ok(Some(transformed_func))
 }
 
+/// Returns whether `func` is a member `operator*` or `operator->` without arguments, which are
+/// bound by `api_func_shape_for_operator_deref`.
+fn is_deref_operator(func: &Func) -> bool {
+    func.instance_method_metadata().is_some()
+        && func.params().len() == 1
+        && matches!(func.rs_name(), UnqualifiedIdentifier::Operator(op) if matches!(op.name(), "*" | "->"))
+}
+
+/// Adjusts the function signature for `operator*` and `operator->`. As for the indexing traits,
+/// both the self parameter and the returned reference use the container lifetime ('ctnr), and
+/// a returned pointer is turned into a reference.
+///
+/// Returns the transformed `Func` if any transformations were applied.
+fn adjust_signature_for_deref_operators<'a>(
+    db: &BindingsGenerator<'a>,
+    func: &Func<'a>,
+    return_type: &mut RsTypeKind<'a>,
+    param_types: &mut [RsTypeKind<'a>],
+) -> Result<Option<Func<'a>>> {
+    let transformed_func =
+        adjust_signature_for_indexing_traits(db, func, return_type, param_types)?;
+    let RsTypeKind::Reference { lifetime, .. } = return_type else {
+        bail!(
+            "Expected operator* or operator-> to return a reference, found {}",
+            return_type.display(db)
+        );
+    };
+    *lifetime = Lifetime::new(CONTAINER_LIFETIME_NAME);
+    Ok(transformed_func)
+}
+
 /// This is an implementation of a method of `BindingsGenerator<'db>` - for more
 /// information please see the corresponding doc comment in `db.rs`.
 pub fn mangled_name_counts(db: &BindingsGenerator<'_>) -> Rc<HashMap<Rc<str>, usize>> {
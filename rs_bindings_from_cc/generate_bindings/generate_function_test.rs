@@ -941,6 +941,142 @@ fn test_impl_index_and_index_mut_with_lifetimes_pin() -> Result<()> {
     Ok(())
 }
 
+#[gtest]
+fn test_impl_deref_and_deref_mut_for_operator_star() -> Result<()> {
+    let proto = ir_proto_from_assumed_lifetimes_cc(
+        r#"
+        struct Pointee final { int i; };
+        struct SmartPtr final {
+            const Pointee& operator*() const [[clang::lifetimebound]];
+            Pointee& operator*() [[clang::lifetimebound]];
+            Pointee* p;
+        };"#,
+    )?;
+
+    let ir = make_test_ir_dependency(&proto, Some("assume_lifetimes"))?;
+    let rs_api = generate_bindings_tokens_for_test(ir)?.rs_api;
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            impl ::core::ops::Deref for SmartPtr {
+                type Target = crate::Pointee;
+                #[inline(always)]
+                fn deref<'ctnr>(&'ctnr self) -> &'ctnr Self::Target {
+                    unsafe { crate::detail::__rust_thunk___ZNK8SmartPtrdeEv(self) }
+                }
+            }
+        }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            impl ::core::ops::DerefMut for SmartPtr {
+                #[inline(always)]
+                fn deref_mut<'ctnr>(&'ctnr mut self) -> &'ctnr mut crate::Pointee {
+                    unsafe { crate::detail::__rust_thunk___ZN8SmartPtrdeEv(self) }
+                }
+            }
+        }
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_impl_deref_for_operator_arrow() -> Result<()> {
+    let proto = ir_proto_from_assumed_lifetimes_cc(
+        r#"
+        struct Pointee final { int i; };
+        struct SmartPtr final {
+            const Pointee* operator->() const [[clang::lifetimebound]];
+            Pointee* p;
+        };"#,
+    )?;
+
+    let ir = make_test_ir_dependency(&proto, Some("assume_lifetimes"))?;
+    let rs_api = generate_bindings_tokens_for_test(ir)?.rs_api;
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            impl ::core::ops::Deref for SmartPtr {
+                type Target = crate::Pointee;
+                #[inline(always)]
+                fn deref<'ctnr>(&'ctnr self) -> &'ctnr Self::Target {
+                    unsafe { &*crate::detail::__rust_thunk___ZNK8SmartPtrptEv(self) }
+                }
+            }
+        }
+    );
+    Ok(())
+}
+
+/// Verifies that `operator->` doesn't receive bindings when `operator*` already provides `Deref`.
+#[gtest]
+fn test_impl_deref_prefers_operator_star_over_operator_arrow() -> Result<()> {
+    let proto = ir_proto_from_assumed_lifetimes_cc(
+        r#"
+        struct Pointee final { int i; };
+        struct SmartPtr final {
+            const Pointee& operator*() const [[clang::lifetimebound]];
+            const Pointee* operator->() const [[clang::lifetimebound]];
+            Pointee* p;
+        };"#,
+    )?;
+
+    let ir = make_test_ir_dependency(&proto, Some("assume_lifetimes"))?;
+    let rs_api = generate_bindings_tokens_for_test(ir)?.rs_api;
+    assert_rs_matches!(rs_api, quote! { __rust_thunk___ZNK8SmartPtrdeEv(self) });
+    assert_rs_not_matches!(rs_api, quote! { __rust_thunk___ZNK8SmartPtrptEv(self) });
+    Ok(())
+}
+
+#[gtest]
+fn test_no_deref_without_lifetimebound() -> Result<()> {
+    let proto = ir_proto_from_assumed_lifetimes_cc(
+        r#"
+        struct Pointee final { int i; };
+        struct SmartPtr final {
+            Pointee& operator*() const;
+            Pointee* p;
+        };"#,
+    )?;
+
+    let ir = make_test_ir_dependency(&proto, Some("assume_lifetimes"))?;
+    let rs_api = generate_bindings_tokens_for_test(ir)?.rs_api;
+    assert_rs_not_matches!(rs_api, quote! { ::core::ops::Deref });
+    Ok(())
+}
+
+/// Verifies that the non-const `operator*` of a non-`Unpin` pointee becomes `deref_pin_mut`
+/// instead of `DerefMut`.
+#[gtest]
+fn test_deref_pin_mut_for_non_unpin_pointee() -> Result<()> {
+    let proto = ir_proto_from_assumed_lifetimes_cc(
+        r#"
+        struct Pointee final {
+            ~Pointee(); // non-trivial destructor makes it !Unpin
+            int i;
+        };
+        struct SmartPtr final {
+            const Pointee& operator*() const [[clang::lifetimebound]];
+            Pointee& operator*() [[clang::lifetimebound]];
+            Pointee* p;
+        };"#,
+    )?;
+
+    let ir = make_test_ir_dependency(&proto, Some("assume_lifetimes"))?;
+    let rs_api = generate_bindings_tokens_for_test(ir)?.rs_api;
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub fn deref_pin_mut<'ctnr>(&'ctnr mut self) -> ::core::pin::Pin<&'ctnr mut crate::Pointee> {
+                unsafe { crate::detail::__rust_thunk___ZN8SmartPtrdeEv(self) }
+            }
+        }
+    );
+    assert_rs_not_matches!(rs_api, quote! { ::core::ops::DerefMut });
+    Ok(())
+}
+
 #[gtest]
 fn test_impl_eq_for_member_function() -> Result<()> {
     let proto = ir_proto_from_assumed_lifetimes_cc(
@@ -2798,3 +2934,302 @@ fn test_thunkless_accessors_disabled() -> Result<()> {
     assert_cc_matches!(rs_api_impl, quote! { __rust_thunk___ZN1S5set_xEi });
     Ok(())
 }
+
+#[gtest]
+fn test_absl_cord() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"#pragma clang lifetime_elision
+        namespace absl {
+        class Cord {
+         public:
+          Cord();
+          Cord(const Cord&);
+          ~Cord();
+          int size() const;
+         private:
+          char data_[16];
+        };
+        }  // namespace absl
+
+        int CordSize(const absl::Cord& cord);
+        absl::Cord MakeCord();
+        "#,
+    )?;
+    let ir = make_test_ir(&proto)?;
+    let rs_api = generate_bindings_tokens_for_test(ir)?.rs_api;
+    assert_rs_matches!(rs_api, quote! { pub fn CordSize<'a>(cord: &'a ::absl_cord::Cord) });
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub fn MakeCord() -> impl ::ctor::Ctor<Output = ::absl_cord::Cord, Error = ::ctor::Infallible>
+        }
+    );
+    // `absl_cord::Cord` is handwritten, so neither the struct nor its methods are generated.
+    assert_rs_not_matches!(rs_api, quote! { pub struct Cord });
+    assert_rs_not_matches!(rs_api, quote! { fn size });
+    Ok(())
+}
+
+#[gtest]
+fn test_std_filesystem_path() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"#pragma clang lifetime_elision
+        namespace std::filesystem {
+        inline namespace __cxx11 {
+        class path {
+         public:
+          path();
+          path(const path&);
+          ~path();
+         private:
+          char* data_;
+        };
+        }  // namespace __cxx11
+        }  // namespace std::filesystem
+
+        bool Exists(const std::filesystem::path& p);
+        std::filesystem::path TempDir();
+        void Normalize(std::filesystem::path& p);
+        "#,
+    )?;
+    let ir = make_test_ir(&proto)?;
+    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens_for_test(ir)?;
+    assert_rs_matches!(rs_api, quote! { pub fn Exists(p: &::std::path::Path) -> bool });
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            ::bridge_rust::unstable_encode!(
+                @::cc_std::std::PathRefAbi(::core::marker::PhantomData),
+                ::cc_std::std::PathRefAbi<'_>,
+                p
+            )
+        }
+    );
+    assert_rs_matches!(rs_api, quote! { pub fn TempDir() -> ::std::path::PathBuf });
+    // Only `const path&` can be bridged: the C++ callee receives a temporary.
+    assert_rs_not_matches!(rs_api, quote! { pub fn Normalize });
+    assert_cc_matches!(rs_api_impl, quote! { Exists(::crubit::PathAbi().Decode(__p_decoder)) });
+    Ok(())
+}
+
+#[gtest]
+fn test_rust_box() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"#pragma clang lifetime_elision
+        namespace std {
+        template <typename T>
+        struct default_delete {};
+        template <typename T, typename D = default_delete<T>>
+        class unique_ptr {
+         public:
+          unique_ptr();
+          unique_ptr(unique_ptr&&);
+          ~unique_ptr();
+         private:
+          T* ptr_;
+        };
+        }  // namespace std
+
+        namespace rs_std {
+        template <typename T>
+        struct BoxDeleter {
+          void operator()(T* ptr) const;
+        };
+        }  // namespace rs_std
+
+        std::unique_ptr<int, rs_std::BoxDeleter<int>> MakeBox();
+        void TakeBox(std::unique_ptr<int, rs_std::BoxDeleter<int>> b);
+        "#,
+    )?;
+    let ir = make_test_ir(&proto)?;
+    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens_for_test(ir)?;
+    assert_rs_matches!(rs_api, quote! { pub fn MakeBox() -> ::alloc::boxed::Box<::ffi_11::c_int> });
+    assert_rs_matches!(rs_api, quote! { pub fn TakeBox(b: ::alloc::boxed::Box<::ffi_11::c_int>) });
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            ::bridge_rust::unstable_encode!(
+                @::bridge_rust::BoxAbi::<::ffi_11::c_int>(::core::marker::PhantomData),
+                ::bridge_rust::BoxAbi<::ffi_11::c_int>,
+                b
+            )
+        }
+    );
+    assert_cc_matches!(
+        rs_api_impl,
+        quote! { TakeBox(::crubit::BoxAbi<int>().Decode(__b_decoder)) }
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_std_span() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"
+        namespace std {
+        inline constexpr __SIZE_TYPE__ dynamic_extent = -1;
+        template <typename T, __SIZE_TYPE__ Extent = dynamic_extent>
+        class span {
+          T* data_;
+          __SIZE_TYPE__ size_;
+        };
+        }  // namespace std
+
+        int Sum(std::span<const int> s);
+        std::span<int> Mutate(std::span<int> s);
+        "#,
+    )?;
+    let rs_api = generate_bindings_tokens_for_test(make_test_ir(&proto)?)?.rs_api;
+    // Spans are spelled as layout-guaranteed wrappers, never as Rust slice pointers.
+    assert_rs_matches!(
+        rs_api,
+        quote! { pub fn Sum(mut s: ::cc_std::std::span<'_, ::ffi_11::c_int>) -> ::ffi_11::c_int }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub fn Mutate(
+                mut s: ::cc_std::std::span_mut<'_, ::ffi_11::c_int>
+            ) -> ::cc_std::std::raw_span_mut<::ffi_11::c_int>
+        }
+    );
+    assert_rs_not_matches!(rs_api, quote! { [::ffi_11::c_int] });
+    Ok(())
+}
+
+#[gtest]
+fn test_function_ref() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"#pragma clang lifetime_elision
+        namespace absl {
+        template <typename T>
+        class FunctionRef;
+        template <typename R, typename... Args>
+        class FunctionRef<R(Args...)> {
+         public:
+          R operator()(Args... args) const;
+         private:
+          const void* obj_;
+          R (*invoker_)(const void*, Args...);
+        };
+        template <typename R, typename... Args>
+        class FunctionRef<R(Args...) const> {
+         public:
+          R operator()(Args... args) const;
+         private:
+          const void* obj_;
+          R (*invoker_)(const void*, Args...);
+        };
+        }  // namespace absl
+
+        int CallTwice(absl::FunctionRef<int(int)> f);
+        [[clang::annotate("crubit_override_unsafe", false)]]
+        int CallTwiceMarkedSafe(absl::FunctionRef<int(int)> f);
+        void CallConst(absl::FunctionRef<void() const> f);
+        absl::FunctionRef<void()> ReturnFunctionRef();
+        "#,
+    )?;
+    let ir = make_test_ir(&proto)?;
+    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens_for_test(ir)?;
+    // The closure is borrowed, so it needs neither `Send`, `Sync`, nor `'static`, and C++'s
+    // non-const call operator maps to `FnMut` rather than `Fn`. Since C++ could still call it
+    // concurrently, the function is `unsafe` unless it is marked safe.
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub unsafe fn CallTwice(
+                f: &mut dyn ::core::ops::FnMut(::ffi_11::c_int) -> ::ffi_11::c_int
+            ) -> ::ffi_11::c_int
+        }
+    );
+    assert_that!(
+        rs_api.to_string(),
+        contains_substring(
+            "* `f`: the `FnMut` closure may only be called from the calling thread, one call at a \
+             time, and not after the function returns"
+        )
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub fn CallTwiceMarkedSafe(
+                f: &mut dyn ::core::ops::FnMut(::ffi_11::c_int) -> ::ffi_11::c_int
+            ) -> ::ffi_11::c_int
+        }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            ::dyn_callable_rs::FunctionRefAbi::<'_, dyn ::core::ops::FnMut(::ffi_11::c_int) -> ::ffi_11::c_int>(
+                ::core::marker::PhantomData
+            )
+        }
+    );
+    assert_rs_not_matches!(rs_api, quote! { ::core::marker::Sync });
+    assert_rs_not_matches!(rs_api, quote! { ::dyn_callable_rs::manager });
+    assert_rs_not_matches!(rs_api, quote! { pub fn CallConst });
+    assert_rs_not_matches!(rs_api, quote! { pub fn ReturnFunctionRef });
+    assert_cc_matches!(
+        rs_api_impl,
+        quote! { ::rs_std::internal_dyn_callable::FunctionRefAbi<int(int)> }
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_move_only_function() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"#pragma clang lifetime_elision
+        namespace std {
+        template <typename... T>
+        class move_only_function;
+        template <typename R, typename... Args>
+        class move_only_function<R(Args...) &&> {
+         public:
+          move_only_function(move_only_function&&);
+          ~move_only_function();
+          R operator()(Args... args) &&;
+         private:
+          void* storage_[4];
+        };
+        }  // namespace std
+
+        void CallOnce(std::move_only_function<void() &&> f);
+        std::move_only_function<int(int) &&> MakeOnce();
+        "#,
+    )?;
+    let ir = make_test_ir(&proto)?;
+    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens_for_test(ir)?;
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub fn CallOnce(
+                f: ::alloc::boxed::Box<
+                    dyn ::core::ops::FnOnce() + ::core::marker::Send + ::core::marker::Sync + 'static
+                >
+            )
+        }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub fn MakeOnce() -> ::alloc::boxed::Box<
+                dyn ::core::ops::FnOnce(::ffi_11::c_int) -> ::ffi_11::c_int
+                    + ::core::marker::Send + ::core::marker::Sync + 'static
+            >
+        }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            |raw_move_only_function: ::dyn_callable_rs::RawCppCallable| -> ::alloc::boxed::Box<...>
+        }
+    );
+    assert_rs_matches!(rs_api, quote! { raw_move_only_function.as_ptr() });
+    assert_cc_matches!(
+        rs_api_impl,
+        quote! { ::rs_std::internal_dyn_callable::MoveOnlyFunctionAbi<int(int) &&> }
+    );
+    assert_cc_matches!(rs_api_impl, quote! { ::std::move_only_function<int(int) &&>* f });
+    Ok(())
+}
@@ -48,6 +48,12 @@ pub fn can_skip_cc_thunk<'a>(db: &BindingsGenerator<'a>, func: &Func<'a>) -> boo
     if func.is_member_or_descendant_of_class_template() {
         return false;
     }
+    // ## Function template specializations requested by `cc_template_fn!`
+    //
+    // Same as above: the specialization may not be instantiated anywhere else.
+    if func.cc_template_fn_instantiation().is_some() {
+        return false;
+    }
     // ## Virtual functions
     //
     // When calling virtual `A::Method()`, it's not necessarily the case that we'll
@@ -136,7 +142,7 @@ pub fn generate_function_thunk<'a>(
 ) -> Result<Thunk> {
     let assume_lifetimes = db
         .ir()
-        .target_crubit_features(func.owning_target())
+        .item_crubit_features(func.id(), func.owning_target())
         .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
 
     // TODO(b/454627672): is it worth caching this?
@@ -240,7 +246,7 @@ pub fn generate_function_thunk<'a>(
 // The escaping scheme is not collision-free, i.e. two different inputs may map to the same output.
 // In practice, though, collisions are extremely unlikely, and other aspects of the way we create
 // thunk names may also cause (very unlikely) collisions.
-fn ident_fragment_from_mangled_name(mangled_name: &str) -> Cow<'_, str> {
+pub fn ident_fragment_from_mangled_name(mangled_name: &str) -> Cow<'_, str> {
     // LLVM identifiers use the `\01` prefix to suppress mangling:
     // https://llvm.org/docs/LangRef.html#identifiers
     // We won't be passing the name to LLVM anyway, so we simply strip the prefix if present.
@@ -307,13 +313,17 @@ fn generate_function_assertion_for_identifier<'a>(
     func: &Func<'a>,
     id: &Identifier<'a>,
 ) -> Result<ThunkImpl> {
-    let features = db.ir().target_crubit_features(func.owning_target());
-    let fn_ident = format_nonportable_cc_ident(id.as_str())?;
-    let mut namespace_qualifier = db.namespace_qualifier(func);
-    // Keep goldens the same.
-    namespace_qualifier.use_leading_colons = true;
-    let path_to_func = namespace_qualifier.format_for_cc(features)?;
-    let implementation_function = quote! { #path_to_func #fn_ident };
+    let implementation_function = if let Some(instantiation) = func.cc_template_fn_instantiation() {
+        syn::parse_str::<TokenStream>(instantiation)?
+    } else {
+        let features = db.ir().item_crubit_features(func.id(), func.owning_target());
+        let fn_ident = format_nonportable_cc_ident(id.as_str())?;
+        let mut namespace_qualifier = db.namespace_qualifier(func);
+        // Keep goldens the same.
+        namespace_qualifier.use_leading_colons = true;
+        let path_to_func = namespace_qualifier.format_for_cc(features)?;
+        quote! { #path_to_func #fn_ident }
+    };
     let method_qualification;
     let member_function_prefix;
     let func_params;
@@ -443,13 +453,21 @@ pub fn generate_function_thunk_impl<'a>(
             quote! { operator #name }
         }
         UnqualifiedIdentifier::Identifier(id) => {
-            let features = db.ir().target_crubit_features(func.owning_target());
-            let fn_ident = format_nonportable_cc_ident(id.as_str())?;
-            let namespace_qualifier = db.namespace_qualifier(func).format_for_cc(features)?;
-            if func.instance_method_metadata().is_some() || func.adl_enclosing_record().is_some() {
-                quote! {#fn_ident}
+            if let Some(instantiation) = func.cc_template_fn_instantiation() {
+                // The specialization is spelled out in full, because calling it by name could
+                // deduce different template arguments (or pick a different overload).
+                syn::parse_str::<TokenStream>(instantiation)?
             } else {
-                quote! { #namespace_qualifier #fn_ident }
+                let features = db.ir().item_crubit_features(func.id(), func.owning_target());
+                let fn_ident = format_nonportable_cc_ident(id.as_str())?;
+                let namespace_qualifier = db.namespace_qualifier(func).format_for_cc(features)?;
+                if func.instance_method_metadata().is_some()
+                    || func.adl_enclosing_record().is_some()
+                {
+                    quote! {#fn_ident}
+                } else {
+                    quote! { #namespace_qualifier #fn_ident }
+                }
             }
         }
         // Use `destroy_at` to avoid needing to spell out the class name. Destructor identiifers
@@ -519,7 +537,7 @@ pub fn generate_cc_thunk_parts<'a>(
     func: &Func<'a>,
     kind: ThunkCallKind,
 ) -> Result<CcThunkParts> {
-    let features = db.ir().target_crubit_features(func.owning_target());
+    let features = db.ir().item_crubit_features(func.id(), func.owning_target());
     let mut param_idents = Vec::new();
     let mut param_types = Vec::new();
     let mut conversion_stmts = quote! {};
@@ -578,14 +596,18 @@ pub fn generate_cc_thunk_parts<'a>(
         .iter()
         .map(|p| {
             let ident = format_nonportable_cc_ident(p.identifier().as_str())?;
+            let rs_type_kind = db.rs_type_kind(p.type_().clone())?;
             match p.type_().variant() {
-                CcTypeVariant::Pointer(pointer) => match pointer.kind() {
-                    PointerTypeKind::RValueRef => Ok(quote! { std::move(*#ident) }),
-                    PointerTypeKind::LValueRef => Ok(quote! { *#ident }),
-                    PointerTypeKind::Nullable
-                    | PointerTypeKind::NonNull
-                    | PointerTypeKind::Owned => Ok(quote! { #ident }),
-                },
+                // References to bridge types (`const std::filesystem::path&`) are decoded below.
+                CcTypeVariant::Pointer(pointer) if !rs_type_kind.is_bridge_type() => {
+                    match pointer.kind() {
+                        PointerTypeKind::RValueRef => Ok(quote! { std::move(*#ident) }),
+                        PointerTypeKind::LValueRef => Ok(quote! { *#ident }),
+                        PointerTypeKind::Nullable
+                        | PointerTypeKind::NonNull
+                        | PointerTypeKind::Owned => Ok(quote! { #ident }),
+                    }
+                }
                 CcTypeVariant::FuncPointer { non_null, .. } => {
                     if *non_null {
                         Ok(quote! { *#ident })
@@ -594,7 +616,6 @@ pub fn generate_cc_thunk_parts<'a>(
                     }
                 }
                 _ => {
-                    let rs_type_kind = db.rs_type_kind(p.type_().clone())?;
                     // non-Unpin types are wrapped by a pointer in the thunk.
                     match rs_type_kind.passing_convention() {
                         PassingConvention::ComposablyBridged => {
@@ -6,11 +6,16 @@
 use arc_anyhow::{Context, Result};
 use code_gen_utils::{format_nonportable_cc_type_name, make_rs_ident, make_rs_lifetime_ident};
 use cpp_type_name::{cpp_tagless_type_name_for_record, cpp_type_name_for_record};
+use crubit_abi_type::{
+    CrubitAbiTypeToCppExprTokens, CrubitAbiTypeToCppTokens, CrubitAbiTypeToRustExprTokens,
+    CrubitAbiTypeToRustTokens,
+};
 use database::code_snippet::{
-    ApiSnippets, AssertableTrait, Assertion, BitPadding, BitfieldComment, CfiEncodingAttr,
-    DeleteImpl, DeprecatedAttr, DeriveAttr, DisplayImpl, DocCommentAttr, Feature, FieldDefinition,
-    FieldType, GeneratedItem, MustUseAttr, NoUniqueAddressAccessor, RecursivelyPinnedAttr,
-    SizeofImpl, StructOrUnion, Thunk, ThunkImpl, UpcastImpl, UpcastImplBody, Visibility,
+    AnonymousMemberAccessor, ApiSnippets, AssertableTrait, Assertion, BitPadding, BitfieldComment,
+    BridgeFieldAccessor, CfiEncodingAttr, DeleteImpl, DeprecatedAttr, DeriveAttr, DisplayImpl,
+    DocCommentAttr, Feature, FieldDefinition, FieldType, GeneratedItem, MustUseAttr,
+    NoUniqueAddressAccessor, RecursivelyPinnedAttr, SizeofImpl, StructOrUnion, Thunk, ThunkImpl,
+    UpcastImpl, UpcastImplBody, Visibility,
 };
 use database::rs_snippet::{should_derive_clone, RsTypeKind};
 use database::{intern, BindingsGenerator};
@@ -64,7 +69,9 @@ pub fn generate_incomplete_record<'a>(
         .has_bindings(ir::Item::IncompleteRecord(incomplete_record.clone()))
         .unwrap_or_default()
         .visibility;
-    let features = db.ir().target_crubit_features(&incomplete_record.owning_target().unwrap());
+    let features = db
+        .ir()
+        .item_crubit_features(incomplete_record.id(), &incomplete_record.owning_target().unwrap());
     let cc_type = format_nonportable_cc_type_name(incomplete_record.cc_name().as_str())
         .expect("IncompleteRecord has invalid type name");
     let namespace_qualifier = db.namespace_qualifier(&incomplete_record).format_for_cc(features)?;
@@ -88,6 +95,100 @@ fn make_rs_field_ident(field: &Field<'_>, field_index: usize) -> Ident {
     }
 }
 
+/// Returns the non-bitfield fields of `record`, together with their Rust identifiers.
+///
+/// This numbers unnamed fields the same way as `generate_record`: zero-sized fields are skipped,
+/// and consecutive bitfields share a single index.
+fn rs_field_idents<'a, 'pb>(record: &'a Record<'pb>) -> Vec<(&'a Field<'pb>, Ident)> {
+    record
+        .fields()
+        .iter()
+        .filter(|field| field.size() != 0)
+        .map(|field| if field.is_bitfield() { None } else { Some(field) })
+        .coalesce(|first, second| match (first, second) {
+            (None, None) => Ok(None),
+            pair => Err(pair),
+        })
+        .enumerate()
+        .filter_map(|(field_index, field)| {
+            let field = field?;
+            Some((field, make_rs_field_ident(field, field_index)))
+        })
+        .collect()
+}
+
+/// Returns the record of `field`'s type, if it is an anonymous struct or union.
+fn anonymous_record_of_field<'a>(
+    db: &BindingsGenerator<'a>,
+    field: &Field<'a>,
+) -> Option<Rc<Record<'a>>> {
+    let CcTypeVariant::Decl { id, .. } = field.type_().variant() else {
+        return None;
+    };
+    db.find_decl::<Rc<Record>>(*id).ok().filter(|record| record.is_anonymous()).cloned()
+}
+
+/// Returns the accessors for the members of anonymous structs and unions in `record`, which C++
+/// makes accessible as if they were members of `record` itself.
+///
+/// `field_path` is the path of Rust fields leading to `record`, and `is_in_union` is whether any
+/// record along that path is a union.
+fn anonymous_member_accessors<'a>(
+    db: &BindingsGenerator<'a>,
+    record: &Record<'a>,
+    field_path: &[Ident],
+    is_in_union: bool,
+    has_mut_accessor: bool,
+    accessors: &mut Vec<AnonymousMemberAccessor>,
+) {
+    for (field, rs_ident) in rs_field_idents(record) {
+        if field.access() != AccessSpecifier::Public || field.is_mutable() {
+            continue;
+        }
+        let Ok(type_kind) = get_field_rs_type_kind_for_layout(db, record, field) else {
+            continue;
+        };
+        let mut member_path = field_path.to_vec();
+        member_path.push(rs_ident);
+        match field.cpp_identifier() {
+            Some(cpp_identifier) => {
+                // Members of anonymous records aren't wrapped in `ManuallyDrop` or `Cell`
+                // whenever they are `Copy`, so only these can be returned directly.
+                if !type_kind.implements_copy() {
+                    continue;
+                }
+                accessors.push(AnonymousMemberAccessor {
+                    doc_comment: generate_doc_comment(
+                        field.doc_comment(),
+                        None,
+                        None,
+                        db.is_golden_test(),
+                        db.kythe_annotations(),
+                    ),
+                    field_path: member_path,
+                    ident: make_rs_ident(cpp_identifier.as_str()),
+                    type_: type_kind.to_token_stream(db),
+                    is_in_union,
+                    has_mut_accessor,
+                });
+            }
+            None => {
+                let Some(anonymous_record) = anonymous_record_of_field(db, field) else {
+                    continue;
+                };
+                anonymous_member_accessors(
+                    db,
+                    &anonymous_record,
+                    &member_path,
+                    is_in_union || anonymous_record.is_union(),
+                    has_mut_accessor,
+                    accessors,
+                );
+            }
+        }
+    }
+}
+
 fn is_debug_formattable(db: &BindingsGenerator, ty: &ir::CcType) -> bool {
     match ty.variant() {
         ir::CcTypeVariant::Primitive(_)
@@ -136,7 +237,7 @@ fn get_field_rs_type_kind_for_layout<'a>(
             for target in
                 db.defining_target(record.id()).as_ref().into_iter().chain([record.owning_target()])
             {
-                let enabled_features = ir.target_crubit_features(target);
+                let enabled_features = ir.item_crubit_features(record.id(), target);
                 ensure!(
                     enabled_features.contains(crubit_feature::CrubitFeature::Experimental),
                     "crubit.rs/errors/unknown_attribute: unknown field attributes are only \
@@ -160,7 +261,7 @@ fn get_field_rs_type_kind_for_layout<'a>(
     for target in
         db.defining_target(record.id()).as_ref().into_iter().chain([record.owning_target()])
     {
-        let enabled_features = ir.target_crubit_features(target);
+        let enabled_features = ir.item_crubit_features(record.id(), target);
         let reasons = type_kind.missing_feature_descriptions_of_type(target, enabled_features);
         ensure!(reasons.is_empty(), reasons.join(", "));
     }
@@ -174,7 +275,7 @@ fn get_field_rs_type_kind_for_layout<'a>(
         for target in
             db.defining_target(record.id()).as_ref().into_iter().chain([record.owning_target()])
         {
-            let enabled_features = ir.target_crubit_features(target);
+            let enabled_features = ir.item_crubit_features(record.id(), target);
             ensure!(
                 enabled_features.contains(crubit_feature::CrubitFeature::Experimental),
                 "nontrivial fields would be destroyed in the wrong order"
@@ -593,9 +694,17 @@ pub fn generate_record<'a>(
     let head_padding =
         if head_padding > 0 || !allow_direct_init { Some(head_padding) } else { None };
 
-    api_snippets.cc_details.push(cc_struct_layout_assertion(db, &record)?);
+    // Anonymous records can't be named in C++. Their layout is instead checked through the
+    // offsets of their members in the enclosing record.
+    if !record.is_anonymous() {
+        api_snippets.cc_details.push(cc_struct_layout_assertion(db, &record)?);
+    }
 
-    let fully_qualified_cc_name = cpp_tagless_type_name_for_record(&record, db)?.to_string();
+    let fully_qualified_cc_name = if record.is_anonymous() {
+        None
+    } else {
+        Some(cpp_tagless_type_name_for_record(&record, db)?.to_string())
+    };
 
     let mut items = vec![];
     let mut nested_items = vec![];
@@ -644,9 +753,9 @@ pub fn generate_record<'a>(
 
     // Both the template definition and its instantiation should enable experimental
     // features.
-    let mut crubit_features = ir.target_crubit_features(record.as_ref().owning_target());
+    let mut crubit_features = ir.item_crubit_features(record.id(), record.as_ref().owning_target());
     if let Some(defining_target) = db.defining_target(record.id()) {
-        crubit_features |= ir.target_crubit_features(&defining_target);
+        crubit_features |= ir.item_crubit_features(record.id(), &defining_target);
     }
     let mut upcast_impls = vec![];
     let assume_lifetimes = crubit_features.contains(crubit_feature::CrubitFeature::AssumeLifetimes);
@@ -677,6 +786,9 @@ pub fn generate_record<'a>(
         api_snippets.cc_details.push(thunk_impl);
         operator_delete_impl = Some(delete);
     }
+    let bridge_field_accessors = cc_struct_bridge_field_accessors(db, &record)?;
+    api_snippets.thunks.extend(bridge_field_accessors.thunks);
+    api_snippets.cc_details.extend(bridge_field_accessors.thunk_impls);
     let stubbed_lifetime_params = if lifetime_params.is_empty() {
         quote! {}
     } else {
@@ -765,34 +877,59 @@ pub fn generate_record<'a>(
         } else {
             vec![]
         };
-    let incomplete_definition = if crubit_features.contains(crubit_feature::CrubitFeature::Wrapper)
-    {
-        Some(quote! {
-            forward_declare::unsafe_define!(forward_declare::symbol!(#fully_qualified_cc_name), #qualified_ident #stubbed_lifetime_params);
-        })
-    } else {
-        None
+    let incomplete_definition = match &fully_qualified_cc_name {
+        Some(fully_qualified_cc_name)
+            if crubit_features.contains(crubit_feature::CrubitFeature::Wrapper) =>
+        {
+            Some(quote! {
+                forward_declare::unsafe_define!(forward_declare::symbol!(#fully_qualified_cc_name), #qualified_ident #stubbed_lifetime_params);
+            })
+        }
+        _ => None,
     };
 
-    let cxx_impl = if fully_qualified_cc_name.contains('<') {
+    let cxx_impl = match &fully_qualified_cc_name {
         // cxx can't parse templated type names.
         // In particular, it can only parse ::-delimited idents.
-        None
-    } else {
-        Some(database::code_snippet::CxxExternTypeImpl {
-            id: intern!(db.interner(), "{fully_qualified_cc_name}"),
-            kind: if record.is_unpin() {
-                database::code_snippet::CxxKind::Trivial
-            } else {
-                database::code_snippet::CxxKind::Opaque
-            },
-        })
+        Some(fully_qualified_cc_name) if !fully_qualified_cc_name.contains('<') => {
+            Some(database::code_snippet::CxxExternTypeImpl {
+                id: intern!(db.interner(), "{fully_qualified_cc_name}"),
+                kind: if record.is_unpin() {
+                    database::code_snippet::CxxKind::Trivial
+                } else {
+                    database::code_snippet::CxxKind::Opaque
+                },
+            })
+        }
+        _ => None,
     };
 
+    let mut anonymous_member_accessors_for_record = vec![];
+    for (field, rs_ident) in rs_field_idents(&record) {
+        if field.cpp_identifier().is_some() || field.access() != AccessSpecifier::Public {
+            continue;
+        }
+        let Some(anonymous_record) = anonymous_record_of_field(db, field) else {
+            continue;
+        };
+        if get_field_rs_type_kind_for_layout(db, &record, field).is_err() {
+            continue;
+        }
+        anonymous_member_accessors(
+            db,
+            &anonymous_record,
+            &[rs_ident],
+            record.is_union() || anonymous_record.is_union(),
+            record.is_unpin(),
+            &mut anonymous_member_accessors_for_record,
+        );
+    }
+
     let owned_ptr_config =
         record.owned_ptr_config().as_ref().map(|cfg| database::code_snippet::OwnedPtrConfig {
             owned_type_name: make_rs_ident(cfg.owned_ptr_type()),
             drop_impl: make_rs_ident(cfg.drop_impl()),
+            impl_deref_mut: record.is_unpin(),
         });
     let member_methods = api_snippets.member_functions.remove(&record.id()).unwrap_or_default();
     let free_functions = api_snippets.free_functions.remove(&record.id()).unwrap_or_default();
@@ -822,10 +959,13 @@ pub fn generate_record<'a>(
         // TODO(b/481405536): we should do this unconditionally.
         internally_mutable_unknown_fields: !record.should_derive_copy(),
         crubit_annotations: {
-            let mut annotations = vec![DocCommentAttr(intern!(
-                db.interner(),
-                "CRUBIT_ANNOTATE: cpp_type={fully_qualified_cc_name}"
-            ))];
+            let mut annotations = vec![];
+            if let Some(fully_qualified_cc_name) = &fully_qualified_cc_name {
+                annotations.push(DocCommentAttr(intern!(
+                    db.interner(),
+                    "CRUBIT_ANNOTATE: cpp_type={fully_qualified_cc_name}"
+                )));
+            }
             if record.is_thread_safe() {
                 annotations.push(DocCommentAttr(intern!(
                     db.interner(),
@@ -855,6 +995,8 @@ pub fn generate_record<'a>(
         display_impl,
         debug_impl,
         no_unique_address_accessors,
+        anonymous_member_accessors: anonymous_member_accessors_for_record,
+        bridge_field_accessors: bridge_field_accessors.accessors,
         items,
         nested_items,
         indirect_functions,
@@ -970,9 +1112,9 @@ pub fn generate_derives(record: &Record<'_>) -> DeriveAttr {
 }
 
 fn cc_struct_layout_assertion(db: &BindingsGenerator, record: &Record) -> Result<ThunkImpl> {
-    let features = db.ir().target_crubit_features(record.owning_target());
+    let features = db.ir().item_crubit_features(record.id(), record.owning_target());
     let namespace_qualifier = db.namespace_qualifier(record).format_for_cc(features)?;
-    let fields_and_expected_offsets: Vec<(TokenStream, usize)> = record
+    let mut fields_and_expected_offsets: Vec<(TokenStream, usize)> = record
         .fields()
         .iter()
         .filter_map(|field| {
@@ -988,17 +1130,34 @@ fn cc_struct_layout_assertion(db: &BindingsGenerator, record: &Record) -> Result
             }
 
             // The IR contains the offset in bits, while `CRUBIT_OFFSET_OF` returns the
-            // offset in bytes, so we need to convert.  We can assert that
-            // `field.offset` is always at field boundaries, because the
-            // bitfields have been filtered out earlier.
-            assert_eq!(field.offset() % 8, 0);
-            let expected_offset = field.offset() / 8;
-            let field_ident =
-                format_nonportable_cc_type_name(field.cpp_identifier().as_ref()?.as_str())
-                    .expect("field has invalid type name");
-            Some((field_ident, expected_offset))
+            // offset in bytes, so we need to convert.  `field.offset` is expected to be at
+            // byte boundaries, because the bitfields have been filtered out earlier.
+            let cpp_identifier = field.cpp_identifier()?;
+            Some(cc_member_offset(quote! {}, cpp_identifier.as_str(), field.offset()))
         })
-        .collect();
+        .collect::<Result<_>>()?;
+    // Anonymous records can't be named in C++, so the offsets of their members are checked here
+    // instead. Members of anonymous members are named directly (`CRUBIT_OFFSET_OF(i, S)`), and
+    // members of named fields are named through the field (`CRUBIT_OFFSET_OF(u.i, S)`).
+    for field in record.fields() {
+        if field.access() != AccessSpecifier::Public || field.is_bitfield() {
+            continue;
+        }
+        let Some(anonymous_record) = anonymous_record_of_field(db, field) else {
+            continue;
+        };
+        let cc_path = field
+            .cpp_identifier()
+            .map(|cpp_identifier| format_nonportable_cc_type_name(cpp_identifier.as_str()))
+            .transpose()?;
+        anonymous_record_member_offsets(
+            db,
+            &anonymous_record,
+            cc_path,
+            field.offset(),
+            &mut fields_and_expected_offsets,
+        )?;
+    }
 
     // only use CRUBIT_SIZEOF for alignment > 1, so as to simplify the generated
     // code.
@@ -1019,6 +1178,64 @@ fn cc_struct_layout_assertion(db: &BindingsGenerator, record: &Record) -> Result
     })
 }
 
+/// Appends the expected byte offsets of the public members of `anonymous_record`, which is the
+/// type of a field at bit offset `base_offset` in the enclosing record.
+///
+/// `cc_path` is the C++ member designator of the field, or `None` if it is an anonymous member.
+fn anonymous_record_member_offsets(
+    db: &BindingsGenerator,
+    anonymous_record: &Record,
+    cc_path: Option<TokenStream>,
+    base_offset: usize,
+    fields_and_expected_offsets: &mut Vec<(TokenStream, usize)>,
+) -> Result<()> {
+    for field in anonymous_record.fields() {
+        if field.access() != AccessSpecifier::Public || field.is_bitfield() {
+            continue;
+        }
+        let offset = base_offset + field.offset();
+        let member_path = match field.cpp_identifier() {
+            Some(cpp_identifier) => {
+                let prefix = match &cc_path {
+                    Some(cc_path) => quote! { #cc_path . },
+                    None => quote! {},
+                };
+                let (member_path, expected_offset) =
+                    cc_member_offset(prefix, cpp_identifier.as_str(), offset)?;
+                fields_and_expected_offsets.push((member_path.clone(), expected_offset));
+                Some(member_path)
+            }
+            None => cc_path.clone(),
+        };
+        if let Some(nested_record) = anonymous_record_of_field(db, field) {
+            anonymous_record_member_offsets(
+                db,
+                &nested_record,
+                member_path,
+                offset,
+                fields_and_expected_offsets,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the C++ member designator `prefix` followed by `cpp_identifier`, and the byte offset of
+/// that member given its `offset` in bits.
+fn cc_member_offset(
+    prefix: TokenStream,
+    cpp_identifier: &str,
+    offset: usize,
+) -> Result<(TokenStream, usize)> {
+    let ident = format_nonportable_cc_type_name(cpp_identifier)
+        .with_context(|| format!("Field `{cpp_identifier}` has an invalid C++ name"))?;
+    ensure!(
+        offset % 8 == 0,
+        "Field `{cpp_identifier}` is at bit offset {offset}, which is not at a byte boundary"
+    );
+    Ok((quote! { #prefix #ident }, offset / 8))
+}
+
 /// Returns the accessor functions for no_unique_address member variables.
 fn cc_struct_no_unique_address_impl(
     db: &BindingsGenerator,
@@ -1068,6 +1285,181 @@ fn cc_struct_no_unique_address_impl(
     Ok(no_unique_address_accessors)
 }
 
+#[derive(Default)]
+struct BridgeFieldAccessorImplementation {
+    accessors: Vec<BridgeFieldAccessor>,
+    thunks: Vec<Thunk>,
+    thunk_impls: Vec<ThunkImpl>,
+}
+
+/// Returns the getters and setters for public fields of bridge types, which are otherwise
+/// represented as opaque blobs of bytes (see crubit.rs/errors/bridge_field).
+///
+/// The field stays in place as a blob; the accessors convert to and from it in C++ thunks.
+fn cc_struct_bridge_field_accessors<'a>(
+    db: &BindingsGenerator<'a>,
+    record: &Rc<Record<'a>>,
+) -> Result<BridgeFieldAccessorImplementation> {
+    let mut implementation = BridgeFieldAccessorImplementation::default();
+    // Reading a union field requires knowing which member is active, which we can't do from the
+    // thunk.
+    if record.is_union() || record.is_anonymous() {
+        return Ok(implementation);
+    }
+    let ir = db.ir();
+    let crate_root_path = ir.crate_root_path_tokens();
+    let cc_record_name = cpp_type_name_for_record(record.as_ref(), db)?;
+    let member_function_names: Vec<Identifier> = db
+        .collect_unqualified_member_functions(record.clone())
+        .iter()
+        .filter_map(|func| match func.rs_name() {
+            UnqualifiedIdentifier::Identifier(id) => Some(id.clone()),
+            _ => None,
+        })
+        .collect();
+    let collides_with_member_function =
+        |name: &str| member_function_names.iter().any(|id| id.as_str() == name);
+
+    for field in record.fields() {
+        if field.access() != AccessSpecifier::Public || field.is_bitfield() {
+            continue;
+        }
+        let (Some(rust_identifier), Some(cpp_identifier)) =
+            (field.rust_identifier(), field.cpp_identifier())
+        else {
+            continue;
+        };
+        let Ok(type_kind) = db.rs_type_kind(field.type_().clone()) else {
+            continue;
+        };
+        let RsTypeKind::BridgeType { original_type, .. } = type_kind.unalias() else {
+            continue;
+        };
+        let has_missing_features = db
+            .defining_target(record.id())
+            .as_ref()
+            .into_iter()
+            .chain([record.owning_target()])
+            .any(|target| {
+                !type_kind
+                    .missing_feature_descriptions_of_type(
+                        target,
+                        ir.item_crubit_features(record.id(), target),
+                    )
+                    .is_empty()
+            });
+        if has_missing_features {
+            continue;
+        }
+        let Ok(crubit_abi_type) = db.crubit_abi_type(type_kind.clone()) else {
+            continue;
+        };
+        let crubit_abi_type_cc_tokens = CrubitAbiTypeToCppTokens(&crubit_abi_type);
+        let crubit_abi_type_cc_expr_tokens = CrubitAbiTypeToCppExprTokens(&crubit_abi_type);
+        let cc_field_ident = format_nonportable_cc_type_name(cpp_identifier.as_str())?;
+        let thunk_ident = |kind: &str| {
+            make_rs_ident(&format!(
+                "__crubit_field_{kind}__{}_{}_{}",
+                record.mangled_cc_name(),
+                cpp_identifier.as_str(),
+                record.as_ref().owning_target().convert_to_cc_identifier(),
+            ))
+        };
+
+        let getter_name = rust_identifier.as_str();
+        // The getter copies the field into the encoder.
+        let getter = if original_type.copy_constructor() != SpecialMemberFunc::Unavailable
+            && !collides_with_member_function(getter_name)
+        {
+            let getter_thunk_ident = thunk_ident("getter");
+            implementation.thunks.push(Thunk::Function {
+                mangled_name: None,
+                thunk_ident: getter_thunk_ident.clone(),
+                generic_params: quote! {},
+                param_idents: vec![make_rs_ident("__return_abi_buffer"), make_rs_ident("__this")],
+                param_types: vec![
+                    quote! { *mut ::core::ffi::c_uchar },
+                    quote! { *const ::core::ffi::c_void },
+                ],
+                return_type_fragment: None,
+            });
+            implementation.thunk_impls.push(ThunkImpl::Function {
+                return_type_name: quote! { void },
+                thunk_ident: getter_thunk_ident.clone(),
+                param_types: vec![quote! { unsigned char* }, quote! { const #cc_record_name* }],
+                param_idents: vec![make_rs_ident("__return_abi_buffer"), make_rs_ident("__this")],
+                conversion_stmts: quote! {
+                    ::crubit::Encoder __return_encoder(
+                        #crubit_abi_type_cc_tokens::kSize, __return_abi_buffer);
+                },
+                return_stmt: quote! {
+                    #crubit_abi_type_cc_expr_tokens.Encode(__this->#cc_field_ident, __return_encoder)
+                },
+            });
+            Some((make_rs_ident(getter_name), getter_thunk_ident))
+        } else {
+            None
+        };
+
+        let setter_name = format!("set_{getter_name}");
+        let setter = if !field.type_().is_const() && !collides_with_member_function(&setter_name) {
+            let setter_thunk_ident = thunk_ident("setter");
+            implementation.thunks.push(Thunk::Function {
+                mangled_name: None,
+                thunk_ident: setter_thunk_ident.clone(),
+                generic_params: quote! {},
+                param_idents: vec![make_rs_ident("__this"), make_rs_ident("value")],
+                param_types: vec![
+                    quote! { *mut ::core::ffi::c_void },
+                    quote! { *const ::core::ffi::c_uchar },
+                ],
+                return_type_fragment: None,
+            });
+            implementation.thunk_impls.push(ThunkImpl::Function {
+                return_type_name: quote! { void },
+                thunk_ident: setter_thunk_ident.clone(),
+                param_types: vec![quote! { #cc_record_name* }, quote! { const unsigned char* }],
+                param_idents: vec![make_rs_ident("__this"), make_rs_ident("value")],
+                conversion_stmts: quote! {
+                    ::crubit::Decoder __value_decoder(#crubit_abi_type_cc_tokens::kSize, value);
+                },
+                return_stmt: quote! {
+                    __this->#cc_field_ident =
+                        #crubit_abi_type_cc_expr_tokens.Decode(__value_decoder)
+                },
+            });
+            Some((make_rs_ident(&setter_name), setter_thunk_ident))
+        } else {
+            None
+        };
+
+        if getter.is_none() && setter.is_none() {
+            continue;
+        }
+        implementation.accessors.push(BridgeFieldAccessor {
+            doc_comment: generate_doc_comment(
+                field.doc_comment(),
+                None,
+                None,
+                db.is_golden_test(),
+                db.kythe_annotations(),
+            ),
+            visibility: db
+                .type_visibility(record.owning_target(), type_kind.clone())
+                .unwrap_or_default(),
+            type_: type_kind.to_token_stream(db),
+            crubit_abi_type_tokens: CrubitAbiTypeToRustTokens(&crubit_abi_type).to_token_stream(),
+            crubit_abi_type_expr_tokens: CrubitAbiTypeToRustExprTokens(&crubit_abi_type)
+                .to_token_stream(),
+            crate_root_path: crate_root_path.clone(),
+            getter,
+            setter,
+            is_pinned: !record.is_unpin(),
+        });
+    }
+    Ok(implementation)
+}
+
 type UpcastImplResult = Result<UpcastImpl, String>;
 
 struct UpcastImplementation<'pb> {
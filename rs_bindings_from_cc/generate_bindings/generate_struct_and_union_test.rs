@@ -582,23 +582,61 @@ fn test_struct_with_unnamed_struct_and_union_members() -> Result<()> {
     )?;
 
     let ir = make_test_ir(&proto)?;
-    let BindingsTokens { rs_api, .. } = generate_bindings_tokens_for_test(ir)?;
-    // Once anonymous structs and unions are supported, `__unnamed_field1` and `__unnamed_field2`
-    // should have a real, usable type.
+    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens_for_test(ir)?;
     assert_rs_matches!(
         rs_api,
         quote! {
-            #[repr(C, align(4))]
+            #[repr(C)]
             #[doc="CRUBIT_ANNOTATE: cpp_type=StructWithUnnamedMembers"]
             pub struct StructWithUnnamedMembers {
                pub first_field: ::ffi_11::c_int,
-               #[doc =" Reason for representing this field as a blob of bytes:\n Unsupported type 'StructWithUnnamedMembers::(anonymous struct at ./ir_from_cc_virtual_header.h:7:11)': No generated bindings found for ''"]
-               pub(crate) __unnamed_field1: [::core::mem::MaybeUninit<u8>; 8],
-               #[doc =" Reason for representing this field as a blob of bytes:\n Unsupported type 'StructWithUnnamedMembers::(anonymous union at ./ir_from_cc_virtual_header.h:11:11)': No generated bindings found for ''"]
-               pub(crate) __unnamed_field2: [::core::mem::MaybeUninit<u8>; 4],
+               pub __unnamed_field1: crate::struct_with_unnamed_members::StructWithUnnamedMembers__anon_0,
+               pub __unnamed_field2: crate::struct_with_unnamed_members::StructWithUnnamedMembers__anon_1,
                pub last_field: ::ffi_11::c_int,
             }
-            ...
+        }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            impl StructWithUnnamedMembers {
+                pub fn anonymous_struct_field_1(&self) -> &::ffi_11::c_int {
+                    &self.__unnamed_field1.anonymous_struct_field_1
+                }
+                pub fn anonymous_struct_field_1_mut(&mut self) -> &mut ::ffi_11::c_int {
+                    &mut self.__unnamed_field1.anonymous_struct_field_1
+                }
+                ...
+                pub unsafe fn anonymous_union_field_1(&self) -> &::ffi_11::c_int {
+                    unsafe { &self.__unnamed_field2.anonymous_union_field_1 }
+                }
+                ...
+            }
+        }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub mod struct_with_unnamed_members {
+                ...
+                #[repr(C)]
+                pub struct StructWithUnnamedMembers__anon_0 {
+                    pub anonymous_struct_field_1: ::ffi_11::c_int,
+                    pub anonymous_struct_field_2: ::ffi_11::c_int,
+                }
+                ...
+                #[repr(C)]
+                pub union StructWithUnnamedMembers__anon_1 {
+                    pub anonymous_union_field_1: ::ffi_11::c_int,
+                    pub anonymous_union_field_2: ::ffi_11::c_int,
+                }
+                ...
+            }
+        }
+    );
+    assert_rs_matches!(
+        rs_api,
+        quote! {
             const _: () = {
                 ...
                 assert!(::core::mem::offset_of!(
@@ -613,6 +651,58 @@ fn test_struct_with_unnamed_struct_and_union_members() -> Result<()> {
             };
         }
     );
+    // Anonymous records can't be named in C++, so their layout is checked through the enclosing
+    // record.
+    assert_cc_not_matches!(rs_api_impl, quote! { StructWithUnnamedMembers__anon_0 });
+    assert_cc_matches!(
+        rs_api_impl,
+        quote! {
+            static_assert(CRUBIT_OFFSET_OF(anonymous_struct_field_1,
+                struct StructWithUnnamedMembers) == 4);
+            static_assert(CRUBIT_OFFSET_OF(anonymous_struct_field_2,
+                struct StructWithUnnamedMembers) == 8);
+            static_assert(CRUBIT_OFFSET_OF(anonymous_union_field_1,
+                struct StructWithUnnamedMembers) == 12);
+            static_assert(CRUBIT_OFFSET_OF(anonymous_union_field_2,
+                struct StructWithUnnamedMembers) == 12);
+        }
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_struct_with_field_of_unnamed_type() -> Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"
+        struct TaggedValue {
+          int tag;
+          union {
+            int i;
+            float f;
+          } value;
+        }; "#,
+    )?;
+
+    let ir = make_test_ir(&proto)?;
+    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens_for_test(ir)?;
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            pub struct TaggedValue {
+               pub tag: ::ffi_11::c_int,
+               pub value: crate::tagged_value::TaggedValue__anon_0,
+            }
+        }
+    );
+    // Only anonymous members are flattened.
+    assert_rs_not_matches!(rs_api, quote! { pub unsafe fn i });
+    assert_cc_matches!(
+        rs_api_impl,
+        quote! {
+            static_assert(CRUBIT_OFFSET_OF(value.i, struct TaggedValue) == 4);
+            static_assert(CRUBIT_OFFSET_OF(value.f, struct TaggedValue) == 4);
+        }
+    );
     Ok(())
 }
 
@@ -2167,3 +2257,64 @@ fn test_crubit_override_debug_false() -> Result<()> {
     );
     Ok(())
 }
+
+#[gtest]
+fn test_bridge_field_accessors() -> Result<()> {
+    let ir = make_test_ir(&ir_proto_from_cc(
+        r#"
+        struct
+            [[clang::annotate("crubit_bridge_rust_name", "RustStruct")]]
+            [[clang::annotate("crubit_bridge_abi_rust", "RustStructAbi")]]
+            [[clang::annotate("crubit_bridge_abi_cpp", "::crubit::CppStructAbi")]]
+            CppStruct {};
+
+        struct Config final {
+            CppStruct field;
+            const CppStruct const_field;
+        };
+    "#,
+    )?)?;
+    let BindingsTokens { rs_api, rs_api_impl } = generate_bindings_tokens_for_test(ir)?;
+    assert_rs_matches!(
+        rs_api,
+        quote! {
+            impl Config {
+                #[inline(always)]
+                pub fn field(&self) -> crate::RustStruct {
+                    unsafe {
+                        ::bridge_rust::unstable_return!(@crate::RustStructAbi, crate::RustStructAbi, |__crubit_return_abi_buffer| {
+                            crate::detail::__crubit_field_getter__6Config_field___2f_2ftest_3atesting_5ftarget(
+                                __crubit_return_abi_buffer,
+                                self as *const Self as *const ::core::ffi::c_void,
+                            );
+                        })
+                    }
+                }
+                #[inline(always)]
+                pub fn set_field(&mut self, value: crate::RustStruct) {
+                    unsafe {
+                        crate::detail::__crubit_field_setter__6Config_field___2f_2ftest_3atesting_5ftarget(
+                            self as *mut Self as *mut ::core::ffi::c_void,
+                            ::bridge_rust::unstable_encode!(@crate::RustStructAbi, crate::RustStructAbi, value)
+                                .as_ptr() as *const u8,
+                        )
+                    }
+                }
+                #[inline(always)]
+                pub fn const_field(&self) -> crate::RustStruct { ... }
+            }
+        }
+    );
+    assert_rs_not_matches!(rs_api, quote! { pub fn set_const_field });
+    assert_cc_matches!(
+        rs_api_impl,
+        quote! {
+            extern "C" void __crubit_field_setter__6Config_field___2f_2ftest_3atesting_5ftarget(
+                struct Config* __this, const unsigned char* value) {
+                ::crubit::Decoder __value_decoder(::crubit::CppStructAbi::kSize, value);
+                __this->field = ::crubit::CppStructAbi().Decode(__value_decoder);
+            }
+        }
+    );
+    Ok(())
+}
@@ -195,7 +195,10 @@ pub fn has_bindings<'a>(
             if matches!(item, Item::Record(_)) {
                 // Only check those item kinds that decl_lifetime_arity explicitly supports.
                 if let Some(ot) = &item.owning_target()
-                    && db.ir().target_crubit_features(ot).contains(CrubitFeature::AssumeLifetimes)
+                    && db
+                        .ir()
+                        .item_crubit_features(item.id(), ot)
+                        .contains(CrubitFeature::AssumeLifetimes)
                     && let Err(error) = (db.codegen_functions().decl_lifetime_arity)(db, item.id())
                 {
                     return Err(NoBindingsReason::Unsupported(error));
@@ -232,6 +235,15 @@ pub fn has_bindings<'a>(
         }
         // Global variables receive bindings if the underlying type is visible.
         Item::GlobalVar(ref global_var) => match db.rs_type_kind(global_var.type_().clone()) {
+            // Bridged types are converted by value when passed between Rust and C++, so there is
+            // no Rust object for a binding to refer to. This only rejects the variable itself:
+            // other items using the type are unaffected.
+            Ok(rs_type_kind) if rs_type_kind.is_bridge_type() => {
+                Err(NoBindingsReason::Unsupported(anyhow!(
+                    "global variables of bridged type `{}` are not supported",
+                    rs_type_kind.display(db)
+                )))
+            }
             Ok(rs_type_kind) => {
                 let visibility = type_visibility(db, &item, rs_type_kind)?;
                 Ok(BindingsInfo { visibility })
@@ -263,7 +275,7 @@ fn func_has_bindings<'a>(
 
     let ir = db.ir();
     let target = func.as_ref().owning_target();
-    let enabled_features = ir.target_crubit_features(target);
+    let enabled_features = ir.item_crubit_features(func.id(), target);
 
     if matches!(func.cc_name(), ir::UnqualifiedIdentifier::ConversionOperator)
         && !enabled_features.contains(CrubitFeature::AssumeThisLifetimes)
@@ -407,11 +419,11 @@ fn type_target_restriction_shallow<'a>(
         return None;
     };
     let target = record.as_ref().owning_target();
+    let features = db.ir().item_crubit_features(record.id(), target);
     // Template types (except for the special-cased ones like `[w]string_view`)
     // are the only types whose bindings have restrictions, and they do not have
     // unique owning targets.
-    if record.has_unique_owning_target()
-        || db.ir().target_crubit_features(target).contains(CrubitFeature::TemplateInstantiation)
+    if record.has_unique_owning_target() || features.contains(CrubitFeature::TemplateInstantiation)
     {
         return None;
     }
@@ -420,7 +432,7 @@ fn type_target_restriction_shallow<'a>(
         return None;
     }
     // Targets with experimental features generate `pub` bindings (for now?), no matter what.
-    if db.ir().target_crubit_features(target).contains(CrubitFeature::Experimental) {
+    if features.contains(CrubitFeature::Experimental) {
         return None;
     }
     Some(TargetRestriction { target: target.clone(), exemplar_type: rs_type_kind.clone() })
@@ -5,7 +5,10 @@
 
 use arc_anyhow::{anyhow, ensure, Context, Error, Result};
 use code_gen_utils::make_rs_lifetime_ident;
-use code_gen_utils::{format_cc_includes, is_cpp_reserved_keyword, make_rs_ident, CcInclude};
+use code_gen_utils::{
+    format_cc_includes, format_nonportable_cc_ident, is_cpp_reserved_keyword, make_rs_ident,
+    CcInclude,
+};
 use cpp_type_name::format_cpp_type_with_references;
 use crubit_abi_type::{
     CrubitAbiType, CrubitAbiTypeToCppExprTokens, CrubitAbiTypeToCppTokens,
@@ -13,7 +16,7 @@ use crubit_abi_type::{
 };
 use database::code_snippet::{
     self, integer_constant_to_token_stream, ApiSnippets, Bindings, BindingsTokens, CppDetails,
-    CppIncludes, DeprecatedAttr, Feature, GeneratedItem,
+    CppIncludes, DeprecatedAttr, Feature, GeneratedItem, NoBindingsReason, Thunk, ThunkImpl,
 };
 use database::db::{BindingsGenerator, CodegenFunctions, Interner};
 use database::intern;
@@ -28,7 +31,7 @@ use generate_comment::{generate_comment, generate_doc_comment, generate_unsuppor
 use generate_struct_and_union::generate_incomplete_record;
 use ir::*;
 use itertools::Itertools;
-use kythe_metadata::rs_embed_provenance_map;
+use kythe_metadata::{rs_embed_provenance_map, rs_provenance_map_to_lsif};
 use lifetime_defaults_transform::lifetime_defaults_transform_type_alias;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, ToTokens};
@@ -44,6 +47,7 @@ use token_stream_printer::{
 };
 
 mod generate_dyn_callable;
+mod generate_fix_its;
 
 /// Generates bindings source code from an `IR` instance.
 #[allow(clippy::too_many_arguments)]
@@ -59,6 +63,7 @@ pub fn generate_bindings(
     is_golden_test: bool,
     kythe_annotations: bool,
     kythe_default_corpus: &str,
+    lsif_rs_api_path: &str,
 ) -> Result<Bindings> {
     let crubit_support_path_format =
         Format::parse_with_metavars(crubit_support_path_format, &["header"])?;
@@ -84,6 +89,7 @@ pub fn generate_bindings(
 
     let top_level_comment = generate_top_level_comment(&ir, is_golden_test);
 
+    let mut lsif_index = String::new();
     let rs_api: String = {
         let rustfmt_exe_path =
             if rustfmt_exe_path.is_empty() { None } else { Some(Path::new(rustfmt_exe_path)) };
@@ -106,7 +112,18 @@ pub fn generate_bindings(
         if kythe_annotations {
             let (rs_api, provenance_map) =
                 rs_tokens_to_formatted_string_with_provenance(rs_api, rustfmt_config.as_ref())?;
-            rs_embed_provenance_map(&provenance_map, kythe_default_corpus, adjust_rs_api(rs_api))
+            let rs_api = adjust_rs_api(rs_api);
+            if !lsif_rs_api_path.is_empty() {
+                let project_root = std::env::current_dir()?;
+                lsif_index = rs_provenance_map_to_lsif(
+                    &provenance_map,
+                    &format!("file://{}", project_root.display()),
+                    lsif_rs_api_path,
+                    &rs_api,
+                    &mut |header| std::fs::read_to_string(header).ok(),
+                );
+            }
+            rs_embed_provenance_map(&provenance_map, kythe_default_corpus, rs_api)
         } else {
             adjust_rs_api(rs_tokens_to_formatted_string(rs_api, rustfmt_config.as_ref())?)
         }
@@ -124,7 +141,7 @@ pub fn generate_bindings(
         {rs_api_impl}"
     );
 
-    Ok(Bindings { rs_api, rs_api_impl })
+    Ok(Bindings { rs_api, rs_api_impl, lsif_index })
 }
 
 fn generate_type_alias<'a>(
@@ -133,7 +150,7 @@ fn generate_type_alias<'a>(
 ) -> Result<ApiSnippets> {
     let assume_lifetimes = db
         .ir()
-        .target_crubit_features(raw_type_alias.as_ref().owning_target())
+        .item_crubit_features(raw_type_alias.id(), raw_type_alias.as_ref().owning_target())
         .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
     let type_alias = if assume_lifetimes {
         &lifetime_defaults_transform_type_alias(db, raw_type_alias.as_ref())?
@@ -241,6 +258,11 @@ fn generate_constant<'a>(
 fn generate_global_var(db: &BindingsGenerator, var: &GlobalVar) -> Result<ApiSnippets> {
     db.errors().add_category(error_report::Category::Variable);
     let type_ = db.rs_type_kind(var.type_().clone())?;
+    if matches!(type_.unalias(), RsTypeKind::Record { .. } | RsTypeKind::IncompleteRecord { .. })
+        && !type_.is_unpin()
+    {
+        return generate_global_var_accessor(db, var, type_);
+    }
 
     Ok(ApiSnippets {
         generated_items: HashMap::from([(
@@ -258,6 +280,105 @@ fn generate_global_var(db: &BindingsGenerator, var: &GlobalVar) -> Result<ApiSni
     })
 }
 
+/// Generates an accessor function for a global variable that can't be bound as an `extern`
+/// `static`.
+///
+/// A Rust `static` of a non-`Unpin` type would allow the C++ object to be moved (e.g. with
+/// `std::mem::swap` on a `static mut`), so instead we go through a C++ thunk that returns the
+/// address of the variable, and expose it as a `&'static T` (for `const` globals) or as a
+/// `Pin<&'static mut T>`.
+fn generate_global_var_accessor<'a>(
+    db: &BindingsGenerator<'a>,
+    var: &GlobalVar<'a>,
+    type_: RsTypeKind,
+) -> Result<ApiSnippets> {
+    let ir = db.ir();
+    let features = ir.item_crubit_features(var.id(), var.owning_target());
+    let crate_root_path = ir.crate_root_path_tokens();
+    let is_const = var.type_().is_const();
+
+    let thunk_ident = make_rs_ident(&format!(
+        "__rust_thunk__{}",
+        generate_function_thunk::ident_fragment_from_mangled_name(
+            var.mangled_name().unwrap_or(var.cc_name().as_str())
+        )
+    ));
+    let rs_type = type_.to_token_stream(db);
+    let rs_ptr_type = if is_const {
+        quote! { *const #rs_type }
+    } else {
+        quote! { *mut #rs_type }
+    };
+    let thunk = Thunk::Function {
+        mangled_name: None,
+        thunk_ident: thunk_ident.clone(),
+        generic_params: quote! {},
+        param_idents: vec![],
+        param_types: vec![],
+        return_type_fragment: Some(rs_ptr_type),
+    };
+
+    let cc_type = cpp_type_name::format_cpp_type(&type_, db)?;
+    let cc_ptr_type = if is_const {
+        quote! { #cc_type const* }
+    } else {
+        quote! { #cc_type* }
+    };
+    let namespace_qualifier = db.namespace_qualifier(var).format_for_cc(features)?;
+    let cc_var_ident = format_nonportable_cc_ident(var.cc_name().as_str())?;
+    let thunk_impl = ThunkImpl::Function {
+        return_type_name: cc_ptr_type,
+        thunk_ident: thunk_ident.clone(),
+        param_types: vec![],
+        param_idents: vec![],
+        conversion_stmts: quote! {},
+        return_stmt: quote! { return &#namespace_qualifier #cc_var_ident },
+    };
+
+    let (return_type, body) = if is_const {
+        (quote! { &'static #rs_type }, quote! { &*#crate_root_path::detail::#thunk_ident() })
+    } else {
+        (
+            quote! { ::core::pin::Pin<&'static mut #rs_type> },
+            quote! {
+                ::core::pin::Pin::new_unchecked(&mut *#crate_root_path::detail::#thunk_ident())
+            },
+        )
+    };
+    let safety_doc = if is_const {
+        "The variable must be initialized, and must not be mutated while the returned reference \
+         is alive."
+    } else {
+        "The variable must be initialized, and the returned reference must not alias any other \
+         reference to it, including one returned by another call to this function."
+    };
+    let doc_comment = generate_doc_comment(
+        var.doc_comment(),
+        Some(safety_doc),
+        Some(var.source_loc()),
+        db.is_golden_test(),
+        db.kythe_annotations(),
+    );
+    let deprecated_attr = var.deprecated().map(|s| DeprecatedAttr(Rc::from(s)));
+    let visibility = db.type_visibility(var.owning_target(), type_).unwrap_or_default();
+    let ident = make_rs_ident(var.rs_name().as_str());
+    let accessor = quote! {
+        #doc_comment
+        #deprecated_attr
+        #[inline(always)]
+        #visibility unsafe fn #ident() -> #return_type {
+            unsafe { #body }
+        }
+    };
+
+    Ok(ApiSnippets {
+        generated_items: HashMap::from([(var.id(), GeneratedItem::Func(accessor))]),
+        thunks: vec![thunk],
+        cc_details: vec![thunk_impl],
+        ..Default::default()
+    })
+}
+
 fn generate_namespace<'a>(
     db: &BindingsGenerator<'a>,
     namespace: Rc<Namespace<'a>>,
@@ -294,8 +415,13 @@ fn generate_item<'a>(db: &BindingsGenerator<'a>, item: Item<'a>) -> Result<ApiSn
         Err(err) => err,
     };
 
-    if db.has_bindings(item.clone()).is_ok() && !matches!(item, Item::Func(_)) {
-        return Err(err);
+    match db.has_bindings(item.clone()) {
+        Ok(_) if !matches!(item, Item::Func(_)) => return Err(err),
+        Err(
+            NoBindingsReason::MissingRequiredFeatures { .. }
+            | NoBindingsReason::DependencyFailed { .. },
+        ) => generate_fix_its::suggest_aspect_hints(db, &item),
+        _ => {}
     }
     // We didn't guarantee that bindings would exist, so it is not invalid to
     // write down the error but continue.
@@ -322,6 +448,7 @@ fn generate_item_impl<'a>(db: &BindingsGenerator<'a>, item: &Item<'a>) -> Result
                     db.errors().report(e);
                 }
                 if db.is_ambiguous_function(&generated_function.id, func.id()) {
+                    generate_fix_its::suggest_rust_name_for_overload(db, func);
                     bail!("Cannot generate bindings for overloaded function")
                 } else {
                     (*generated_function.snippets).clone()
@@ -494,8 +621,8 @@ pub fn generate_bindings_tokens(
                 // We assume !has_reference_param: it doesn't matter since we're just checking for
                 // presence of DynCallable.
 
-                // Find records that are template instantiations of `rs_std::DynCallable` or
-                // `absl::AnyInvocable`.
+                // Find records that are template instantiations of `rs_std::DynCallable`,
+                // `absl::AnyInvocable`, `std::move_only_function`, or `absl::FunctionRef`.
                 let Ok(Some(BridgeRsTypeKind::Callable(callable))) =
                     BridgeRsTypeKind::new(record, &LifetimeOptions::default(), &None, &db, &[])
                 else {
@@ -523,24 +650,31 @@ pub fn generate_bindings_tokens(
                 let mut rust_api =
                     generate_dyn_callable_invoker_and_manager_defs(&db, &callable, param_idents)?;
 
-                if let BackingType::AnyInvocable { invoke_any_invocable_ident } =
-                    &callable.backing_type
-                {
-                    rust_api.extend(generate_any_invocable_invoker_decl(
+                let invoke_cpp_callable_ident = match &callable.backing_type {
+                    BackingType::AnyInvocable { invoke_any_invocable_ident } => {
+                        Some(invoke_any_invocable_ident)
+                    }
+                    BackingType::MoveOnlyFunction { invoke_move_only_function_ident } => {
+                        Some(invoke_move_only_function_ident)
+                    }
+                    BackingType::DynCallable | BackingType::FunctionRef => None,
+                };
+                if let Some(invoke_cpp_callable_ident) = invoke_cpp_callable_ident {
+                    rust_api.extend(generate_cpp_callable_invoker_decl(
                         &db,
                         &callable,
                         param_idents,
-                        invoke_any_invocable_ident,
+                        invoke_cpp_callable_ident,
                     )?);
-                    // We need to defer the invoke_any_invocable def because it might depend on
+                    // We need to defer the invoke_cpp_callable def because it might depend on
                     // invoker/manager decls which are generated in later iterations of this loop.
                     // By appending them to cpp_api after this loop finishes, we ensure that all the
                     // C++ decls appear before the defs.
-                    deferred_cpp_api.extend(generate_any_invocable_invoker_def(
+                    deferred_cpp_api.extend(generate_cpp_callable_invoker_def(
                         &db,
                         &callable,
                         param_idents,
-                        invoke_any_invocable_ident,
+                        invoke_cpp_callable_ident,
                         &mut internal_includes,
                     )?);
                 }
@@ -723,6 +857,7 @@ fn rs_type_kind_safety<'a>(
                 // Full unsafe reason is not shown here, it's documented on the type instead.
                 .map(|_reason| UnsafeReason(intern!(db.interner(), "unsafe proto message type"))),
             BridgeRsTypeKind::StdOptional(t) => db.rs_type_kind_safety(t.as_ref().clone()),
+            BridgeRsTypeKind::RustBox(t) => db.rs_type_kind_safety(t.as_ref().clone()),
             BridgeRsTypeKind::StdPair(t1, t2) => {
                 let s1 = db.rs_type_kind_safety(t1.as_ref().clone());
                 let s2 = db.rs_type_kind_safety(t2.as_ref().clone());
@@ -739,9 +874,25 @@ fn rs_type_kind_safety<'a>(
                     (None, None) => None,
                 }
             }
-            BridgeRsTypeKind::StdString { .. } => None,
+            BridgeRsTypeKind::StdString { .. }
+            | BridgeRsTypeKind::Duration
+            | BridgeRsTypeKind::Time
+            | BridgeRsTypeKind::StdPath { .. } => None,
             BridgeRsTypeKind::Callable(callable) => {
-                callable_safety(db, &callable.param_types, &callable.return_type)
+                let reason = callable_safety(db, &callable.param_types, &callable.return_type);
+                if !callable.is_borrowed() {
+                    return reason;
+                }
+                // The `&mut dyn FnMut` of an `absl::FunctionRef` is neither `Sync` nor protected
+                // from reentrant calls, so it's only sound to call it the way Rust would.
+                let borrowed_reason = "the `FnMut` closure may only be called from the calling \
+                    thread, one call at a time, and not after the function returns";
+                match reason {
+                    Some(reason) => {
+                        Some(UnsafeReason(intern!(db.interner(), "{borrowed_reason}; {reason}")))
+                    }
+                    None => Some(UnsafeReason(intern!(db.interner(), "{borrowed_reason}"))),
+                }
             }
             BridgeRsTypeKind::C9Co { result_type, .. } => {
                 // A Co<T> logically produces a T, so it is unsafe iff T is unsafe.
@@ -752,7 +903,7 @@ fn rs_type_kind_safety<'a>(
             if !record.is_raw_string_view()
                 && db
                     .ir()
-                    .target_crubit_features(record.as_ref().owning_target())
+                    .item_crubit_features(record.id(), record.as_ref().owning_target())
                     .contains(crubit_feature::CrubitFeature::AssumeLifetimes)
             {
                 match (db.codegen_functions().decl_lifetime_arity)(db, record.id()) {
@@ -975,6 +1126,36 @@ fn generate_rs_api_impl_includes(
                         // absl::AnyInvocable will not receieve bridge bindings.
                     }
                 }
+                BridgeRsTypeKind::Duration | BridgeRsTypeKind::Time => {
+                    internal_includes.insert(CcInclude::SupportLibHeader(
+                        crubit_support_path_format.clone(),
+                        intern!(db.interner(), "bridge_time.h"),
+                    ));
+                    internal_includes.insert(CcInclude::SupportLibHeader(
+                        crubit_support_versioned_path_format.clone(),
+                        intern!(db.interner(), "slot.h"),
+                    ));
+                }
+                BridgeRsTypeKind::StdPath { .. } => {
+                    internal_includes.insert(CcInclude::SupportLibHeader(
+                        crubit_support_path_format.clone(),
+                        intern!(db.interner(), "bridge_path.h"),
+                    ));
+                    internal_includes.insert(CcInclude::SupportLibHeader(
+                        crubit_support_versioned_path_format.clone(),
+                        intern!(db.interner(), "slot.h"),
+                    ));
+                }
+                BridgeRsTypeKind::RustBox(_) => {
+                    internal_includes.insert(CcInclude::SupportLibHeader(
+                        crubit_support_path_format.clone(),
+                        intern!(db.interner(), "rs_std/box.h"),
+                    ));
+                    internal_includes.insert(CcInclude::SupportLibHeader(
+                        crubit_support_versioned_path_format.clone(),
+                        intern!(db.interner(), "slot.h"),
+                    ));
+                }
                 _ => {
                     internal_includes.insert(CcInclude::SupportLibHeader(
                         crubit_support_path_format.clone(),
@@ -1213,6 +1394,19 @@ fn crubit_abi_type<'a>(
                 Ok(CrubitAbiType::Pair(Rc::from(first_abi), Rc::from(second_abi)))
             }
             BridgeRsTypeKind::StdString { in_cc_std } => Ok(CrubitAbiType::StdString { in_cc_std }),
+            BridgeRsTypeKind::Duration => Ok(CrubitAbiType::Duration {
+                cpp_type: cpp_type_name::cpp_tagless_type_name_for_record(&original_type, db)?,
+            }),
+            BridgeRsTypeKind::Time => Ok(CrubitAbiType::Time {
+                cpp_type: cpp_type_name::cpp_tagless_type_name_for_record(&original_type, db)?,
+            }),
+            BridgeRsTypeKind::StdPath { in_cc_std, is_const_ref } => {
+                Ok(CrubitAbiType::StdPath { in_cc_std, is_const_ref })
+            }
+            BridgeRsTypeKind::RustBox(pointee) => Ok(CrubitAbiType::RustBox {
+                rust_type: pointee.all_static_lifetimes(false).to_token_stream(db),
+                cpp_type: cpp_type_name::format_cpp_type(&pointee, db)?,
+            }),
             BridgeRsTypeKind::Callable(callable) => {
                 generate_dyn_callable::callable_crubit_abi_type(db, &callable)
             }
@@ -1420,7 +1614,17 @@ fn generate_dyn_callable_invoker_and_manager_decls(
     }
 
     let invoker_ident = &callable.invoker_ident;
-    let manager_ident = &callable.manager_ident;
+    // A borrowed callable is never relocated or disposed of by C++, so it has no manager.
+    let manager_decl = (!callable.is_borrowed()).then(|| {
+        let manager_ident = &callable.manager_ident;
+        quote! {
+            extern "C" void #manager_ident(
+                ::absl::internal_any_invocable::FunctionToCall operation,
+                ::absl::internal_any_invocable::TypeErasedState* from,
+                ::absl::internal_any_invocable::TypeErasedState* to
+            ) noexcept;
+        }
+    });
 
     Some(quote! {
         extern "C" #decl_return_type_tokens #invoker_ident(
@@ -1428,11 +1632,7 @@ fn generate_dyn_callable_invoker_and_manager_decls(
             #params
             #out_param
         );
-        extern "C" void #manager_ident(
-            ::absl::internal_any_invocable::FunctionToCall operation,
-            ::absl::internal_any_invocable::TypeErasedState* from,
-            ::absl::internal_any_invocable::TypeErasedState* to
-        ) noexcept;
+        #manager_decl
         __NEWLINE__ __NEWLINE__
     })
 }
@@ -1575,14 +1775,28 @@ fn generate_dyn_callable_invoker_and_manager_defs<'a>(
         }
     }
 
-    let dyn_fn_spelling = callable.dyn_fn_spelling(db);
+    let storage_type = callable.rust_storage_type(db);
     let invoker_ident = &callable.invoker_ident;
-    let manager_ident = &callable.manager_ident;
+    let manager_def = (!callable.is_borrowed()).then(|| {
+        let manager_ident = &callable.manager_ident;
+        quote! {
+            #[unsafe(no_mangle)]
+            unsafe extern "C" fn #manager_ident(
+                operation: ::dyn_callable_rs::FunctionToCall,
+                from: *mut #storage_type,
+                to: *mut #storage_type
+            ) {
+                unsafe {
+                    ::dyn_callable_rs::manager(operation, from, to);
+                }
+            }
+        }
+    });
 
     Some(quote! {
         #[unsafe(no_mangle)]
         unsafe extern "C" fn #invoker_ident(
-            f: *mut ::alloc::boxed::Box<#dyn_fn_spelling>
+            f: *mut #storage_type
             #params
             #out_param
         ) #return_type_fragment {
@@ -1590,20 +1804,12 @@ fn generate_dyn_callable_invoker_and_manager_defs<'a>(
 
             #invoke_rust_and_return_to_ffi
         }
-        #[unsafe(no_mangle)]
-        unsafe extern "C" fn #manager_ident(
-            operation: ::dyn_callable_rs::FunctionToCall,
-            from: *mut ::alloc::boxed::Box<#dyn_fn_spelling>,
-            to: *mut ::alloc::boxed::Box<#dyn_fn_spelling>
-        ) {
-            unsafe {
-                ::dyn_callable_rs::manager(operation, from, to);
-            }
-        }
+        #manager_def
     })
 }
 
-/// Generates a unique Rust declaration of an extern "C" function for invoking an AnyInvocable.
+/// Generates a unique Rust declaration of an extern "C" function for invoking an AnyInvocable or a
+/// `std::move_only_function`.
 ///
 /// This roughly has the form:
 /// ```rust
@@ -1615,20 +1821,20 @@ fn generate_dyn_callable_invoker_and_manager_defs<'a>(
 /// }
 /// ```
 ///
-/// This declaration allows Rust to invoke the AnyInvocable, and is used by preparing the arguments
-/// for FFI, passing them and the AnyInvocable to this declaration, where it lands in a C++ defined
-/// function generated by `generate_any_invocable_invoker_def`. That definition then translates the
-/// arguments from their FFI representation to their C++ representation, invokes the AnyInvocable,
-/// and translates the result back to an FFI representation, which is returned to the callee of this
-/// declaration.
+/// This declaration allows Rust to invoke the C++ function object, and is used by preparing the
+/// arguments for FFI, passing them and the function object to this declaration, where it lands in a
+/// C++ defined function generated by `generate_cpp_callable_invoker_def`. That definition then
+/// translates the arguments from their FFI representation to their C++ representation, invokes the
+/// function object, and translates the result back to an FFI representation, which is returned to
+/// the callee of this declaration.
 ///
 /// `None` is returned if there is issue generating the declaration. The specific error is not
 /// reported because it will be reported elsewhere.
-fn generate_any_invocable_invoker_decl<'a>(
+fn generate_cpp_callable_invoker_decl<'a>(
     db: &BindingsGenerator<'a>,
     callable: &Callable<'a>,
     param_idents: &[Ident],
-    invoke_any_invocable_ident: &Ident,
+    invoke_cpp_callable_ident: &Ident,
 ) -> Option<TokenStream> {
     assert_eq!(
         param_idents.len(),
@@ -1686,16 +1892,22 @@ fn generate_any_invocable_invoker_decl<'a>(
         }
     }
 
-    let any_invocable_crate = db
-        .ir()
-        .crate_name(&BazelLabel::from("@abseil-cpp//absl/functional:any_invocable"))
-        .map(|ident| quote! { ::#ident })
-        .unwrap_or_else(|| quote! { ::any_invocable });
+    let raw_callable_type = match &callable.backing_type {
+        BackingType::MoveOnlyFunction { .. } => quote! { ::core::ffi::c_void },
+        _ => {
+            let any_invocable_crate = db
+                .ir()
+                .crate_name(&BazelLabel::from("@abseil-cpp//absl/functional:any_invocable"))
+                .map(|ident| quote! { ::#ident })
+                .unwrap_or_else(|| quote! { ::any_invocable });
+            quote! { #any_invocable_crate::RawAnyInvocable }
+        }
+    };
 
     Some(quote! {
         unsafe extern "C" {
-            pub(crate) unsafe fn #invoke_any_invocable_ident(
-                f: *mut #any_invocable_crate::RawAnyInvocable,
+            pub(crate) unsafe fn #invoke_cpp_callable_ident(
+                f: *mut #raw_callable_type,
                 #params
                 #out_param
             ) #return_type_fragment;
@@ -1703,7 +1915,8 @@ fn generate_any_invocable_invoker_decl<'a>(
     })
 }
 
-/// Generates a unique C++ definition of an extern "C" function for invoking an AnyInvocable.
+/// Generates a unique C++ definition of an extern "C" function for invoking an AnyInvocable or a
+/// `std::move_only_function`.
 ///
 /// This roughly has the form:
 /// ```c++
@@ -1716,15 +1929,15 @@ fn generate_any_invocable_invoker_decl<'a>(
 /// ```
 ///
 /// The generated function has an equivalent Rust declaration generated by
-/// `generate_any_invocable_invoker_decl`, which allows Rust to invoke this generated function.
+/// `generate_cpp_callable_invoker_decl`, which allows Rust to invoke this generated function.
 ///
 /// `None` is returned if there is issue generating the definition. The specific error is not
 /// reported because it will be reported elsewhere.
-fn generate_any_invocable_invoker_def<'a>(
+fn generate_cpp_callable_invoker_def<'a>(
     db: &BindingsGenerator<'a>,
     callable: &Callable<'a>,
     param_idents: &[Ident],
-    invoke_any_invocable_ident: &Ident,
+    invoke_cpp_callable_ident: &Ident,
     internal_includes: &mut BTreeSet<CcInclude>,
 ) -> Option<TokenStream> {
     assert_eq!(
@@ -1839,11 +2052,15 @@ fn generate_any_invocable_invoker_def<'a>(
         }
     }
 
-    let any_invocable_sig_spelling = any_invocable_sig_spelling(callable, db).ok()?;
+    let sig_spelling = cpp_callable_sig_spelling(callable, db).ok()?;
+    let cpp_callable_type = match &callable.backing_type {
+        BackingType::MoveOnlyFunction { .. } => quote! { ::std::move_only_function<#sig_spelling> },
+        _ => quote! { ::absl::AnyInvocable<#sig_spelling> },
+    };
 
     Some(quote! {
-        extern "C" #decl_return_type_tokens #invoke_any_invocable_ident(
-            ::absl::AnyInvocable<#any_invocable_sig_spelling>* f
+        extern "C" #decl_return_type_tokens #invoke_cpp_callable_ident(
+            #cpp_callable_type* f
             #params
             #out_param
         ) {
@@ -1857,7 +2074,7 @@ fn generate_any_invocable_invoker_def<'a>(
 ///
 /// An error is returned if there is issue generating the declaration. The specific error is not
 /// reported because it will be reported elsewhere.
-fn any_invocable_sig_spelling(callable: &Callable, db: &BindingsGenerator) -> Result<TokenStream> {
+fn cpp_callable_sig_spelling(callable: &Callable, db: &BindingsGenerator) -> Result<TokenStream> {
     let return_type_tokens =
         cpp_type_name::format_cpp_type_with_references(&callable.return_type, db)?;
     let param_type_tokens = callable
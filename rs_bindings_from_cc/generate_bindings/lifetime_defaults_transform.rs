@@ -67,7 +67,7 @@ pub fn record_lifetime_arity(
     // Do nothing if this record isn't opted into assumed lifetimes.
     let assume_lifetimes = db
         .ir()
-        .target_crubit_features(rc.owning_target())
+        .item_crubit_features(rc.id(), rc.owning_target())
         .contains(crubit_feature::CrubitFeature::AssumeLifetimes);
     if !assume_lifetimes {
         return Ok(0);
@@ -139,6 +139,7 @@ fn decl_lifetime_arity_impl(
                 && matches!(
                     ts.kind(),
                     ir::TemplateSpecializationKind::StdStringView
+                        | ir::TemplateSpecializationKind::StdSpan { .. }
                         | ir::TemplateSpecializationKind::AbslSpan { .. }
                         | ir::TemplateSpecializationKind::C9Co { .. }
                 )
@@ -424,6 +425,8 @@ impl<'a, 'db> LifetimeDefaults<'a, 'db> {
                     ir::TemplateSpecializationKind::StdVector { raw_element_type, .. }
                     | ir::TemplateSpecializationKind::StdUniquePtr { raw_element_type, .. }
                     | ir::TemplateSpecializationKind::C9Co { raw_element_type, .. }
+                    | ir::TemplateSpecializationKind::StdSpan { raw_element_type, .. }
+                    | ir::TemplateSpecializationKind::StdArray { raw_element_type, .. }
                     | ir::TemplateSpecializationKind::AbslSpan { raw_element_type, .. } => {
                         Some(raw_element_type.clone())
                     }
@@ -879,6 +882,7 @@ pub fn lifetime_defaults_transform<'pb>(db: &BindingsGenerator<'pb>) -> Result<I
         crate_root_path: ir.tree_ir().crate_root_path.clone(),
         crubit_features: ir.tree_ir().crubit_features.clone(),
         crate_names: ir.tree_ir().crate_names.clone(),
+        item_crubit_features: ir.tree_ir().item_crubit_features.clone(),
         reexported_namespaces: ir.tree_ir().reexported_namespaces.clone(),
         unstable_rust_features: ir.tree_ir().unstable_rust_features.clone(),
         top_level_items,
@@ -1336,6 +1336,46 @@ fn test_string_view_annotated_alias_detected() -> Result<()> {
     Ok(())
 }
 
+#[gtest]
+fn test_std_span_detected() -> Result<()> {
+    let proto = ir_proto_from_assumed_lifetimes_cc(
+        &(with_full_lifetime_macros()
+            + r#"
+      namespace std {
+        template<typename T, __SIZE_TYPE__ Extent = __SIZE_TYPE__(-1)> class span {};
+      }
+      void f(std::span<const int> s, std::span<int> $a t);
+      "#),
+    )?;
+
+    let ir = make_test_ir_dependency(&proto, Some("assume_lifetimes"))?;
+    let factory = TestDbFactory::new(ir);
+    let dir = lifetime_defaults_transform(&factory.make_db())?;
+    assert_ir_matches!(
+        dir,
+        quote! {
+            Func {
+                cc_name: "f",
+                rs_name: "f", ...
+                params: [
+                    FuncParam {
+                        type_: CcType { ... explicit_lifetimes: ["s"] ... },
+                       identifier: "s", ...
+                    },
+                    FuncParam {
+                        type_: CcType { ... explicit_lifetimes: ["a"] ... },
+                       identifier: "t", ...
+                    }
+                ],
+                ...
+                lifetime_inputs: ["s", "a"],
+                ...
+            }
+        }
+    );
+    Ok(())
+}
+
 #[gtest]
 fn test_string_view_assumed_output_lifetime_matches_input() -> Result<()> {
     let proto = ir_proto_from_assumed_lifetimes_cc(
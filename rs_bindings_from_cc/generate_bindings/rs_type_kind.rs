@@ -5,7 +5,9 @@
 use arc_anyhow::{anyhow, ensure, Error, Result};
 use database::code_snippet::{NoBindingsReason, Visibility};
 use database::intern;
-use database::rs_snippet::{Lifetime, LifetimeOptions, Mutability, RsTypeKind, RustPtrKind};
+use database::rs_snippet::{
+    BridgeRsTypeKind, Lifetime, LifetimeOptions, Mutability, RsTypeKind, RustPtrKind,
+};
 use database::BindingsGenerator;
 use ir::GenericItem;
 use ir::{CcType, CcTypeVariant, PointerTypeKind};
@@ -78,6 +80,25 @@ pub fn rs_type_kind_with_lifetime_elision<'a>(
                 },
             )?;
 
+            // `const std::filesystem::path&` parameters are bridged to `&Path`: the C++ callee
+            // receives a temporary path, which the reference can bind to.
+            if let RsTypeKind::BridgeType {
+                bridge_type: BridgeRsTypeKind::StdPath { in_cc_std, is_const_ref: false },
+                original_type,
+            } = pointee.unalias()
+                && pointer.kind() == PointerTypeKind::LValueRef
+                && mutability.is_const()
+                && !lifetime_options.is_return_type
+            {
+                return Ok(RsTypeKind::BridgeType {
+                    bridge_type: BridgeRsTypeKind::StdPath {
+                        in_cc_std: *in_cc_std,
+                        is_const_ref: true,
+                    },
+                    original_type: original_type.clone(),
+                });
+            }
+
             // TODO(b/464492052): Support bridge types by pointer/reference.
             if let RsTypeKind::BridgeType { original_type, .. } = pointee.unalias() {
                 let visibility_override = if pointee.is_proto_message_bridge_type() {
@@ -247,7 +268,7 @@ pub fn rs_type_kind_with_lifetime_elision<'a>(
                 if let Some(owning_target) = item.owning_target() {
                     let decl_assumes_lifetimes = db
                         .ir()
-                        .target_crubit_features(&owning_target)
+                        .item_crubit_features(item.id(), &owning_target)
                         .contains(crubit_feature::CrubitFeature::AssumeLifetimes)
                         && !item_is_or_aliases_string_view(db, item);
                     (
@@ -70,7 +70,7 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
   const CmdlineArgs& args = cmdline.args();
 
   CRUBIT_ASSIGN_OR_RETURN(
-      std::vector<std::string> requested_instantiations,
+      RequestedInstantiations requested_instantiations,
       CollectInstantiations(args.srcs_to_scan_for_instantiations));
 
   std::optional<absl::flat_hash_set<std::string>> do_not_bind_allowlist =
@@ -106,7 +106,10 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
                  .reexported_namespaces = args.reexported_namespaces,
                  .unstable_rust_features = args.unstable_rust_features,
                  .clang_args = clang_args_view,
-                 .extra_instantiations = requested_instantiations,
+                 .extra_instantiations =
+                     requested_instantiations.class_templates,
+                 .extra_function_instantiations =
+                     requested_instantiations.function_templates,
                  .crubit_features = args.target_to_features,
                  .crate_names = args.target_to_crate_name,
                  .driver_path = args.driver_path,
@@ -132,6 +135,13 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
            Identifier(std::string(record->rs_name().identifier()))});
     }
   }
+  for (const auto* func : get_items_if<ir_proto::Func>(ir)) {
+    if (func->has_cc_template_fn_instantiation()) {
+      instantiations.insert(
+          {Identifier(std::string(func->cc_template_fn_instantiation())),
+           Identifier(std::string(func->rs_name().ident().identifier()))});
+    }
+  }
 
   NamespacesHierarchy top_level_namespaces;
   if (!args.namespaces_out.empty()) {
@@ -152,7 +162,8 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
                        args.clang_format_exe_path, args.rustfmt_exe_path,
                        args.rustfmt_config_path, generate_error_report,
                        args.is_golden_test, args.kythe_annotations,
-                       args.kythe_default_corpus));
+                       args.kythe_default_corpus,
+                       args.lsif_out.empty() ? "" : args.rs_out));
 
   return BindingsAndMetadata{
       .ir = std::move(ir_out),
@@ -161,6 +172,7 @@ absl::StatusOr<BindingsAndMetadata> GenerateBindingsAndMetadata(
       .namespaces = std::move(top_level_namespaces),
       .instantiations = std::move(instantiations),
       .error_report = bindings.error_report,
+      .lsif_index = bindings.lsif_index,
   };
 }
 
@@ -32,6 +32,8 @@ struct BindingsAndMetadata {
   absl::flat_hash_map<Identifier, Identifier> instantiations;
   // A JSON error report, if requested.
   std::string error_report;
+  // An LSIF index, if requested.
+  std::string lsif_index;
 };
 
 // Returns `BindingsAndMetadata` as requested by the user on the command line.
@@ -4,8 +4,11 @@
 
 #include "rs_bindings_from_cc/generate_bindings_and_metadata.h"
 
+#include <fstream>
+#include <sstream>
 #include <string>
 #include <utility>
+#include <vector>
 
 #include "gmock/gmock.h"
 #include "gtest/gtest.h"
@@ -26,6 +29,7 @@ namespace crubit {
 namespace {
 
 using ::testing::ElementsAre;
+using ::testing::HasSubstr;
 using ::testing::IsEmpty;
 using ::testing::Pair;
 using ::testing::StrEq;
@@ -199,6 +203,23 @@ TEST(GenerateBindingsAndMetadataTest,
                        Identifier("__CcTemplateInst16ExpectedTemplateIbE"))));
 }
 
+TEST(GenerateBindingsAndMetadataTest,
+     InstantiationsAreGeneratedForCcTemplateFnMacro) {
+  ASSERT_OK_AND_ASSIGN(auto instantiations,
+                       GetInstantiationsFor(
+                           R"cc(
+                             template <typename T>
+                             T Twice(T t) {
+                               return t + t;
+                             }
+                           )cc",
+                           "cc_template_fn!{Twice<int>}"));
+
+  ASSERT_THAT(instantiations,
+              ElementsAre(Pair(Identifier("Twice<int>"),
+                               Identifier("__CcTemplateInst_Z5TwiceIiET_S0_"))));
+}
+
 TEST(GenerateBindingsAndMetadataTest, NamespacesJsonGenerated) {
   constexpr absl::string_view kHeaderContent = R"(
     namespace top_level_1 {
@@ -259,5 +280,30 @@ TEST(GenerateBindingsAndMetadataTest, NamespacesJsonGenerated) {
   ASSERT_THAT(NamespacesAsJson(result.namespaces), StrEq(kExpected));
 }
 
+// `--cache_dir` relies on `-MD -MF` to find the headers that were read, even
+// though `clang::tooling` strips these flags.
+TEST(GenerateBindingsAndMetadataTest, DependencyFileWritten) {
+  Cmdline cmdline = MakeCmdline("a.h");
+  WriteFileForCurrentTest("b.h", "struct B {};");
+  std::string depfile_path = testing::TempDir() + "/rs_api.d";
+  std::vector<std::string> clang_args = DefaultClangArgs();
+  clang_args.insert(clang_args.end(), {"-MD", "-MF", depfile_path});
+
+  ASSERT_OK_AND_ASSIGN(
+      BindingsAndMetadata result,
+      GenerateBindingsAndMetadata(
+          cmdline, clang_args,
+          /*virtual_headers_contents_for_testing=*/
+          {{HeaderName("a.h"), "#include \"b.h\"\nstruct A { B b; };"}}));
+
+  std::ifstream depfile(depfile_path);
+  ASSERT_TRUE(depfile.good());
+  std::stringstream contents;
+  contents << depfile.rdbuf();
+  EXPECT_THAT(contents.str(), HasSubstr("rs_bindings_from_cc:"));
+  EXPECT_THAT(contents.str(), HasSubstr("a.h"));
+  EXPECT_THAT(contents.str(), HasSubstr("b.h"));
+}
+
 }  // namespace
 }  // namespace crubit
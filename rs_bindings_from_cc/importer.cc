@@ -6,6 +6,7 @@
 
 #include <stdint.h>
 
+#include <algorithm>
 #include <cassert>
 #include <functional>
 #include <iterator>
@@ -33,6 +34,7 @@
 #include "absl/strings/str_split.h"
 #include "absl/strings/string_view.h"
 #include "absl/strings/substitute.h"
+#include "absl/types/span.h"
 #include "common/annotation_reader.h"
 #include "common/status_macros.h"
 #include "common/string_view_conversion.h"
@@ -610,10 +612,15 @@ absl::StatusOr<std::optional<ItemId>> Importer::GetEnclosingItemId(
     if (decl_context->isTranslationUnit()) {
       return std::nullopt;
     }
-    // Class template specializations are always emitted in the top-level
+    // Class template specializations (and function template specializations
+    // requested by `cc_template_fn!`) are always emitted in the top-level
     // namespace.  See also Importer::GetOrderedItemIdsOfTemplateInstantiations.
     if (clang::isa<clang::ClassTemplateSpecializationDecl>(decl))
       return std::nullopt;
+    if (auto* function_decl = clang::dyn_cast<clang::FunctionDecl>(decl);
+        function_decl &&
+        GetFunctionTemplateInstantiation(*function_decl).has_value())
+      return std::nullopt;
 
     if (decl_context->isFunctionOrMethod()) {
       return std::nullopt;
@@ -769,10 +776,17 @@ std::vector<ItemId> Importer::GetItemIdsInSourceOrder(
 std::vector<ItemId> Importer::GetOrderedItemIdsOfTemplateInstantiations()
     const {
   std::vector<SourceLocationComparator::OrderedItemId> items;
-  items.reserve(class_template_instantiations_.size());
+  items.reserve(class_template_instantiations_.size() +
+                function_template_instantiations_.size());
   for (const auto* decl : class_template_instantiations_) {
     items.push_back({GetSourceOrderKey(*decl), GenerateItemId(*decl)});
   }
+  for (const auto& entry : function_template_instantiations_) {
+    const clang::FunctionDecl* decl = entry.first;
+    if (GetImportedItem(*decl) != nullptr) {
+      items.push_back({GetSourceOrderKey(*decl), GenerateItemId(*decl)});
+    }
+  }
 
   clang::SourceManager& sm = ctx_.getSourceManager();
   auto compare_locations = SourceLocationComparator(sm);
@@ -901,6 +915,85 @@ void SetMustBindItem(ir_proto::Item& item) {
   }
 }
 
+// LINT.IfChange(feature_names)
+constexpr absl::string_view kCrubitFeatureNames[] = {
+    "all",
+    "supported",
+    "wrapper",
+    "types",
+    "experimental",
+    "assume_lifetimes",
+    "assume_this_lifetimes",
+    "no_assume_lifetimes",
+    "unsafe_view",
+    "check_default_initialized",
+    "leading_colons_for_cpp_type",
+    "template_instantiation",
+    "layout_compat_tuple",
+    "always_specialize_generics_in_cpp_api_from_rust",
+    "record_impl_debug",
+    "ctor_plain_values",
+    "reserve_standard_macros",
+    "thunkless_accessors",
+    "oo_casting",
+    "async_fn_send_modulo_regions",
+    "bridge_collections",
+    "std_hash_collections",
+};
+// LINT.ThenChange(//depot/common/crubit_feature.rs)
+
+// Returns the Crubit features enabled by `CRUBIT_ENABLE_FEATURE` on `decl` or
+// on any declaration that encloses it, sorted and without duplicates.
+//
+// Returns an error if a feature name is not a known Crubit feature.
+absl::StatusOr<std::vector<std::string>> GetEnabledCrubitFeatures(
+    const clang::Decl& decl) {
+  std::vector<std::string> features;
+  const clang::Decl* current = &decl;
+  while (current != nullptr) {
+    CRUBIT_ASSIGN_OR_RETURN(
+        std::optional<std::vector<std::string>> args,
+        GetAnnotationWithStringArgs(*current, "crubit_enable_feature"));
+    if (args.has_value()) {
+      if (args->empty()) {
+        return absl::InvalidArgumentError(
+            "CRUBIT_ENABLE_FEATURE requires at least one feature name");
+      }
+      for (const std::string& feature : *args) {
+        if (!absl::c_linear_search(kCrubitFeatureNames, feature)) {
+          return absl::InvalidArgumentError(absl::StrCat(
+              "CRUBIT_ENABLE_FEATURE: unknown Crubit feature \"", feature,
+              "\""));
+        }
+      }
+      features.insert(features.end(), args->begin(), args->end());
+    }
+    const clang::DeclContext* context = current->getDeclContext();
+    current = context == nullptr || context->isTranslationUnit()
+                  ? nullptr
+                  : clang::Decl::castFromDeclContext(context);
+  }
+  absl::c_sort(features);
+  features.erase(std::unique(features.begin(), features.end()),
+                 features.end());
+  return features;
+}
+
+void Importer::AddItemCrubitFeatures(
+    const clang::Decl& decl, absl::Span<const std::string> features) {
+  if (features.empty()) {
+    return;
+  }
+  ir_proto::IRProto::FeatureSet& set =
+      (*invocation_.ir_.mutable_item_crubit_features())[GenerateItemId(decl)
+                                                            .value()];
+  for (const std::string& feature : features) {
+    if (!absl::c_linear_search(set.features(), feature)) {
+      set.add_features(feature);
+    }
+  }
+}
+
 void Importer::Import(
     clang::TranslationUnitDecl* absl_nonnull translation_unit_decl) {
   FindAlwaysInstantiateSpecs(*translation_unit_decl);
@@ -1078,6 +1171,12 @@ const ir_proto::Item* absl_nullable Importer::GetDeclItem(
                    "to break the cycle.";
       return nullptr;
     }
+    // A class template instantiation can be used by several decls, and gets
+    // the features enabled on each of them.
+    if (it->second.proto_item != nullptr &&
+        clang::isa<clang::ClassTemplateSpecializationDecl>(decl)) {
+      AddItemCrubitFeatures(*decl, importing_item_features_);
+    }
     return it->second.proto_item.get();
   }
   // Here, we need to be careful. Recursive imports break cycles as follows:
@@ -1191,6 +1290,27 @@ absl_nullable std::unique_ptr<ir_proto::Item> Importer::ImportDecl(
     return HardError(*decl, FormattedError::FromStatus(must_bind.status()));
   }
 
+  absl::StatusOr<std::vector<std::string>> enabled_features =
+      GetEnabledCrubitFeatures(*decl);
+  if (!enabled_features.ok()) {
+    return ImportUnsupportedItem(
+        *decl, std::nullopt,
+        {FormattedError::FromStatus(enabled_features.status())},
+        /*is_hard_error=*/*must_bind);
+  }
+  // A class template instantiation gets the features of the decl whose import
+  // instantiated it, e.g. `std::vector<int>` in
+  // `CRUBIT_ENABLE_FEATURE("template_instantiation") void F(std::vector<int>);`
+  if (clang::isa<clang::ClassTemplateSpecializationDecl>(decl)) {
+    enabled_features->insert(enabled_features->end(),
+                             importing_item_features_.begin(),
+                             importing_item_features_.end());
+    absl::c_sort(*enabled_features);
+    enabled_features->erase(
+        std::unique(enabled_features->begin(), enabled_features->end()),
+        enabled_features->end());
+  }
+
   if (IsTransitivelyInPrivate(decl)) {
     if (*must_bind) {
       return HardError(*decl,
@@ -1240,15 +1360,22 @@ absl_nullable std::unique_ptr<ir_proto::Item> Importer::ImportDecl(
         /*is_hard_error=*/*must_bind);
   }
 
+  std::vector<std::string> enclosing_item_features =
+      std::exchange(importing_item_features_, *enabled_features);
+  std::unique_ptr<ir_proto::Item> result;
   for (auto& importer : decl_importers_) {
-    std::unique_ptr<ir_proto::Item> result =
-        importer->ImportDecl(decl, *must_bind);
+    result = importer->ImportDecl(decl, *must_bind);
     if (result != nullptr) {
-      if (*must_bind) {
-        SetMustBindItem(*result);
-      }
-      return result;
+      break;
+    }
+  }
+  importing_item_features_ = std::move(enclosing_item_features);
+  if (result != nullptr) {
+    if (*must_bind) {
+      SetMustBindItem(*result);
     }
+    AddItemCrubitFeatures(*decl, *enabled_features);
+    return result;
   }
 
   if (*must_bind) {
@@ -1269,12 +1396,34 @@ const ir_proto::Item* absl_nullable Importer::GetImportedItem(
   return nullptr;
 }
 
+void Importer::ImportFunctionTemplateInstantiation(
+    clang::FunctionDecl* absl_nonnull function_decl, std::string spelling) {
+  function_template_instantiations_.try_emplace(
+      function_decl->getCanonicalDecl(), std::move(spelling));
+  (void)GetDeclItem(CanonicalizeDecl(function_decl));
+}
+
+std::optional<absl::string_view> Importer::GetFunctionTemplateInstantiation(
+    const clang::FunctionDecl& function_decl) const {
+  auto it =
+      function_template_instantiations_.find(function_decl.getCanonicalDecl());
+  if (it == function_template_instantiations_.end()) {
+    return std::nullopt;
+  }
+  return it->second;
+}
+
 BazelLabel Importer::GetOwningTarget(const clang::Decl& decl) const {
   // Template instantiations need to be generated in the target that triggered
   // the instantiation (not in the target where the template is defined).
   if (IsFullClassTemplateSpecializationOrChild(&decl)) {
     return invocation_.target_;
   }
+  if (auto* function_decl = clang::dyn_cast<clang::FunctionDecl>(&decl);
+      function_decl &&
+      GetFunctionTemplateInstantiation(*function_decl).has_value()) {
+    return invocation_.target_;
+  }
 
   // Built-in functions are defined by the compiler and are not associated with
   // any target. Without this check, the decl item ID will show up in the IR
@@ -1452,29 +1601,46 @@ bool Importer::IsFeatureEnabledForTarget(const BazelLabel& label,
   return false;
 }
 
+bool Importer::IsFeatureEnabledForDecl(const clang::Decl& decl,
+                                       absl::string_view feature) const {
+  if (IsFeatureEnabledForTarget(GetOwningTarget(decl), feature)) {
+    return true;
+  }
+  // Invalid `CRUBIT_ENABLE_FEATURE` annotations are reported when the item
+  // that carries them is imported.
+  absl::StatusOr<std::vector<std::string>> item_features =
+      GetEnabledCrubitFeatures(decl);
+  if (item_features.ok() && absl::c_linear_search(*item_features, feature)) {
+    return true;
+  }
+  // A class template instantiation also gets the features of the decl whose
+  // import instantiated it.
+  return clang::isa<clang::ClassTemplateSpecializationDecl>(decl) &&
+         absl::c_linear_search(importing_item_features_, feature);
+}
+
 // LINT.IfChange
-bool Importer::AreAssumedLifetimesEnabledForTarget(
-    const BazelLabel& label) const {
+bool Importer::AreAssumedLifetimesEnabledForDecl(
+    const clang::Decl& decl) const {
   // TODO(b/530193579): After expanding features earlier, just check the
   // specific feature without manually checking for "all" or
   // "no_assume_lifetimes".
-  return (IsFeatureEnabledForTarget(label, "assume_lifetimes") ||
-          IsFeatureEnabledForTarget(label, "all")) &&
-         !IsFeatureEnabledForTarget(label, "no_assume_lifetimes");
+  return (IsFeatureEnabledForDecl(decl, "assume_lifetimes") ||
+          IsFeatureEnabledForDecl(decl, "all")) &&
+         !IsFeatureEnabledForDecl(decl, "no_assume_lifetimes");
 }
 // LINT.ThenChange(//depot/common/crubit_feature.rs,
 // //depot/features/BUILD)
 
-bool Importer::IsUnsafeViewEnabledForTarget(const BazelLabel& label) const {
-  return IsFeatureEnabledForTarget(label, "unsafe_view");
+bool Importer::IsUnsafeViewEnabledForDecl(const clang::Decl& decl) const {
+  return IsFeatureEnabledForDecl(decl, "unsafe_view");
 }
 
-bool Importer::IsRecordImplDebugEnabledForTarget(
-    const BazelLabel& label) const {
+bool Importer::IsRecordImplDebugEnabledForDecl(const clang::Decl& decl) const {
   // TODO(b/530193579): After expanding features earlier, just check the
   // specific feature without manually checking for "all".
-  return IsFeatureEnabledForTarget(label, "record_impl_debug") ||
-         IsFeatureEnabledForTarget(label, "all");
+  return IsFeatureEnabledForDecl(decl, "record_impl_debug") ||
+         IsFeatureEnabledForDecl(decl, "all");
 }
 
 absl::StatusOr<bool> Importer::DetectFormatter(
@@ -1851,6 +2017,25 @@ std::string Importer::ConvertSourceLocation(
   return absl::StrCat(spelling_loc_str, "\n", expansion_loc_str);
 }
 
+std::optional<ir_proto::FileLocation> Importer::ConvertFileLocation(
+    clang::SourceLocation loc) const {
+  auto& sm = ctx_.getSourceManager();
+  clang::SourceLocation expansion_loc = sm.getExpansionLoc(loc);
+  if (expansion_loc.isInvalid()) return std::nullopt;
+  absl::string_view filename =
+      StringViewFromStringRef(sm.getFilename(expansion_loc));
+  if (filename.empty()) return std::nullopt;
+  if (absl::StartsWith(filename, "./")) {
+    filename = filename.substr(2);
+  }
+  ir_proto::FileLocation location;
+  location.set_file(std::string(filename));
+  location.set_offset(sm.getFileOffset(expansion_loc));
+  location.set_line(sm.getExpansionLineNumber(expansion_loc));
+  location.set_column(sm.getExpansionColumnNumber(expansion_loc));
+  return location;
+}
+
 CcType Importer::ConvertTemplateSpecializationType(
     const clang::TemplateSpecializationType& type) {
   // Qualifiers are handled separately in TypeMapper::ConvertQualType().
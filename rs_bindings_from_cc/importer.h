@@ -17,6 +17,7 @@
 #include "absl/log/check.h"
 #include "absl/status/statusor.h"
 #include "absl/strings/string_view.h"
+#include "absl/types/span.h"
 #include "lifetime_annotations/type_lifetimes.h"
 #include "rs_bindings_from_cc/bazel_types.h"
 #include "rs_bindings_from_cc/decl_importer.h"
@@ -98,6 +99,11 @@ class Importer final : public ImportContext {
   std::optional<ir_proto::UnsupportedItem::Path>
   GetUnsupportedItemPathForTemplateDecl(
       clang::RedeclarableTemplateDecl* absl_nonnull template_decl) override;
+  void ImportFunctionTemplateInstantiation(
+      clang::FunctionDecl* absl_nonnull function_decl,
+      std::string spelling) override;
+  std::optional<absl::string_view> GetFunctionTemplateInstantiation(
+      const clang::FunctionDecl& function_decl) const override;
   BazelLabel GetOwningTarget(const clang::Decl& decl) const override;
   bool IsFromCurrentTarget(const clang::Decl& decl) const override;
   bool RefersToOwnedDefinition(const clang::CXXRecordDecl& decl) const override;
@@ -105,11 +111,12 @@ class Importer final : public ImportContext {
       const clang::Decl& decl) const;
   bool IsFromProtoTarget(const clang::Decl& decl) const override;
   bool IsCrubitEnabledForTarget(const BazelLabel& label) const override;
-  bool AreAssumedLifetimesEnabledForTarget(
-      const BazelLabel& label) const override;
-  bool IsUnsafeViewEnabledForTarget(const BazelLabel& label) const override;
-  bool IsRecordImplDebugEnabledForTarget(
-      const BazelLabel& label) const override;
+  bool AreAssumedLifetimesEnabledForDecl(
+      const clang::Decl& decl) const override;
+  bool IsUnsafeViewEnabledForDecl(const clang::Decl& decl) const override;
+  bool IsRecordImplDebugEnabledForDecl(const clang::Decl& decl) const override;
+  bool IsFeatureEnabledForDecl(const clang::Decl& decl,
+                               absl::string_view feature) const override;
   absl::StatusOr<bool> DetectFormatter(
       const clang::TypeDecl& decl) const override;
   bool ImplementsCoreFmtDebug(const clang::TypeDecl& type) const override;
@@ -123,6 +130,8 @@ class Importer final : public ImportContext {
   std::string ConvertSourceLocation(
       clang::SourceLocation loc,
       clang::DeclarationNameInfo* absl_nullable name_info) const override;
+  std::optional<ir_proto::FileLocation> ConvertFileLocation(
+      clang::SourceLocation loc) const override;
   CcType ConvertQualType(
       clang::QualType qual_type,
       const clang::tidy::lifetimes::ValueLifetimes* absl_nullable lifetimes,
@@ -172,6 +181,10 @@ class Importer final : public ImportContext {
 
   const ir_proto::Item* absl_nullable GetDeclItem(
       clang::Decl* absl_nonnull decl) override;
+  // Records `features` as enabled for the item imported from `decl`, in
+  // addition to the features of its target.
+  void AddItemCrubitFeatures(const clang::Decl& decl,
+                             absl::Span<const std::string> features);
   // Stores the comments of this target in source order.
   void ImportFreeComments();
 
@@ -206,7 +219,7 @@ class Importer final : public ImportContext {
       absl::flat_hash_set<const clang::CXXRecordDecl*>& visited) const;
 
   bool IsFeatureEnabledForTarget(const BazelLabel& label,
-                                 absl::string_view feature) const override;
+                                 absl::string_view feature) const;
 
   absl::StatusOr<std::optional<bool>> GetCrubitOverrideDisplayAnnotation(
       const clang::TypeDecl& decl) const;
@@ -221,7 +234,15 @@ class Importer final : public ImportContext {
   absl::flat_hash_map<const clang::Decl*, ItemCacheEntry> import_cache_;
   absl::flat_hash_set<const clang::ClassTemplateSpecializationDecl*>
       class_template_instantiations_;
+  // Function template specializations requested by `cc_template_fn!`, mapped
+  // to the argument of the macro.
+  absl::flat_hash_map<const clang::FunctionDecl*, std::string>
+      function_template_instantiations_;
   std::vector<const clang::RawComment*> comments_;
+  // The Crubit features enabled by `CRUBIT_ENABLE_FEATURE` for the decl that is
+  // being imported. Class template instantiations triggered by its import
+  // inherit them.
+  std::vector<std::string> importing_item_features_;
 
   // Set of decls that have been successfully imported (i.e. that will be
   // present in the IR output / that will not produce dangling ItemIds in the IR
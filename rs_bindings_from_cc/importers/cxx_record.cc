@@ -329,10 +329,10 @@ std::optional<BridgeType> GetBridgeTypeAnnotation(
            specialization_decl->getTemplateArgs().asArray()) {
         if (template_arg.getKind() == clang::TemplateArgument::ArgKind::Type) {
           template_args.push_back(
-              ictx.ConvertQualType(template_arg.getAsType(),
-                                   /*lifetimes=*/nullptr, /*nullable=*/true,
-                                   ictx.AreAssumedLifetimesEnabledForTarget(
-                                       ictx.GetOwningTarget(record_decl))));
+              ictx.ConvertQualType(
+                  template_arg.getAsType(),
+                  /*lifetimes=*/nullptr, /*nullable=*/true,
+                  ictx.AreAssumedLifetimesEnabledForDecl(record_decl)));
         }
       }
     }
@@ -552,6 +552,65 @@ std::optional<llvm::StringRef> AsTopLevelNamespace(
   return identifier_info->getName();
 }
 
+// Returns true if `decl` is declared in the `std::<name>` namespace, e.g.
+// `std::chrono`.
+bool IsInStdSubNamespace(const clang::Decl& decl, llvm::StringRef name) {
+  const clang::DeclContext* context = decl.getDeclContext();
+  while (context->isInlineNamespace()) {
+    context = context->getParent();
+  }
+  const auto* namespace_decl = clang::dyn_cast<clang::NamespaceDecl>(context);
+  return namespace_decl != nullptr && namespace_decl->getName() == name &&
+         AsTopLevelNamespace(namespace_decl->getParent()) == "std";
+}
+
+// Returns the bridge type of `absl::Duration` and `absl::Time`, which are
+// bridged to `bridge_rust::SignedDuration` and `std::time::SystemTime`.
+std::optional<BridgeType> GetAbslTimeBridgeType(
+    const clang::RecordDecl& record_decl) {
+  if (AsTopLevelNamespace(record_decl.getDeclContext()) != "absl") {
+    return std::nullopt;
+  }
+  if (record_decl.getName() == "Duration") {
+    return BridgeType{BridgeType::Duration{}};
+  }
+  if (record_decl.getName() == "Time") {
+    return BridgeType{BridgeType::Time{}};
+  }
+  return std::nullopt;
+}
+
+// Returns the bridge type of `std::filesystem::path`, which is bridged to
+// `std::path::PathBuf`.
+std::optional<BridgeType> GetStdPathBridgeType(
+    const clang::RecordDecl& record_decl) {
+  if (record_decl.getName() == "path" &&
+      IsInStdSubNamespace(record_decl, "filesystem")) {
+    return BridgeType{BridgeType::StdPath{}};
+  }
+  return std::nullopt;
+}
+
+// Returns true if `deleter` is `rs_std::BoxDeleter<T>`, so that a
+// `std::unique_ptr` with this deleter owns a Rust `Box<T>`.
+bool IsRustBoxDeleter(const clang::TemplateArgument& deleter) {
+  if (deleter.getKind() != clang::TemplateArgument::Type) {
+    return false;
+  }
+  const auto* deleter_decl =
+      clang::dyn_cast_or_null<clang::ClassTemplateSpecializationDecl>(
+          deleter.getAsType()->getAsCXXRecordDecl());
+  return deleter_decl != nullptr && deleter_decl->getName() == "BoxDeleter" &&
+         AsTopLevelNamespace(deleter_decl->getDeclContext()) == "rs_std";
+}
+
+// Returns true if `record_decl` is `absl::Cord`, which is mapped to the
+// handwritten `absl_cord::Cord` in Rust.
+bool IsAbslCord(const clang::RecordDecl& record_decl) {
+  return AsTopLevelNamespace(record_decl.getDeclContext()) == "absl" &&
+         record_decl.getName() == "Cord";
+}
+
 // Checks that a ClassTemplateSpecializationDecl has template arguments of the
 // form `<T, std::std_trait_name<T>>`. If so, returns `T`.
 //
@@ -636,6 +695,12 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
       }
       // Other character types do not get special support.
     } else if (templated_decl->getName() == "unique_ptr") {
+      const clang::TemplateArgumentList& args =
+          specialization_decl->getTemplateArgs();
+      if (args.size() == 2 && IsRustBoxDeleter(args[1])) {
+        // `rs_std::Box<T>` is bridged to `Box<T>` by `GetBuiltinBridgeType`.
+        return TemplateSpecialization::NonSpecial();
+      }
       CRUBIT_ASSIGN_OR_RETURN(clang::QualType t,
                               ParameterizedByTAndStdTraitT(
                                   ictx, specialization_decl, "default_delete"));
@@ -644,8 +709,7 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
           // for assumed_lifetimes?
           ictx.ConvertQualType(
               t, /*lifetimes=*/nullptr, /*nullable=*/true,
-              ictx.AreAssumedLifetimesEnabledForTarget(
-                  ictx.GetOwningTarget(*specialization_decl))));
+              ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)));
     } else if (templated_decl->getName() == "shared_ptr") {
       if (specialization_decl->getTemplateArgs().size() != 1) {
         return absl::InvalidArgumentError(
@@ -657,8 +721,7 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
           // for assumed_lifetimes?
           ictx.ConvertQualType(
               t, /*lifetimes=*/nullptr, /*nullable=*/true,
-              ictx.AreAssumedLifetimesEnabledForTarget(
-                  ictx.GetOwningTarget(*specialization_decl))));
+              ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)));
     } else if (templated_decl->getName() == "vector") {
       CRUBIT_ASSIGN_OR_RETURN(
           clang::QualType t,
@@ -667,8 +730,40 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
       // assumed_lifetimes?
       return TemplateSpecialization::StdVector(ictx.ConvertQualType(
           t, /*lifetimes=*/nullptr, /*nullable=*/true,
-          ictx.AreAssumedLifetimesEnabledForTarget(
-              ictx.GetOwningTarget(*specialization_decl))));
+          ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)));
+    } else if (templated_decl->getName() == "span") {
+      const clang::TemplateArgumentList& args =
+          specialization_decl->getTemplateArgs();
+      if (args.size() != 2 ||
+          args[1].getKind() != clang::TemplateArgument::Integral) {
+        return absl::InvalidArgumentError(
+            "std::span should have a type and an extent template arg");
+      }
+      // Only a span with `std::dynamic_extent` (that is, `size_t(-1)`) stores
+      // its size, and so has the layout of `cc_std::std::span`.
+      if (!args[1].getAsIntegral().isAllOnes()) {
+        return TemplateSpecialization::NonSpecial();
+      }
+      // TODO(b/454627672): is specialization_decl the right decl to check for
+      // assumed_lifetimes?
+      return TemplateSpecialization::StdSpan(ictx.ConvertQualType(
+          args[0].getAsType(), /*lifetimes=*/nullptr, /*nullable=*/true,
+          ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)));
+    } else if (templated_decl->getName() == "array") {
+      const clang::TemplateArgumentList& args =
+          specialization_decl->getTemplateArgs();
+      if (args.size() != 2 ||
+          args[1].getKind() != clang::TemplateArgument::Integral) {
+        return absl::InvalidArgumentError(
+            "std::array should have a type and a size template arg");
+      }
+      // TODO(b/454627672): is specialization_decl the right decl to check for
+      // assumed_lifetimes?
+      return TemplateSpecialization::StdArray{
+          .element_type = ictx.ConvertQualType(
+              args[0].getAsType(), /*lifetimes=*/nullptr, /*nullable=*/true,
+              ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)),
+          .size = args[1].getAsIntegral().getZExtValue()};
     }
     if (templated_decl->getName() == "atomic") {
       if (specialization_decl->getTemplateArgs().size() != 1) {
@@ -709,8 +804,7 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
 
       return TemplateSpecialization::StdAtomic{ictx.ConvertQualType(
           t, /*lifetimes=*/nullptr, /*nullable=*/true,
-          ictx.AreAssumedLifetimesEnabledForTarget(
-              ictx.GetOwningTarget(*specialization_decl)))};
+          ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl))};
     }
   } else if (top_level_namespace == "absl") {
     if (templated_decl->getName() == "Span") {
@@ -722,8 +816,7 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
       return TemplateSpecialization::AbslSpan(ictx.ConvertQualType(
           t,
           /*lifetimes=*/nullptr, /*nullable=*/true,
-          ictx.AreAssumedLifetimesEnabledForTarget(
-              ictx.GetOwningTarget(*specialization_decl))));
+          ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)));
     } else if (templated_decl->getName() == "flat_hash_map") {
       LOG_IF(FATAL, specialization_decl->getTemplateArgs().size() < 2)
           << "absl::flat_hash_map should have at least two template args";
@@ -735,13 +828,11 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
           .key_type = ictx.ConvertQualType(
               k,
               /*lifetimes=*/nullptr, /*nullable=*/true,
-              ictx.AreAssumedLifetimesEnabledForTarget(
-                  ictx.GetOwningTarget(*specialization_decl))),
+              ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)),
           .value_type = ictx.ConvertQualType(
               v,
               /*lifetimes=*/nullptr, /*nullable=*/true,
-              ictx.AreAssumedLifetimesEnabledForTarget(
-                  ictx.GetOwningTarget(*specialization_decl)))};
+              ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl))};
     } else if (templated_decl->getName() == "flat_hash_set") {
       LOG_IF(FATAL, specialization_decl->getTemplateArgs().size() < 1)
           << "absl::flat_hash_set should have at least one template arg";
@@ -752,8 +843,7 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
           .element_type = ictx.ConvertQualType(
               t,
               /*lifetimes=*/nullptr, /*nullable=*/true,
-              ictx.AreAssumedLifetimesEnabledForTarget(
-                  ictx.GetOwningTarget(*specialization_decl)))};
+              ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl))};
     }
   } else if (top_level_namespace == "c9") {
     if (templated_decl->getName() == "Co") {
@@ -773,19 +863,19 @@ absl::StatusOr<TemplateSpecialization::Kind> GetTemplateSpecializationKind(
       return TemplateSpecialization::C9Co(ictx.ConvertQualType(
           t,
           /*lifetimes=*/nullptr, /*nullable=*/true,
-          ictx.AreAssumedLifetimesEnabledForTarget(
-              ictx.GetOwningTarget(*specialization_decl))));
+          ictx.AreAssumedLifetimesEnabledForDecl(*specialization_decl)));
     }
   }
 
   return TemplateSpecialization::NonSpecial();
 }
 
-// Returns the `DynCallable` information for the given `specialization_decl`.
+// Returns the `Callable` information for the given `specialization_decl`.
 //
-// If the given `specialization_decl` is not a `rs_std::DynCallable`, returns
-// `std::nullopt`. If it is a `rs_std::DynCallable` but has other errors,
-// returns an error.
+// If the given `specialization_decl` is not a `rs_std::DynCallable`,
+// `absl::AnyInvocable`, `std::move_only_function`, or `absl::FunctionRef`,
+// returns `std::nullopt`. If it is one of those but has other errors, returns
+// an error.
 std::optional<absl::StatusOr<BridgeType>> ExtractCallable(
     ImportContext& ictx,
     const clang::ClassTemplateSpecializationDecl& specialization_decl) {
@@ -801,21 +891,32 @@ std::optional<absl::StatusOr<BridgeType>> ExtractCallable(
   } else if (top_level_namespace == "absl" &&
              templated_decl->getName() == "AnyInvocable") {
     backing_type = BridgeType::Callable::BackingType::kAnyInvocable;
+  } else if (top_level_namespace == "std" &&
+             templated_decl->getName() == "move_only_function") {
+    backing_type = BridgeType::Callable::BackingType::kMoveOnlyFunction;
+  } else if (top_level_namespace == "absl" &&
+             templated_decl->getName() == "FunctionRef") {
+    backing_type = BridgeType::Callable::BackingType::kFunctionRef;
   } else {
     return std::nullopt;
   }
 
-  if (specialization_decl.getTemplateArgs().size() != 1) {
+  llvm::ArrayRef<clang::TemplateArgument> template_args =
+      specialization_decl.getTemplateArgs().asArray();
+  // `std::move_only_function` is declared as a variadic template, so its
+  // signature is wrapped in a parameter pack.
+  if (template_args.size() == 1 &&
+      template_args[0].getKind() == clang::TemplateArgument::Pack) {
+    template_args = template_args[0].getPackAsArray();
+  }
+  if (template_args.size() != 1 ||
+      template_args[0].getKind() != clang::TemplateArgument::Type) {
     return absl::InvalidArgumentError(
         "Callable template specialization must have exactly one template "
         "argument");
   }
   const clang::FunctionProtoType* sig_fn_type =
-      specialization_decl.getTemplateArgs()
-          .get(0)
-          .getAsType()
-          .getTypePtr()
-          ->getAs<clang::FunctionProtoType>();
+      template_args[0].getAsType()->getAs<clang::FunctionProtoType>();
 
   if (sig_fn_type == nullptr) {
     return absl::InvalidArgumentError(
@@ -851,8 +952,8 @@ std::optional<absl::StatusOr<BridgeType>> ExtractCallable(
       ictx.ConvertQualType(sig_fn_type->getReturnType(),
                            /*lifetimes=*/nullptr,
                            /*nullable=*/true,
-                           ictx.AreAssumedLifetimesEnabledForTarget(
-                               ictx.GetOwningTarget(*templated_decl)));
+                           ictx.AreAssumedLifetimesEnabledForDecl(
+                               *templated_decl));
 
   std::vector<CcType> param_types;
   // Convert the parameter types, ensuring that they are complete first.
@@ -872,8 +973,8 @@ std::optional<absl::StatusOr<BridgeType>> ExtractCallable(
     CcType param_cc_type =
         ictx.ConvertQualType(param_type, /*lifetimes=*/nullptr,
                              /*nullable=*/true,
-                             ictx.AreAssumedLifetimesEnabledForTarget(
-                                 ictx.GetOwningTarget(specialization_decl)));
+                             ictx.AreAssumedLifetimesEnabledForDecl(
+                                 specialization_decl));
     param_types.push_back(std::move(param_cc_type));
   }
 
@@ -911,7 +1012,7 @@ absl::StatusOr<SafetyAnnotation> CXXRecordDeclImporter::GetSafetyAnnotation(
   }
 
   if (crubit::HasAttr<clang::PointerAttr>(&decl) &&
-      ictx_.IsUnsafeViewEnabledForTarget(ictx_.GetOwningTarget(decl))) {
+      ictx_.IsUnsafeViewEnabledForDecl(decl)) {
     return SafetyAnnotation::SAFETY_ANNOTATION_UNSAFE;
   }
 
@@ -940,6 +1041,43 @@ std::optional<Identifier> CXXRecordDeclImporter::GetTranslatedFieldName(
   return (*name).rs_identifier();
 }
 
+absl::StatusOr<std::string> CXXRecordDeclImporter::GetAnonymousRecordName(
+    const clang::CXXRecordDecl& record_decl) {
+  const auto& parent =
+      *clang::cast<clang::CXXRecordDecl>(record_decl.getDeclContext());
+  std::string parent_name;
+  if (IsAnonymousNestedRecord(parent)) {
+    CRUBIT_ASSIGN_OR_RETURN(parent_name, GetAnonymousRecordName(parent));
+  } else if (auto* specialization_decl =
+                 clang::dyn_cast<clang::ClassTemplateSpecializationDecl>(
+                     &parent)) {
+    parent_name = ictx_.GetMangledName(*specialization_decl);
+  } else {
+    const clang::NamedDecl* named_parent = &parent;
+    if (auto* typedef_decl = parent.getTypedefNameForAnonDecl()) {
+      named_parent = typedef_decl;
+    }
+    CRUBIT_ASSIGN_OR_RETURN(TranslatedIdentifier parent_identifier,
+                            ictx_.GetTranslatedIdentifier(*named_parent));
+    parent_name = parent_identifier.rs_identifier().Ident();
+  }
+
+  // Anonymous records are numbered in declaration order, so that the names
+  // are stable as long as the parent record doesn't change.
+  int index = 0;
+  for (const clang::Decl* decl : parent.decls()) {
+    const auto* sibling = clang::dyn_cast<clang::CXXRecordDecl>(decl);
+    if (sibling == nullptr || !IsAnonymousNestedRecord(*sibling)) {
+      continue;
+    }
+    if (sibling->getCanonicalDecl() == record_decl.getCanonicalDecl()) {
+      break;
+    }
+    ++index;
+  }
+  return absl::StrCat(parent_name, "__anon_", index);
+}
+
 bool IsKnownAttr(const clang::Attr& attr) {
   return clang::isa<clang::AlignedAttr>(attr) ||
          clang::isa<clang::FinalAttr>(attr) ||
@@ -1037,9 +1175,16 @@ std::unique_ptr<ir_proto::Item> CXXRecordDeclImporter::Import(
   clang::SourceLocation source_loc;
   std::optional<std::string> doc_comment;
   bool is_explicit_class_template_instantiation_definition = false;
+  bool is_anonymous = false;
   std::optional<TemplateSpecialization> template_specialization;
   std::optional<BridgeType> bridge_type =
       GetBridgeTypeAnnotation(ictx_, *record_decl);
+  if (!bridge_type.has_value()) {
+    bridge_type = GetAbslTimeBridgeType(*record_decl);
+  }
+  if (!bridge_type.has_value()) {
+    bridge_type = GetStdPathBridgeType(*record_decl);
+  }
 
   absl::StatusOr<std::optional<std::vector<std::string>>> args =
       GetAnnotationWithStringArgs(*record_decl, "crubit_owned_pointee");
@@ -1207,6 +1352,33 @@ std::unique_ptr<ir_proto::Item> CXXRecordDeclImporter::Import(
       }
       bridge_type = *std::move(builtin_bridge_type);
     }
+  } else if (IsAnonymousNestedRecord(*record_decl)) {
+    // Anonymous structs and unions nested in a record have no C++ name, so we
+    // synthesize one from the enclosing record. They can still be used as the
+    // types of fields, and their members are flattened into the enclosing
+    // record if they are anonymous members.
+    is_anonymous = true;
+    if (!record_decl->isTriviallyCopyable() ||
+        !record_decl->hasTrivialDestructor()) {
+      return ictx_.ImportUnsupportedItem(
+          *record_decl, std::nullopt,
+          {FormattedError::Static(
+              "Anonymous structs and unions with nontrivial special member "
+              "functions are not supported")});
+    }
+    absl::StatusOr<std::string> anonymous_name =
+        GetAnonymousRecordName(*record_decl);
+    if (!anonymous_name.ok()) {
+      return ictx_.ImportUnsupportedItem(
+          *record_decl, std::nullopt,
+          {FormattedError::PrefixedStrCat(
+              "Enclosing record name is not supported",
+              anonymous_name.status().message())});
+    }
+    rs_name = *anonymous_name;
+    cc_name = *std::move(anonymous_name);
+    doc_comment = ictx_.GetComment(*record_decl);
+    source_loc = record_decl->getBeginLoc();
   } else {
     const clang::NamedDecl* named_decl = record_decl;
     if (record_decl->getName().empty()) {
@@ -1342,8 +1514,7 @@ std::unique_ptr<ir_proto::Item> CXXRecordDeclImporter::Import(
   }
 
   std::vector<std::string> lifetime_inputs;
-  if (ictx_.AreAssumedLifetimesEnabledForTarget(
-          ictx_.GetOwningTarget(*record_decl))) {
+  if (ictx_.AreAssumedLifetimesEnabledForDecl(*record_decl)) {
     auto lifetime_inputs_or_err =
         CollectLifetimeInputs(ictx_.sema_.getASTContext(), record_decl);
     if (!lifetime_inputs_or_err.ok()) {
@@ -1368,8 +1539,8 @@ std::unique_ptr<ir_proto::Item> CXXRecordDeclImporter::Import(
   ItemId id = ictx_.GenerateItemId(*record_decl);
   ictx_.invocation_.child_item_ids_[id] = std::move(item_ids);
 
-  bool record_impl_debug_enabled = ictx_.IsRecordImplDebugEnabledForTarget(
-      ictx_.GetOwningTarget(*record_decl));
+  bool record_impl_debug_enabled =
+      ictx_.IsRecordImplDebugEnabledForDecl(*record_decl);
 
   bool impl_debug = false;
   if (record_impl_debug_enabled) {
@@ -1453,6 +1624,8 @@ std::unique_ptr<ir_proto::Item> CXXRecordDeclImporter::Import(
   record->set_is_aggregate(record_decl->isAggregate());
   record->set_is_canonical_alias(anon_typedef != nullptr ||
                                  is_canonical_template_alias);
+  record->set_is_anonymous(is_anonymous);
+  record->set_is_absl_cord(IsAbslCord(*record_decl));
   record->set_is_explicit_class_template_instantiation_definition(
       is_explicit_class_template_instantiation_definition);
   if (enclosing_item_id.has_value()) {
@@ -1533,8 +1706,7 @@ std::vector<ir_proto::Field> CXXRecordDeclImporter::ImportFields(
           return ictx_.ConvertQualType(
               field_decl->getType(), no_lifetimes,
               /*nullable=*/true,
-              ictx_.AreAssumedLifetimesEnabledForTarget(
-                  ictx_.GetOwningTarget(*record_decl)));
+              ictx_.AreAssumedLifetimesEnabledForDecl(*record_decl));
         case clang::AS_protected:
         case clang::AS_private:
         case clang::AS_none:
@@ -1720,8 +1892,7 @@ CXXRecordDeclImporter::GetBuiltinBridgeType(
     return ictx_.ConvertQualType(
         /*qual_type=*/decl->getTemplateArgs()[index].getAsType(),
         /*lifetimes=*/nullptr, /*nullable=*/true,
-        ictx_.AreAssumedLifetimesEnabledForTarget(
-            ictx_.GetOwningTarget(*cxx_record_decl)));
+        ictx_.AreAssumedLifetimesEnabledForDecl(*cxx_record_decl));
   };
 
   if (name == "optional") {
@@ -1753,6 +1924,27 @@ CXXRecordDeclImporter::GetBuiltinBridgeType(
       return absl::InternalError(error->message());
     }
   }
+  if (name == "unique_ptr" && decl->getTemplateArgs().size() == 2 &&
+      IsRustBoxDeleter(decl->getTemplateArgs()[1])) {
+    CcType pointee = cc_type_of_arg(0);
+    return BridgeType{BridgeType::RustBox{
+        .pointee_type = std::make_shared<CcType>(std::move(pointee)),
+    }};
+  }
+
+  if (name == "duration" && IsInStdSubNamespace(*cxx_record_decl, "chrono")) {
+    return BridgeType{BridgeType::Duration{}};
+  }
+
+  // Only the epoch of `system_clock` is specified to be the Unix epoch.
+  if (name == "time_point" && IsInStdSubNamespace(*cxx_record_decl, "chrono")) {
+    const clang::CXXRecordDecl* clock =
+        decl->getTemplateArgs()[0].getAsType()->getAsCXXRecordDecl();
+    if (clock != nullptr && clock->getName() == "system_clock" &&
+        IsInStdSubNamespace(*clock, "chrono")) {
+      return BridgeType{BridgeType::Time{}};
+    }
+  }
   // Add builtin bridge types here as needed.
 
   return std::nullopt;
@@ -44,6 +44,12 @@ class CXXRecordDeclImporter : public DeclImporterBase<clang::CXXRecordDecl> {
   std::optional<Identifier> GetTranslatedFieldName(
       const clang::FieldDecl* field);
 
+  // Returns the synthesized Rust name of an anonymous nested record (see
+  // `IsAnonymousNestedRecord`), e.g. `Outer__anon_0` for the first anonymous
+  // struct or union nested in `Outer`.
+  absl::StatusOr<std::string> GetAnonymousRecordName(
+      const clang::CXXRecordDecl& record_decl);
+
   // Returns the bridge type of the given `decl` if it is a builtin bridge type
   // (e.g., `std::optional`). Otherwise, returns `std::nullopt`.
   absl::StatusOr<std::optional<BridgeType>> GetBuiltinBridgeType(
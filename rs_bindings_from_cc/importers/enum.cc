@@ -72,8 +72,8 @@ std::unique_ptr<ir_proto::Item> EnumDeclImporter::Import(
   const clang::tidy::lifetimes::ValueLifetimes* no_lifetimes = nullptr;
   absl::StatusOr<CcType> type =
       ictx_.ConvertQualType(cpp_type, no_lifetimes, /*nullable=*/true,
-                            ictx_.AreAssumedLifetimesEnabledForTarget(
-                                ictx_.GetOwningTarget(*enum_decl)));
+                            ictx_.AreAssumedLifetimesEnabledForDecl(
+                                *enum_decl));
   if (!type.ok()) {
     return unsupported(FormattedError::FromStatus(std::move(type.status())));
   }
@@ -50,8 +50,8 @@ std::unique_ptr<ir_proto::Item> EnumConstantDeclImporter::Import(
 
   absl::StatusOr<CcType> type =
       ictx_.ConvertQualType(cpp_type, nullptr, /*nullable=*/true,
-                            ictx_.AreAssumedLifetimesEnabledForTarget(
-                                ictx_.GetOwningTarget(*enum_constant_decl)));
+                            ictx_.AreAssumedLifetimesEnabledForDecl(
+                                *enum_constant_decl));
   if (!type.ok()) {
     return ictx_.ImportUnsupportedItem(
         *enum_constant_decl, std::nullopt,
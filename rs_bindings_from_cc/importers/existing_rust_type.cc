@@ -233,8 +233,7 @@ GetCrubitInternalRustTypeAttr(ImportContext& ictx, const clang::Decl& decl) {
       // for assumed_lifetimes?
       format_args.push_back(TemplateArg(
           ictx.ConvertQualType(type, /*lifetimes=*/nullptr, /*nullable=*/true,
-                               ictx.AreAssumedLifetimesEnabledForTarget(
-                                   ictx.GetOwningTarget(*spec)))));
+                               ictx.AreAssumedLifetimesEnabledForDecl(*spec))));
     }
   }
 
@@ -301,8 +300,7 @@ std::unique_ptr<ir_proto::Item> ExistingRustTypeImporter::Import(
   }
   existing->set_is_same_abi(*is_same_abi);
   existing->set_id(ictx_.GenerateItemId(*type_decl).value());
-  existing->set_impl_debug(ictx_.IsRecordImplDebugEnabledForTarget(
-                               ictx_.GetOwningTarget(*type_decl)) &&
+  existing->set_impl_debug(ictx_.IsRecordImplDebugEnabledForDecl(*type_decl) &&
                            ictx_.ImplementsCoreFmtDebug(*type_decl));
   return item;
 }
@@ -58,6 +58,7 @@
 #include "llvm/ADT/STLExtras.h"
 #include "llvm/ADT/StringRef.h"
 #include "llvm/Support/Error.h"
+#include "llvm/Support/MD5.h"
 #include "llvm/Support/Path.h"
 #include "llvm/Support/raw_ostream.h"
 
@@ -297,8 +298,8 @@ std::optional<PropertyFieldInfo> GetPropertyFieldInfo(ImportContext& ictx,
   }
   CcType type = ictx.ConvertQualType(field_decl->getType(),
                                      /*lifetimes=*/nullptr, /*nullable=*/true,
-                                     ictx.AreAssumedLifetimesEnabledForTarget(
-                                         ictx.GetOwningTarget(*record_decl)));
+                                     ictx.AreAssumedLifetimesEnabledForDecl(
+                                         *record_decl));
 
   const clang::ASTRecordLayout& layout =
       record_decl->getASTContext().getASTRecordLayout(record_decl);
@@ -476,8 +477,8 @@ std::unique_ptr<ir_proto::Item> FunctionDeclImporter::Import(
   if (!ictx_.IsFromCurrentTarget(*function_decl)) return nullptr;
   if (function_decl->isDeleted()) return nullptr;
   const bool only_import_types =
-      ictx_.IsFeatureEnabledForCurrentTarget("types") &&
-      !ictx_.IsFeatureEnabledForCurrentTarget("supported");
+      ictx_.IsFeatureEnabledForDecl(*function_decl, "types") &&
+      !ictx_.IsFeatureEnabledForDecl(*function_decl, "supported");
   if (!must_bind_ && only_import_types &&
       FunctionNameIsIdentifier(*function_decl)) {
     return nullptr;
@@ -502,6 +503,20 @@ std::unique_ptr<ir_proto::Item> FunctionDeclImporter::Import(
         // TODO(b/475810473): Revisit this for protected methods.
         return nullptr;
     }
+    // Anonymous structs and unions have no C++ name, so thunks can't refer to
+    // them. They are only imported if their special member functions are
+    // trivial, in which case the Rust side doesn't need to call them.
+    if (IsAnonymousNestedRecord(*method_decl->getParent())) {
+      if (method_decl->isImplicit()) {
+        return nullptr;
+      }
+      return ictx_.ImportUnsupportedItem(
+          *function_decl, std::nullopt,
+          {FormattedError::Static(
+              "Member functions of anonymous structs and unions are not "
+              "supported")},
+          must_bind_);
+    }
   }
 
   absl::StatusOr<TranslatedUnqualifiedIdentifier> translated_name =
@@ -513,6 +528,21 @@ std::unique_ptr<ir_proto::Item> FunctionDeclImporter::Import(
                                         translated_name.status().message())},
         must_bind_);
   }
+  std::optional<absl::string_view> cc_template_fn_instantiation =
+      ictx_.GetFunctionTemplateInstantiation(*function_decl);
+  if (cc_template_fn_instantiation.has_value()) {
+    // All specializations of a function template share its name, so (like for
+    // class template specializations) the mangled name is used instead.
+    std::string rs_name = absl::StrCat("__CcTemplateInst",
+                                       ictx_.GetMangledName(*function_decl));
+    if (rs_name.size() > 160) {
+      // rustdoc generates filenames using these names, so they can't be too
+      // long. See also `CXXRecordDeclImporter::Import`.
+      rs_name = absl::StrCat(rs_name.substr(0, 160 - 17), "_",
+                             absl::Hex(llvm::MD5Hash(rs_name)));
+    }
+    translated_name->crubit_rust_name = Identifier(std::move(rs_name));
+  }
 
   auto enclosing_item_id = ictx_.GetEnclosingItemId(function_decl);
   if (!enclosing_item_id.ok()) {
@@ -650,8 +680,8 @@ std::unique_ptr<ir_proto::Item> FunctionDeclImporter::Import(
     }
   }
 
-  bool assumed_lifetimes_enabled = ictx_.AreAssumedLifetimesEnabledForTarget(
-      ictx_.GetOwningTarget(*function_decl));
+  bool assumed_lifetimes_enabled =
+      ictx_.AreAssumedLifetimesEnabledForDecl(*function_decl);
   clang::tidy::lifetimes::LifetimeSymbolTable lifetime_symbol_table;
   std::optional<clang::tidy::lifetimes::FunctionLifetimes> lifetimes;
   std::vector<std::string> lifetime_inputs;
@@ -953,7 +983,8 @@ std::unique_ptr<ir_proto::Item> FunctionDeclImporter::Import(
       GetSafetyAnnotation(*function_decl, errors);
 
   std::optional<std::string> doc_comment = ictx_.GetComment(*function_decl);
-  if (!doc_comment.has_value() && is_member_or_descendant_of_class_template) {
+  if (!doc_comment.has_value() && (is_member_or_descendant_of_class_template ||
+                                   cc_template_fn_instantiation.has_value())) {
     // Despite `is_member_or_descendant_of_class_template` check above, we are
     // not guaranteed that a `func_pattern` exists below.  For example, it may
     // be missing when `function_decl` is an implicitly defined constructor of
@@ -1073,6 +1104,11 @@ std::unique_ptr<ir_proto::Item> FunctionDeclImporter::Import(
   func->set_safety_annotation(safety_annotation);
   func->set_source_loc(
       ictx_.ConvertSourceLocation(function_decl->getBeginLoc(), &name_info));
+  if (std::optional<ir_proto::FileLocation> decl_begin =
+          ictx_.ConvertFileLocation(function_decl->getBeginLoc());
+      decl_begin.has_value()) {
+    *func->mutable_decl_begin() = std::move(*decl_begin);
+  }
   func->set_id(ictx_.GenerateItemId(*function_decl).value());
   if (enclosing_item_id->has_value()) {
     func->set_enclosing_item_id((*enclosing_item_id)->value());
@@ -1086,6 +1122,9 @@ std::unique_ptr<ir_proto::Item> FunctionDeclImporter::Import(
   if (source_text.has_value()) {
     func->set_inline_cpp_source_text(*source_text);
   }
+  if (cc_template_fn_instantiation.has_value()) {
+    func->set_cc_template_fn_instantiation(*cc_template_fn_instantiation);
+  }
   auto semantic = GetMemberFuncSemantic(ictx_, function_decl);
   if (semantic.has_value()) {
     *func->mutable_semantic() = std::move(*semantic);
@@ -148,7 +148,7 @@ std::unique_ptr<ir_proto::Item> crubit::TypeAliasImporter::Import(
   // type aliases, pass these to ConvertQualType().
   absl::StatusOr<CcType> underlying_type = ictx_.ConvertQualType(
       underlying_qualtype, no_lifetimes, /*nullable=*/true,
-      ictx_.AreAssumedLifetimesEnabledForTarget(ictx_.GetOwningTarget(*decl)));
+      ictx_.AreAssumedLifetimesEnabledForDecl(*decl));
 
   if (!underlying_type.ok()) {
     return ictx_.ImportUnsupportedItem(
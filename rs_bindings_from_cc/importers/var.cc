@@ -17,12 +17,36 @@
 #include "clang/AST/Decl.h"
 #include "clang/AST/DeclBase.h"
 #include "clang/AST/DeclTemplate.h"
+#include "clang/AST/Expr.h"
 #include "clang/AST/TypeBase.h"
 #include "clang/Basic/LLVM.h"
+#include "clang/Basic/SourceLocation.h"
+#include "clang/Lex/Lexer.h"
 #include "llvm/Support/Casting.h"
 
 namespace crubit {
 
+void VarDeclImporter::ImportFunctionTemplateInstantiation(
+    const clang::VarDecl& var_decl) {
+  // The variable is initialized with `&<spelling>`, see `IrFromCc`.
+  const clang::Expr* init = var_decl.getInit();
+  if (init == nullptr) return;
+  const auto* address_of =
+      clang::dyn_cast<clang::UnaryOperator>(init->IgnoreImplicit());
+  if (address_of == nullptr) return;
+  auto* decl_ref = clang::dyn_cast<clang::DeclRefExpr>(
+      address_of->getSubExpr()->IgnoreParenImpCasts());
+  if (decl_ref == nullptr) return;
+  auto* function_decl =
+      clang::dyn_cast<clang::FunctionDecl>(decl_ref->getDecl());
+  if (function_decl == nullptr) return;
+
+  llvm::StringRef spelling = clang::Lexer::getSourceText(
+      clang::CharSourceRange::getTokenRange(decl_ref->getSourceRange()),
+      ictx_.ctx_.getSourceManager(), ictx_.ctx_.getLangOpts());
+  ictx_.ImportFunctionTemplateInstantiation(function_decl, spelling.str());
+}
+
 std::unique_ptr<ir_proto::Item> VarDeclImporter::Import(
     clang::VarDecl* var_decl) {
   // Most vars are not globals – fail fast if this is the case.
@@ -38,6 +62,16 @@ std::unique_ptr<ir_proto::Item> VarDeclImporter::Import(
     return nullptr;
   }
 
+  // Explicitly support `cc_template_fn!`: see
+  // support/cc_template/cc_template.rs. The variable itself is an
+  // implementation detail, only the specialization it points to is imported.
+  if (const auto* ns_decl = clang::dyn_cast<clang::NamespaceDecl>(decl_context);
+      ns_decl && ns_decl->getName() == "__cc_template_instantiations" &&
+      var_decl->getName().starts_with("__cc_template_fn_instantiation_")) {
+    ImportFunctionTemplateInstantiation(*var_decl);
+    return nullptr;
+  }
+
   if (var_decl->isStaticDataMember()) {
     return ictx_.ImportUnsupportedItem(
         *var_decl, std::nullopt,
@@ -104,8 +138,7 @@ std::unique_ptr<ir_proto::Item> VarDeclImporter::Import(
 
   CcType type =
       ictx_.ConvertQualType(var_decl->getType(), nullptr, /*nullable=*/true,
-                            ictx_.AreAssumedLifetimesEnabledForTarget(
-                                ictx_.GetOwningTarget(*var_decl)));
+                            ictx_.AreAssumedLifetimesEnabledForDecl(*var_decl));
 
   if (has_const_init) {
     const clang::Type& var_type = *var_decl->getType().getTypePtr();
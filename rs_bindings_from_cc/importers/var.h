@@ -19,6 +19,11 @@ class VarDeclImporter : public DeclImporterBase<clang::VarDecl> {
   explicit VarDeclImporter(ImportContext& context)
       : DeclImporterBase(context) {}
   std::unique_ptr<ir_proto::Item> Import(clang::VarDecl*) override;
+
+ private:
+  // Imports the function template specialization pointed to by a variable that
+  // `IrFromCc` generated for a `cc_template_fn!` request.
+  void ImportFunctionTemplateInstantiation(const clang::VarDecl& var_decl);
 };
 
 }  // namespace crubit
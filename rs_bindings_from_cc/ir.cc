@@ -204,6 +204,13 @@ void BridgeType::WriteToProto(ir_proto::BridgeType& proto) const {
             // signify its presence
             proto.mutable_std_string();
           },
+          [&](const BridgeType::Duration&) { proto.mutable_duration(); },
+          [&](const BridgeType::Time&) { proto.mutable_time(); },
+          [&](const BridgeType::StdPath&) { proto.mutable_std_path(); },
+          [&](const BridgeType::RustBox& rust_box) {
+            rust_box.pointee_type->WriteToProto(
+                *proto.mutable_rust_box()->mutable_pointee_type());
+          },
           [&](const BridgeType::ProtoMessageBridge& proto_message_bridge) {
             proto.mutable_proto_message_bridge()->set_rust_name(
                 proto_message_bridge.rust_name);
@@ -219,6 +226,14 @@ void BridgeType::WriteToProto(ir_proto::BridgeType& proto) const {
                 c->set_backing_type(
                     flat_proto::BridgeType::Callable::ANY_INVOCABLE);
                 break;
+              case BridgeType::Callable::BackingType::kMoveOnlyFunction:
+                c->set_backing_type(
+                    flat_proto::BridgeType::Callable::MOVE_ONLY_FUNCTION);
+                break;
+              case BridgeType::Callable::BackingType::kFunctionRef:
+                c->set_backing_type(
+                    flat_proto::BridgeType::Callable::FUNCTION_REF);
+                break;
             }
             switch (callable.fn_trait) {
               case BridgeType::Callable::FnTrait::kFn:
@@ -268,6 +283,15 @@ void TemplateSpecialization::WriteToProto(
             std_unique_ptr.element_type.WriteToProto(
                 *proto.mutable_std_unique_ptr()->mutable_element_type());
           },
+          [&](const StdSpan& std_span) {
+            std_span.element_type.WriteToProto(
+                *proto.mutable_std_span()->mutable_element_type());
+          },
+          [&](const StdArray& std_array) {
+            auto* msg = proto.mutable_std_array();
+            std_array.element_type.WriteToProto(*msg->mutable_element_type());
+            msg->set_size(std_array.size);
+          },
           [&](const AbslSpan& absl_span) {
             absl_span.element_type.WriteToProto(
                 *proto.mutable_absl_span()->mutable_element_type());
@@ -428,10 +428,26 @@ struct BridgeType {
 
   struct StdString {};
 
+  // `absl::Duration` or a `std::chrono::duration`.
+  struct Duration {};
+
+  // `absl::Time` or a `std::chrono::time_point` of `std::chrono::system_clock`.
+  struct Time {};
+
+  // `std::filesystem::path`.
+  struct StdPath {};
+
+  // `std::unique_ptr<T, rs_std::BoxDeleter<T>>`, an owned Rust `Box<T>`.
+  struct RustBox {
+    std::shared_ptr<CcType> pointee_type;
+  };
+
   struct Callable {
     enum BackingType {
       kDynCallable,
       kAnyInvocable,
+      kMoveOnlyFunction,
+      kFunctionRef,
     } backing_type;
     enum FnTrait {
       kFn,
@@ -443,7 +459,7 @@ struct BridgeType {
   };
 
   std::variant<Bridge, StdOptional, StdPair, StdString, ProtoMessageBridge,
-               Callable>
+               Callable, Duration, Time, StdPath, RustBox>
       variant;
 };
 
@@ -464,6 +480,13 @@ struct TemplateSpecialization {
   struct StdUniquePtr {
     CcType element_type;
   };
+  struct StdSpan {
+    CcType element_type;
+  };
+  struct StdArray {
+    CcType element_type;
+    uint64_t size;
+  };
   struct AbslSpan {
     CcType element_type;
   };
@@ -485,8 +508,8 @@ struct TemplateSpecialization {
 
   using Kind =
       std::variant<StdStringView, StdWStringView, StdVector, StdSharedPtr,
-                   StdUniquePtr, AbslSpan, AbslFlatHashMap, AbslFlatHashSet,
-                   C9Co, StdAtomic, NonSpecial>;
+                   StdUniquePtr, StdSpan, StdArray, AbslSpan, AbslFlatHashMap,
+                   AbslFlatHashSet, C9Co, StdAtomic, NonSpecial>;
 
   BazelLabel defining_target;
   Kind kind = NonSpecial{};
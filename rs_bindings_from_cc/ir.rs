@@ -10,10 +10,10 @@ use code_gen_utils::{make_rs_ident, try_make_rs_ident};
 use crubit_feature::CrubitFeature;
 use ir_rust_proto::{
     BaseClassView, CommentView, ConstantView, EnumView, EnumeratorView, ExistingRustTypeView,
-    FieldView, FuncParamView, FuncView, GlobalVarView, HeaderNameView, IdentifierView,
-    IncompleteRecordView, InstanceMethodMetadataView, IntegerConstantView, NamespaceView,
-    OwnedPtrConfigView, RecordView, SizeAlignView, StatusOrOptionalStringView, TypeAliasView,
-    UseModView,
+    FieldView, FileLocationView, FuncParamView, FuncView, GlobalVarView, HeaderNameView,
+    IdentifierView, IncompleteRecordView, InstanceMethodMetadataView, IntegerConstantView,
+    NamespaceView, OwnedPtrConfigView, RecordView, SizeAlignView, StatusOrOptionalStringView,
+    TypeAliasView, UseModView,
 };
 use itertools::Itertools;
 use proc_macro2::{Ident, TokenStream};
@@ -921,7 +921,7 @@ impl Debug for Operator<'_> {
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct ItemId(usize);
 
 impl Debug for ItemId {
@@ -1295,12 +1295,14 @@ impl<'pb> ProtoToIr for FuncView<'pb> {
         self.unknown_attr_opt().into_option().validate()?;
         self.safety_annotation().validate()?;
         self.source_loc().validate()?;
+        self.decl_begin_opt().into_option().validate()?;
         for s in self.lifetime_inputs().iter() {
             s.validate()?;
         }
         if let Some(sem) = self.semantic_opt().into_option() {
             let _ = MemberFuncSemantic::try_from(sem)?;
         }
+        self.cc_template_fn_instantiation_opt().into_option().validate()?;
         self.inline_cpp_source_text_opt().into_option().validate()
     }
 
@@ -1439,6 +1441,11 @@ derive_debug_partialeq_eq_hash! {
             self.proto.source_loc().to_ir()
         }
 
+        /// Where the declaration begins in the file that would be edited to change it.
+        pub fn decl_begin(&self) -> Option<FileLocation<'pb>> {
+            self.proto.decl_begin_opt().into_option().to_ir()
+        }
+
         pub fn id(&self) -> ItemId {
             ItemId(self.proto.id() as usize)
         }
@@ -1466,6 +1473,12 @@ derive_debug_partialeq_eq_hash! {
             self.proto.must_bind()
         }
 
+        /// If this function is a function template specialization requested by
+        /// `cc_template_fn!`, the C++ spelling of the specialization.
+        pub fn cc_template_fn_instantiation(&self) -> Option<&'pb str> {
+            self.proto.cc_template_fn_instantiation_opt().into_option().to_ir()
+        }
+
         pub fn inline_cpp_source_text(&self) -> Option<&str> {
             self.inline_cpp_source_text.as_deref()
         }
@@ -1918,6 +1931,35 @@ impl ToTokens for RecordType {
     }
 }
 
+/// A position in a source file.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct FileLocation<'pb> {
+    pub file: &'pb str,
+    /// Byte offset from the start of the file.
+    pub offset: u64,
+    /// 1-based line number.
+    pub line: u32,
+    /// 1-based column number, in bytes.
+    pub column: u32,
+}
+
+impl<'pb> ProtoToIr for FileLocationView<'pb> {
+    type IrType = FileLocation<'pb>;
+
+    fn validate(self) -> Result<()> {
+        self.file().validate()
+    }
+
+    fn to_ir(self) -> FileLocation<'pb> {
+        FileLocation {
+            file: self.file().to_ir(),
+            offset: self.offset(),
+            line: self.line(),
+            column: self.column(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 pub struct SizeAlign {
     pub(crate) size: usize,
@@ -1961,6 +2003,14 @@ pub enum BridgeType<'pb> {
     StdOptional(CcType),
     StdPair(CcType, CcType),
     StdString,
+    /// `absl::Duration` or a `std::chrono::duration`.
+    Duration,
+    /// `absl::Time` or a `std::chrono::time_point` of `std::chrono::system_clock`.
+    Time,
+    /// `std::filesystem::path`.
+    StdPath,
+    /// `std::unique_ptr<T, rs_std::BoxDeleter<T>>`, an owned Rust `Box<T>`.
+    RustBox(CcType),
     Callable {
         backing_type: BackingType,
         fn_trait: FnTrait,
@@ -2015,6 +2065,10 @@ pub enum TemplateSpecializationKind {
     C9Co { raw_element_type: CcType },
     /// std::atomic<T>
     StdAtomic { raw_element_type: CcType },
+    /// std::span<T, std::dynamic_extent>
+    StdSpan { raw_element_type: CcType },
+    /// std::array<T, N>
+    StdArray { raw_element_type: CcType, size: u64 },
     /// absl::Span<T>
     AbslSpan { raw_element_type: CcType },
     /// absl::flat_hash_map<K, V, ...>
@@ -2362,6 +2416,19 @@ derive_debug_partialeq_eq_hash! {
             self.proto.is_canonical_alias()
         }
 
+        /// Whether this is an anonymous struct or union nested in another record.
+        ///
+        /// Anonymous records have a synthesized name (e.g. `Outer__anon_0`) and can't be
+        /// spelled in C++.
+        pub fn is_anonymous(&self) -> bool {
+            self.proto.is_anonymous()
+        }
+
+        /// Whether this is `absl::Cord`, which is mapped to the handwritten `absl_cord::Cord`.
+        pub fn is_absl_cord(&self) -> bool {
+            self.proto.is_absl_cord()
+        }
+
         pub fn enclosing_item_id(&self) -> Option<ItemId> {
             self.proto.enclosing_item_id_opt().into_option().map(|id| ItemId(id as usize))
         }
@@ -4168,6 +4235,8 @@ pub struct TreeIR<'pb> {
     pub crate_root_path: Option<Rc<str>>,
     pub crubit_features: BTreeMap<BazelLabel, crubit_feature::SerializedCrubitFeatures>,
     pub crate_names: BTreeMap<BazelLabel, Ident>,
+    /// Features enabled for individual items, in addition to those of their target.
+    pub item_crubit_features: BTreeMap<ItemId, crubit_feature::SerializedCrubitFeatures>,
     pub unstable_rust_features: Vec<String>,
     pub reexported_namespaces: Vec<Rc<str>>,
     pub top_level_items: BTreeMap<BazelLabel, Vec<Item<'pb>>>,
@@ -4198,6 +4267,7 @@ impl<'pb> Debug for TreeIR<'pb> {
             crate_root_path,
             crubit_features,
             crate_names,
+            item_crubit_features,
             unstable_rust_features,
             reexported_namespaces,
             top_level_items,
@@ -4208,6 +4278,7 @@ impl<'pb> Debug for TreeIR<'pb> {
             .field("crate_root_path", crate_root_path)
             .field("crubit_features", &DebugBTreeMap(crubit_features))
             .field("crate_names", &DebugBTreeMap(crate_names))
+            .field("item_crubit_features", &DebugBTreeMap(item_crubit_features))
             .field("unstable_rust_features", unstable_rust_features)
             .field("reexported_namespaces", reexported_namespaces)
             .field("top_level_items", &DebugBTreeMap(top_level_items))
@@ -4375,6 +4446,42 @@ impl<'pb> IR<'pb> {
         &mut self.tree_ir.crubit_features.entry(target.clone()).or_default().0
     }
 
+    /// Returns the Crubit features enabled for the item `item_id` owned by `target`: the features
+    /// of `target`, and those enabled by `CRUBIT_ENABLE_FEATURE` on the item or on a declaration
+    /// enclosing it.
+    ///
+    /// Class template instantiations also get the features of the declarations that use them,
+    /// and pass them on to their members.
+    #[must_use]
+    pub fn item_crubit_features(
+        &self,
+        item_id: ItemId,
+        target: &BazelLabel,
+    ) -> flagset::FlagSet<CrubitFeature> {
+        let mut features = self.target_crubit_features(target);
+        let mut current = Some(item_id);
+        while let Some(id) = current {
+            if let Some(item_features) = self.tree_ir.item_crubit_features.get(&id) {
+                features |= item_features.0;
+            }
+            current = self.get_decl(id).and_then(|item| item.enclosing_item_id());
+        }
+        features
+    }
+
+    /// Returns a mutable reference to the Crubit features enabled for the item `item_id`, in
+    /// addition to the features of its target.
+    ///
+    /// Since IR is generally only held immutably, this is only useful for
+    /// testing.
+    #[must_use]
+    pub fn item_crubit_features_mut(
+        &mut self,
+        item_id: ItemId,
+    ) -> &mut flagset::FlagSet<CrubitFeature> {
+        &mut self.tree_ir.item_crubit_features.entry(item_id).or_default().0
+    }
+
     pub fn current_target(&self) -> &BazelLabel {
         &self.tree_ir.current_target
     }
@@ -4485,6 +4592,7 @@ mod tests {
             crate_root_path: None,
             crubit_features: Default::default(),
             crate_names: BTreeMap::new(),
+            item_crubit_features: BTreeMap::new(),
             unstable_rust_features: vec![],
             reexported_namespaces: vec![],
             top_level_items: BTreeMap::new(),
@@ -21,6 +21,7 @@
 #include "absl/strings/str_cat.h"
 #include "absl/strings/str_split.h"
 #include "absl/strings/string_view.h"
+#include "absl/strings/strip.h"
 #include "absl/strings/substitute.h"
 #include "absl/types/span.h"
 #include "common/status_macros.h"
@@ -177,13 +178,35 @@ absl::Status AddUseModToIr(IR& ir,
   }
   return absl::OkStatus();
 }
+
+// Returns the path passed to Clang's `-MF` flag, if a dependency file was
+// requested with `-MD` or `-MMD`, and an empty string otherwise.
+//
+// `clang::tooling::runToolOnCodeWithArgs` strips these flags, so the dependency
+// file is written by `FrontendAction` instead.
+std::string GetDependencyFile(absl::Span<const std::string> clang_args) {
+  bool write_dependency_file = false;
+  std::string dependency_file;
+  for (size_t i = 0; i < clang_args.size(); ++i) {
+    absl::string_view arg = clang_args[i];
+    if (arg == "-MD" || arg == "-MMD") {
+      write_dependency_file = true;
+    } else if (arg == "-MF" && i + 1 < clang_args.size()) {
+      dependency_file = clang_args[++i];
+    } else if (absl::ConsumePrefix(&arg, "-MF")) {
+      dependency_file = std::string(arg);
+    }
+  }
+  return write_dependency_file ? dependency_file : "";
+}
 }  // namespace
 
 absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
   // Caller should verify that the inputs are not empty.
   CHECK(!options.extra_source_code_for_testing.empty() ||
         !options.public_headers.empty() ||
-        !options.extra_instantiations.empty());
+        !options.extra_instantiations.empty() ||
+        !options.extra_function_instantiations.empty());
 
   clang::tooling::FileContentMappings file_contents;
 
@@ -220,7 +243,8 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
     options.headers_to_targets.insert(
         {HeaderName(cc_src), options.current_target});
   }
-  if (!options.extra_instantiations.empty()) {
+  if (!options.extra_instantiations.empty() ||
+      !options.extra_function_instantiations.empty()) {
     absl::SubstituteAndAppend(&virtual_input_file_content, "namespace $0 {\n",
                               kInstantiationsNamespaceName);
     int counter = 0;
@@ -230,6 +254,17 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
                                 "using __cc_template_instantiation_$0 = $1;\n",
                                 counter++, extra_instantiation);
     }
+    // Taking the address of the specialization selects a single overload and
+    // odr-uses it, so that Clang instantiates its definition. See
+    // `VarDeclImporter` for how the specialization is then imported.
+    counter = 0;
+    for (const std::string& extra_function_instantiation :
+         options.extra_function_instantiations) {
+      absl::SubstituteAndAppend(
+          &virtual_input_file_content,
+          "inline constexpr auto* __cc_template_fn_instantiation_$0 = &$1;\n",
+          counter++, extra_function_instantiation);
+    }
     absl::SubstituteAndAppend(&virtual_input_file_content,
                               "}  // namespace $0\n",
                               kInstantiationsNamespaceName);
@@ -247,7 +282,8 @@ absl::StatusOr<IR> IrFromCc(IrFromCcOptions options) {
       options.kythe_annotations, options.template_blocklist_path_regex,
       options.carcinize_mode);
   if (!clang::tooling::runToolOnCodeWithArgs(
-          std::make_unique<FrontendAction>(invocation),
+          std::make_unique<FrontendAction>(
+              invocation, GetDependencyFile(args_as_strings)),
           virtual_input_file_content, args_as_strings,
           StringRefFromStringView(kVirtualInputPath),
           // Passing the path to the driver script here allows Clang to find the
@@ -46,6 +46,7 @@ struct IrFromCcOptions final {
   absl::Span<const std::string> unstable_rust_features = {};
   absl::Span<const absl::string_view> clang_args = {};
   absl::Span<const std::string> extra_instantiations = {};
+  absl::Span<const std::string> extra_function_instantiations = {};
   absl::flat_hash_map<BazelLabel, absl::flat_hash_set<std::string>>
       crubit_features = {};
   absl::flat_hash_map<BazelLabel, std::string> crate_names = {};
@@ -94,6 +95,9 @@ static_assert(std::is_aggregate_v<IrFromCcOptions>);
 //    the crate. This is done via `#[path="..."] mod <...>; pub use <...>::*;`.
 // * `extra_instantiations`: names of full C++ class template specializations
 //   to instantiate and generate bindings from.
+// * `extra_function_instantiations`: names of C++ function template
+//   specializations (with all template arguments spelled out) to instantiate
+//   and generate bindings from.
 // * `crubit_features`: The set of Crubit features to enable for each target.
 //
 CRUBIT_PROFILE_NOINLINE absl::StatusOr<IR> IrFromCc(IrFromCcOptions options);
@@ -4,6 +4,7 @@
 #![cfg(test)]
 
 use arc_anyhow::Result;
+use crubit_feature::CrubitFeature;
 use googletest::matchers::{eq, pat, some};
 use googletest::{expect_eq, expect_that, fail, gtest, OrFail};
 use ir::*;
@@ -1059,12 +1060,12 @@ fn test_struct_with_unnamed_struct_and_union_members() {
                 fields: [
                     Field {
                         rust_identifier: None, ...
-                        type_ : CcType {variant: Error(...), ...}, ...
+                        type_ : CcType {variant: Decl { ... }, ...}, ...
                         offset: 0, ...
                     } ...
                     Field {
                         rust_identifier: None, ...
-                        type_ : CcType {variant: Error(...), ...}, ...
+                        type_ : CcType {variant: Decl { ... }, ...}, ...
                         offset: 64, ...
                     } ...
                 ], ...
@@ -1075,6 +1076,66 @@ fn test_struct_with_unnamed_struct_and_union_members() {
             }
         }
     );
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Record {
+                rs_name: "StructWithUnnamedMembers__anon_0" ...
+                cc_name: "StructWithUnnamedMembers__anon_0" ...
+                fields: [
+                    Field {
+                        rust_identifier: Some("anonymous_struct_field_1"), ...
+                    } ...
+                    Field {
+                        rust_identifier: Some("anonymous_struct_field_2"), ...
+                        offset: 32, ...
+                    } ...
+                ], ...
+                record_type: Struct, ...
+                is_anonymous: true, ...
+            }
+        }
+    );
+    assert_ir_matches!(
+        ir,
+        quote! {
+            Record {
+                rs_name: "StructWithUnnamedMembers__anon_1" ...
+                record_type: Union, ...
+                is_anonymous: true, ...
+            }
+        }
+    );
+}
+
+#[gtest]
+fn test_anonymous_record_with_nontrivial_member_is_unsupported() {
+    let proto = ir_proto_from_cc(
+        r#"
+        struct Nontrivial {
+          ~Nontrivial();
+        };
+        struct Outer {
+          struct {
+            Nontrivial n;
+          } inner;
+        }; "#,
+    )
+    .unwrap();
+
+    let ir = make_test_ir(&proto).unwrap();
+
+    assert_ir_matches!(
+        ir,
+        quote! {
+            UnsupportedItem {
+                ...
+                errors: [FormattedError {
+                    ... message: "Anonymous structs and unions with nontrivial special member functions are not supported", ...
+                }], ...
+            }
+        }
+    );
 }
 
 #[gtest]
@@ -1365,6 +1426,111 @@ fn test_must_bind_annotation_on_unbindable_function_produces_must_bind_error(
     Ok(())
 }
 
+#[gtest]
+fn test_enable_feature_annotation() -> googletest::Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"
+        namespace [[clang::annotate("crubit_enable_feature", "template_instantiation")]] ns {
+          [[clang::annotate("crubit_enable_feature", "experimental")]] void f();
+          void g();
+        }
+        void h();
+        "#,
+    )
+    .or_fail()?;
+
+    let ir = ir_testing::make_test_ir(&proto).or_fail()?;
+    let item_features = |name: &str| {
+        let func = ir.functions().find(|func| *func.rs_name() == name).unwrap();
+        ir.tree_ir().item_crubit_features.get(&func.id()).map(|features| features.0)
+    };
+    expect_eq!(
+        item_features("f"),
+        Some(CrubitFeature::Experimental | CrubitFeature::TemplateInstantiation)
+    );
+    expect_eq!(item_features("g"), Some(CrubitFeature::TemplateInstantiation.into()));
+    expect_eq!(item_features("h"), None);
+    Ok(())
+}
+
+#[gtest]
+fn test_enable_feature_annotation_without_features() -> googletest::Result<()> {
+    let proto =
+        ir_proto_from_cc(r#"[[clang::annotate("crubit_enable_feature")]] void f();"#).or_fail()?;
+
+    let ir = ir_testing::make_test_ir(&proto).or_fail()?;
+    let func = ir.unsupported_items().find(|item| item.name() == "f").or_fail()?;
+    expect_eq!(
+        func.errors()[0].message.as_ref(),
+        "CRUBIT_ENABLE_FEATURE requires at least one feature name"
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_enable_feature_annotation_with_unknown_feature() -> googletest::Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"
+        [[clang::annotate("crubit_enable_feature", "no_such_feature")]] void f();
+        void g();
+        "#,
+    )
+    .or_fail()?;
+
+    // Only the annotated item is affected.
+    let ir = ir_testing::make_test_ir(&proto).or_fail()?;
+    let func = ir.unsupported_items().find(|item| item.name() == "f").or_fail()?;
+    expect_eq!(
+        func.errors()[0].message.as_ref(),
+        "CRUBIT_ENABLE_FEATURE: unknown Crubit feature \"no_such_feature\""
+    );
+    ir.functions().find(|func| *func.rs_name() == "g").or_fail()?;
+    Ok(())
+}
+
+#[gtest]
+fn test_enable_feature_annotation_applies_to_template_instantiations() -> googletest::Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"
+        template <typename T> struct MyTemplate { T value; };
+        [[clang::annotate("crubit_enable_feature", "template_instantiation")]]
+        void Consume(MyTemplate<int> t);
+        "#,
+    )
+    .or_fail()?;
+
+    let ir = ir_testing::make_test_ir(&proto).or_fail()?;
+    let record = ir
+        .records()
+        .find(|record| record.rs_name().as_str().contains("__CcTemplateInst"))
+        .or_fail()?;
+    expect_eq!(
+        ir.tree_ir().item_crubit_features.get(&record.id()).map(|features| features.0),
+        Some(CrubitFeature::TemplateInstantiation.into())
+    );
+    Ok(())
+}
+
+#[gtest]
+fn test_enable_feature_annotation_applies_to_importer_gates() -> googletest::Result<()> {
+    let proto = ir_proto_from_cc(
+        r#"
+        class [[gsl::Pointer(int)]] View {};
+        class [[gsl::Pointer(int)]] [[clang::annotate("crubit_enable_feature", "unsafe_view")]]
+            UnsafeView {};
+        "#,
+    )
+    .or_fail()?;
+
+    let ir = ir_testing::make_test_ir(&proto).or_fail()?;
+    let safety_annotation = |name: &str| {
+        ir.records().find(|record| record.rs_name().as_str() == name).unwrap().safety_annotation()
+    };
+    expect_eq!(safety_annotation("View"), SafetyAnnotation::Unannotated);
+    expect_eq!(safety_annotation("UnsafeView"), SafetyAnnotation::Unsafe);
+    Ok(())
+}
+
 #[gtest]
 fn test_typedef() -> Result<()> {
     let proto = ir_proto_from_cc(
@@ -5263,6 +5429,89 @@ fn test_absl_container_template_specialization_kind() {
     );
 }
 
+#[gtest]
+fn test_absl_cord() {
+    let proto = ir_proto_from_cc(
+        "
+        namespace absl {
+        inline namespace lts_20250127 {
+        class Cord {};
+        }  // namespace lts_20250127
+        }  // namespace absl
+
+        namespace other {
+        class Cord {};
+        }  // namespace other
+        ",
+    )
+    .unwrap();
+
+    let ir = ir_testing::make_test_ir(&proto).unwrap();
+
+    let is_absl_cord: Vec<bool> = ir
+        .records()
+        .filter(|record| record.cc_name() == "Cord")
+        .map(|record| record.is_absl_cord())
+        .collect();
+    expect_eq!(is_absl_cord, vec![true, false]);
+}
+
+#[gtest]
+fn test_std_span_and_array_template_specialization_kind() {
+    let proto = ir_proto_from_cc(
+        "
+        namespace std {
+
+        inline constexpr __SIZE_TYPE__ dynamic_extent = -1;
+
+        template <typename T, __SIZE_TYPE__ Extent = dynamic_extent>
+        class span {};
+
+        template <typename T, __SIZE_TYPE__ N>
+        struct array { T elems[N]; };
+
+        }  // namespace std
+
+        using IntSpan = std::span<const int>;
+        using FixedIntSpan = std::span<int, 4>;
+        using DoubleArray = std::array<double, 3>;
+        ",
+    )
+    .unwrap();
+
+    let ir = ir_testing::make_test_ir(&proto).unwrap();
+
+    let record = retrieve_type_alias_record(&ir, "IntSpan");
+    expect_that!(
+        &record.template_specialization().map(|ts| ts.kind().clone()),
+        some(pat!(TemplateSpecializationKind::StdSpan {
+            raw_element_type: property!(
+                &CcType.variant(),
+                pat!(CcTypeVariant::Primitive(eq(&Primitive::Int)))
+            ),
+        })),
+    );
+
+    // Only spans with a dynamic extent are special.
+    let record = retrieve_type_alias_record(&ir, "FixedIntSpan");
+    expect_that!(
+        &record.template_specialization().map(|ts| ts.kind().clone()),
+        some(pat!(TemplateSpecializationKind::NonSpecial)),
+    );
+
+    let record = retrieve_type_alias_record(&ir, "DoubleArray");
+    expect_that!(
+        &record.template_specialization().map(|ts| ts.kind().clone()),
+        some(pat!(TemplateSpecializationKind::StdArray {
+            raw_element_type: property!(
+                &CcType.variant(),
+                pat!(CcTypeVariant::Primitive(eq(&Primitive::Double)))
+            ),
+            size: eq(&3),
+        })),
+    );
+}
+
 #[gtest]
 fn test_impl_debug_default_true() {
     let proto = ir_proto_from_record_impl_debug_cc("struct S {};").unwrap();
@@ -105,6 +105,7 @@ pub fn make_ir_from_items<'pb>(items: impl IntoIterator<Item = Item<'pb>>) -> IR
         crate_root_path: None,
         crubit_features: BTreeMap::new(),
         crate_names: BTreeMap::new(),
+        item_crubit_features: BTreeMap::new(),
         unstable_rust_features: vec![],
         reexported_namespaces: vec![],
         top_level_items,
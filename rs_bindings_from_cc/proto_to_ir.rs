@@ -303,6 +303,12 @@ impl<'pb> TryFrom<::ir_rust_proto::BridgeTypeView<'pb>> for BridgeType<'pb> {
                 CcType::try_from(sp.second_type())?,
             )),
             ::ir_rust_proto::bridge_type::VariantOneof::StdString(_) => Ok(BridgeType::StdString),
+            ::ir_rust_proto::bridge_type::VariantOneof::Duration(_) => Ok(BridgeType::Duration),
+            ::ir_rust_proto::bridge_type::VariantOneof::Time(_) => Ok(BridgeType::Time),
+            ::ir_rust_proto::bridge_type::VariantOneof::StdPath(_) => Ok(BridgeType::StdPath),
+            ::ir_rust_proto::bridge_type::VariantOneof::RustBox(rb) => {
+                Ok(BridgeType::RustBox(CcType::try_from(rb.pointee_type())?))
+            }
             ::ir_rust_proto::bridge_type::VariantOneof::ProtoMessageBridge(pmb) => {
                 Ok(BridgeType::ProtoMessageBridge { rust_name: pmb.rust_name().to_str()? })
             }
@@ -350,6 +356,17 @@ impl TryFrom<::ir_rust_proto::TemplateSpecializationView<'_>> for TemplateSpecia
                     raw_element_type: CcType::try_from(up.element_type())?,
                 }
             }
+            ::ir_rust_proto::template_specialization::KindOneof::StdSpan(ss) => {
+                TemplateSpecializationKind::StdSpan {
+                    raw_element_type: CcType::try_from(ss.element_type())?,
+                }
+            }
+            ::ir_rust_proto::template_specialization::KindOneof::StdArray(sa) => {
+                TemplateSpecializationKind::StdArray {
+                    raw_element_type: CcType::try_from(sa.element_type())?,
+                    size: sa.size(),
+                }
+            }
             ::ir_rust_proto::template_specialization::KindOneof::AbslSpan(as_) => {
                 TemplateSpecializationKind::AbslSpan {
                     raw_element_type: CcType::try_from(as_.element_type())?,
@@ -554,20 +571,21 @@ pub fn proto_to_ir<'pb>(proto: ::ir_rust_proto::IRProtoView<'pb>) -> Result<IR<'
         .crubit_features()
         .iter()
         .map(|(target, feature_set)| {
-            let mut features = flagset::FlagSet::<CrubitFeature>::default();
-            for feature_str in feature_set.features().iter() {
-                let feature_name = feature_str.to_str()?;
-                let feature_flags = crubit_feature::named_features(feature_name.as_bytes())
-                    .with_context(|| format!("Invalid Crubit feature name: {:?}", feature_name))?;
-                features |= feature_flags;
-            }
             Ok::<_, Error>((
                 BazelLabel::from(target.to_str()?),
-                crubit_feature::SerializedCrubitFeatures::resolved(features),
+                features_from_names(feature_set.features())?,
             ))
         })
         .try_collect()?;
 
+    let item_crubit_features = proto
+        .item_crubit_features()
+        .iter()
+        .map(|(item_id, feature_set)| {
+            Ok::<_, Error>((ItemId(item_id as usize), features_from_names(feature_set.features())?))
+        })
+        .try_collect()?;
+
     let unstable_rust_features = proto
         .unstable_rust_features()
         .iter()
@@ -606,9 +624,23 @@ pub fn proto_to_ir<'pb>(proto: ::ir_rust_proto::IRProtoView<'pb>) -> Result<IR<'
         crate_root_path,
         crubit_features,
         crate_names,
+        item_crubit_features,
         unstable_rust_features,
         reexported_namespaces,
         top_level_items,
     };
     Ok(super::make_ir(tree_ir))
 }
+
+fn features_from_names<'a>(
+    names: impl IntoIterator<Item = &'a ::protobuf::ProtoStr>,
+) -> Result<crubit_feature::SerializedCrubitFeatures> {
+    let mut features = flagset::FlagSet::<CrubitFeature>::default();
+    for feature_str in names {
+        let feature_name = feature_str.to_str()?;
+        let feature_flags = crubit_feature::named_features(feature_name.as_bytes())
+            .with_context(|| format!("Invalid Crubit feature name: {:?}", feature_name))?;
+        features |= feature_flags;
+    }
+    Ok(crubit_feature::SerializedCrubitFeatures::resolved(features))
+}
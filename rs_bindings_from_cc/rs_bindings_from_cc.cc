@@ -107,6 +107,11 @@ absl::Status Main(absl::Span<char* const> positional_args) {
                                            bindings_and_metadata.error_report));
   }
 
+  if (!args.lsif_out.empty()) {
+    CRUBIT_RETURN_IF_ERROR(
+        SetFileContents(args.lsif_out, bindings_and_metadata.lsif_index));
+  }
+
   return absl::OkStatus();
 }
 
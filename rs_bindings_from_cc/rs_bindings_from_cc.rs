@@ -2,7 +2,9 @@
 // Exceptions. See /LICENSE for license information.
 // SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
 
+use output_cache::{parse_depfile, CacheKeyBuilder, OutputCache};
 use std::ffi::{c_char, c_int, CString};
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 unsafe extern "C" {
@@ -10,15 +12,199 @@ unsafe extern "C" {
     fn crubit_rs_bindings_from_cc_main(argc: c_int, argv: *mut *mut c_char) -> std::ffi::c_int;
 }
 
-pub fn main() -> ExitCode {
-    let mut args: Vec<Vec<u8>> = std::env::args_os()
-        .map(|s| CString::new(s.into_encoded_bytes()).unwrap().into_bytes_with_nul())
-        .collect();
+fn run(args: impl IntoIterator<Item = Vec<u8>>) -> c_int {
+    let mut args: Vec<Vec<u8>> =
+        args.into_iter().map(|s| CString::new(s).unwrap().into_bytes_with_nul()).collect();
     // Pointers to each Vec storage in `args`, which must outlive this.
     let mut ptrs: Vec<*mut c_char> =
         args.iter_mut().map(|s| s.as_mut_ptr() as *mut c_char).collect();
     let argc: i32 = ptrs.len().try_into().unwrap();
     let argv: *mut *mut c_char = ptrs.as_mut_ptr();
-    let r = unsafe { crubit_rs_bindings_from_cc_main(argc, argv) };
+    unsafe { crubit_rs_bindings_from_cc_main(argc, argv) }
+}
+
+/// Expands `@paramfile` arguments, like `ExpandParamfiles` in `cmdline.cc`: each line of the file
+/// is an argument, and a backslash escapes the next character.
+fn expand_paramfiles(args: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+    let mut expanded = vec![];
+    for arg in args {
+        let Some(paramfile) = arg.strip_prefix(b"@") else {
+            expanded.push(arg);
+            continue;
+        };
+        let contents = std::str::from_utf8(paramfile)
+            .ok()
+            .and_then(|paramfile| std::fs::read(paramfile).ok())
+            .unwrap_or_default();
+        let mut next_arg = vec![];
+        let mut bytes = contents.into_iter();
+        while let Some(byte) = bytes.next() {
+            match byte {
+                b'\\' => next_arg.push(bytes.next().unwrap_or(b'\\')),
+                b'\n' => expanded.push(std::mem::take(&mut next_arg)),
+                _ => next_arg.push(byte),
+            }
+        }
+        if !next_arg.is_empty() {
+            expanded.push(next_arg);
+        }
+    }
+    expanded
+}
+
+/// The command line of an invocation with `--cache_dir`. The cache flags are handled here, rather
+/// than by the C++ tool, and are removed from `args`.
+struct CachedInvocation {
+    args: Vec<String>,
+    cache_dir: PathBuf,
+    cache_stats: bool,
+}
+
+impl CachedInvocation {
+    fn parse(args: Vec<Vec<u8>>) -> Option<Self> {
+        let args = expand_paramfiles(args)
+            .into_iter()
+            .map(String::from_utf8)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        let mut cache_dir = None;
+        let mut cache_stats = false;
+        let mut remaining_args = vec![];
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                remaining_args.push(arg);
+                remaining_args.extend(args.by_ref());
+                break;
+            }
+            match arg.split_once('=') {
+                Some(("--cache_dir", value)) => cache_dir = Some(PathBuf::from(value)),
+                None if arg == "--cache_dir" => cache_dir = args.next().map(PathBuf::from),
+                Some(("--cache_stats", value)) => cache_stats = value == "true",
+                None if arg == "--cache_stats" => cache_stats = true,
+                _ => remaining_args.push(arg),
+            }
+        }
+        Some(CachedInvocation { args: remaining_args, cache_dir: cache_dir?, cache_stats })
+    }
+
+    /// Returns the values of the last `--name` flag, which are separated by commas.
+    ///
+    /// Like Abseil, this accepts both `--name=value` and `--name value`, with one or two dashes.
+    fn flag_values(&self, name: &str) -> Vec<&str> {
+        let mut last_value = None;
+        let mut args = self.args.iter().take_while(|arg| *arg != "--");
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix("--").or_else(|| arg.strip_prefix('-')) else {
+                continue;
+            };
+            match flag.split_once('=') {
+                Some((flag_name, value)) if flag_name == name => last_value = Some(value),
+                None if flag == name => last_value = args.next().map(String::as_str),
+                _ => {}
+            }
+        }
+        last_value
+            .map_or(vec![], |values| values.split(',').filter(|value| !value.is_empty()).collect())
+    }
+
+    fn run(self) -> c_int {
+        let cache = OutputCache::new(&self.cache_dir);
+        let mut key = CacheKeyBuilder::new("rs_bindings_from_cc");
+        for arg in &self.args {
+            key.add_str(arg);
+        }
+        // The formatters are run on the generated bindings, so the output depends on their
+        // versions.
+        for flag in ["rustfmt_exe_path", "clang_format_exe_path"] {
+            for path in self.flag_values(flag) {
+                key.add_file(Path::new(path));
+            }
+        }
+        // These filter what is bound, however they are spelled on the command line.
+        for flag in ["do_not_bind_allowlist", "template_blocklist_path_regex"] {
+            key.add_str(flag);
+            for value in self.flag_values(flag) {
+                key.add_str(value);
+            }
+        }
+        let key = key.build();
+        let outputs: Vec<PathBuf> = [
+            "rs_out",
+            "cc_out",
+            "ir_out",
+            "namespaces_out",
+            "instantiations_out",
+            "error_report_out",
+            "lsif_out",
+        ]
+        .into_iter()
+        .flat_map(|flag| self.flag_values(flag))
+        .map(PathBuf::from)
+        .collect();
+        let outputs: Vec<&Path> = outputs.iter().map(PathBuf::as_path).collect();
+
+        if !cache.restore(&key, &outputs) {
+            // Clang lists the headers it reads in a dependency file. The other inputs are named by
+            // flags.
+            let mut input_files: Vec<PathBuf> = [
+                "extra_rs_srcs",
+                "extra_cpp_srcs",
+                "srcs_to_scan_for_instantiations",
+                "rustfmt_config_path",
+            ]
+            .into_iter()
+            .flat_map(|flag| self.flag_values(flag))
+            .map(PathBuf::from)
+            .collect();
+            let depfile = std::env::temp_dir().join(format!("crubit_{}.d", std::process::id()));
+            let mut args = self.args.clone();
+            if !args.iter().any(|arg| arg == "--") {
+                args.push("--".to_string());
+            }
+            args.extend(["-MD".to_string(), "-MF".to_string(), depfile.display().to_string()]);
+            let result = run(args.into_iter().map(String::into_bytes));
+            if result != 0 {
+                let _ = std::fs::remove_file(&depfile);
+                return result;
+            }
+            match std::fs::read_to_string(&depfile) {
+                Ok(contents) => {
+                    input_files.extend(parse_depfile(&contents));
+                    input_files.sort();
+                    input_files.dedup();
+                    if let Err(e) = cache.store(&key, &input_files, &outputs) {
+                        eprintln!(
+                            "warning: failed to store the bindings in {}: {e}",
+                            self.cache_dir.display()
+                        );
+                    }
+                }
+                Err(e) => eprintln!(
+                    "warning: not caching the bindings, failed to read {}: {e}",
+                    depfile.display()
+                ),
+            }
+            let _ = std::fs::remove_file(&depfile);
+        }
+        if self.cache_stats {
+            match cache.stats() {
+                Ok(stats) => eprintln!("{stats}"),
+                Err(e) => eprintln!(
+                    "warning: failed to read the statistics of {}: {e}",
+                    self.cache_dir.display()
+                ),
+            }
+        }
+        0
+    }
+}
+
+pub fn main() -> ExitCode {
+    let args: Vec<Vec<u8>> = std::env::args_os().map(|s| s.into_encoded_bytes()).collect();
+    let r = match CachedInvocation::parse(args.clone()) {
+        Some(invocation) => invocation.run(),
+        None => run(args),
+    };
     ExitCode::from(u8::try_from(r).unwrap_or(u8::MAX))
 }
@@ -26,7 +26,8 @@ absl::StatusOr<Bindings> GenerateBindings(
     absl::string_view clang_format_exe_path, absl::string_view rustfmt_exe_path,
     absl::string_view rustfmt_config_path, bool generate_error_report,
     bool is_golden_test, bool kythe_annotations,
-    absl::string_view kythe_default_corpus) {
+    absl::string_view kythe_default_corpus,
+    absl::string_view lsif_rs_api_path) {
   GenerateBindingsRequest request;
 
   *request.mutable_ir_proto() = std::move(ir);
@@ -40,6 +41,7 @@ absl::StatusOr<Bindings> GenerateBindings(
   request.set_is_golden_test(is_golden_test);
   request.set_kythe_annotations(kythe_annotations);
   request.set_kythe_default_corpus(kythe_default_corpus);
+  request.set_lsif_rs_api_path(lsif_rs_api_path);
 
   GenerateBindingsResponse response =
       GenerateBindingsProtoCall(std::move(request));
@@ -52,6 +54,7 @@ absl::StatusOr<Bindings> GenerateBindings(
   bindings.rs_api = response.rs_api();
   bindings.rs_api_impl = response.rs_api_impl();
   bindings.error_report = response.error_report();
+  bindings.lsif_index = response.lsif_index();
   return bindings;
 }
 
@@ -22,6 +22,8 @@ struct Bindings {
   std::string rs_api_impl;
   // Optional JSON error report.
   std::string error_report;
+  // Optional LSIF index.
+  std::string lsif_index;
 };
 
 // Generates bindings from the given `IR`.
@@ -31,7 +33,8 @@ CRUBIT_PROFILE_NOINLINE absl::StatusOr<Bindings> GenerateBindings(
     absl::string_view clang_format_exe_path, absl::string_view rustfmt_exe_path,
     absl::string_view rustfmt_config_path, bool generate_error_report,
     bool is_golden_test, bool kythe_annotations,
-    absl::string_view kythe_default_corpus);
+    absl::string_view kythe_default_corpus,
+    absl::string_view lsif_rs_api_path);
 
 }  // namespace crubit
 
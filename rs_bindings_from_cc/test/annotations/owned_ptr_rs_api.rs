@@ -61,6 +61,19 @@ impl Drop for OwnedThing {
         self.DropImpl();
     }
 }
+impl ::core::ops::Deref for OwnedThing {
+    type Target = RawThing;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.0.as_ref() }
+    }
+}
+impl ::core::ops::DerefMut for OwnedThing {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.0.as_mut() }
+    }
+}
 
 impl From<i32> for RawThing {
     #[inline(always)]
@@ -136,6 +149,19 @@ impl Drop for CustomOwnedThing {
         self.CustomDropImpl();
     }
 }
+impl ::core::ops::Deref for CustomOwnedThing {
+    type Target = CustomRawThing;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.0.as_ref() }
+    }
+}
+impl ::core::ops::DerefMut for CustomOwnedThing {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.0.as_mut() }
+    }
+}
 
 impl From<i32> for CustomRawThing {
     #[inline(always)]
@@ -34,3 +34,12 @@ fn make_and_use_thing_with_raw_ptr() {
 fn make_and_drop_custom_thing_with_owned_ptr() {
     let _thing: owned_ptr::CustomOwnedThing = owned_ptr_user::MakeCustomOwnedThing(42);
 }
+
+#[gtest]
+fn deref_owned_thing() {
+    let mut thing: owned_ptr::OwnedThing = owned_ptr_user::MakeOwnedThing(42);
+    assert_eq!(thing.value, 42);
+
+    thing.value = 7;
+    assert_eq!(thing.value, 7);
+}
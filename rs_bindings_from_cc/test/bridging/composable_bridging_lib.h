@@ -130,4 +130,11 @@ CRUBIT_MUST_BIND
 std::optional<std::unique_ptr<StructWithVirtualDestructor>>
 MakeOptionalWithVirtualDestructor();
 
+// Bridge-typed fields are stored as opaque bytes, and accessed through
+// generated getters and setters.
+struct ConfigWithOptionalFields {
+  std::optional<int> foo;
+  std::optional<int> bar = 2;
+};
+
 #endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_COMPOSABLE_BRIDGING_LIB_H_
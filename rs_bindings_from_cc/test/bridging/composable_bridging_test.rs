@@ -146,3 +146,15 @@ fn test_composable_bridge_with_virtual_destructor() {
     let optional = composable_bridging_lib::MakeOptionalWithVirtualDestructor();
     assert!(optional.is_some());
 }
+
+#[gtest]
+fn test_bridge_field_accessors() {
+    let mut config = ConfigWithOptionalFields::default();
+    expect_eq!(config.foo(), None);
+    expect_eq!(config.bar(), Some(2));
+
+    config.set_foo(Some(42));
+    config.set_bar(None);
+    expect_eq!(config.foo(), Some(42));
+    expect_eq!(config.bar(), None);
+}
@@ -0,0 +1,30 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use googletest::prelude::*;
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+#[gtest]
+fn test_path_buf_by_value() {
+    expect_eq!(
+        path_test_lib::AppendFileName(PathBuf::from("/tmp/dir")),
+        PathBuf::from("/tmp/dir/file.txt")
+    );
+}
+
+#[gtest]
+fn test_path_by_const_ref() {
+    expect_eq!(path_test_lib::NativeLength(Path::new("")), 0);
+    expect_eq!(path_test_lib::NativeLength(Path::new("/tmp")), 4);
+    expect_eq!(path_test_lib::GetParentPath(Path::new("/tmp/dir/")), Path::new("/tmp/dir"));
+}
+
+#[gtest]
+fn test_non_utf8_path() {
+    let dir = Path::new(OsStr::from_bytes(b"/tmp/\xff\xfe"));
+    expect_eq!(path_test_lib::NativeLength(dir), 7);
+    expect_eq!(path_test_lib::AppendFileName(dir.to_path_buf()), dir.join("file.txt"));
+}
@@ -0,0 +1,23 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_BRIDGING_PATH_TEST_LIB_H_
+#define THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_BRIDGING_PATH_TEST_LIB_H_
+
+#include <cstddef>
+#include <filesystem>  // NOLINT(build/c++17)
+
+inline std::filesystem::path AppendFileName(std::filesystem::path dir) {
+  return dir / "file.txt";
+}
+
+inline std::size_t NativeLength(const std::filesystem::path& p) {
+  return p.native().size();
+}
+
+inline std::filesystem::path GetParentPath(const std::filesystem::path& p) {
+  return p.parent_path();
+}
+
+#endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_BRIDGING_PATH_TEST_LIB_H_
@@ -0,0 +1,41 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use bridge_rust::SignedDuration;
+use core::time::Duration;
+use googletest::prelude::*;
+use std::time::UNIX_EPOCH;
+
+#[gtest]
+fn test_absl_duration() {
+    expect_eq!(
+        time_test_lib::DoubleDuration(SignedDuration::new(-2, 500_000_000)),
+        SignedDuration::new(-3, 0)
+    );
+}
+
+#[gtest]
+fn test_absl_infinite_duration() {
+    expect_eq!(time_test_lib::GetInfiniteDuration(), SignedDuration::MAX);
+}
+
+#[gtest]
+fn test_chrono_duration() {
+    let duration = SignedDuration::try_from(Duration::from_millis(750)).unwrap();
+    expect_eq!(
+        Duration::try_from(time_test_lib::DoubleMilliseconds(duration)),
+        Ok(Duration::from_millis(1500))
+    );
+}
+
+#[gtest]
+fn test_absl_time() {
+    expect_eq!(time_test_lib::AddSecond(UNIX_EPOCH), UNIX_EPOCH + Duration::from_secs(1));
+}
+
+#[gtest]
+fn test_system_clock_time_point() {
+    let time = UNIX_EPOCH - Duration::from_secs(10);
+    expect_eq!(time_test_lib::AddSecondToTimePoint(time), time + Duration::from_secs(1));
+}
@@ -0,0 +1,28 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_BRIDGING_TIME_TEST_LIB_H_
+#define THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_BRIDGING_TIME_TEST_LIB_H_
+
+#include <chrono>  // NOLINT(build/c++11)
+
+#include "absl/time/time.h"
+
+inline absl::Duration DoubleDuration(absl::Duration d) { return d * 2; }
+
+inline absl::Duration GetInfiniteDuration() { return absl::InfiniteDuration(); }
+
+inline std::chrono::milliseconds DoubleMilliseconds(
+    std::chrono::milliseconds d) {
+  return d * 2;
+}
+
+inline absl::Time AddSecond(absl::Time t) { return t + absl::Seconds(1); }
+
+inline std::chrono::system_clock::time_point AddSecondToTimePoint(
+    std::chrono::system_clock::time_point t) {
+  return t + std::chrono::seconds(1);
+}
+
+#endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_BRIDGING_TIME_TEST_LIB_H_
@@ -8,6 +8,7 @@
 #include <utility>
 
 #include "absl/functional/any_invocable.h"
+#include "absl/functional/function_ref.h"
 #include "support/rs_std/dyn_callable.h"
 
 void invoke_once(rs_std::DynCallable<void() &&> f) { std::move(f)(); }
@@ -45,3 +46,9 @@ void invoke_as_absl_anyinvocable(rs_std::DynCallable<void()> f) {
   absl::AnyInvocable<void()> any_invocable = std::move(f);
   any_invocable();
 }
+
+int call_function_ref_twice(absl::FunctionRef<int(int)> f, int arg) {
+  return f(f(arg));
+}
+
+void call_function_ref_once(absl::FunctionRef<void()> f) { f(); }
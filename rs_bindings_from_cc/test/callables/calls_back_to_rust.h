@@ -7,6 +7,7 @@
 
 #include <optional>
 
+#include "absl/functional/function_ref.h"
 #include "support/annotations.h"
 #include "support/rs_std/dyn_callable.h"
 
@@ -53,4 +54,10 @@ rs_std::DynCallable<void() &&> bridge_to_and_from_cpp(
 
 void invoke_as_absl_anyinvocable(rs_std::DynCallable<void()> f);
 
+int call_function_ref_twice(absl::FunctionRef<int(int)> f, int arg);
+
+// Only calls `f` on the calling thread, before returning, so it is safe.
+CRUBIT_UNSAFE_MARK_SAFE
+void call_function_ref_once(absl::FunctionRef<void()> f);
+
 #endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_CALLABLES_CALLS_BACK_TO_RUST_H_
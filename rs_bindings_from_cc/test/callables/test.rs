@@ -3,11 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
 
 use calls_back_to_rust::{
-    bridge_to_and_from_cpp, invoke, invoke_as_absl_anyinvocable, invoke_const, invoke_once,
-    map_abi_compatible, map_int, map_layout_compatible, map_optional_int, ABICompatible,
-    LayoutCompatible,
+    bridge_to_and_from_cpp, call_function_ref_once, call_function_ref_twice, invoke,
+    invoke_as_absl_anyinvocable, invoke_const, invoke_once, map_abi_compatible, map_int,
+    map_layout_compatible, map_optional_int, ABICompatible, LayoutCompatible,
 };
 use googletest::{expect_eq, gtest};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
 #[gtest]
@@ -120,3 +122,23 @@ fn test_invoke_as_absl_anyinvocable() {
         "invoke_as_absl_anyinvocable should have dropped the cloned state"
     );
 }
+
+#[gtest]
+fn test_call_function_ref_twice() {
+    // `absl::FunctionRef` only borrows the closure, so it need not be `Send` or `'static`.
+    let calls = Rc::new(Cell::new(0));
+    let mut add_calls = |x: i32| {
+        calls.set(calls.get() + 1);
+        x + calls.get()
+    };
+    // SAFETY: `call_function_ref_twice` calls `f` from this thread, and only before returning.
+    expect_eq!(unsafe { call_function_ref_twice(&mut add_calls, 10) }, 13);
+    expect_eq!(calls.get(), 2);
+}
+
+#[gtest]
+fn test_call_function_ref_marked_safe() {
+    let calls = RefCell::new(Vec::new());
+    call_function_ref_once(&mut || calls.borrow_mut().push("called"));
+    expect_eq!(calls.into_inner(), vec!["called"]);
+}
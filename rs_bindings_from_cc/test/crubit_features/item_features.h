@@ -0,0 +1,19 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+#ifndef CRUBIT_RS_BINDINGS_FROM_CC_TEST_CRUBIT_FEATURES_ITEM_FEATURES_H_
+#define CRUBIT_RS_BINDINGS_FROM_CC_TEST_CRUBIT_FEATURES_ITEM_FEATURES_H_
+
+#include "support/annotations.h"
+
+// `[[noreturn]]` functions require the `experimental` feature, which this
+// target doesn't enable.
+[[noreturn]] void NoReturnWithoutFeature();
+
+[[noreturn]] CRUBIT_ENABLE_FEATURE("experimental") void NoReturnWithFeature();
+
+namespace CRUBIT_ENABLE_FEATURE("experimental") namespace_with_feature {
+[[noreturn]] void NoReturnInNamespace();
+}  // namespace namespace_with_feature
+
+#endif  // CRUBIT_RS_BINDINGS_FROM_CC_TEST_CRUBIT_FEATURES_ITEM_FEATURES_H_
@@ -108,3 +108,23 @@ mod structs {
         assert!(type_exists!(wrapper_struct_enabled::EnabledStructWithDisabledField));
     }
 }
+
+mod per_item_features {
+    use super::*;
+    use googletest::gtest;
+
+    #[gtest]
+    fn test_feature_not_enabled() {
+        assert!(!value_exists!(item_features::NoReturnWithoutFeature));
+    }
+
+    #[gtest]
+    fn test_feature_enabled_for_item() {
+        assert!(value_exists!(item_features::NoReturnWithFeature));
+    }
+
+    #[gtest]
+    fn test_feature_enabled_for_enclosing_item() {
+        assert!(value_exists!(item_features::namespace_with_feature::NoReturnInNamespace));
+    }
+}
@@ -0,0 +1,16 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "rs_bindings_from_cc/test/global/nontrivial_global.h"
+
+Counter g_counter{1};
+const Counter kConstCounter{2};
+
+namespace foo {
+Counter g_counter_namespaced{3};
+}  // namespace foo
+
+int GetCounterValue() { return g_counter.value(); }
+
+int GetNamespacedCounterValue() { return foo::g_counter_namespaced.value(); }
@@ -0,0 +1,31 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_GLOBAL_NONTRIVIAL_GLOBAL_H_
+#define THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_GLOBAL_NONTRIVIAL_GLOBAL_H_
+
+// Not trivially relocatable, so it can't be bound as a Rust `static`.
+struct Counter {
+  explicit Counter(int value) : value_(value) {}
+  Counter(const Counter& other) : value_(other.value_) {}
+  ~Counter() {}
+
+  int value() const { return value_; }
+  void Increment() { ++value_; }
+
+ private:
+  int value_;
+};
+
+extern Counter g_counter;
+extern const Counter kConstCounter;
+
+namespace foo {
+extern Counter g_counter_namespaced;
+}  // namespace foo
+
+int GetCounterValue();
+int GetNamespacedCounterValue();
+
+#endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_GLOBAL_NONTRIVIAL_GLOBAL_H_
@@ -0,0 +1,26 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use googletest::gtest;
+
+#[gtest]
+fn test_mut_global() {
+    assert_eq!(nontrivial_global::GetCounterValue(), 1);
+    unsafe { nontrivial_global::g_counter() }.Increment();
+    assert_eq!(nontrivial_global::GetCounterValue(), 2);
+    assert_eq!(unsafe { nontrivial_global::g_counter() }.value(), 2);
+}
+
+#[gtest]
+fn test_const_global() {
+    let counter: &'static nontrivial_global::Counter =
+        unsafe { nontrivial_global::kConstCounter() };
+    assert_eq!(counter.value(), 2);
+}
+
+#[gtest]
+fn test_namespaced_global() {
+    unsafe { nontrivial_global::foo::g_counter_namespaced() }.Increment();
+    assert_eq!(nontrivial_global::GetNamespacedCounterValue(), 4);
+}
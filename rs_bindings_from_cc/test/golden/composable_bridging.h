@@ -27,6 +27,8 @@ CppStruct ReturnCppStruct();
 
 void TakeCppStruct(CppStruct);
 
+extern CppStruct bridged_global;
+
 template <typename T>
 // clang-format off
 struct
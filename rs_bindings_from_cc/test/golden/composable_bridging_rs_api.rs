@@ -34,6 +34,25 @@ unsafe impl ::cxx::ExternType for StructWithBridgeField {
     type Kind = ::cxx::kind::Trivial;
 }
 
+impl StructWithBridgeField {
+    #[inline(always)]
+    pub fn bridge_field(&self) -> crate::RustStruct {
+        unsafe {
+            ::bridge_rust::unstable_return!(@crate::RustStructAbi,crate::RustStructAbi,|__crubit_return_abi_buffer|{ crate::detail::__crubit_field_getter__21StructWithBridgeField_bridge_field___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3acomposable_5fbridging_5fcc(__crubit_return_abi_buffer,self as*const Self as*const::core::ffi::c_void,); })
+        }
+    }
+    #[inline(always)]
+    pub fn set_bridge_field(&mut self, value: crate::RustStruct) {
+        unsafe {
+            crate::detail::__crubit_field_setter__21StructWithBridgeField_bridge_field___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3acomposable_5fbridging_5fcc(
+                self as *mut Self as *mut ::core::ffi::c_void,
+                ::bridge_rust::unstable_encode!(@crate::RustStructAbi,crate::RustStructAbi,value)
+                    .as_ptr() as *const u8,
+            )
+        }
+    }
+}
+
 impl Default for StructWithBridgeField {
     #[inline(always)]
     fn default() -> Self {
@@ -62,6 +81,9 @@ pub fn TakeCppStruct(__param_0: crate::RustStruct) {
     }
 }
 
+// error: global variable `bridged_global` could not be bound
+//   global variables of bridged type `crate::RustStruct` are not supported
+
 // error: class `MyOption` could not be bound
 //   Class templates are not yet supported
 
@@ -313,6 +335,14 @@ mod detail {
         pub(crate) unsafe fn __rust_thunk___ZN21StructWithBridgeFieldC1Ev(
             __this: *mut ::core::ffi::c_void,
         );
+        pub(crate) unsafe fn __crubit_field_getter__21StructWithBridgeField_bridge_field___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3acomposable_5fbridging_5fcc(
+            __return_abi_buffer: *mut ::core::ffi::c_uchar,
+            __this: *const ::core::ffi::c_void,
+        );
+        pub(crate) unsafe fn __crubit_field_setter__21StructWithBridgeField_bridge_field___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3acomposable_5fbridging_5fcc(
+            __this: *mut ::core::ffi::c_void,
+            value: *const ::core::ffi::c_uchar,
+        );
         pub(crate) unsafe fn __rust_thunk___Z15ReturnCppStructv(
             __return_abi_buffer: *mut ::core::ffi::c_uchar,
         );
@@ -30,6 +30,22 @@ extern "C" void __rust_thunk___ZN21StructWithBridgeFieldC1Ev(
   crubit::construct_at(__this);
 }
 
+extern "C" void
+__crubit_field_getter__21StructWithBridgeField_bridge_field___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3acomposable_5fbridging_5fcc(
+    unsigned char* __return_abi_buffer,
+    const struct StructWithBridgeField* __this) {
+  ::crubit::Encoder __return_encoder(::crubit::CppStructAbi::kSize,
+                                     __return_abi_buffer);
+  ::crubit::CppStructAbi().Encode(__this->bridge_field, __return_encoder);
+}
+
+extern "C" void
+__crubit_field_setter__21StructWithBridgeField_bridge_field___2f_2fthird_5fparty_2fcrubit_2frs_5fbindings_5ffrom_5fcc_2ftest_2fgolden_3acomposable_5fbridging_5fcc(
+    struct StructWithBridgeField* __this, const unsigned char* value) {
+  ::crubit::Decoder __value_decoder(::crubit::CppStructAbi::kSize, value);
+  __this->bridge_field = ::crubit::CppStructAbi().Decode(__value_decoder);
+}
+
 extern "C" void __rust_thunk___Z15ReturnCppStructv(
     unsigned char* __return_abi_buffer) {
   ::crubit::Encoder __return_encoder(::crubit::CppStructAbi::kSize,
@@ -0,0 +1,30 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// Automatically @generated Rust bindings for the following C++ target:
+// //rs_bindings_from_cc/test/golden:item_features_cc
+
+#![rustfmt::skip]
+#![feature(custom_inner_attributes)]
+#![allow(stable_features)]
+#![allow(improper_ctypes)]
+#![allow(nonstandard_style)]
+#![allow(unused)]
+#![allow(deprecated)]
+#![allow(unknown_lints, suspicious_runtime_symbol_definitions)]
+#![deny(warnings)]
+#[inline(always)]
+pub fn Consume(mut v: ::cc_std::std::vector<::ffi_11::c_int>) {
+    unsafe { crate::detail::__rust_thunk___Z7ConsumeNSt3__u6vectorIiNS_9allocatorIiEEEE(&mut v) }
+}
+
+mod detail {
+    #[allow(unused_imports)]
+    use super::*;
+    unsafe extern "C" {
+        pub(crate) unsafe fn __rust_thunk___Z7ConsumeNSt3__u6vectorIiNS_9allocatorIiEEEE(
+            v: &mut ::cc_std::std::vector<::ffi_11::c_int>,
+        );
+    }
+}
@@ -0,0 +1,26 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// Automatically @generated Rust bindings for the following C++ target:
+// //rs_bindings_from_cc/test/golden:item_features_cc
+
+#include "support/internal/cxx20_backports.h"
+#include "support/internal/offsetof.h"
+
+#include <memory>
+
+// Public headers of the C++ library being wrapped.
+#include "rs_bindings_from_cc/test/golden/item_features.h"
+
+#pragma clang diagnostic push
+#pragma clang diagnostic ignored "-Wthread-safety-analysis"
+
+extern "C" void __rust_thunk___Z7ConsumeNSt3__u6vectorIiNS_9allocatorIiEEEE(
+    class std::__u::vector<int>* v) {
+  Consume(std::move(*v));
+}
+
+static_assert((void (*)(class std::__u::vector<int>)) & ::Consume);
+
+#pragma clang diagnostic pop
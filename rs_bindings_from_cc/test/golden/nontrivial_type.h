@@ -131,4 +131,7 @@ struct Nonmovable final {
 void TakesNonmovableByValue(Nonmovable nonmovable);
 Nonmovable ReturnsNonmovableByValue();
 
+extern Nontrivial nontrivial_global;
+extern const Nontrivial const_nontrivial_global;
+
 #endif  // CRUBIT_RS_BINDINGS_FROM_CC_TEST_GOLDEN_NONTRIVIAL_TYPE_H_
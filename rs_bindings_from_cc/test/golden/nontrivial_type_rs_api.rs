@@ -1024,6 +1024,24 @@ pub fn ReturnsNonmovableByValue() -> ::ctor::Ctor![crate::Nonmovable] {
     }
 }
 
+/// # Safety
+///
+/// The variable must be initialized, and the returned reference must not alias any other reference to it, including one returned by another call to this function.
+#[inline(always)]
+pub unsafe fn nontrivial_global() -> ::core::pin::Pin<&'static mut crate::Nontrivial> {
+    unsafe {
+        ::core::pin::Pin::new_unchecked(&mut *crate::detail::__rust_thunk__nontrivial_global())
+    }
+}
+
+/// # Safety
+///
+/// The variable must be initialized, and must not be mutated while the returned reference is alive.
+#[inline(always)]
+pub unsafe fn const_nontrivial_global() -> &'static crate::Nontrivial {
+    unsafe { &*crate::detail::__rust_thunk__const_nontrivial_global() }
+}
+
 mod detail {
     #[allow(unused_imports)]
     use super::*;
@@ -1293,6 +1311,8 @@ mod detail {
         pub(crate) unsafe fn __rust_thunk___Z24ReturnsNonmovableByValuev(
             __return: *mut ::core::ffi::c_void,
         );
+        pub(crate) unsafe fn __rust_thunk__nontrivial_global() -> *mut crate::Nontrivial;
+        pub(crate) unsafe fn __rust_thunk__const_nontrivial_global() -> *const crate::Nontrivial;
     }
 }
 
@@ -220,4 +220,12 @@ extern "C" void __rust_thunk___Z24ReturnsNonmovableByValuev(
 
 static_assert((struct Nonmovable (*)()) & ::ReturnsNonmovableByValue);
 
+extern "C" struct Nontrivial* __rust_thunk__nontrivial_global() {
+  return &nontrivial_global;
+}
+
+extern "C" struct Nontrivial const* __rust_thunk__const_nontrivial_global() {
+  return &const_nontrivial_global;
+}
+
 #pragma clang diagnostic pop
@@ -0,0 +1,40 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_MANUAL_BRIDGE_VOCABULARY_TYPES_STD_ARRAY_LIB_H_
+#define THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_MANUAL_BRIDGE_VOCABULARY_TYPES_STD_ARRAY_LIB_H_
+
+#include <array>
+#include <utility>
+
+#include "support/annotations.h"
+
+struct CRUBIT_MUST_BIND Point {
+  int x;
+  int y;
+};
+
+struct CRUBIT_MUST_BIND Triangle {
+  std::array<Point, 3> vertices;
+};
+
+CRUBIT_MUST_BIND
+inline std::array<int, 3> Iota3(int start) {
+  return {start, start + 1, start + 2};
+}
+
+CRUBIT_MUST_BIND
+inline int SumArray(std::array<int, 3> array) {
+  return array[0] + array[1] + array[2];
+}
+
+CRUBIT_MUST_BIND
+inline void Reverse(std::array<int, 3>& array) {
+  std::swap(array[0], array[2]);
+}
+
+CRUBIT_MUST_BIND
+inline Triangle MakeTriangle() { return {{{{0, 0}, {1, 0}, {0, 1}}}}; }
+
+#endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_MANUAL_BRIDGE_VOCABULARY_TYPES_STD_ARRAY_LIB_H_
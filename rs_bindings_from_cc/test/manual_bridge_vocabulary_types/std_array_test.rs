@@ -0,0 +1,31 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use googletest::{expect_eq, gtest};
+use std_array_lib::{Iota3, MakeTriangle, Point, Reverse, SumArray};
+
+#[gtest]
+fn test_array_return() {
+    let array: [i32; 3] = Iota3(5);
+    expect_eq!(array, [5, 6, 7]);
+}
+
+#[gtest]
+fn test_array_param() {
+    expect_eq!(SumArray([1, 2, 3]), 6);
+}
+
+#[gtest]
+fn test_array_reference_param() {
+    let mut array = [1, 2, 3];
+    Reverse(&mut array);
+    expect_eq!(array, [3, 2, 1]);
+}
+
+#[gtest]
+fn test_array_field() {
+    let vertices: [Point; 3] = MakeTriangle().vertices;
+    expect_eq!(vertices[1].x, 1);
+    expect_eq!(vertices[2].y, 1);
+}
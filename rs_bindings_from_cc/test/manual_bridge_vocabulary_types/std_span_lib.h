@@ -0,0 +1,42 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_MANUAL_BRIDGE_VOCABULARY_TYPES_STD_SPAN_LIB_H_
+#define THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_MANUAL_BRIDGE_VOCABULARY_TYPES_STD_SPAN_LIB_H_
+
+#include <cstddef>
+#include <span>  // NOLINT(build/c++20)
+
+#include "support/annotations.h"
+
+CRUBIT_MUST_BIND
+inline int Sum(std::span<const int> span) {
+  int sum = 0;
+  for (int i : span) {
+    sum += i;
+  }
+  return sum;
+}
+
+CRUBIT_MUST_BIND
+inline void Double(std::span<int> span) {
+  for (int& i : span) {
+    i *= 2;
+  }
+}
+
+CRUBIT_MUST_BIND
+inline std::span<const int> Truncate(std::span<const int> span, size_t len) {
+  return span.first(len);
+}
+
+CRUBIT_MUST_BIND
+inline std::span<int> TruncateMut(std::span<int> span, size_t len) {
+  return span.first(len);
+}
+
+// An empty span whose data pointer is null.
+CRUBIT_MUST_BIND inline std::span<const int> EmptySpan() { return {}; }
+
+#endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_MANUAL_BRIDGE_VOCABULARY_TYPES_STD_SPAN_LIB_H_
@@ -0,0 +1,61 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use googletest::{expect_eq, expect_true, gtest};
+use std_span_lib::{Double, EmptySpan, Sum, Truncate, TruncateMut};
+
+#[gtest]
+fn test_span_param() {
+    expect_eq!(Sum((&[1, 2, 3]).into()), 6);
+    expect_eq!(Sum((&[][..]).into()), 0);
+}
+
+#[gtest]
+fn test_span_mut_param() {
+    let mut array = [1, 2, 3];
+    Double((&mut array).into());
+    expect_eq!(array, [2, 4, 6]);
+}
+
+#[gtest]
+fn test_span_return() {
+    let array = [1, 2, 3, 4, 5];
+    let truncated: cc_std::std::raw_span<i32> = Truncate((&array).into(), 3);
+    // SAFETY: `truncated` points into `array`, which is still alive.
+    expect_eq!(unsafe { truncated.as_slice() }, &[1, 2, 3]);
+}
+
+#[gtest]
+fn test_span_mut_return() {
+    let mut array = [1, 2, 3, 4, 5];
+    let truncated: cc_std::std::raw_span_mut<i32> = TruncateMut((&mut array).into(), 2);
+    // SAFETY: `truncated` points into `array`, which is still alive and not otherwise borrowed.
+    unsafe { truncated.as_mut_slice()[0] = 100 };
+    expect_eq!(array, [100, 2, 3, 4, 5]);
+}
+
+#[gtest]
+fn test_null_span_return() {
+    let empty: cc_std::std::raw_span<i32> = EmptySpan();
+    expect_eq!(empty.len(), 0);
+    expect_true!(empty.data().is_null());
+    // SAFETY: the span is empty.
+    expect_eq!(unsafe { empty.as_slice() }, &[] as &[i32]);
+}
+
+#[gtest]
+fn test_span_layout() {
+    // The wrappers must have the layout of a C++ `std::span`: a data pointer followed by a size.
+    #[repr(C)]
+    struct SpanLayout {
+        data: *const i32,
+        size: usize,
+    }
+    let array = [1, 2, 3];
+    let span: cc_std::std::span<i32> = (&array).into();
+    // SAFETY: `span` is `#[repr(C)]`, with the same fields as `SpanLayout`.
+    let layout: SpanLayout = unsafe { core::mem::transmute(span) };
+    expect_eq!(layout.data, array.as_ptr());
+    expect_eq!(layout.size, 3);
+}
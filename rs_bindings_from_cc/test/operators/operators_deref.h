@@ -0,0 +1,85 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_OPERATORS_OPERATORS_DEREF_H_
+#define THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_OPERATORS_OPERATORS_DEREF_H_
+
+namespace crubit::test {
+
+struct PointeeUnpin final {
+  int value = 0;
+};
+
+struct PointeeNonUnpin final {
+  int value = 0;
+  // NOLINTNEXTLINE(modernize-use-equals-default)
+  ~PointeeNonUnpin() {};
+};
+
+// A smart pointer with both const and non-const `operator*`, which receives
+// `Deref` and `DerefMut` impls.
+class PtrUnpin final {
+ public:
+  PtrUnpin() = default;
+
+  const PointeeUnpin& operator*() const [[clang::lifetimebound]] {
+    return pointee_;
+  }
+  PointeeUnpin& operator*() [[clang::lifetimebound]] { return pointee_; }
+
+  // Not bound: `operator*` already provides `Deref` and `DerefMut`.
+  const PointeeUnpin* operator->() const [[clang::lifetimebound]] {
+    return &pointee_;
+  }
+  PointeeUnpin* operator->() [[clang::lifetimebound]] { return &pointee_; }
+
+ private:
+  PointeeUnpin pointee_;
+};
+
+// A smart pointer with only `operator->`, which receives `Deref` and `DerefMut`
+// impls.
+class ArrowPtrUnpin final {
+ public:
+  ArrowPtrUnpin() = default;
+
+  const PointeeUnpin* operator->() const [[clang::lifetimebound]] {
+    return &pointee_;
+  }
+  PointeeUnpin* operator->() [[clang::lifetimebound]] { return &pointee_; }
+
+ private:
+  PointeeUnpin pointee_;
+};
+
+// A smart pointer to a non-Unpin type. The non-const `operator*` is bound as
+// `deref_pin_mut`, which returns a pinned reference.
+class PtrNonUnpin final {
+ public:
+  PtrNonUnpin() = default;
+
+  const PointeeNonUnpin& operator*() const [[clang::lifetimebound]] {
+    return pointee_;
+  }
+  PointeeNonUnpin& operator*() [[clang::lifetimebound]] { return pointee_; }
+
+ private:
+  PointeeNonUnpin pointee_;
+};
+
+// Without `[[clang::lifetimebound]]`, the returned reference is not known to
+// point into `*this`, so no bindings are generated.
+class PtrNotLifetimebound final {
+ public:
+  explicit PtrNotLifetimebound(PointeeUnpin* pointee) : pointee_(pointee) {}
+
+  PointeeUnpin& operator*() const { return *pointee_; }
+
+ private:
+  PointeeUnpin* pointee_;
+};
+
+}  // namespace crubit::test
+
+#endif  // THIRD_PARTY_CRUBIT_RS_BINDINGS_FROM_CC_TEST_OPERATORS_OPERATORS_DEREF_H_
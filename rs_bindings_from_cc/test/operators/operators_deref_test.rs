@@ -0,0 +1,48 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use ctor::{emplace, CtorNew};
+use googletest::prelude::*;
+use operators_deref::crubit::test::*;
+use static_assertions::assert_not_impl_any;
+use std::pin::Pin;
+
+#[gtest]
+fn test_deref_and_deref_mut() {
+    let mut p = PtrUnpin::default();
+    assert_eq!(p.value, 0);
+
+    p.value = 10;
+
+    assert_eq!((*p).value, 10);
+}
+
+#[gtest]
+fn test_deref_and_deref_mut_from_arrow_operator() {
+    let mut p = ArrowPtrUnpin::default();
+    assert_eq!(p.value, 0);
+
+    p.value = 20;
+
+    assert_eq!(p.value, 20);
+}
+
+#[gtest]
+fn test_deref_pin_mut_for_non_unpin_pointee() {
+    let mut p = emplace!(PtrNonUnpin::ctor_new(()));
+    assert_eq!(p.value, 0);
+
+    let pointee: Pin<&mut PointeeNonUnpin> = p.as_mut().deref_pin_mut();
+
+    // SAFETY: We are only mutating the `value` field and not moving the
+    // `PointeeNonUnpin` out of its pinned location.
+    unsafe { Pin::get_unchecked_mut(pointee) }.value = 30;
+
+    assert_eq!(p.value, 30);
+}
+
+#[gtest]
+fn test_no_deref_without_lifetimebound() {
+    assert_not_impl_any!(PtrNotLifetimebound: std::ops::Deref);
+}
@@ -0,0 +1,377 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! The Rust type of `absl::Cord`.
+//!
+//! Functions that take or return `absl::Cord` use [`Cord`] in their Rust bindings.
+
+#![feature(impl_trait_in_assoc_type)]
+#![deny(missing_docs, unsafe_op_in_unsafe_fn)]
+
+use core::ffi::{c_char, c_void};
+use core::fmt;
+use core::marker::{PhantomData, PhantomPinned};
+use core::pin::Pin;
+use core::ptr::NonNull;
+use ctor::{Ctor, CtorNew, FnCtor, Infallible, RvalueReference};
+
+// These functions are defined in `cord_helpers.cc`.
+unsafe extern "C" {
+    fn crubit_absl_cord_default(dst: *mut c_void);
+    fn crubit_absl_cord_from_buffer(dst: *mut c_void, data: *const c_char, size: usize);
+    fn crubit_absl_cord_from_external(
+        dst: *mut c_void,
+        data: *const c_char,
+        size: usize,
+        owner: *mut c_void,
+        release: unsafe extern "C" fn(*mut c_void),
+    );
+    fn crubit_absl_cord_copy(dst: *mut c_void, src: *const c_void);
+    fn crubit_absl_cord_move(dst: *mut c_void, src: *mut c_void);
+    fn crubit_absl_cord_destroy(cord: *mut c_void);
+    fn crubit_absl_cord_size(cord: *const c_void) -> usize;
+    fn crubit_absl_cord_equal(cord1: *const c_void, cord2: *const c_void) -> bool;
+    fn crubit_absl_cord_try_flat(
+        cord: *const c_void,
+        data: *mut *const c_char,
+        size: *mut usize,
+    ) -> bool;
+    fn crubit_absl_cord_append(cord: *mut c_void, data: *const c_char, size: usize);
+    fn crubit_absl_cord_clear(cord: *mut c_void);
+    fn crubit_absl_cord_chunks_new(cord: *const c_void) -> *mut c_void;
+    fn crubit_absl_cord_chunks_next(
+        chunks: *mut c_void,
+        data: *mut *const c_char,
+        size: *mut usize,
+    ) -> bool;
+    fn crubit_absl_cord_chunks_delete(chunks: *mut c_void);
+}
+
+/// A layout-compatible `absl::Cord`: a sequence of bytes, stored as a tree of shared,
+/// immutable chunks.
+///
+/// Like other C++ values, a `Cord` is `!Unpin`: it is created in place with [`ctor::emplace!`],
+/// and mutated through `Pin<&mut Cord>`.
+///
+/// Reading a `Cord` does not copy it: [`Cord::chunks`] borrows each chunk in turn. A `Cord` can
+/// also be created from Rust-owned bytes without copying them, using [`Cord::from_external`].
+///
+/// # Examples
+///
+/// ```
+/// use absl_cord::Cord;
+/// use ctor::{emplace, CtorNew};
+///
+/// let mut cord = emplace!(Cord::ctor_new(b"Hello".to_vec()));
+/// cord.as_mut().append(b", C++!");
+/// assert_eq!(cord.len(), 11);
+/// assert_eq!(cord.to_vec(), b"Hello, C++!");
+/// ```
+#[repr(C, align(8))]
+pub struct Cord {
+    _opaque: core::cfg_select! {
+        target_pointer_width = "64" => [u8; 16],
+        _ => compile_error!("Cord is only supported on 64-bit targets"),
+    },
+    _pinned: PhantomPinned,
+}
+
+// SAFETY: `absl::Cord` is thread-compatible, and mutation is only allowed via `Pin<&mut Self>`.
+unsafe impl Send for Cord {}
+
+// SAFETY: `absl::Cord` is thread-compatible, and mutation is only allowed via `Pin<&mut Self>`.
+unsafe impl Sync for Cord {}
+
+impl Drop for Cord {
+    fn drop(&mut self) {
+        // SAFETY: `self` is a valid `absl::Cord`, which is not used again.
+        unsafe { crubit_absl_cord_destroy(self.as_mut_ptr()) }
+    }
+}
+
+impl Cord {
+    fn as_ptr(&self) -> *const c_void {
+        self as *const Cord as *const c_void
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut c_void {
+        self as *mut Cord as *mut c_void
+    }
+
+    /// Returns a `Ctor` for a cord which references `data` without copying it.
+    ///
+    /// The cord takes ownership of `data`, and drops it, possibly on another thread, once no cord
+    /// references it. `bytes::Bytes` and `Vec<u8>` can both be used as `data`.
+    ///
+    /// Small inputs may be copied into the cord instead, in which case `data` is dropped right
+    /// away.
+    pub fn from_external<T>(data: T) -> impl Ctor<Output = Self, Error = Infallible>
+    where
+        T: AsRef<[u8]> + Send + 'static,
+    {
+        unsafe extern "C" fn release<T>(owner: *mut c_void) {
+            // SAFETY: `owner` is the `Box<T>` leaked below, and `release` is called only once.
+            drop(unsafe { Box::from_raw(owner as *mut T) });
+        }
+
+        // SAFETY: `dest` is valid for writing a `Cord`.
+        unsafe {
+            FnCtor::new(move |dest: *mut Cord| {
+                // The bytes must not move while the cord references them, so `data` is boxed
+                // before they are borrowed.
+                let owner = Box::into_raw(Box::new(data));
+                let bytes = (*owner).as_ref();
+                crubit_absl_cord_from_external(
+                    dest as *mut c_void,
+                    bytes.as_ptr() as *const c_char,
+                    bytes.len(),
+                    owner as *mut c_void,
+                    release::<T>,
+                );
+            })
+        }
+    }
+
+    /// Returns the number of bytes in the cord.
+    pub fn len(&self) -> usize {
+        // SAFETY: `self` is a valid `absl::Cord`.
+        unsafe { crubit_absl_cord_size(self.as_ptr()) }
+    }
+
+    /// Returns true if the cord has no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bytes of the cord if they are stored in a single chunk.
+    pub fn as_flat(&self) -> Option<&[u8]> {
+        let mut data = core::ptr::null();
+        let mut size = 0;
+        // SAFETY: `self` is a valid `absl::Cord`, and the chunk lives as long as the cord is not
+        // mutated, which `&self` guarantees.
+        unsafe {
+            if !crubit_absl_cord_try_flat(self.as_ptr(), &mut data, &mut size) {
+                return None;
+            }
+            Some(slice_from_raw_parts(data, size))
+        }
+    }
+
+    /// Returns an iterator over the chunks of the cord, without copying them.
+    ///
+    /// The chunks are never empty.
+    pub fn chunks(&self) -> Chunks<'_> {
+        if let Some(flat) = self.as_flat() {
+            return Chunks { inner: ChunksInner::Flat(Some(flat).filter(|flat| !flat.is_empty())) };
+        }
+        // SAFETY: `self` is a valid `absl::Cord`, which outlives the returned iterator.
+        let raw = unsafe { crubit_absl_cord_chunks_new(self.as_ptr()) };
+        Chunks {
+            inner: ChunksInner::Tree {
+                raw: NonNull::new(raw).expect("failed to allocate a Cord::ChunkIterator"),
+                _cord: PhantomData,
+            },
+        }
+    }
+
+    /// Copies the bytes of the cord into a new `Vec<u8>`.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len());
+        for chunk in self.chunks() {
+            bytes.extend_from_slice(chunk);
+        }
+        bytes
+    }
+
+    /// Appends a copy of `bytes` to the cord.
+    pub fn append(self: Pin<&mut Self>, bytes: &[u8]) {
+        // SAFETY: `self` is a valid `absl::Cord`, which `Append` does not move.
+        unsafe {
+            crubit_absl_cord_append(
+                self.get_unchecked_mut().as_mut_ptr(),
+                bytes.as_ptr() as *const c_char,
+                bytes.len(),
+            )
+        }
+    }
+
+    /// Removes all bytes from the cord.
+    pub fn clear(self: Pin<&mut Self>) {
+        // SAFETY: `self` is a valid `absl::Cord`, which `Clear` does not move.
+        unsafe { crubit_absl_cord_clear(self.get_unchecked_mut().as_mut_ptr()) }
+    }
+}
+
+/// Creates a slice from a C++ pointer and size, which may be null if the size is zero.
+///
+/// # Safety
+///
+/// If `size` is not zero, `data` must be valid for reads of `size` bytes during `'a`.
+unsafe fn slice_from_raw_parts<'a>(data: *const c_char, size: usize) -> &'a [u8] {
+    if size == 0 {
+        return &[];
+    }
+    // SAFETY: guaranteed by the caller.
+    unsafe { core::slice::from_raw_parts(data as *const u8, size) }
+}
+
+/// An iterator over the chunks of a [`Cord`], returned by [`Cord::chunks`].
+pub struct Chunks<'a> {
+    inner: ChunksInner<'a>,
+}
+
+enum ChunksInner<'a> {
+    /// The cord is a single chunk, which has not been returned yet.
+    Flat(Option<&'a [u8]>),
+    /// An owned `absl::Cord::ChunkIterator`.
+    Tree { raw: NonNull<c_void>, _cord: PhantomData<&'a Cord> },
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        match &mut self.inner {
+            ChunksInner::Flat(flat) => flat.take(),
+            ChunksInner::Tree { raw, .. } => {
+                let mut data = core::ptr::null();
+                let mut size = 0;
+                // SAFETY: `raw` is a valid `absl::Cord::ChunkIterator` of a cord which outlives
+                // `'a`, and isn't mutated during `'a`, so neither are its chunks.
+                unsafe {
+                    if !crubit_absl_cord_chunks_next(raw.as_ptr(), &mut data, &mut size) {
+                        return None;
+                    }
+                    Some(slice_from_raw_parts(data, size))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Chunks<'_> {
+    fn drop(&mut self) {
+        if let ChunksInner::Tree { raw, .. } = &self.inner {
+            // SAFETY: `raw` was returned by `crubit_absl_cord_chunks_new`, and is not used again.
+            unsafe { crubit_absl_cord_chunks_delete(raw.as_ptr()) }
+        }
+    }
+}
+
+impl CtorNew<()> for Cord {
+    type CtorType = impl Ctor<Output = Self, Error = Infallible>;
+    type Error = Infallible;
+
+    fn ctor_new(_args: ()) -> Self::CtorType {
+        // SAFETY: `dest` is valid for writing a `Cord`.
+        unsafe { FnCtor::new(|dest: *mut Cord| crubit_absl_cord_default(dest as *mut c_void)) }
+    }
+}
+
+impl<'a> CtorNew<&'a [u8]> for Cord {
+    type CtorType = impl Ctor<Output = Self, Error = Infallible> + use<'a>;
+    type Error = Infallible;
+
+    fn ctor_new(args: &'a [u8]) -> Self::CtorType {
+        // SAFETY: `dest` is valid for writing a `Cord`.
+        unsafe {
+            FnCtor::new(move |dest: *mut Cord| {
+                crubit_absl_cord_from_buffer(
+                    dest as *mut c_void,
+                    args.as_ptr() as *const c_char,
+                    args.len(),
+                )
+            })
+        }
+    }
+}
+
+impl<'a> CtorNew<&'a str> for Cord {
+    type CtorType = impl Ctor<Output = Self, Error = Infallible> + use<'a>;
+    type Error = Infallible;
+
+    fn ctor_new(args: &'a str) -> Self::CtorType {
+        Self::ctor_new(args.as_bytes())
+    }
+}
+
+/// Constructs a `Cord` which takes ownership of the `Vec<u8>`, without copying it. See
+/// [`Cord::from_external`].
+impl CtorNew<Vec<u8>> for Cord {
+    type CtorType = impl Ctor<Output = Self, Error = Infallible>;
+    type Error = Infallible;
+
+    fn ctor_new(args: Vec<u8>) -> Self::CtorType {
+        Self::from_external(args)
+    }
+}
+
+/// Constructs a copy of a `Cord`, which shares its chunks.
+impl<'a> CtorNew<&'a Cord> for Cord {
+    type CtorType = impl Ctor<Output = Self, Error = Infallible> + use<'a>;
+    type Error = Infallible;
+
+    fn ctor_new(args: &'a Cord) -> Self::CtorType {
+        // SAFETY: `dest` is valid for writing a `Cord`, and `args` is a valid `absl::Cord`.
+        unsafe {
+            FnCtor::new(move |dest: *mut Cord| {
+                crubit_absl_cord_copy(dest as *mut c_void, args.as_ptr())
+            })
+        }
+    }
+}
+
+impl<'a> CtorNew<RvalueReference<'a, Cord>> for Cord {
+    type CtorType = impl Ctor<Output = Self, Error = Infallible> + use<'a>;
+    type Error = Infallible;
+
+    fn ctor_new(args: RvalueReference<'a, Cord>) -> Self::CtorType {
+        // SAFETY: `dest` is valid for writing a `Cord`, and `args` is a valid `absl::Cord`, which
+        // is left in a valid but unspecified state.
+        unsafe {
+            FnCtor::new(move |dest: *mut Cord| {
+                crubit_absl_cord_move(dest as *mut c_void, args.0.get_unchecked_mut().as_mut_ptr())
+            })
+        }
+    }
+}
+
+impl PartialEq for Cord {
+    fn eq(&self, other: &Cord) -> bool {
+        // SAFETY: `self` and `other` are valid `absl::Cord`s.
+        unsafe { crubit_absl_cord_equal(self.as_ptr(), other.as_ptr()) }
+    }
+}
+
+impl Eq for Cord {}
+
+impl PartialEq<[u8]> for Cord {
+    fn eq(&self, other: &[u8]) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        let mut rest = other;
+        self.chunks().all(|chunk| {
+            let (head, tail) = rest.split_at(chunk.len());
+            rest = tail;
+            head == chunk
+        })
+    }
+}
+
+impl PartialEq<&[u8]> for Cord {
+    fn eq(&self, other: &&[u8]) -> bool {
+        *self == **other
+    }
+}
+
+impl fmt::Debug for Cord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Cord(\"")?;
+        for chunk in self.chunks() {
+            write!(f, "{}", chunk.escape_ascii())?;
+        }
+        f.write_str("\")")
+    }
+}
@@ -0,0 +1,102 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "support/absl_cord/cord_helpers.h"
+
+#include <cstddef>
+#include <new>
+#include <optional>
+#include <utility>
+
+#include "absl/strings/cord.h"
+#include "absl/strings/string_view.h"
+
+namespace crubit::absl_cord_internal {
+namespace {
+
+// The layout of the Rust `Cord` in `cord.rs`.
+static_assert(sizeof(void*) != 8 || sizeof(absl::Cord) == 16);
+static_assert(sizeof(void*) != 8 || alignof(absl::Cord) == 8);
+
+absl::Cord* AsCord(void* cord) { return static_cast<absl::Cord*>(cord); }
+
+const absl::Cord* AsCord(const void* cord) {
+  return static_cast<const absl::Cord*>(cord);
+}
+
+}  // namespace
+
+extern "C" void crubit_absl_cord_default(void* dst) { new (dst) absl::Cord(); }
+
+extern "C" void crubit_absl_cord_from_buffer(void* dst, const char* data,
+                                             size_t size) {
+  new (dst) absl::Cord(absl::string_view(data, size));
+}
+
+extern "C" void crubit_absl_cord_from_external(void* dst, const char* data,
+                                               size_t size, void* owner,
+                                               void (*release)(void*)) {
+  new (dst) absl::Cord(absl::MakeCordFromExternal(
+      absl::string_view(data, size), [owner, release] { release(owner); }));
+}
+
+extern "C" void crubit_absl_cord_copy(void* dst, const void* src) {
+  new (dst) absl::Cord(*AsCord(src));
+}
+
+extern "C" void crubit_absl_cord_move(void* dst, void* src) {
+  new (dst) absl::Cord(std::move(*AsCord(src)));
+}
+
+extern "C" void crubit_absl_cord_destroy(void* cord) { AsCord(cord)->~Cord(); }
+
+extern "C" size_t crubit_absl_cord_size(const void* cord) {
+  return AsCord(cord)->size();
+}
+
+extern "C" bool crubit_absl_cord_equal(const void* cord1, const void* cord2) {
+  return *AsCord(cord1) == *AsCord(cord2);
+}
+
+extern "C" bool crubit_absl_cord_try_flat(const void* cord, const char** data,
+                                          size_t* size) {
+  std::optional<absl::string_view> flat = AsCord(cord)->TryFlat();
+  if (!flat.has_value()) {
+    return false;
+  }
+  *data = flat->data();
+  *size = flat->size();
+  return true;
+}
+
+extern "C" void crubit_absl_cord_append(void* cord, const char* data,
+                                        size_t size) {
+  AsCord(cord)->Append(absl::string_view(data, size));
+}
+
+extern "C" void crubit_absl_cord_clear(void* cord) { AsCord(cord)->Clear(); }
+
+extern "C" void* crubit_absl_cord_chunks_new(const void* cord) {
+  return new absl::Cord::ChunkIterator(AsCord(cord)->chunk_begin());
+}
+
+extern "C" bool crubit_absl_cord_chunks_next(void* chunks, const char** data,
+                                             size_t* size) {
+  auto& it = *static_cast<absl::Cord::ChunkIterator*>(chunks);
+  // A default-constructed `ChunkIterator` is the end iterator of every cord.
+  if (it == absl::Cord::ChunkIterator()) {
+    return false;
+  }
+  absl::string_view chunk = *it;
+  *data = chunk.data();
+  *size = chunk.size();
+  ++it;
+  return true;
+}
+
+extern "C" void crubit_absl_cord_chunks_delete(void* chunks) {
+  delete static_cast<absl::Cord::ChunkIterator*>(chunks);
+}
+
+}  // namespace crubit::absl_cord_internal
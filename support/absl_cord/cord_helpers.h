@@ -0,0 +1,56 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_SUPPORT_ABSL_CORD_CORD_HELPERS_H_
+#define THIRD_PARTY_CRUBIT_SUPPORT_ABSL_CORD_CORD_HELPERS_H_
+
+#include <cstddef>
+
+namespace crubit::absl_cord_internal {
+
+// These extern "C" functions defined in `cord_helpers.cc` are used by
+// `cord.rs` to implement the Rust `Cord` API.
+//
+// Every `void*` cord points to an `absl::Cord`. `dst` parameters point to
+// uninitialized memory, and are initialized by the function.
+
+extern "C" void crubit_absl_cord_default(void* dst);
+extern "C" void crubit_absl_cord_from_buffer(void* dst, const char* data,
+                                             size_t size);
+
+// Constructs a cord which references `data` without copying it, and calls
+// `release(owner)` once the cord no longer references `data`. This may happen
+// before `crubit_absl_cord_from_external` returns.
+extern "C" void crubit_absl_cord_from_external(void* dst, const char* data,
+                                               size_t size, void* owner,
+                                               void (*release)(void*));
+extern "C" void crubit_absl_cord_copy(void* dst, const void* src);
+extern "C" void crubit_absl_cord_move(void* dst, void* src);
+extern "C" void crubit_absl_cord_destroy(void* cord);
+
+extern "C" size_t crubit_absl_cord_size(const void* cord);
+extern "C" bool crubit_absl_cord_equal(const void* cord1, const void* cord2);
+
+// Returns true and sets `*data` and `*size` if the cord is a single chunk.
+extern "C" bool crubit_absl_cord_try_flat(const void* cord, const char** data,
+                                          size_t* size);
+
+extern "C" void crubit_absl_cord_append(void* cord, const char* data,
+                                        size_t size);
+extern "C" void crubit_absl_cord_clear(void* cord);
+
+// Returns an owned, heap-allocated `absl::Cord::ChunkIterator` positioned at
+// the first chunk of `cord`. It must be deleted with
+// `crubit_absl_cord_chunks_delete`, and must not outlive `cord`.
+extern "C" void* crubit_absl_cord_chunks_new(const void* cord);
+
+// Returns false at the end of the cord. Otherwise, sets `*data` and `*size` to
+// the current chunk and advances the iterator.
+extern "C" bool crubit_absl_cord_chunks_next(void* chunks, const char** data,
+                                             size_t* size);
+extern "C" void crubit_absl_cord_chunks_delete(void* chunks);
+
+}  // namespace crubit::absl_cord_internal
+
+#endif  // THIRD_PARTY_CRUBIT_SUPPORT_ABSL_CORD_CORD_HELPERS_H_
@@ -0,0 +1,88 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use absl_cord::Cord;
+use ctor::{emplace, mov, CtorNew};
+use googletest::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[gtest]
+fn test_empty() {
+    let cord = emplace!(Cord::ctor_new(()));
+    expect_eq!(cord.len(), 0);
+    expect_true!(cord.is_empty());
+    expect_eq!(cord.chunks().count(), 0);
+    expect_eq!(cord.to_vec(), b"");
+}
+
+#[gtest]
+fn test_flat() {
+    let cord = emplace!(Cord::ctor_new("hello"));
+    expect_eq!(cord.as_flat(), Some(&b"hello"[..]));
+    expect_eq!(cord.chunks().collect::<Vec<_>>(), vec![&b"hello"[..]]);
+    expect_eq!(format!("{:?}", &*cord), r#"Cord("hello")"#);
+}
+
+#[gtest]
+fn test_append_many_chunks() {
+    let chunk = [b'x'; 4096];
+    let mut cord = emplace!(Cord::ctor_new(()));
+    for _ in 0..16 {
+        cord.as_mut().append(&chunk);
+    }
+    expect_eq!(cord.len(), 16 * 4096);
+    expect_eq!(cord.as_flat(), None);
+    expect_that!(cord.chunks().count(), gt(1));
+    expect_eq!(cord.chunks().map(<[u8]>::len).sum::<usize>(), cord.len());
+    expect_eq!(cord.to_vec(), [b'x'; 16 * 4096]);
+    expect_true!(*cord == [b'x'; 16 * 4096][..]);
+
+    cord.as_mut().clear();
+    expect_true!(cord.is_empty());
+}
+
+/// Bytes which count how many times they are dropped.
+struct CountedBytes {
+    bytes: Vec<u8>,
+    drops: Arc<AtomicUsize>,
+}
+
+impl AsRef<[u8]> for CountedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Drop for CountedBytes {
+    fn drop(&mut self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[gtest]
+fn test_from_external_is_zero_copy() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let bytes = vec![b'y'; 4096];
+    let data_ptr = bytes.as_ptr();
+    {
+        let cord = emplace!(Cord::from_external(CountedBytes { bytes, drops: Arc::clone(&drops) }));
+        expect_eq!(cord.as_flat().map(<[u8]>::as_ptr), Some(data_ptr));
+        {
+            let copy = emplace!(Cord::ctor_new(&*cord));
+            expect_true!(*copy == *cord);
+            expect_eq!(copy.as_flat().map(<[u8]>::as_ptr), Some(data_ptr));
+        }
+        expect_eq!(drops.load(Ordering::SeqCst), 0);
+    }
+    expect_eq!(drops.load(Ordering::SeqCst), 1);
+}
+
+#[gtest]
+fn test_from_vec() {
+    let mut cord = emplace!(Cord::ctor_new(b"abc".repeat(1000)));
+    expect_eq!(cord.len(), 3000);
+    let moved = emplace!(Cord::ctor_new(mov!(cord)));
+    expect_eq!(moved.to_vec(), b"abc".repeat(1000));
+}
@@ -57,6 +57,26 @@
 // `do_not_bind_allowlist` or bindings generation will fail with a hard error.
 #define CRUBIT_DO_NOT_BIND CRUBIT_INTERNAL_ANNOTATE("crubit_do_not_bind")
 
+// Enables Crubit features for a single function or type, in addition to the
+// features enabled for its whole target via `aspect_hints`.
+//
+// The annotation accepts one or more feature names, such as
+// "template_instantiation" or "experimental" (see `//features`). It also
+// applies to the declarations nested in the annotated one, such as the methods
+// of an annotated class, or the declarations in an annotated namespace.
+//
+// This allows trying out experimental bindings for a few declarations, without
+// enabling the feature for the whole library.
+//
+// For example:
+//
+// ```c++
+// CRUBIT_ENABLE_FEATURE("template_instantiation")
+// void Consume(std::vector<int> v);
+// ```
+#define CRUBIT_ENABLE_FEATURE(...) \
+  CRUBIT_INTERNAL_ANNOTATE("crubit_enable_feature", __VA_ARGS__)
+
 // By default, crubit.rs will infer Rust safety based on the types of the
 // function's parameters. This annotation can be used to override that
 // inference.
@@ -0,0 +1,166 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_COLLECTIONS_H_
+#define THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_COLLECTIONS_H_
+
+#include "support/bridge.h"
+
+#ifdef CRUBIT_BRIDGE_ENABLED
+
+#include <cstddef>
+#include <cstdlib>
+#include <map>
+#include <set>
+#include <unordered_map>
+#include <unordered_set>
+#include <utility>
+
+#include "absl/container/flat_hash_map.h"
+#include "absl/container/flat_hash_set.h"
+
+namespace crubit {
+
+namespace internal {
+
+// The ABI of a container with `len` elements, each encoded with an element ABI
+// `Abi`, is `len` as a `size_t`, followed by a pointer to a `std::malloc`ed
+// buffer of `len * Abi::kSize` bytes which contains the encoded elements. The
+// buffer is null if it would be empty. The decoding side takes ownership of the
+// buffer, and releases it with `std::free`.
+//
+// This must match `encode_elements` and `decode_elements` in Rust.
+inline constexpr size_t kContainerSize = sizeof(size_t) + sizeof(void*);
+
+// Encodes the elements of `container`, moving each element out of its node.
+// `get_element` turns a node handle into an `Abi::Value`.
+template <typename Abi, typename Container, typename GetElement>
+  requires(is_crubit_abi<Abi>)
+void EncodeElements(const Abi& abi, Container container, Encoder& encoder,
+                    GetElement get_element) {
+  size_t len = container.size();
+  size_t size = len * Abi::kSize;
+  unsigned char* buf = nullptr;
+  if (size != 0) {
+    buf = static_cast<unsigned char*>(std::malloc(size));
+    if (buf == nullptr) {
+      std::abort();
+    }
+  }
+  Encoder element_encoder(size, buf);
+  while (!container.empty()) {
+    auto node = container.extract(container.begin());
+    Abi element_abi = abi;
+    std::move(element_abi).Encode(get_element(node), element_encoder);
+  }
+  TransmuteAbi<size_t>().Encode(len, encoder);
+  TransmuteAbi<void*>().Encode(buf, encoder);
+}
+
+template <typename Abi, typename Container>
+  requires(is_crubit_abi<Abi>)
+Container DecodeElements(const Abi& abi, Decoder& decoder) {
+  size_t len = TransmuteAbi<size_t>().Decode(decoder);
+  void* buf = TransmuteAbi<void*>().Decode(decoder);
+  Decoder element_decoder(len * Abi::kSize,
+                          static_cast<const unsigned char*>(buf));
+  Container container;
+  if constexpr (requires { container.reserve(len); }) {
+    container.reserve(len);
+  }
+  for (size_t i = 0; i < len; ++i) {
+    Abi element_abi = abi;
+    container.insert(std::move(element_abi).Decode(element_decoder));
+  }
+  std::free(buf);
+  return container;
+}
+
+}  // namespace internal
+
+// A Crubit ABI for a map, which is bridged to a Rust `HashMap` or `BTreeMap` by
+// converting each entry: keys are encoded with `KeyAbi`, and values with
+// `ValueAbi`.
+template <typename Map, typename KeyAbi, typename ValueAbi>
+  requires(is_crubit_abi<KeyAbi> && is_crubit_abi<ValueAbi>)
+struct MapAbi {
+  explicit MapAbi(KeyAbi key_abi, ValueAbi value_abi)
+      : element_abi(std::move(key_abi), std::move(value_abi)) {}
+
+  using Value = Map;
+  static constexpr size_t kSize = internal::kContainerSize;
+  void Encode(Value value, Encoder& encoder) && {
+    internal::EncodeElements(element_abi, std::move(value), encoder,
+                             [](auto& node) {
+                               return typename ElementAbi::Value(
+                                   std::move(node.key()),
+                                   std::move(node.mapped()));
+                             });
+  }
+  Value Decode(Decoder& decoder) && {
+    return internal::DecodeElements<ElementAbi, Value>(element_abi, decoder);
+  }
+
+  using ElementAbi = PairAbi<KeyAbi, ValueAbi>;
+  ElementAbi element_abi;
+};
+
+// A Crubit ABI for a set, which is bridged to a Rust `HashSet` or `BTreeSet` by
+// converting each element with `Abi`.
+template <typename Set, typename Abi>
+  requires(is_crubit_abi<Abi>)
+struct SetAbi {
+  explicit SetAbi(Abi abi) : abi(std::move(abi)) {}
+
+  using Value = Set;
+  static constexpr size_t kSize = internal::kContainerSize;
+  void Encode(Value value, Encoder& encoder) && {
+    internal::EncodeElements(abi, std::move(value), encoder, [](auto& node) {
+      return std::move(node.value());
+    });
+  }
+  Value Decode(Decoder& decoder) && {
+    return internal::DecodeElements<Abi, Value>(abi, decoder);
+  }
+
+  Abi abi;
+};
+
+// `HashMap<K, V>`, bridged to `absl::flat_hash_map<K, V>`.
+template <typename KeyAbi, typename ValueAbi>
+using FlatHashMapAbi =
+    MapAbi<absl::flat_hash_map<typename KeyAbi::Value,
+                               typename ValueAbi::Value>,
+           KeyAbi, ValueAbi>;
+
+// `HashMap<K, V>`, bridged to `std::unordered_map<K, V>`.
+template <typename KeyAbi, typename ValueAbi>
+using StdUnorderedMapAbi =
+    MapAbi<std::unordered_map<typename KeyAbi::Value,
+                              typename ValueAbi::Value>,
+           KeyAbi, ValueAbi>;
+
+// `BTreeMap<K, V>`, bridged to `std::map<K, V>`.
+template <typename KeyAbi, typename ValueAbi>
+using StdMapAbi =
+    MapAbi<std::map<typename KeyAbi::Value, typename ValueAbi::Value>, KeyAbi,
+           ValueAbi>;
+
+// `HashSet<T>`, bridged to `absl::flat_hash_set<T>`.
+template <typename Abi>
+using FlatHashSetAbi = SetAbi<absl::flat_hash_set<typename Abi::Value>, Abi>;
+
+// `HashSet<T>`, bridged to `std::unordered_set<T>`.
+template <typename Abi>
+using StdUnorderedSetAbi =
+    SetAbi<std::unordered_set<typename Abi::Value>, Abi>;
+
+// `BTreeSet<T>`, bridged to `std::set<T>`.
+template <typename Abi>
+using StdSetAbi = SetAbi<std::set<typename Abi::Value>, Abi>;
+
+}  // namespace crubit
+
+#endif  // CRUBIT_BRIDGE_ENABLED
+#endif  // THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_COLLECTIONS_H_
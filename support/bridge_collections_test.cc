@@ -0,0 +1,68 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "support/bridge_collections.h"
+
+#include <cstdint>
+#include <map>
+#include <optional>
+#include <set>
+#include <string>
+#include <unordered_map>
+#include <utility>
+
+#include "gtest/gtest.h"
+#include "absl/container/flat_hash_map.h"
+#include "absl/container/flat_hash_set.h"
+#include "support/bridge.h"
+
+namespace crubit {
+namespace {
+
+template <typename Abi>
+typename Abi::Value Roundtrip(Abi abi, typename Abi::Value value) {
+  unsigned char buf[Abi::kSize];
+  internal::Encode<Abi>(Abi(abi), buf, std::move(value));
+  return internal::Decode<Abi>(std::move(abi), buf);
+}
+
+TEST(BridgeCollectionsTest, FlatHashMap) {
+  using Abi =
+      FlatHashMapAbi<TransmuteAbi<uint32_t>, OptionAbi<TransmuteAbi<int64_t>>>;
+  absl::flat_hash_map<uint32_t, std::optional<int64_t>> map = {
+      {1, -1}, {2, std::nullopt}, {3, 3}};
+  EXPECT_EQ(Roundtrip(Abi(TransmuteAbi<uint32_t>(),
+                          OptionAbi<TransmuteAbi<int64_t>>()),
+                      map),
+            map);
+}
+
+TEST(BridgeCollectionsTest, MovesElements) {
+  using Abi = StdUnorderedMapAbi<BoxedAbi<std::string>, BoxedAbi<std::string>>;
+  std::unordered_map<std::string, std::string> map = {
+      {"key", std::string(100, 'v')}};
+  EXPECT_EQ(
+      Roundtrip(Abi(BoxedAbi<std::string>(), BoxedAbi<std::string>()), map),
+      map);
+}
+
+TEST(BridgeCollectionsTest, EmptyStdMap) {
+  using Abi = StdMapAbi<TransmuteAbi<int>, TransmuteAbi<int>>;
+  EXPECT_EQ(Roundtrip(Abi(TransmuteAbi<int>(), TransmuteAbi<int>()), {}),
+            (std::map<int, int>()));
+}
+
+TEST(BridgeCollectionsTest, Sets) {
+  absl::flat_hash_set<int16_t> hash_set = {-1, 0, 1};
+  EXPECT_EQ(Roundtrip(FlatHashSetAbi<TransmuteAbi<int16_t>>(
+                          TransmuteAbi<int16_t>()),
+                      hash_set),
+            hash_set);
+  std::set<char> set = {'a', 'b'};
+  EXPECT_EQ(Roundtrip(StdSetAbi<TransmuteAbi<char>>(TransmuteAbi<char>()), set),
+            set);
+}
+
+}  // namespace
+}  // namespace crubit
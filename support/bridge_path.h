@@ -0,0 +1,43 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_PATH_H_
+#define THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_PATH_H_
+
+#include "support/bridge.h"
+
+#ifdef CRUBIT_BRIDGE_ENABLED
+
+#include <cstddef>
+#include <filesystem>  // NOLINT(build/c++17)
+#include <string>
+#include <type_traits>
+
+namespace crubit {
+
+// A Crubit ABI for `std::filesystem::path`, which is bridged to
+// `std::path::PathBuf`, and to `&std::path::Path` for `const path&` parameters.
+// The path is encoded as the `BoxedAbi<std::string>` of its native format,
+// which is the Rust ABI of `std::string`.
+struct PathAbi {
+  // On POSIX, the native format of a path is a byte string, just like an
+  // `OsStr`.
+  static_assert(
+      std::is_same_v<std::filesystem::path::string_type, std::string>,
+      "std::filesystem::path is only bridged where paths are byte strings");
+
+  using Value = std::filesystem::path;
+  static constexpr size_t kSize = BoxedAbi<std::string>::kSize;
+  void Encode(Value value, Encoder& encoder) && {
+    BoxedAbi<std::string>().Encode(value.native(), encoder);
+  }
+  Value Decode(Decoder& decoder) && {
+    return Value(BoxedAbi<std::string>().Decode(decoder));
+  }
+};
+
+}  // namespace crubit
+
+#endif  // CRUBIT_BRIDGE_ENABLED
+#endif  // THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_PATH_H_
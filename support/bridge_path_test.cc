@@ -0,0 +1,38 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "support/bridge_path.h"
+
+#include <filesystem>  // NOLINT(build/c++17)
+#include <string>
+
+#include "gtest/gtest.h"
+#include "support/bridge.h"
+
+namespace crubit {
+namespace {
+
+std::filesystem::path Roundtrip(std::filesystem::path value) {
+  unsigned char buf[PathAbi::kSize];
+  internal::Encode<PathAbi>(PathAbi(), buf, value);
+  return internal::Decode<PathAbi>(PathAbi(), buf);
+}
+
+TEST(BridgePathTest, EncodesNativeString) {
+  unsigned char buf[PathAbi::kSize];
+  internal::Encode<PathAbi>(PathAbi(), buf, "/tmp/a b");
+  EXPECT_EQ(internal::Decode<BoxedAbi<std::string>>(BoxedAbi<std::string>(),
+                                                    buf),
+            "/tmp/a b");
+}
+
+TEST(BridgePathTest, Roundtrip) {
+  EXPECT_EQ(Roundtrip(""), "");
+  EXPECT_EQ(Roundtrip("relative/dir/"), "relative/dir/");
+  // Paths are not required to be valid UTF-8.
+  EXPECT_EQ(Roundtrip("/tmp/\xff\xfe").native(), "/tmp/\xff\xfe");
+}
+
+}  // namespace
+}  // namespace crubit
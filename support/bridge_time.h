@@ -0,0 +1,190 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_TIME_H_
+#define THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_TIME_H_
+
+#include "support/bridge.h"
+
+#ifdef CRUBIT_BRIDGE_ENABLED
+
+#include <algorithm>
+#include <chrono>  // NOLINT(build/c++11)
+#include <cstddef>
+#include <cstdint>
+#include <limits>
+
+#include "absl/time/time.h"
+
+namespace crubit {
+
+// A Crubit ABI for C++ durations, which are bridged to
+// `bridge_rust::SignedDuration`: the whole seconds, rounded towards negative
+// infinity, as an `int64_t`, followed by the remaining nanoseconds as a
+// `uint32_t` in [0, 10^9).
+//
+// `absl::InfiniteDuration()` and `-absl::InfiniteDuration()` are bridged as the
+// largest and the smallest such value. A `std::chrono::duration` saturates to
+// its `max()` or `min()` if its representation cannot hold a bridged value.
+template <typename T>
+struct DurationAbi;
+
+// A Crubit ABI for C++ points in time, which are bridged to
+// `std::time::SystemTime`: the time since the Unix epoch, encoded with
+// `DurationAbi`.
+template <typename T>
+struct TimeAbi;
+
+namespace internal {
+
+inline constexpr int64_t kNanosPerSecond = 1'000'000'000;
+
+// The value of a `bridge_rust::SignedDuration`.
+struct SignedDuration {
+  static constexpr SignedDuration Max() {
+    return {std::numeric_limits<int64_t>::max(), kNanosPerSecond - 1};
+  }
+  static constexpr SignedDuration Min() {
+    return {std::numeric_limits<int64_t>::min(), 0};
+  }
+
+  friend bool operator==(const SignedDuration&,
+                         const SignedDuration&) = default;
+
+  int64_t secs;
+  uint32_t nanos;
+};
+
+inline constexpr size_t kSignedDurationSize =
+    sizeof(int64_t) + sizeof(uint32_t);
+
+inline void EncodeSignedDuration(SignedDuration value, Encoder& encoder) {
+  TransmuteAbi<int64_t>().Encode(value.secs, encoder);
+  TransmuteAbi<uint32_t>().Encode(value.nanos, encoder);
+}
+
+inline SignedDuration DecodeSignedDuration(Decoder& decoder) {
+  int64_t secs = TransmuteAbi<int64_t>().Decode(decoder);
+  uint32_t nanos = TransmuteAbi<uint32_t>().Decode(decoder);
+  return {secs, nanos};
+}
+
+}  // namespace internal
+
+template <>
+struct DurationAbi<absl::Duration> {
+  using Value = absl::Duration;
+  static constexpr size_t kSize = internal::kSignedDurationSize;
+  void Encode(Value value, Encoder& encoder) && {
+    internal::SignedDuration signed_duration;
+    if (value == absl::InfiniteDuration()) {
+      signed_duration = internal::SignedDuration::Max();
+    } else if (value == -absl::InfiniteDuration()) {
+      signed_duration = internal::SignedDuration::Min();
+    } else {
+      // `IDivDuration` truncates towards zero, so the remainder has the sign of
+      // `value`.
+      absl::Duration remainder;
+      int64_t secs = absl::IDivDuration(value, absl::Seconds(1), &remainder);
+      if (remainder < absl::ZeroDuration()) {
+        secs -= 1;
+        remainder += absl::Seconds(1);
+      }
+      signed_duration = {
+          secs, static_cast<uint32_t>(absl::ToInt64Nanoseconds(remainder))};
+    }
+    internal::EncodeSignedDuration(signed_duration, encoder);
+  }
+  Value Decode(Decoder& decoder) && {
+    internal::SignedDuration signed_duration =
+        internal::DecodeSignedDuration(decoder);
+    if (signed_duration == internal::SignedDuration::Max()) {
+      return absl::InfiniteDuration();
+    }
+    if (signed_duration == internal::SignedDuration::Min()) {
+      return -absl::InfiniteDuration();
+    }
+    return absl::Seconds(signed_duration.secs) +
+           absl::Nanoseconds(signed_duration.nanos);
+  }
+};
+
+template <typename Rep, typename Period>
+struct DurationAbi<std::chrono::duration<Rep, Period>> {
+  using Value = std::chrono::duration<Rep, Period>;
+  static constexpr size_t kSize = internal::kSignedDurationSize;
+  void Encode(Value value, Encoder& encoder) && {
+    internal::SignedDuration signed_duration;
+    // Range checks are done in floating point, which can hold any `Value`.
+    LongDoubleSeconds approx = value;
+    if (approx >= LongDoubleSeconds(Seconds::max())) {
+      signed_duration = internal::SignedDuration::Max();
+    } else if (approx <= LongDoubleSeconds(Seconds::min())) {
+      signed_duration = internal::SignedDuration::Min();
+    } else {
+      Seconds secs = std::chrono::floor<Seconds>(value);
+      int64_t nanos =
+          std::chrono::duration_cast<std::chrono::nanoseconds>(value - secs)
+              .count();
+      // A floating point `Rep` may round up to a whole second.
+      signed_duration = {secs.count(),
+                         static_cast<uint32_t>(std::min(
+                             nanos, internal::kNanosPerSecond - 1))};
+    }
+    internal::EncodeSignedDuration(signed_duration, encoder);
+  }
+  Value Decode(Decoder& decoder) && {
+    internal::SignedDuration signed_duration =
+        internal::DecodeSignedDuration(decoder);
+    LongDoubleSeconds approx(
+        static_cast<long double>(signed_duration.secs) +
+        static_cast<long double>(signed_duration.nanos) /
+            internal::kNanosPerSecond);
+    if (approx >= LongDoubleSeconds(Value::max())) {
+      return Value::max();
+    }
+    if (approx <= LongDoubleSeconds(Value::min())) {
+      return Value::min();
+    }
+    return std::chrono::duration_cast<Value>(Seconds(signed_duration.secs)) +
+           std::chrono::duration_cast<Value>(
+               std::chrono::nanoseconds(signed_duration.nanos));
+  }
+
+ private:
+  using Seconds = std::chrono::duration<int64_t>;
+  using LongDoubleSeconds = std::chrono::duration<long double>;
+};
+
+template <>
+struct TimeAbi<absl::Time> {
+  using Value = absl::Time;
+  static constexpr size_t kSize = DurationAbi<absl::Duration>::kSize;
+  void Encode(Value value, Encoder& encoder) && {
+    // `absl::InfiniteFuture()` and `absl::InfinitePast()` are infinitely far
+    // from the epoch.
+    DurationAbi<absl::Duration>().Encode(value - absl::UnixEpoch(), encoder);
+  }
+  Value Decode(Decoder& decoder) && {
+    return absl::UnixEpoch() + DurationAbi<absl::Duration>().Decode(decoder);
+  }
+};
+
+// Since C++20, the epoch of `system_clock` is the Unix epoch.
+template <typename Duration>
+struct TimeAbi<std::chrono::time_point<std::chrono::system_clock, Duration>> {
+  using Value = std::chrono::time_point<std::chrono::system_clock, Duration>;
+  static constexpr size_t kSize = DurationAbi<Duration>::kSize;
+  void Encode(Value value, Encoder& encoder) && {
+    DurationAbi<Duration>().Encode(value.time_since_epoch(), encoder);
+  }
+  Value Decode(Decoder& decoder) && {
+    return Value(DurationAbi<Duration>().Decode(decoder));
+  }
+};
+
+}  // namespace crubit
+
+#endif  // CRUBIT_BRIDGE_ENABLED
+#endif  // THIRD_PARTY_CRUBIT_SUPPORT_BRIDGE_TIME_H_
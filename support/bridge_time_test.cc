@@ -0,0 +1,99 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#include "support/bridge_time.h"
+
+#include <chrono>  // NOLINT(build/c++11)
+
+#include "absl/time/time.h"
+#include "gtest/gtest.h"
+#include "support/bridge.h"
+
+namespace crubit {
+namespace {
+
+// Encodes `value` with `Abi`, and returns the encoded
+// `bridge_rust::SignedDuration`.
+template <typename Abi>
+internal::SignedDuration EncodeToSignedDuration(typename Abi::Value value) {
+  unsigned char buf[Abi::kSize];
+  internal::Encode<Abi>(Abi(), buf, value);
+  Decoder decoder(Abi::kSize, buf);
+  return internal::DecodeSignedDuration(decoder);
+}
+
+template <typename Abi>
+typename Abi::Value Roundtrip(typename Abi::Value value) {
+  unsigned char buf[Abi::kSize];
+  internal::Encode<Abi>(Abi(), buf, value);
+  return internal::Decode<Abi>(Abi(), buf);
+}
+
+TEST(BridgeTimeTest, AbslDuration) {
+  using Abi = DurationAbi<absl::Duration>;
+  EXPECT_EQ(EncodeToSignedDuration<Abi>(absl::Milliseconds(1500)),
+            (internal::SignedDuration{1, 500'000'000}));
+  EXPECT_EQ(EncodeToSignedDuration<Abi>(absl::Milliseconds(-1500)),
+            (internal::SignedDuration{-2, 500'000'000}));
+  EXPECT_EQ(Roundtrip<Abi>(absl::Milliseconds(-1500)),
+            absl::Milliseconds(-1500));
+}
+
+TEST(BridgeTimeTest, AbslInfiniteDuration) {
+  using Abi = DurationAbi<absl::Duration>;
+  EXPECT_EQ(EncodeToSignedDuration<Abi>(absl::InfiniteDuration()),
+            internal::SignedDuration::Max());
+  EXPECT_EQ(EncodeToSignedDuration<Abi>(-absl::InfiniteDuration()),
+            internal::SignedDuration::Min());
+  EXPECT_EQ(Roundtrip<Abi>(absl::InfiniteDuration()),
+            absl::InfiniteDuration());
+  EXPECT_EQ(Roundtrip<Abi>(-absl::InfiniteDuration()),
+            -absl::InfiniteDuration());
+}
+
+TEST(BridgeTimeTest, ChronoDuration) {
+  EXPECT_EQ(EncodeToSignedDuration<DurationAbi<std::chrono::milliseconds>>(
+                std::chrono::milliseconds(-1500)),
+            (internal::SignedDuration{-2, 500'000'000}));
+  EXPECT_EQ(Roundtrip<DurationAbi<std::chrono::milliseconds>>(
+                std::chrono::milliseconds(-1500)),
+            std::chrono::milliseconds(-1500));
+  EXPECT_EQ(Roundtrip<DurationAbi<std::chrono::duration<double>>>(
+                std::chrono::duration<double>(-0.25)),
+            std::chrono::duration<double>(-0.25));
+}
+
+TEST(BridgeTimeTest, ChronoDurationSaturates) {
+  EXPECT_EQ(EncodeToSignedDuration<DurationAbi<std::chrono::hours>>(
+                std::chrono::hours::max()),
+            internal::SignedDuration::Max());
+
+  // `SignedDuration::Max()` doesn't fit in `std::chrono::nanoseconds`.
+  using Abi = DurationAbi<std::chrono::nanoseconds>;
+  unsigned char buf[Abi::kSize];
+  internal::Encode<DurationAbi<absl::Duration>>(
+      DurationAbi<absl::Duration>(), buf, absl::InfiniteDuration());
+  EXPECT_EQ(internal::Decode<Abi>(Abi(), buf), std::chrono::nanoseconds::max());
+}
+
+TEST(BridgeTimeTest, AbslTime) {
+  using Abi = TimeAbi<absl::Time>;
+  absl::Time time = absl::FromUnixMillis(-1500);
+  EXPECT_EQ(EncodeToSignedDuration<Abi>(time),
+            (internal::SignedDuration{-2, 500'000'000}));
+  EXPECT_EQ(Roundtrip<Abi>(time), time);
+  EXPECT_EQ(Roundtrip<Abi>(absl::InfiniteFuture()), absl::InfiniteFuture());
+}
+
+TEST(BridgeTimeTest, SystemClockTimePoint) {
+  using TimePoint = std::chrono::system_clock::time_point;
+  using Abi = TimeAbi<TimePoint>;
+  TimePoint time = std::chrono::system_clock::from_time_t(1'700'000'000);
+  EXPECT_EQ(EncodeToSignedDuration<Abi>(time),
+            (internal::SignedDuration{1'700'000'000, 0}));
+  EXPECT_EQ(Roundtrip<Abi>(time), time);
+}
+
+}  // namespace
+}  // namespace crubit
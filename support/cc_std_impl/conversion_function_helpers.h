@@ -74,6 +74,49 @@ inline void StringCreateCopyInPlace(void* dst, const void* src) {
 inline void StringDestroyInPlace(void* s) {
   reinterpret_cast<std::string*>(s)->~basic_string();
 }
+
+inline size_t StringGetCapacity(const void* s) {
+  return reinterpret_cast<const std::string*>(s)->capacity();
+}
+
+inline size_t StringGetMaxSize(const void* s) {
+  return reinterpret_cast<const std::string*>(s)->max_size();
+}
+
+// Reserves capacity for at least `additional` more bytes than the current size.
+//
+// The new capacity must not exceed `max_size()`.
+inline void StringReserve(void* s, size_t additional) {
+  auto* string = reinterpret_cast<std::string*>(s);
+  string->reserve(string->size() + additional);
+}
+
+inline void StringShrinkToFit(void* s) {
+  reinterpret_cast<std::string*>(s)->shrink_to_fit();
+}
+
+inline void StringClear(void* s) { reinterpret_cast<std::string*>(s)->clear(); }
+
+// The callers of the functions below check `pos` and `count`, so they don't
+// throw `std::out_of_range`.
+
+inline void StringAppend(void* s, const char* buffer, size_t size) {
+  reinterpret_cast<std::string*>(s)->append(buffer, size);
+}
+
+inline void StringInsert(void* s, size_t pos, const char* buffer,
+                         size_t size) {
+  reinterpret_cast<std::string*>(s)->insert(pos, buffer, size);
+}
+
+inline void StringErase(void* s, size_t pos, size_t count) {
+  reinterpret_cast<std::string*>(s)->erase(pos, count);
+}
+
+inline void StringReplace(void* s, size_t pos, size_t count,
+                          const char* buffer, size_t size) {
+  reinterpret_cast<std::string*>(s)->replace(pos, count, buffer, size);
+}
 // std::string helpers end
 
 }  // namespace crubit_cc_std_internal::conversion_function_helpers
@@ -37,6 +37,7 @@ LIBCXX_HEADERS = [
     "cwctype",
     "deque",
     "exception",
+    "filesystem",
     "forward_list",
     "functional",
     "initializer_list",
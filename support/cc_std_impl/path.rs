@@ -0,0 +1,60 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+extern crate std;
+
+use crate::std::{string_wrapper, BoxedCppStringAbi};
+use bridge_rust::{CrubitAbi, Decoder, Encoder};
+use core::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+
+/// The Crubit ABI for C++ `std::filesystem::path`, bridged to [`PathBuf`].
+///
+/// On POSIX, both are byte strings, so a path is specified as the [`BoxedCppStringAbi`] of its
+/// native format. This is the same ABI as [`PathRefAbi`].
+#[cfg(unix)]
+#[derive(Clone, Default)]
+pub struct PathBufAbi;
+
+#[cfg(unix)]
+unsafe impl CrubitAbi for PathBufAbi {
+    type Value = PathBuf;
+
+    const SIZE: usize = BoxedCppStringAbi::SIZE;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        PathRefAbi(PhantomData).encode(&value, encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains a `BoxedCppStringAbi` value.
+        let native = unsafe { BoxedCppStringAbi.decode(decoder) };
+        native.to_os_string().into()
+    }
+}
+
+/// The Crubit ABI for a `const std::filesystem::path&` parameter, bridged to `&'a Path`.
+///
+/// The C++ side receives a temporary copy of the path, so this can only be encoded.
+#[cfg(unix)]
+#[derive(Clone, Default)]
+pub struct PathRefAbi<'a>(pub PhantomData<&'a Path>);
+
+#[cfg(unix)]
+unsafe impl<'a> CrubitAbi for PathRefAbi<'a> {
+    type Value = &'a Path;
+
+    const SIZE: usize = BoxedCppStringAbi::SIZE;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        BoxedCppStringAbi.encode(string_wrapper::from(value.as_os_str().as_bytes()), encoder);
+    }
+
+    unsafe fn decode(self, _decoder: &mut Decoder) -> Self::Value {
+        unreachable!("`const std::filesystem::path&` is never returned to Rust")
+    }
+}
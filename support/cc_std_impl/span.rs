@@ -0,0 +1,258 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// A C++ `std::span<const T>` which is live and immutable for the lifetime `'a`.
+///
+/// This type is similar to `&'a [T]`, and converts to and from it, but is guaranteed to have the
+/// same in-memory layout as C++'s `std::span<const T>`: a data pointer followed by a size. (Rust
+/// does not guarantee the layout of slice references.)
+///
+/// This type is distinct from the `raw_span` type, which does not have a lifetime and cannot be
+/// used directly in safe Rust code.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct span<'a, T> {
+    // Invariant: unless `size` is zero, `data` is non-null, aligned, and valid for reads of `size`
+    // elements for `'a`. (C++ may produce an empty span with a null `data`.)
+    data: *const T,
+    size: usize,
+    phantom_data: PhantomData<&'a [T]>,
+}
+
+impl<T> Clone for span<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for span<'_, T> {}
+
+impl<'a, T> span<'a, T> {
+    /// Returns a Rust slice referring to the span's data.
+    pub fn as_slice(&self) -> &'a [T] {
+        // SAFETY: guaranteed by the `span` invariants.
+        unsafe { &*self.into_raw().as_ptr() }
+    }
+
+    pub fn into_raw(self) -> raw_span<T> {
+        raw_span { data: self.data, size: self.size }
+    }
+}
+
+impl<'a, T> From<&'a [T]> for span<'a, T> {
+    fn from(slice: &'a [T]) -> Self {
+        span { data: slice.as_ptr(), size: slice.len(), phantom_data: PhantomData }
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a [T; N]> for span<'a, T> {
+    fn from(array: &'a [T; N]) -> Self {
+        span::from(array.as_slice())
+    }
+}
+
+impl<T> Deref for span<'_, T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for span<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+/// A C++ `std::span<T>` which is live and exclusively borrowed for the lifetime `'a`.
+///
+/// This type is similar to `&'a mut [T]`, and converts to and from it, but is guaranteed to have
+/// the same in-memory layout as C++'s `std::span<T>`.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct span_mut<'a, T> {
+    // Invariant: unless `size` is zero, `data` is non-null, aligned, and valid for reads and writes
+    // of `size` elements, and not otherwise accessed, for `'a`.
+    data: *mut T,
+    size: usize,
+    phantom_data: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> span_mut<'a, T> {
+    /// Returns a Rust slice referring to the span's data.
+    pub fn into_slice(self) -> &'a mut [T] {
+        // SAFETY: guaranteed by the `span_mut` invariants.
+        unsafe { &mut *self.into_raw().as_mut_ptr() }
+    }
+
+    pub fn into_raw(self) -> raw_span_mut<T> {
+        self.raw()
+    }
+
+    fn raw(&self) -> raw_span_mut<T> {
+        raw_span_mut { data: self.data, size: self.size }
+    }
+}
+
+impl<'a, T> From<&'a mut [T]> for span_mut<'a, T> {
+    fn from(slice: &'a mut [T]) -> Self {
+        span_mut { data: slice.as_mut_ptr(), size: slice.len(), phantom_data: PhantomData }
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a mut [T; N]> for span_mut<'a, T> {
+    fn from(array: &'a mut [T; N]) -> Self {
+        span_mut::from(array.as_mut_slice())
+    }
+}
+
+impl<T> Deref for span_mut<'_, T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        // SAFETY: guaranteed by the `span_mut` invariants.
+        unsafe { &*self.raw().as_mut_ptr() }
+    }
+}
+
+impl<T> DerefMut for span_mut<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: guaranteed by the `span_mut` invariants.
+        unsafe { &mut *self.raw().as_mut_ptr() }
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for span_mut<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A C++ `std::span<const T>` with no lifetime, such as one returned from C++.
+///
+/// Unlike a Rust slice pointer, the data pointer may be null if the span is empty.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct raw_span<T> {
+    data: *const T,
+    size: usize,
+}
+
+impl<T> Clone for raw_span<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for raw_span<T> {}
+
+impl<T> raw_span<T> {
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the data pointer of the span, which may be null.
+    pub fn data(&self) -> *const T {
+        self.data
+    }
+
+    /// Returns a Rust slice pointer with the same data and length. A null data pointer is replaced
+    /// with a dangling one, so that the result can be dereferenced if the span is empty.
+    pub fn as_ptr(&self) -> *const [T] {
+        let data = if self.data.is_null() { NonNull::dangling().as_ptr() } else { self.data };
+        core::ptr::slice_from_raw_parts(data, self.size)
+    }
+
+    /// Returns a Rust slice referring to the span's data.
+    ///
+    /// # Safety
+    ///
+    /// Unless the span is empty, its data must be valid for reads, and not mutated, for `'a`.
+    pub unsafe fn as_slice<'a>(&self) -> &'a [T] {
+        // SAFETY: `as_ptr` is non-null and aligned, and the rest is guaranteed by the caller.
+        unsafe { &*self.as_ptr() }
+    }
+}
+
+impl<T> From<*const [T]> for raw_span<T> {
+    fn from(slice: *const [T]) -> Self {
+        raw_span { data: slice.cast::<T>(), size: slice.len() }
+    }
+}
+
+impl<T> From<&[T]> for raw_span<T> {
+    fn from(slice: &[T]) -> Self {
+        raw_span::from(slice as *const [T])
+    }
+}
+
+/// A C++ `std::span<T>` with no lifetime, such as one returned from C++.
+///
+/// Unlike a Rust slice pointer, the data pointer may be null if the span is empty.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct raw_span_mut<T> {
+    data: *mut T,
+    size: usize,
+}
+
+impl<T> Clone for raw_span_mut<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for raw_span_mut<T> {}
+
+impl<T> raw_span_mut<T> {
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Returns the data pointer of the span, which may be null.
+    pub fn data(&self) -> *mut T {
+        self.data
+    }
+
+    /// Returns a Rust slice pointer with the same data and length. A null data pointer is replaced
+    /// with a dangling one, so that the result can be dereferenced if the span is empty.
+    pub fn as_mut_ptr(&self) -> *mut [T] {
+        let data = if self.data.is_null() { NonNull::dangling().as_ptr() } else { self.data };
+        core::ptr::slice_from_raw_parts_mut(data, self.size)
+    }
+
+    /// Returns a mutable Rust slice referring to the span's data.
+    ///
+    /// # Safety
+    ///
+    /// Unless the span is empty, its data must be valid for reads and writes, and not otherwise
+    /// accessed, for `'a`.
+    pub unsafe fn as_mut_slice<'a>(&self) -> &'a mut [T] {
+        // SAFETY: `as_mut_ptr` is non-null and aligned, and the rest is guaranteed by the caller.
+        unsafe { &mut *self.as_mut_ptr() }
+    }
+}
+
+impl<T> From<*mut [T]> for raw_span_mut<T> {
+    fn from(slice: *mut [T]) -> Self {
+        raw_span_mut { data: slice.cast::<T>(), size: slice.len() }
+    }
+}
+
+impl<T> From<&mut [T]> for raw_span_mut<T> {
+    fn from(slice: &mut [T]) -> Self {
+        raw_span_mut::from(slice as *mut [T])
+    }
+}
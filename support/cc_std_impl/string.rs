@@ -16,7 +16,8 @@ use core::cmp::Eq;
 use core::cmp::PartialEq;
 use core::ffi::c_void;
 use core::mem::{ManuallyDrop, MaybeUninit};
-use core::ops::Deref;
+use core::ops::{Bound, Deref, RangeBounds};
+use core::pin::Pin;
 use core::ptr::NonNull;
 use ctor::{Ctor, CtorNew, FnCtor, Infallible, PinnedDrop, RvalueReference};
 use std::fmt::Display;
@@ -439,6 +440,28 @@ impl<'a> CtorNew<&'a str> for new_string {
     }
 }
 
+/// Constructs a `new_string` with the contents of a Rust `String`.
+///
+/// A `std::string` can't take ownership of the `String`'s buffer, since it must be allocated by
+/// `std::allocator<char>`, so the bytes are copied into a single new allocation of the exact length.
+impl CtorNew<String> for new_string {
+    type CtorType = impl Ctor<Output = Self, Error = Infallible>;
+    type Error = Infallible;
+
+    fn ctor_new(args: String) -> Self::CtorType {
+        // SAFETY: `dest` is a valid, pinned reference to a `new_string`.
+        unsafe {
+            FnCtor::new(move |dest| {
+                conversion_function_helpers::StringCreateFromBufferInPlace(
+                    dest as *mut c_void,
+                    args.as_ptr() as _,
+                    args.len(),
+                );
+            })
+        }
+    }
+}
+
 impl<'a> CtorNew<&'a new_string> for new_string {
     type CtorType = impl Ctor<Output = Self, Error = Infallible> + use<'a>;
     type Error = Infallible;
@@ -528,6 +551,269 @@ impl new_string {
     }
 }
 
+/// In-place mutation, which calls through to the corresponding `std::string` members.
+///
+/// Like the rest of `new_string`, these operate on bytes, and don't require the string to be valid
+/// UTF-8. Indices are byte offsets, and out of bounds indices panic rather than throw.
+impl new_string {
+    /// Returns the number of bytes the string can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        // SAFETY: `self` is a valid `new_string`.
+        unsafe {
+            conversion_function_helpers::StringGetCapacity(self as *const Self as *const c_void)
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds the maximum size of a `std::string`.
+    pub fn reserve(self: Pin<&mut Self>, additional: usize) {
+        // SAFETY: `self` is a valid `new_string`.
+        let max_size = unsafe {
+            conversion_function_helpers::StringGetMaxSize(self.as_ptr() as *const c_void)
+        };
+        match self.len().checked_add(additional) {
+            Some(new_len) if new_len <= max_size => {}
+            _ => panic!("capacity overflow"),
+        }
+        // SAFETY: `self` is a valid `new_string`, and the new capacity doesn't exceed `max_size()`.
+        unsafe {
+            conversion_function_helpers::StringReserve(self.as_mut_ptr() as *mut c_void, additional)
+        }
+    }
+
+    /// Asks the string to reduce its capacity to its length.
+    pub fn shrink_to_fit(self: Pin<&mut Self>) {
+        // SAFETY: `self` is a valid `new_string`.
+        unsafe { conversion_function_helpers::StringShrinkToFit(self.as_mut_ptr() as *mut c_void) }
+    }
+
+    /// Removes all bytes, keeping the capacity.
+    pub fn clear(self: Pin<&mut Self>) {
+        // SAFETY: `self` is a valid `new_string`.
+        unsafe { conversion_function_helpers::StringClear(self.as_mut_ptr() as *mut c_void) }
+    }
+
+    /// Shortens the string to `new_len` bytes. Has no effect if `new_len` is greater than the
+    /// current length.
+    pub fn truncate(self: Pin<&mut Self>, new_len: usize) {
+        let len = self.len();
+        if new_len < len {
+            self.replace_range(new_len..len, b"");
+        }
+    }
+
+    /// Appends a byte.
+    pub fn push(self: Pin<&mut Self>, byte: u8) {
+        self.extend_from_slice(&[byte]);
+    }
+
+    /// Appends the UTF-8 encoding of `s`.
+    pub fn push_str(self: Pin<&mut Self>, s: &str) {
+        self.extend_from_slice(s.as_bytes());
+    }
+
+    /// Appends `bytes`.
+    pub fn extend_from_slice(self: Pin<&mut Self>, bytes: &[u8]) {
+        // SAFETY: `self` is a valid `new_string`, and `bytes` is a valid buffer, which can't alias
+        // the string's data while the string is mutably borrowed.
+        unsafe {
+            conversion_function_helpers::StringAppend(
+                self.as_mut_ptr() as *mut c_void,
+                bytes.as_ptr() as _,
+                bytes.len(),
+            )
+        }
+    }
+
+    /// Removes the last byte and returns it, or returns `None` if the string is empty.
+    pub fn pop(self: Pin<&mut Self>) -> Option<u8> {
+        let byte = *self.last()?;
+        let len = self.len();
+        self.truncate(len - 1);
+        Some(byte)
+    }
+
+    /// Inserts a byte at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the string's length.
+    pub fn insert(self: Pin<&mut Self>, index: usize, byte: u8) {
+        self.insert_slice(index, &[byte]);
+    }
+
+    /// Inserts the UTF-8 encoding of `s` at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the string's length.
+    pub fn insert_str(self: Pin<&mut Self>, index: usize, s: &str) {
+        self.insert_slice(index, s.as_bytes());
+    }
+
+    /// Inserts `bytes` at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the string's length.
+    pub fn insert_slice(self: Pin<&mut Self>, index: usize, bytes: &[u8]) {
+        let len = self.len();
+        assert!(index <= len, "insertion index (is {index}) should be <= len (is {len})");
+        // SAFETY: `self` is a valid `new_string`, `index` is in bounds, and `bytes` is a valid
+        // buffer, which can't alias the string's data while the string is mutably borrowed.
+        unsafe {
+            conversion_function_helpers::StringInsert(
+                self.as_mut_ptr() as *mut c_void,
+                index,
+                bytes.as_ptr() as _,
+                bytes.len(),
+            )
+        }
+    }
+
+    /// Removes the byte at `index` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(self: Pin<&mut Self>, index: usize) -> u8 {
+        let len = self.len();
+        assert!(index < len, "removal index (is {index}) should be < len (is {len})");
+        let byte = self[index];
+        self.replace_range(index..=index, b"");
+        byte
+    }
+
+    /// Replaces the bytes in `range` with `replace_with`, which need not have the same length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or out of bounds.
+    pub fn replace_range(
+        self: Pin<&mut Self>,
+        range: impl RangeBounds<usize>,
+        replace_with: &[u8],
+    ) {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => {
+                start.checked_add(1).expect("attempted to index string from after usize::MAX")
+            }
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => {
+                end.checked_add(1).expect("attempted to index string up to usize::MAX")
+            }
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end, "range start index {start} should be <= range end index {end}");
+        assert!(end <= len, "range end index {end} out of range for string of length {len}");
+        // SAFETY: `self` is a valid `new_string`, the range is in bounds, and `replace_with` is a
+        // valid buffer, which can't alias the string's data while the string is mutably borrowed.
+        unsafe {
+            if replace_with.is_empty() {
+                conversion_function_helpers::StringErase(
+                    self.as_mut_ptr() as *mut c_void,
+                    start,
+                    end - start,
+                )
+            } else {
+                conversion_function_helpers::StringReplace(
+                    self.as_mut_ptr() as *mut c_void,
+                    start,
+                    end - start,
+                    replace_with.as_ptr() as _,
+                    replace_with.len(),
+                )
+            }
+        }
+    }
+}
+
+/// Appends bytes to a `new_string` through a fixed-size buffer, rather than calling into C++ for
+/// every byte.
+struct ChunkedAppender<'a> {
+    string: Pin<&'a mut new_string>,
+    chunk: [u8; 256],
+    len: usize,
+}
+
+impl<'a> ChunkedAppender<'a> {
+    fn new(string: Pin<&'a mut new_string>) -> Self {
+        ChunkedAppender { string, chunk: [0; 256], len: 0 }
+    }
+
+    fn append(&mut self, bytes: &[u8]) {
+        if self.len + bytes.len() > self.chunk.len() {
+            self.flush();
+        }
+        if bytes.len() > self.chunk.len() {
+            self.string.as_mut().extend_from_slice(bytes);
+        } else {
+            self.chunk[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        }
+    }
+
+    fn flush(&mut self) {
+        self.string.as_mut().extend_from_slice(&self.chunk[..self.len]);
+        self.len = 0;
+    }
+}
+
+impl Extend<u8> for Pin<&mut new_string> {
+    fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.as_mut().reserve(iter.size_hint().0);
+        let mut appender = ChunkedAppender::new(self.as_mut());
+        for byte in iter {
+            appender.append(&[byte]);
+        }
+        appender.flush();
+    }
+}
+
+impl<'a> Extend<&'a u8> for Pin<&mut new_string> {
+    fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl Extend<char> for Pin<&mut new_string> {
+    fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.as_mut().reserve(iter.size_hint().0);
+        let mut appender = ChunkedAppender::new(self.as_mut());
+        for c in iter {
+            appender.append(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        appender.flush();
+    }
+}
+
+impl<'a> Extend<&'a str> for Pin<&mut new_string> {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        let mut appender = ChunkedAppender::new(self.as_mut());
+        for s in iter {
+            appender.append(s.as_bytes());
+        }
+        appender.flush();
+    }
+}
+
+impl core::fmt::Write for Pin<&mut new_string> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.as_mut().push_str(s);
+        Ok(())
+    }
+}
+
 impl Deref for new_string {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
@@ -203,3 +203,81 @@ fn test_new_string_debug() {
     let s_invalid = emplace!(new_string::ctor_new(b"hello \xffworld" as &[u8]));
     expect_eq!(format!("{:?}", s_invalid), "\"hello \\xffworld\"");
 }
+
+#[gtest]
+fn test_new_string_push_and_insert() {
+    let mut s = emplace!(new_string::ctor_new("hello"));
+    s.as_mut().push(b'!');
+    s.as_mut().push_str(" world");
+    s.as_mut().insert(0, b'>');
+    s.as_mut().insert_str(1, " ");
+    s.as_mut().extend_from_slice(b"\xff");
+    expect_eq!(s.as_slice(), b"> hello! world\xff");
+}
+
+#[gtest]
+fn test_new_string_remove() {
+    let mut s = emplace!(new_string::ctor_new("hello, world"));
+    expect_eq!(s.as_mut().remove(0), b'h');
+    expect_eq!(s.as_mut().pop(), Some(b'd'));
+    s.as_mut().replace_range(4..6, b"!");
+    expect_eq!(s.as_slice(), b"ello! worl");
+    s.as_mut().truncate(4);
+    expect_eq!(s.as_slice(), b"ello");
+    s.as_mut().truncate(100);
+    expect_eq!(s.as_slice(), b"ello");
+    s.as_mut().clear();
+    expect_eq!(s.as_mut().pop(), None);
+}
+
+#[gtest]
+fn test_new_string_replace_range_grows_past_sso() {
+    let mut s = emplace!(new_string::ctor_new("<>"));
+    s.as_mut().replace_range(1..1, b"A super longggggggggggggggggggggg non sso string");
+    expect_eq!(s.as_slice(), b"<A super longggggggggggggggggggggg non sso string>");
+}
+
+#[test]
+#[should_panic(expected = "range end index 6 out of range for string of length 5")]
+fn test_new_string_replace_range_out_of_bounds() {
+    let mut s = emplace!(new_string::ctor_new("hello"));
+    s.as_mut().replace_range(..=5, b"");
+}
+
+#[gtest]
+fn test_new_string_reserve() {
+    let mut s = emplace!(new_string::ctor_new(()));
+    s.as_mut().reserve(100);
+    expect_that!(s.capacity(), googletest::matchers::ge(100));
+    s.as_mut().shrink_to_fit();
+    expect_eq!(s.len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn test_new_string_reserve_overflow() {
+    let mut s = emplace!(new_string::ctor_new("hello"));
+    s.as_mut().reserve(usize::MAX);
+}
+
+#[gtest]
+fn test_new_string_extend_and_write() {
+    use core::fmt::Write;
+    let mut s = emplace!(new_string::ctor_new(()));
+    let mut s = s.as_mut();
+    s.extend("ab".chars());
+    s.extend(b"cd");
+    s.extend(["é", "f"]);
+    write!(s, "{}", 42).unwrap();
+    expect_eq!(s.to_str().unwrap(), "abcdéf42");
+
+    s.as_mut().clear();
+    s.extend(std::iter::repeat_n(b'x', 1000));
+    expect_eq!(s.as_slice(), &[b'x'; 1000][..]);
+}
+
+#[gtest]
+fn test_new_string_from_string() {
+    let s = emplace!(new_string::ctor_new(String::from("A string")));
+    expect_eq!(s.as_slice(), b"A string");
+}
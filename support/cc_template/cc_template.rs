@@ -27,3 +27,29 @@ pub fn cc_template(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .unwrap_or_else(|err| err.into_compile_error())
         .into()
 }
+
+/// The `cc_template_fn!` macro tells the C++ / Rust interop tooling to
+/// instantiate the specified C++ function template with the given explicit
+/// template arguments. The tooling will generate bindings (and a C++ thunk) for
+/// the specialization, and the macro will expand to a path to the Rust function
+/// calling it.
+///
+/// Example:
+///   Consider the following snippet of Rust code:
+///   ```rust
+///     let s = cc_template_fn!(absl::StrCat<int, std::string>)(&a, &b);
+///   ```
+///   The C++ / Rust interop tooling will detect the use of the
+///   `cc_template_fn!` macro with `absl::StrCat<int, std::string>` as an
+///   argument. The tooling will generate bindings for the specialization and
+///   the macro will expand to something like:
+///   ```rust
+///     let s = __cc_template_instantiations_rs_api::__CcTemplateInst_ZN4absl6StrCat...(
+///         &a, &b);
+///   ```
+#[proc_macro]
+pub fn cc_template_fn(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    cc_template_impl::to_private_fn_path(input.into())
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
@@ -15,6 +15,14 @@ pub fn to_private_struct_path(input: TokenStream) -> Result<TokenStream, syn::Er
     get_instantiation_struct_name(input, instantiations)
 }
 
+/// Function template specializations requested by `cc_template_fn!` share the
+/// instantiations map with class template specializations requested by
+/// `cc_template!`: the C++ spelling of a specialization is enough to tell them
+/// apart.
+pub fn to_private_fn_path(input: TokenStream) -> Result<TokenStream, syn::Error> {
+    to_private_struct_path(input)
+}
+
 fn validate_user_input(_input: &TokenStream) -> Result<(), syn::Error> {
     // TODO(b/228299446): actually validate the user input and show useful error
     // messages
@@ -158,6 +166,27 @@ mod tests {
         );
     }
 
+    #[gtest]
+    fn test_successful_fn_expansion() {
+        let path =
+            Path::join(Path::new(&env::var("TEST_TMPDIR").unwrap()), "fn_instantiations.json");
+        let key = "absl::StrCat<int,std::string>";
+        let value = "__CcTemplateInst_ZN4absl6StrCatIiNSt3__112basic_stringIcEEEE";
+        std::fs::write(&path, serde_json::to_string(&hashmap! {key => value}).unwrap()).unwrap();
+        unsafe {
+            env::set_var("CRUBIT_INSTANTIATIONS_FILE", &path);
+        }
+
+        let expanded = to_private_fn_path(quote! { absl::StrCat<int, std::string> }).unwrap();
+        assert_eq!(
+            expanded.to_string(),
+            quote! {
+                __cc_template_instantiations_rs_api::__CcTemplateInst_ZN4absl6StrCatIiNSt3__112basic_stringIcEEEE
+            }
+            .to_string()
+        );
+    }
+
     #[gtest]
     fn test_parsing_valid_cc_instantiations() {
         validate_user_input(&quote! {vector<bool>}).unwrap();
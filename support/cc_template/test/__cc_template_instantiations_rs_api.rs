@@ -11,3 +11,7 @@ impl __CcTemplateInst_my_namespace_my_template_my_arg {
         Self { value }
     }
 }
+
+pub(crate) fn __CcTemplateInst_my_namespace_my_function_template_my_arg(value: usize) -> usize {
+    value * 2
+}
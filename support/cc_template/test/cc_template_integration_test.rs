@@ -4,16 +4,16 @@
 
 #![cfg(test)]
 
-use cc_template::cc_template;
+use cc_template::{cc_template, cc_template_fn};
 use googletest::gtest;
 
-/// Test the `cc_template!` macro with:
+/// Test the `cc_template!` and `cc_template_fn!` macros with:
 /// * the JSON file `__cc_template_instantiations.json` (the environment
 ///   variable is configured in the BUILD file)
 /// * `__cc_template_instantiations_rs_api.rs` as the "generated" Rust bindings
 ///   file
 
-#[allow(non_camel_case_types)]
+#[allow(non_camel_case_types, non_snake_case)]
 mod __cc_template_instantiations_rs_api;
 
 #[gtest]
@@ -21,3 +21,9 @@ fn test_in_mocked_context() {
     let x = <cc_template!(my_namespace::MyTemplate<MyArg>)>::new(42);
     assert!(x.value == 42);
 }
+
+#[gtest]
+fn test_fn_in_mocked_context() {
+    let x = cc_template_fn!(my_namespace::my_function_template<MyArg>)(21);
+    assert!(x == 42);
+}
@@ -11,6 +11,7 @@ use proc_macro2::Span;
 use quote::quote;
 use std::collections::{hash_map::Entry, HashMap};
 use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 use syn::token;
 use syn::{parse_macro_input, Ident, LitStr};
 
@@ -434,3 +435,39 @@ pub fn do_not_bind(attribute: TokenStream, input: TokenStream) -> TokenStream {
         key_value_to_doc_comment("do_not_bind", "")
     })
 }
+
+/// Enables Crubit features for a single Rust item, in addition to the features of its crate.
+///
+/// The annotation accepts one or more feature names, as used by the `--crate-feature` flag (e.g.
+/// `"experimental"`). The features also apply to the items nested in the annotated item, such as
+/// the methods of an annotated `impl` block.
+///
+/// This allows trying out experimental bindings for a few items, without enabling the feature for
+/// the whole crate.
+///
+/// Example:
+///
+/// ```rs
+/// #[crubit_annotate::feature("experimental")]
+/// pub fn my_function() -> (i32, i32) {...}
+/// ```
+#[proc_macro_attribute]
+pub fn feature(attribute: TokenStream, input: TokenStream) -> TokenStream {
+    make_prefix_for(input, || {
+        let parser = Punctuated::<LitStr, token::Comma>::parse_terminated;
+        let features = parse_macro_input!(attribute with parser);
+        if features.is_empty() {
+            return TokenStream::from(
+                syn::Error::new(
+                    Span::call_site(),
+                    "The `feature` annotation requires at least one feature name.",
+                )
+                .into_compile_error(),
+            );
+        }
+        features
+            .iter()
+            .map(|feature| key_value_to_doc_comment("feature", &feature.value()))
+            .collect()
+    })
+}
@@ -6,6 +6,11 @@ use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
 use core::ptr;
 
+mod collections;
+pub use collections::{BTreeMapAbi, BTreeSetAbi, HashMapAbi, HashSetAbi};
+mod time;
+pub use time::{DurationAbi, DurationOutOfRangeError, SignedDuration, SystemTimeAbi};
+
 /// A mutually understood ABI for sending bridge types between Rust and C++.
 ///
 /// Bridging values between Rust and C++ is typically done by breaking down values into their
@@ -358,6 +363,42 @@ unsafe impl<A: CrubitAbi> CrubitAbi for OptionAbi<A> {
     }
 }
 
+/// A [`CrubitAbi`] for transferring ownership of a `Box<T>`, which is encoded as the raw pointer
+/// returned by [`Box::into_raw`].
+///
+/// This is bridged to `rs_std::Box<T>` in C++, which is a `std::unique_ptr` whose deleter drops the
+/// `Box` again.
+pub struct BoxAbi<T>(pub PhantomData<T>);
+
+impl<T> Default for BoxAbi<T> {
+    fn default() -> Self {
+        BoxAbi(PhantomData)
+    }
+}
+
+impl<T> Clone for BoxAbi<T> {
+    fn clone(&self) -> Self {
+        BoxAbi(PhantomData)
+    }
+}
+
+// SAFETY: The ABI contract for `BoxAbi<T>` is that the value is encoded as a non-null `*mut T`,
+// which owns a `T` allocated by the Rust global allocator with the layout of `T`.
+unsafe impl<T> CrubitAbi for BoxAbi<T> {
+    type Value = Box<T>;
+
+    const SIZE: usize = mem::size_of::<*mut T>();
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        transmute_abi().encode(Box::into_raw(value), encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains a pointer which owns a `Box<T>`.
+        unsafe { Box::from_raw(transmute_abi::<*mut T>().decode(decoder)) }
+    }
+}
+
 /// Internal functions and types for Crubit generated code.
 #[doc(hidden)]
 pub mod internal {
@@ -456,6 +497,21 @@ mod tests {
         expect_eq!(value, original);
     }
 
+    #[gtest]
+    fn test_encode_decode_box() {
+        type Abi = BoxAbi<String>;
+
+        // SAFETY: the buffer contains a value encoded as Abi.
+        let value = unsafe {
+            internal::decode::<Abi>(
+                Abi::default(),
+                unstable_encode!(@ Abi::default(), Abi, Box::new("hello".to_string())).as_ptr()
+                    as *const u8,
+            )
+        };
+        expect_eq!(*value, "hello");
+    }
+
     #[gtest]
     fn test_encode_decode_stuff() {
         type Abi = (
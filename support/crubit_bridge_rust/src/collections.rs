@@ -0,0 +1,250 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Bridges for standard library collections: `HashMap`, `HashSet`, `BTreeMap` and `BTreeSet`.
+//!
+//! A collection is converted element by element, so the Rust and C++ containers don't need to
+//! share a layout. The ABI of a collection with `len` elements, each encoded with an element ABI
+//! `A`, is:
+//!
+//! * `len`, as a `usize`, followed by
+//! * a pointer to a `malloc`ed buffer of `len * A::SIZE` bytes, which contains the elements
+//!   encoded with `A`. The buffer is null if it would be empty.
+//!
+//! The decoding side takes ownership of the buffer, and releases it with `free`.
+
+use crate::{transmute_abi, CrubitAbi, Decoder, Encoder};
+use core::ffi::c_void;
+use core::hash::Hash;
+use core::ptr;
+use std::alloc::{handle_alloc_error, Layout};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+// The C++ side frees buffers encoded by Rust, and vice versa, so both must use the C allocator.
+unsafe extern "C" {
+    fn malloc(size: usize) -> *mut c_void;
+    fn free(ptr: *mut c_void);
+}
+
+const COLLECTION_SIZE: usize = size_of::<usize>() + size_of::<*mut u8>();
+
+/// Encodes `elements` with the element ABI `abi`, using the ABI described in the module docs.
+fn encode_elements<A, I>(abi: A, elements: I, encoder: &mut Encoder)
+where
+    A: CrubitAbi + Clone,
+    I: ExactSizeIterator<Item = A::Value>,
+{
+    let len = elements.len();
+    let size = len.checked_mul(A::SIZE).expect("collection is too large to bridge");
+    let buf = if size == 0 {
+        ptr::null_mut()
+    } else {
+        // SAFETY: `size` is non-zero.
+        let buf = unsafe { malloc(size) }.cast::<u8>();
+        if buf.is_null() {
+            handle_alloc_error(Layout::array::<u8>(size).unwrap());
+        }
+        buf
+    };
+    let mut element_encoder = Encoder { remaining_bytes: size, buf };
+    for element in elements {
+        abi.clone().encode(element, &mut element_encoder);
+    }
+    transmute_abi().encode(len, encoder);
+    transmute_abi().encode(buf, encoder);
+}
+
+/// Decodes the elements of a collection encoded with the element ABI `abi`, using the ABI
+/// described in the module docs.
+///
+/// # Safety
+///
+/// The decoder's current position must contain a collection encoded with the element ABI `abi`.
+unsafe fn decode_elements<A, C>(abi: A, decoder: &mut Decoder) -> C
+where
+    A: CrubitAbi + Clone,
+    C: FromIterator<A::Value>,
+{
+    // SAFETY: the caller guarantees that the buffer contains a length and a pointer.
+    let (len, buf): (usize, *mut u8) =
+        unsafe { (transmute_abi().decode(decoder), transmute_abi().decode(decoder)) };
+    let mut element_decoder = Decoder { remaining_bytes: len * A::SIZE, buf };
+    let collection = (0..len)
+        // SAFETY: the caller guarantees that `buf` contains `len` elements encoded with `abi`.
+        .map(|_| unsafe { abi.clone().decode(&mut element_decoder) })
+        .collect();
+    // SAFETY: the buffer was allocated with `malloc` (or is null), and ownership was transferred
+    // to us.
+    unsafe { free(buf.cast()) };
+    collection
+}
+
+/// A [`CrubitAbi`] for a [`HashMap`], whose keys are encoded with `KA` and values with `VA`.
+///
+/// This is bridged to `absl::flat_hash_map` or `std::unordered_map`.
+#[derive(Clone, Default)]
+pub struct HashMapAbi<KA, VA>(pub KA, pub VA);
+
+// SAFETY: The ABI contract for `HashMapAbi` is described in the module docs, where each element
+// is a `(KA, VA)` pair, matching `crubit::MapAbi` in C++.
+unsafe impl<KA, VA> CrubitAbi for HashMapAbi<KA, VA>
+where
+    KA: CrubitAbi + Clone,
+    VA: CrubitAbi + Clone,
+    KA::Value: Eq + Hash,
+{
+    type Value = HashMap<KA::Value, VA::Value>;
+
+    const SIZE: usize = COLLECTION_SIZE;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        encode_elements((self.0, self.1), value.into_iter(), encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains a map encoded with this ABI.
+        unsafe { decode_elements((self.0, self.1), decoder) }
+    }
+}
+
+/// A [`CrubitAbi`] for a [`BTreeMap`], whose keys are encoded with `KA` and values with `VA`.
+///
+/// This is bridged to `std::map`.
+#[derive(Clone, Default)]
+pub struct BTreeMapAbi<KA, VA>(pub KA, pub VA);
+
+// SAFETY: The ABI contract for `BTreeMapAbi` is described in the module docs, where each element
+// is a `(KA, VA)` pair, matching `crubit::MapAbi` in C++.
+unsafe impl<KA, VA> CrubitAbi for BTreeMapAbi<KA, VA>
+where
+    KA: CrubitAbi + Clone,
+    VA: CrubitAbi + Clone,
+    KA::Value: Ord,
+{
+    type Value = BTreeMap<KA::Value, VA::Value>;
+
+    const SIZE: usize = COLLECTION_SIZE;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        encode_elements((self.0, self.1), value.into_iter(), encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains a map encoded with this ABI.
+        unsafe { decode_elements((self.0, self.1), decoder) }
+    }
+}
+
+/// A [`CrubitAbi`] for a [`HashSet`], whose elements are encoded with `A`.
+///
+/// This is bridged to `absl::flat_hash_set` or `std::unordered_set`.
+#[derive(Clone, Default)]
+pub struct HashSetAbi<A>(pub A);
+
+// SAFETY: The ABI contract for `HashSetAbi` is described in the module docs, matching
+// `crubit::SetAbi` in C++.
+unsafe impl<A> CrubitAbi for HashSetAbi<A>
+where
+    A: CrubitAbi + Clone,
+    A::Value: Eq + Hash,
+{
+    type Value = HashSet<A::Value>;
+
+    const SIZE: usize = COLLECTION_SIZE;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        encode_elements(self.0, value.into_iter(), encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains a set encoded with this ABI.
+        unsafe { decode_elements(self.0, decoder) }
+    }
+}
+
+/// A [`CrubitAbi`] for a [`BTreeSet`], whose elements are encoded with `A`.
+///
+/// This is bridged to `std::set`.
+#[derive(Clone, Default)]
+pub struct BTreeSetAbi<A>(pub A);
+
+// SAFETY: The ABI contract for `BTreeSetAbi` is described in the module docs, matching
+// `crubit::SetAbi` in C++.
+unsafe impl<A> CrubitAbi for BTreeSetAbi<A>
+where
+    A: CrubitAbi + Clone,
+    A::Value: Ord,
+{
+    type Value = BTreeSet<A::Value>;
+
+    const SIZE: usize = COLLECTION_SIZE;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        encode_elements(self.0, value.into_iter(), encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains a set encoded with this ABI.
+        unsafe { decode_elements(self.0, decoder) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{internal, transmute_abi, unstable_encode, OptionAbi, TransmuteAbi};
+    use googletest::expect_eq;
+    use googletest::gtest;
+
+    #[gtest]
+    fn test_encode_decode_hash_map() {
+        type Abi = HashMapAbi<TransmuteAbi<u32>, OptionAbi<TransmuteAbi<i64>>>;
+
+        let original = HashMap::from([(1, Some(-1)), (2, None), (3, Some(3))]);
+
+        // SAFETY: the buffer contains a value encoded as Abi.
+        let value = unsafe {
+            internal::decode::<Abi>(
+                Abi::default(),
+                unstable_encode!(@ Abi::default(), Abi, original.clone()).as_ptr() as *const u8,
+            )
+        };
+        expect_eq!(value, original);
+    }
+
+    #[gtest]
+    fn test_encode_decode_empty_btree_map() {
+        type Abi = BTreeMapAbi<TransmuteAbi<u8>, TransmuteAbi<u8>>;
+
+        // SAFETY: the buffer contains a value encoded as Abi.
+        let value = unsafe {
+            internal::decode::<Abi>(
+                Abi::default(),
+                unstable_encode!(@ Abi::default(), Abi, BTreeMap::new()).as_ptr() as *const u8,
+            )
+        };
+        expect_eq!(value, BTreeMap::new());
+    }
+
+    #[gtest]
+    fn test_encode_decode_sets() {
+        type Abi = (HashSetAbi<TransmuteAbi<i16>>, BTreeSetAbi<TransmuteAbi<char>>);
+
+        let original = (HashSet::from([-1, 0, 1]), BTreeSet::from(['a', 'b']));
+
+        // SAFETY: the buffer contains a value encoded as Abi.
+        let value = unsafe {
+            internal::decode::<Abi>(
+                Abi::default(),
+                unstable_encode!(@
+                    (HashSetAbi(transmute_abi()), BTreeSetAbi(transmute_abi())),
+                    Abi,
+                    original.clone()
+                )
+                .as_ptr() as *const u8,
+            )
+        };
+        expect_eq!(value, original);
+    }
+}
@@ -0,0 +1,308 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+//! Bridges for time vocabulary types: `absl::Duration`, `absl::Time`, `std::chrono::duration`,
+//! and `std::chrono::time_point<std::chrono::system_clock>`.
+
+use crate::{transmute_abi, CrubitAbi, Decoder, Encoder};
+use core::fmt;
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// A signed span of time, with nanosecond precision.
+///
+/// This is the Rust type of C++ durations (`absl::Duration` and `std::chrono::duration`), which,
+/// unlike [`Duration`], can be negative. Conversions to and from [`Duration`] are explicit about
+/// negative and out of range values:
+///
+/// * [`Duration::try_from`] fails for negative durations, and
+///   [`SignedDuration::saturating_to_duration`] clamps them to zero.
+/// * [`SignedDuration::try_from`] fails for durations longer than [`SignedDuration::MAX`], and
+///   [`SignedDuration::saturating_from_duration`] clamps them to it.
+///
+/// `absl::InfiniteDuration()` and `-absl::InfiniteDuration()` are bridged as
+/// [`SignedDuration::MAX`] and [`SignedDuration::MIN`], and vice versa. A `std::chrono::duration`
+/// whose representation cannot hold a bridged value saturates to its `max()` or `min()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignedDuration {
+    // The duration is `secs + nanos / 10^9` seconds, with `nanos < 10^9`: like `absl::Duration`,
+    // the fractional part is always non-negative, so e.g. -1.5s is `{ secs: -2, nanos: 5 * 10^8 }`.
+    secs: i64,
+    nanos: u32,
+}
+
+impl SignedDuration {
+    pub const ZERO: SignedDuration = SignedDuration { secs: 0, nanos: 0 };
+
+    /// The longest duration, which is bridged to `absl::InfiniteDuration()`.
+    pub const MAX: SignedDuration = SignedDuration { secs: i64::MAX, nanos: NANOS_PER_SEC - 1 };
+
+    /// The most negative duration, which is bridged to `-absl::InfiniteDuration()`.
+    pub const MIN: SignedDuration = SignedDuration { secs: i64::MIN, nanos: 0 };
+
+    /// Creates a duration of `secs + nanos / 10^9` seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if carrying whole seconds from `nanos` overflows `secs`.
+    pub const fn new(secs: i64, nanos: u32) -> SignedDuration {
+        let Some(secs) = secs.checked_add((nanos / NANOS_PER_SEC) as i64) else {
+            panic!("overflow in SignedDuration::new");
+        };
+        SignedDuration { secs, nanos: nanos % NANOS_PER_SEC }
+    }
+
+    /// Returns the whole number of seconds, rounded towards negative infinity.
+    pub const fn secs(&self) -> i64 {
+        self.secs
+    }
+
+    /// Returns the non-negative nanoseconds to add to [`SignedDuration::secs`].
+    pub const fn nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    pub const fn is_negative(&self) -> bool {
+        self.secs < 0
+    }
+
+    /// Returns the absolute value of this duration.
+    pub const fn unsigned_abs(&self) -> Duration {
+        if self.secs >= 0 || self.nanos == 0 {
+            Duration::new(self.secs.unsigned_abs(), self.nanos)
+        } else {
+            // -(secs + nanos) = (-secs - 1) + (1s - nanos), where -secs - 1 cannot overflow.
+            Duration::new((-(self.secs + 1)) as u64, NANOS_PER_SEC - self.nanos)
+        }
+    }
+
+    /// Converts a [`Duration`], clamping durations longer than [`SignedDuration::MAX`].
+    pub fn saturating_from_duration(duration: Duration) -> SignedDuration {
+        SignedDuration::try_from(duration).unwrap_or(SignedDuration::MAX)
+    }
+
+    /// Converts to a [`Duration`], clamping negative durations to zero.
+    pub fn saturating_to_duration(self) -> Duration {
+        Duration::try_from(self).unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns `-duration`, clamping durations longer than `-SignedDuration::MIN`.
+    fn saturating_neg_from_duration(duration: Duration) -> SignedDuration {
+        // -(secs + nanos) = (-secs - 1) + (1s - nanos)
+        let (secs, nanos) = match duration.subsec_nanos() {
+            0 => (Some(duration.as_secs()), 0),
+            nanos => (duration.as_secs().checked_add(1), NANOS_PER_SEC - nanos),
+        };
+        match secs.and_then(|secs| 0i64.checked_sub_unsigned(secs)) {
+            Some(secs) => SignedDuration { secs, nanos },
+            None => SignedDuration::MIN,
+        }
+    }
+}
+
+/// The error returned when a duration doesn't fit in the target type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DurationOutOfRangeError(());
+
+impl fmt::Display for DurationOutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("duration is out of range")
+    }
+}
+
+impl std::error::Error for DurationOutOfRangeError {}
+
+impl TryFrom<Duration> for SignedDuration {
+    type Error = DurationOutOfRangeError;
+
+    fn try_from(duration: Duration) -> Result<SignedDuration, DurationOutOfRangeError> {
+        let secs = i64::try_from(duration.as_secs()).map_err(|_| DurationOutOfRangeError(()))?;
+        Ok(SignedDuration { secs, nanos: duration.subsec_nanos() })
+    }
+}
+
+impl TryFrom<SignedDuration> for Duration {
+    type Error = DurationOutOfRangeError;
+
+    /// Fails if the duration is negative.
+    fn try_from(duration: SignedDuration) -> Result<Duration, DurationOutOfRangeError> {
+        if duration.is_negative() {
+            return Err(DurationOutOfRangeError(()));
+        }
+        Ok(duration.unsigned_abs())
+    }
+}
+
+/// A [`CrubitAbi`] for bridging C++ durations as [`SignedDuration`]: the seconds as an `i64`,
+/// followed by the nanoseconds as a `u32`.
+#[derive(Clone, Default)]
+pub struct DurationAbi;
+
+// SAFETY: The ABI contract for `DurationAbi` is that the value is encoded as an i64 followed by a
+// u32, which matches `DurationAbi` in `bridge_time.h`.
+unsafe impl CrubitAbi for DurationAbi {
+    type Value = SignedDuration;
+
+    const SIZE: usize = core::mem::size_of::<i64>() + core::mem::size_of::<u32>();
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        transmute_abi().encode(value.secs, encoder);
+        transmute_abi().encode(value.nanos, encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains an i64 followed by a u32.
+        let (secs, nanos) =
+            unsafe { (transmute_abi().decode(decoder), transmute_abi().decode(decoder)) };
+        debug_assert!(nanos < NANOS_PER_SEC, "C++ sent a denormalized duration");
+        SignedDuration { secs, nanos }
+    }
+}
+
+/// A [`CrubitAbi`] for bridging C++ points in time (`absl::Time` and
+/// `std::chrono::time_point<std::chrono::system_clock>`) as [`SystemTime`]. The value is encoded
+/// as its [`DurationAbi`] distance from the Unix epoch.
+///
+/// Decoding a time that [`SystemTime`] cannot represent, like `absl::InfinitePast()` on some
+/// platforms, saturates to the earliest or latest [`SystemTime`]. Encoding a [`SystemTime`] further
+/// than [`SignedDuration::MAX`] from the epoch saturates too.
+#[derive(Clone, Default)]
+pub struct SystemTimeAbi;
+
+// SAFETY: The ABI contract for `SystemTimeAbi` is that the value is encoded with `DurationAbi`,
+// which matches `TimeAbi` in `bridge_time.h`.
+unsafe impl CrubitAbi for SystemTimeAbi {
+    type Value = SystemTime;
+
+    const SIZE: usize = DurationAbi::SIZE;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        let since_epoch = match value.duration_since(UNIX_EPOCH) {
+            Ok(after) => SignedDuration::saturating_from_duration(after),
+            Err(before) => SignedDuration::saturating_neg_from_duration(before.duration()),
+        };
+        DurationAbi.encode(since_epoch, encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: the caller guarantees that the buffer contains a `DurationAbi` value.
+        let since_epoch = unsafe { DurationAbi.decode(decoder) };
+        let time = if since_epoch.is_negative() {
+            UNIX_EPOCH.checked_sub(since_epoch.unsigned_abs())
+        } else {
+            UNIX_EPOCH.checked_add(since_epoch.unsigned_abs())
+        };
+        time.unwrap_or_else(|| furthest_system_time(since_epoch.is_negative()))
+    }
+}
+
+/// Returns the earliest [`SystemTime`] if `is_past`, or else the latest one. Their range depends on
+/// the platform, and [`SystemTime`] has no constants for them.
+fn furthest_system_time(is_past: bool) -> SystemTime {
+    let from_epoch = |duration| {
+        if is_past {
+            UNIX_EPOCH.checked_sub(duration)
+        } else {
+            UNIX_EPOCH.checked_add(duration)
+        }
+    };
+    // Binary searches for the largest `n` in `0..=max` for which `from_epoch(to_duration(n))` is
+    // representable, which it is for 0.
+    let largest = |max: u64, to_duration: &dyn Fn(u64) -> Duration| {
+        let (mut low, mut high) = (0, max);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if from_epoch(to_duration(mid)).is_some() {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    };
+    let secs = largest(u64::MAX, &Duration::from_secs);
+    let nanos = largest(u64::from(NANOS_PER_SEC - 1), &|nanos| Duration::new(secs, nanos as u32));
+    from_epoch(Duration::new(secs, nanos as u32)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{internal, unstable_encode};
+    use googletest::{expect_eq, gtest};
+
+    #[gtest]
+    fn test_negative_duration() {
+        let duration = SignedDuration::new(-2, 500_000_000);
+        expect_eq!(duration.is_negative(), true);
+        expect_eq!(duration.unsigned_abs(), Duration::from_millis(1500));
+        expect_eq!(Duration::try_from(duration), Err(DurationOutOfRangeError(())));
+        expect_eq!(duration.saturating_to_duration(), Duration::ZERO);
+        expect_eq!(
+            SignedDuration::saturating_neg_from_duration(Duration::from_millis(1500)),
+            duration
+        );
+    }
+
+    #[gtest]
+    fn test_duration_out_of_range() {
+        expect_eq!(SignedDuration::try_from(Duration::MAX), Err(DurationOutOfRangeError(())));
+        expect_eq!(SignedDuration::saturating_from_duration(Duration::MAX), SignedDuration::MAX);
+        expect_eq!(
+            SignedDuration::saturating_neg_from_duration(Duration::MAX),
+            SignedDuration::MIN
+        );
+        expect_eq!(SignedDuration::MIN.unsigned_abs(), Duration::from_secs(1 << 63));
+    }
+
+    #[gtest]
+    fn test_encode_decode_system_time() {
+        let before_epoch = UNIX_EPOCH - Duration::from_millis(1500);
+        // SAFETY: the buffer contains a SystemTime encoded as SystemTimeAbi.
+        let value = unsafe {
+            internal::decode(
+                SystemTimeAbi,
+                unstable_encode!(@ SystemTimeAbi, SystemTimeAbi, before_epoch).as_ptr()
+                    as *const u8,
+            )
+        };
+        expect_eq!(value, before_epoch);
+    }
+
+    #[gtest]
+    fn test_decode_infinite_future() {
+        // `absl::InfiniteFuture()` is bridged as `SignedDuration::MAX` after the epoch.
+        let infinity = SignedDuration::MAX;
+        // SAFETY: the buffer contains a SignedDuration encoded as DurationAbi, which is how
+        // SystemTimeAbi encodes a SystemTime.
+        let value = unsafe {
+            internal::decode(
+                SystemTimeAbi,
+                unstable_encode!(@ DurationAbi, DurationAbi, infinity).as_ptr() as *const u8,
+            )
+        };
+        expect_eq!(value, furthest_system_time(false));
+        expect_eq!(value.checked_add(Duration::from_secs(1)), None);
+        expect_eq!(value > UNIX_EPOCH + Duration::from_secs(1 << 32), true);
+    }
+
+    #[gtest]
+    fn test_decode_infinite_past() {
+        // `absl::InfinitePast()` is bridged as `SignedDuration::MIN` after the epoch.
+        let infinity = SignedDuration::MIN;
+        // SAFETY: the buffer contains a SignedDuration encoded as DurationAbi, which is how
+        // SystemTimeAbi encodes a SystemTime.
+        let value = unsafe {
+            internal::decode(
+                SystemTimeAbi,
+                unstable_encode!(@ DurationAbi, DurationAbi, infinity).as_ptr() as *const u8,
+            )
+        };
+        expect_eq!(value, furthest_system_time(true));
+        expect_eq!(value.checked_sub(Duration::from_secs(1)), None);
+        expect_eq!(value < UNIX_EPOCH - Duration::from_secs(1 << 32), true);
+    }
+}
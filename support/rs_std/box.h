@@ -0,0 +1,56 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+#ifndef THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_BOX_H_
+#define THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_BOX_H_
+
+#include <cstddef>
+#include <memory>
+
+#include "support/bridge.h"
+#include "support/internal/check.h"
+
+namespace rs_std {
+
+// Deletes a `T` owned by a Rust `Box<T>`, by running its drop glue and
+// deallocating it with the Rust allocator.
+template <typename T>
+struct BoxDeleter final {
+  static_assert(false,
+                "This type should only be used via a generated specialization");
+};
+
+// A Rust `Box<T>`: the unique owner of a `T` allocated by Rust.
+template <typename T>
+using Box = std::unique_ptr<T, BoxDeleter<T>>;
+
+}  // namespace rs_std
+
+#ifdef CRUBIT_BRIDGE_ENABLED
+
+namespace crubit {
+
+// A Crubit ABI for `rs_std::Box<T>`, which is bridged to a Rust `Box<T>` by
+// transferring ownership of the pointer.
+//
+// This must match `BoxAbi` in Rust.
+template <typename T>
+struct BoxAbi {
+  using Value = rs_std::Box<T>;
+  static constexpr size_t kSize = sizeof(T*);
+  void Encode(Value value, Encoder& encoder) && {
+    // A Rust `Box<T>` is never null, so a moved-from `rs_std::Box` can't be
+    // passed to Rust.
+    CRUBIT_CHECK(value != nullptr) << "Passing a null rs_std::Box to Rust";
+    TransmuteAbi<T*>().Encode(value.release(), encoder);
+  }
+  Value Decode(Decoder& decoder) && {
+    return Value(TransmuteAbi<T*>().Decode(decoder));
+  }
+};
+
+}  // namespace crubit
+
+#endif  // CRUBIT_BRIDGE_ENABLED
+#endif  // THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_BOX_H_
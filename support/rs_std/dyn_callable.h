@@ -10,11 +10,13 @@
 #include <cstddef>
 #include <cstring>
 #include <exception>
+#include <functional>
 #include <type_traits>
 #include <utility>
 
 #include "absl/base/nullability.h"
 #include "absl/functional/any_invocable.h"
+#include "absl/functional/function_ref.h"
 #include "support/bridge.h"
 
 namespace rs_std {
@@ -156,6 +158,7 @@ CRUBIT_INTERNAL_RUST_ANY_CALLABLE_IMPL(&&)
 template <class Sig>
 struct DynCallableAbi {
   using Value = DynCallable<Sig>;
+  using InvokerType = Value::Impl::InvokerType;
 
   static constexpr size_t kSize = 24;
 
@@ -177,13 +180,119 @@ struct DynCallableAbi {
   }
 
   explicit DynCallableAbi(ManagerType* absl_nonnull manager,
-                          Value::Impl::InvokerType* invoker)
+                          InvokerType* invoker)
       : manager(manager), invoker(invoker) {}
 
   ManagerType* absl_nonnull manager;
-  Value::Impl::InvokerType* invoker;
+  InvokerType* invoker;
 };
 
+template <class Sig>
+struct FunctionRefAbi;
+
+// A borrowed Rust `&mut dyn FnMut`, received from Rust through
+// `FunctionRefAbi<Sig>`. It does not own the Rust callable, and is only valid
+// for the duration of the call that it was passed to. It must only be invoked
+// from the calling thread, and not reentrantly.
+template <class Sig>
+class DynFnMutRef;
+
+template <class ReturnType, class... P>
+class DynFnMutRef<ReturnType(P...)> {
+ public:
+  using InvokerType = InvokerType<ReturnType, P...>;
+
+  DynFnMutRef(const DynFnMutRef&) = delete;
+  DynFnMutRef& operator=(const DynFnMutRef&) = delete;
+
+  // `absl::FunctionRef` invokes the referenced callable through a const
+  // reference, so the `&mut dyn FnMut` is stored in a mutable member.
+  ReturnType operator()(P... args) const {
+    return invoker_(&state_, std::forward<ForwardedParameterType<P>>(args)...);
+  }
+
+ private:
+  friend struct FunctionRefAbi<ReturnType(P...)>;
+
+  explicit DynFnMutRef(TypeErasedState state, InvokerType* invoker)
+      : state_(state), invoker_(invoker) {}
+
+  mutable TypeErasedState state_;
+  InvokerType* invoker_;
+};
+
+// The ABI for `absl::FunctionRef<Sig>`, which can only be sent from Rust to
+// C++. The value is encoded as a `&mut dyn FnMut`, so nothing is allocated.
+//
+// `Decode` returns a `DynFnMutRef<Sig>` rather than an `absl::FunctionRef`,
+// which would refer to a destroyed temporary. Instead, the `absl::FunctionRef`
+// parameter is constructed from the decoded value in the same full-expression.
+template <class Sig>
+struct FunctionRefAbi {
+  using Value = DynFnMutRef<Sig>;
+  using InvokerType = Value::InvokerType;
+
+  static constexpr size_t kSize = sizeof(TypeErasedState);
+
+  Value Decode(crubit::Decoder& decoder) && {
+    auto state = crubit::TransmuteAbi<TypeErasedState>().Decode(decoder);
+    return Value(state, invoker);
+  }
+
+  explicit FunctionRefAbi(InvokerType* invoker) : invoker(invoker) {}
+
+  InvokerType* invoker;
+};
+
+#ifdef __cpp_lib_move_only_function
+
+// Deletes a `std::move_only_function<Sig>` that was sent to Rust.
+template <class Sig>
+void DeleteMoveOnlyFunction(void* f) noexcept {
+  delete static_cast<std::move_only_function<Sig>*>(f);
+}
+
+// The ABI contract for `MoveOnlyFunctionAbi<Sig>` varies between Rust -> C++,
+// and C++ -> Rust.
+//
+// When sending from Rust to C++, the value is encoded as `Box<dyn F>`, like
+// `DynCallableAbi<Sig>`, and the resulting `DynCallable<Sig>` is stored in the
+// `std::move_only_function`.
+//
+// When sending from C++ to Rust, the value is encoded as a bool indicating
+// whether the value is present. If present, the bool is followed by a pointer
+// to a heap-allocated `std::move_only_function<Sig>`, and a pointer to the
+// function that deletes it.
+template <class Sig>
+struct MoveOnlyFunctionAbi {
+  using Value = std::move_only_function<Sig>;
+  using InvokerType = DynCallableAbi<Sig>::InvokerType;
+
+  static constexpr size_t kSize = DynCallableAbi<Sig>::kSize;
+
+  void Encode(Value value, crubit::Encoder& encoder) && {
+    crubit::TransmuteAbi<bool>().Encode(static_cast<bool>(value), encoder);
+    if (value) {
+      crubit::TransmuteAbi<void*>().Encode(new Value(std::move(value)),
+                                           encoder);
+      crubit::TransmuteAbi<void (*)(void*) noexcept>().Encode(
+          DeleteMoveOnlyFunction<Sig>, encoder);
+    }
+  }
+
+  Value Decode(crubit::Decoder& decoder) && {
+    return Value(std::move(dyn_callable_abi).Decode(decoder));
+  }
+
+  explicit MoveOnlyFunctionAbi(ManagerType* absl_nonnull manager,
+                               InvokerType* invoker)
+      : dyn_callable_abi(manager, invoker) {}
+
+  DynCallableAbi<Sig> dyn_callable_abi;
+};
+
+#endif  // __cpp_lib_move_only_function
+
 }  // namespace internal_dyn_callable
 
 // rs_std::DynCallable
@@ -3,6 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
 
 use bridge_rust::{transmute_abi, CrubitAbi, Decoder, Encoder};
+use core::ffi::c_void;
+use core::marker::PhantomData;
 use core::ptr;
 
 #[track_caller]
@@ -52,6 +54,110 @@ unsafe impl<F: ?Sized> CrubitAbi for DynCallableAbi<F> {
     }
 }
 
+/// The [`CrubitAbi`] for a borrowed `&mut F`, where `F` is `dyn FnMut`, which is received by C++
+/// as an `absl::FunctionRef`.
+///
+/// Unlike [`DynCallableAbi`], nothing is allocated, and `F` need not be `Send`, `Sync`, or
+/// `'static`. In exchange, C++ must only invoke it from the calling thread, one call at a time,
+/// and during the call that it was passed to, which is why functions that take an
+/// `absl::FunctionRef` are `unsafe` to call from Rust.
+pub struct FunctionRefAbi<'a, F: ?Sized + 'a>(pub PhantomData<&'a mut F>);
+
+/// SAFETY: The ABI contract for `FunctionRefAbi<F>` is that the `&mut F` is encoded as-is. It may
+/// only be sent from Rust to C++.
+unsafe impl<'a, F: ?Sized + 'a> CrubitAbi for FunctionRefAbi<'a, F> {
+    type Value = &'a mut F;
+
+    const SIZE: usize = 16;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        transmute_abi().encode(value, encoder);
+    }
+
+    unsafe fn decode(self, _decoder: &mut Decoder) -> Self::Value {
+        unreachable!("absl::FunctionRef cannot be passed from C++ to Rust")
+    }
+}
+
+/// An owned C++ function object, such as a heap-allocated `std::move_only_function`, which is
+/// deleted by C++ when dropped.
+pub struct RawCppCallable {
+    ptr: *mut c_void,
+    delete: unsafe extern "C" fn(*mut c_void),
+}
+
+impl RawCppCallable {
+    /// Returns a pointer to the C++ function object.
+    pub fn as_ptr(&self) -> *mut c_void {
+        self.ptr
+    }
+}
+
+impl Drop for RawCppCallable {
+    fn drop(&mut self) {
+        // SAFETY: `delete` was sent from C++ along with `ptr`, and knows how to delete it.
+        unsafe { (self.delete)(self.ptr) }
+    }
+}
+
+// SAFETY: Owned C++ callables are assumed to be thread-compatible, like `absl::AnyInvocable`.
+unsafe impl Send for RawCppCallable {}
+// SAFETY: See above.
+unsafe impl Sync for RawCppCallable {}
+
+/// The [`CrubitAbi`] for a type `F`, where `F` is one of `dyn Fn`, `dyn FnMut`, or `dyn FnOnce`,
+/// which is received by C++ as a `std::move_only_function`.
+pub struct MoveOnlyFunctionAbi<F: ?Sized> {
+    fallback: Box<F>,
+    make_cpp_invoker: fn(RawCppCallable) -> Box<F>,
+}
+
+impl<F: ?Sized> MoveOnlyFunctionAbi<F> {
+    /// Returns a new `MoveOnlyFunctionAbi` instance.
+    ///
+    /// `fallback` will be used in decoding if C++ returns an empty value, and `make_cpp_invoker`
+    /// wraps a C++ function object in a `Box<F>` that invokes it.
+    pub fn new(fallback: Box<F>, make_cpp_invoker: fn(RawCppCallable) -> Box<F>) -> Self {
+        MoveOnlyFunctionAbi { fallback, make_cpp_invoker }
+    }
+}
+
+/// SAFETY: The ABI contract for `MoveOnlyFunctionAbi<F>` varies between Rust -> C++, and C++ ->
+/// Rust.
+///
+/// When sending from Rust to C++, the value is encoded as `Box<dyn F>`.
+///
+/// When sending from C++ to Rust, the value is encoded as a bool indicating whether the value is
+/// present. If present, the bool is followed by a pointer to the C++ function object, and a
+/// pointer to the function that deletes it.
+unsafe impl<F: ?Sized> CrubitAbi for MoveOnlyFunctionAbi<F> {
+    type Value = Box<F>;
+
+    const SIZE: usize = 24;
+
+    fn encode(self, value: Self::Value, encoder: &mut Encoder) {
+        transmute_abi().encode(value, encoder);
+    }
+
+    unsafe fn decode(self, decoder: &mut Decoder) -> Self::Value {
+        // SAFETY: When receiving from C++, the first value is a bool indicating whether the value
+        // is present.
+        let present = unsafe { transmute_abi().decode(decoder) };
+        if present {
+            // SAFETY: present is true, so the pointer and its deleter are present.
+            let raw = unsafe {
+                RawCppCallable {
+                    ptr: transmute_abi().decode(decoder),
+                    delete: transmute_abi().decode(decoder),
+                }
+            };
+            (self.make_cpp_invoker)(raw)
+        } else {
+            self.fallback
+        }
+    }
+}
+
 /// A Rust equivalent of `FunctionToCall` used internally in absl::AnyInvocable.
 // The variants are never constructed in Rust, they are only constructed in C++.
 #[allow(dead_code)]
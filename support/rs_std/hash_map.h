@@ -0,0 +1,73 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// IWYU pragma: private, include "support/rs_std/hash_map.h"
+
+#ifndef THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_HASH_MAP_H_
+#define THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_HASH_MAP_H_
+
+#include <optional>
+#include <utility>
+
+#include "support/rs_std/iterator_adapter.h"
+#include "support/rs_std/traits.h"
+
+namespace rs_std {
+
+template <typename K, typename V>
+struct HashMap final {
+  static_assert(false,
+                "This type should only be used via a generated specialization");
+};
+
+// Iterator over the entries of a `rs_std::HashMap<K, V>`, which borrows the
+// map. It is returned by `HashMap::begin()` wrapped in an
+// `rs::IteratorAdapter`, and must not be used after the map is modified or
+// destroyed.
+template <typename K, typename V>
+class HashMapIter final {
+ public:
+  HashMapIter(HashMapIter&& other) noexcept
+      : iter_(std::exchange(other.iter_, nullptr)) {}
+  HashMapIter& operator=(HashMapIter&& other) noexcept {
+    std::swap(iter_, other.iter_);
+    return *this;
+  }
+  HashMapIter(const HashMapIter&) = delete;
+  HashMapIter& operator=(const HashMapIter&) = delete;
+  ~HashMapIter() {
+    if (iter_ != nullptr) {
+      HashMap<K, V>::DropIter(iter_);
+    }
+  }
+
+ private:
+  friend struct HashMap<K, V>;
+  friend struct impl<HashMapIter, ::rs::core::iter::Iterator>;
+
+  explicit HashMapIter(void* iter) : iter_(iter) {}
+
+  // A boxed `std::collections::hash_map::Iter<K, V>`.
+  void* iter_;
+};
+
+// Yields a pair of pointers to the key and the value of each entry, like the
+// `(&K, &V)` items of `HashMap::iter()` in Rust.
+template <typename K, typename V>
+struct impl<HashMapIter<K, V>, ::rs::core::iter::Iterator> {
+  static constexpr bool kIsImplemented = true;
+  using Item = std::pair<const K*, const V*>;
+  static std::optional<Item> next(HashMapIter<K, V>& self) {
+    const K* key;
+    const V* value;
+    if (!HashMap<K, V>::NextIter(self.iter_, &key, &value)) {
+      return std::nullopt;
+    }
+    return Item(key, value);
+  }
+};
+
+}  // namespace rs_std
+
+#endif  // THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_HASH_MAP_H_
@@ -0,0 +1,20 @@
+// Part of the Crubit project, under the Apache License v2.0 with LLVM
+// Exceptions. See /LICENSE for license information.
+// SPDX-License-Identifier: Apache-2.0 WITH LLVM-exception
+
+// IWYU pragma: private, include "support/rs_std/rc.h"
+
+#ifndef THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_RC_H_
+#define THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_RC_H_
+
+namespace rs_std {
+
+template <typename T>
+struct Rc final {
+  static_assert(false,
+                "This type should only be used via a generated specialization");
+};
+
+}  // namespace rs_std
+
+#endif  // THIRD_PARTY_CRUBIT_SUPPORT_RS_STD_RC_H_